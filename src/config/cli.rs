@@ -1,5 +1,7 @@
 //! Command-line argument parsing
 
+use std::collections::BTreeSet;
+
 /// Command-line arguments for the LED Matrix Display Controller
 #[derive(argh::FromArgs, Debug, Clone)]
 /// LED Matrix Display Controller
@@ -7,10 +9,11 @@
 /// Controls an LED matrix display with web configuration interface.
 pub struct CliArgs {
     #[argh(option, short = 'd')]
-    /// driver type: "native" or "binding"
+    /// driver type: "native", "binding" or "wall"
     ///
     /// native: Pure Rust library (https://github.com/EmbersArc/rpi_led_panel)
     /// binding: C++ binding (https://github.com/hzeller/rpi-rgb-led-matrix)
+    /// wall: stream regions of the canvas to other Pis (see --wall-tile)
     ///
     /// (REQUIRED)
     pub driver: Option<String>,
@@ -83,9 +86,9 @@ pub struct CliArgs {
     /// "sm5266"/"abc-shift-de"
     pub row_setter: String,
 
-    #[argh(option, default = "String::from(\"RGB\")")]
+    #[argh(option)]
     /// the LED color sequence, Default: "RGB" [native, binding]
-    pub led_sequence: String,
+    pub led_sequence: Option<String>,
 
     #[argh(switch)]
     /// disable hardware pin-pulse generation. Default: false (hardware pulse enabled) [binding]
@@ -112,10 +115,217 @@ pub struct CliArgs {
     /// network interface to bind to. Default: "0.0.0.0" (all interfaces)
     pub interface: String,
 
+    #[argh(option)]
+    /// also serve the API on a Unix domain socket at this path, access is
+    /// limited by the socket's file permissions (owner and group)
+    pub unix_socket: Option<String>,
+
+    #[argh(option)]
+    /// URL prefix to serve the API and web interface under when behind a
+    /// reverse proxy, e.g. "/led". Default: none (served at the root)
+    pub base_path: Option<String>,
+
+    #[argh(switch)]
+    /// let image links, HTTP checks and camera streams reach local and private
+    /// addresses, e.g. a camera on the LAN. Default: false (public addresses only)
+    pub allow_local_urls: bool,
+
+    #[argh(option, default = "0")]
+    /// UDP port to receive ambient color frames on (raw RGB, e.g. from
+    /// Hyperion), shown instead of the playlist while they arrive (0 = off). Default: 0
+    pub ambient_udp_port: u16,
+
+    #[argh(option)]
+    /// ALSA capture device, e.g. "default" or "hw:1,0", whose sound level drives
+    /// animations marked reactive (needs arecord). Default: off
+    pub audio_device: Option<String>,
+
+    #[argh(option)]
+    /// synchronized playback across signs: "master" broadcasts its playlist
+    /// position, "follower" plays along with it. Default: off
+    pub sync_mode: Option<String>,
+
+    #[argh(
+        option,
+        default = "String::from(crate::display::sync::DEFAULT_SYNC_GROUP)"
+    )]
+    /// multicast group (or broadcast/unicast address) and port for sync
+    /// messages. Default: "239.255.76.83:5683"
+    pub sync_group: String,
+
     #[argh(option, default = "100")]
     /// maximum brightness limit (0-100). The UI's 100% setting will equal this value.
     /// Default: 100 (no scaling)
     pub limit_max_brightness: u8,
+
+    #[argh(option)]
+    /// current the power supply can deliver to the panels, in amps. Frames
+    /// estimated to draw more are dimmed. Default: no limit
+    pub power_limit_amps: Option<f32>,
+
+    #[argh(option, default = "0.65")]
+    /// current of one LED channel at full value and full brightness in
+    /// milliamps, used to estimate the power draw. Default: 0.65
+    pub power_ma_per_channel: f32,
+
+    #[argh(option, default = "75.0")]
+    /// temperature of the SoC in °C from which brightness and frame rate
+    /// are reduced (0 = off). Default: 75
+    pub thermal_throttle_c: f32,
+
+    #[argh(option, default = "70.0")]
+    /// temperature of the SoC in °C below which throttled brightness is
+    /// gradually restored. Default: 70
+    pub thermal_resume_c: f32,
+
+    #[argh(switch)]
+    /// show a bundled sample playlist while the playlist is empty,
+    /// instead of the welcome message. Default: false
+    pub demo: bool,
+
+    #[argh(option, default = "0")]
+    /// shift the picture by one pixel every N minutes and briefly exercise the
+    /// edge pixels to even out panel wear (0 = off). Default: 0
+    pub burn_in_shift_minutes: u32,
+
+    #[argh(option, default = "3600")]
+    /// skip a playlist item whose renderer has not finished after this many
+    /// seconds, or after its duration if that is longer (0 = off). Default: 3600
+    pub max_item_seconds: u64,
+
+    #[argh(switch)]
+    /// delete playlist items once their 'expires_at' time has passed, instead
+    /// of only skipping them. Default: false
+    pub delete_expired_items: bool,
+
+    #[argh(option, default = "7")]
+    /// days a deleted playlist item stays in the trash before it is purged
+    /// (0 = delete items right away). Default: 7
+    pub trash_days: u32,
+
+    #[argh(option, default = "0")]
+    /// fade finished playlist items to black over this many milliseconds
+    /// before the next item starts (0 = off). Default: 0
+    pub outro_ms: u32,
+
+    #[argh(option, default = "5")]
+    /// write playlist changes to disk at most every this many seconds, so
+    /// bursts of edits cause one write (0 = write every change). Default: 5
+    pub save_interval_seconds: u64,
+
+    #[argh(switch)]
+    /// reduce SD card writes: keep playlist and brightness changes in memory
+    /// and write them every 5 minutes and on shutdown. Default: false
+    pub low_wear: bool,
+
+    #[argh(option)]
+    /// directory for regenerable files like image thumbnails, e.g. a tmpfs
+    /// mount. Default: inside the data directory
+    pub cache_dir: Option<String>,
+
+    #[argh(option, default = "60")]
+    /// remove uploaded images no item uses every this many minutes (0 = only
+    /// through POST /api/maintenance/cleanup). Default: 60
+    pub cleanup_interval_minutes: u32,
+
+    #[argh(option, default = "60")]
+    /// keep unused images for this many minutes after their upload, so
+    /// images of items still being edited are not removed. Default: 60
+    pub cleanup_grace_minutes: u32,
+
+    #[argh(option)]
+    /// IANA time zone for clock content and log timestamps, e.g.
+    /// "Europe/Berlin". Default: the system time zone
+    pub timezone: Option<String>,
+
+    #[argh(option)]
+    /// language for text the controller draws itself (welcome message,
+    /// clock dates): en, de, es, fr, it or nl. Default: en
+    pub locale: Option<String>,
+
+    #[argh(option)]
+    /// seed for random effects such as sparkle and strobe. Mirrored
+    /// displays with the same seed show identical effects. Default: random
+    pub render_seed: Option<u64>,
+
+    #[argh(option, default = "30")]
+    /// largest accepted image upload in megabytes, at most 1024. Default: 30
+    pub max_image_mb: u32,
+
+    #[argh(option, default = "4096")]
+    /// largest accepted image width or height in pixels, protects against
+    /// images that decode to huge amounts of memory. Default: 4096
+    pub max_image_dimension: u32,
+
+    #[argh(option, default = "100")]
+    /// reject image and video uploads while less than this many megabytes
+    /// are free in the data directory (0 = never). Default: 100
+    pub min_free_space_mb: u32,
+
+    #[argh(switch)]
+    /// serve only the API, without the embedded web interface. Default: false
+    pub headless: bool,
+
+    #[argh(option)]
+    /// directory to load renderer plugins (shared libraries) from.
+    /// Requires the "dynamic-plugins" build feature
+    pub plugin_dir: Option<String>,
+
+    #[argh(option)]
+    /// extra rpi-rgb-led-matrix option as key=value (e.g. "scan_mode=1"),
+    /// passed straight to the driver. Can be repeated [binding]
+    pub driver_opt: Vec<String>,
+
+    #[argh(option)]
+    /// region of the canvas sent to one Pi of a display wall as
+    /// X,Y,WIDTHxHEIGHT@HOST:PORT, e.g. "64,0,64x32@192.168.1.21:19446".
+    /// Can be repeated [wall]
+    pub wall_tile: Vec<String>,
+
+    #[argh(option)]
+    /// local command that Command playlist items may run, as NAME=COMMAND
+    /// (e.g. "temp=vcgencmd measure_temp"). Runs without a shell. Can be repeated
+    pub allow_command: Vec<String>,
+
+    #[argh(option)]
+    /// push button wired from a GPIO pin to ground, as PIN=ACTION with the
+    /// BCM pin number and ACTION one of next, brightness-up, brightness-down,
+    /// power or show:ITEM_ID (e.g. "17=next"). Can be repeated
+    pub button: Vec<String>,
+
+    #[argh(option, default = "50")]
+    /// time a button must be held steadily before a press counts, in
+    /// milliseconds. Default: 50
+    pub button_debounce_ms: u64,
+
+    #[argh(option, default = "String::from(\"/dev/gpiochip0\")")]
+    /// GPIO character device the buttons are on. Default: /dev/gpiochip0
+    pub gpio_chip: String,
+
+    #[argh(switch)]
+    /// run every renderer type at the configured panel size without hardware,
+    /// print frame timings and exit. --driver is not needed
+    pub bench: bool,
+
+    #[argh(option, default = "5")]
+    /// seconds to run each renderer for with --bench. Default: 5
+    pub bench_seconds: u64,
+
+    #[argh(option)]
+    /// render every content type at fixed timestamps and compare the frames
+    /// with the golden PNG files in this directory, then exit. Needs the
+    /// test-util feature
+    pub golden_frames: Option<String>,
+
+    #[argh(switch)]
+    /// with --golden-frames, write the rendered frames as the new golden
+    /// files instead of comparing them
+    pub update_golden: bool,
+
+    #[argh(option)]
+    /// name of a saved hardware profile (panel type, multiplexing, pixel mapper,
+    /// GPIO slowdown) to apply. Other options override its values [native, binding]
+    pub profile: Option<String>,
 }
 
 impl CliArgs {
@@ -124,4 +334,27 @@ impl CliArgs {
         // Use argh to parse args from environment
         argh::from_env()
     }
+
+    /// Names of the options and switches given on the command line (e.g. "gpio_slowdown").
+    /// Used to tell explicitly passed values apart from argh defaults.
+    pub fn provided_options() -> BTreeSet<String> {
+        std::env::args()
+            .skip(1)
+            .filter_map(|arg| {
+                if let Some(long) = arg.strip_prefix("--") {
+                    Some(long.replace('-', "_"))
+                } else {
+                    let name = match arg.as_str() {
+                        "-d" => "driver",
+                        "-r" => "rows",
+                        "-c" => "cols",
+                        "-p" => "parallel",
+                        "-n" => "chain_length",
+                        _ => return None,
+                    };
+                    Some(name.to_string())
+                }
+            })
+            .collect()
+    }
 }
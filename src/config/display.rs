@@ -1,8 +1,17 @@
 //! Display configuration structure and methods
 
-use super::{CliArgs, EnvVars};
-use crate::display::driver::DriverType;
-use log::info;
+use super::{CliArgs, ConfigSource, EffectiveSetting, EnvVars, HardwareProfile, StoredConfig};
+use crate::display::driver::{DriverType, WallTile};
+use crate::display::sync::SyncMode;
+use log::{info, warn};
+use serde_json::json;
+use std::collections::BTreeMap;
+
+/// Shortest interval changes are written at with --low-wear
+const LOW_WEAR_SAVE_INTERVAL_SECONDS: u64 = 300;
+
+/// Largest value --max-image-mb accepts
+const MAX_IMAGE_MB: u32 = 1024;
 
 /// Configuration structure that stores all display settings
 #[derive(Clone, Debug)]
@@ -33,18 +42,567 @@ pub struct DisplayConfig {
     pub limit_refresh_rate: u32,
     pub limit_max_brightness: u8,
 
+    // Power guard: supply limit in amps (None disables it) and estimated mA per LED channel
+    pub power_limit_amps: Option<f32>,
+    pub power_ma_per_channel: f32,
+
+    // Thermal throttling: SoC temperature that starts it (0 disables it) and ends it
+    pub thermal_throttle_c: f32,
+    pub thermal_resume_c: f32,
+
+    // Raw key=value options forwarded to the driver
+    pub driver_opts: Vec<String>,
+
+    // Display wall tiles as X,Y,WIDTHxHEIGHT@HOST:PORT, used by the wall driver
+    pub wall_tiles: Vec<String>,
+
     // Web server configuration
     pub port: u16,
     pub interface: String,
+    pub unix_socket: Option<String>,
+    // URL prefix the server is mounted under, e.g. "/led", empty when served at the root
+    pub base_path: String,
+    // Whether URLs from API clients may reach local and private addresses
+    pub allow_local_urls: bool,
+    // UDP port for ambient color frames, 0 disables the listener
+    pub ambient_udp_port: u16,
+    // ALSA device captured for reactive animations, None leaves audio capture off
+    pub audio_device: Option<String>,
+    // Synchronized playback role ("master" or "follower"), None plays independently
+    pub sync_mode: Option<String>,
+    pub sync_group: String,
+    pub headless: bool,
+
+    // Show the bundled sample playlist while the playlist is empty
+    pub demo: bool,
+
+    // Minutes between anti burn-in pixel shifts, 0 disables them
+    pub burn_in_shift_minutes: u32,
+
+    // Seconds after which an unfinished playlist item is skipped, 0 disables the watchdog
+    pub max_item_seconds: u64,
+
+    // Remove expired playlist items instead of only skipping them
+    pub delete_expired_items: bool,
+
+    // Days deleted playlist items are kept in the trash, 0 deletes them right away
+    pub trash_days: u32,
+
+    // Milliseconds a finished item fades to black before the next one, items can override it
+    pub outro_ms: u32,
+
+    // Seconds playlist changes may wait before they are written, 0 writes every change
+    pub save_interval_seconds: u64,
+
+    // Keep state changes in memory for at least LOW_WEAR_SAVE_INTERVAL_SECONDS
+    pub low_wear: bool,
+
+    // Directory for thumbnails, None keeps them in the data directory
+    pub cache_dir: Option<String>,
+
+    // Minutes between removals of unused images, 0 only removes them on request
+    pub cleanup_interval_minutes: u32,
+
+    // Minutes an unused image is kept after its upload
+    pub cleanup_grace_minutes: u32,
+
+    // IANA time zone for clock content and logs, None uses the system time zone
+    pub timezone: Option<String>,
+
+    // Language for server-rendered text, None uses English
+    pub locale: Option<String>,
+
+    // Seed for random effects, random unless configured so runs can be reproduced
+    pub render_seed: u64,
+
+    // Image upload limits
+    pub max_image_mb: u32,
+    pub max_image_dimension: u32,
+
+    // Free space in megabytes the data directory keeps, uploads are rejected below it
+    pub min_free_space_mb: u32,
+
+    // Plugins
+    pub plugin_dir: Option<String>,
+
+    // Commands Command content may run, as NAME=COMMAND
+    pub allowed_commands: Vec<String>,
+
+    // Push buttons as PIN=ACTION, read from gpio_chip
+    pub buttons: Vec<String>,
+    pub button_debounce_ms: u64,
+    pub gpio_chip: String,
+
+    // Name of the hardware profile applied at startup
+    pub profile: Option<String>,
+
+    // Seconds per renderer when running the benchmark instead of the controller
+    pub bench_seconds: Option<u64>,
+
+    // Golden frame directory when capturing frames instead of running the controller
+    pub golden_frames: Option<String>,
+    #[cfg_attr(not(feature = "test-util"), allow(dead_code))]
+    pub update_golden: bool,
+
+    // Layer each setting was resolved from
+    pub sources: BTreeMap<&'static str, ConfigSource>,
 }
 
 impl DisplayConfig {
-    /// Create a new configuration by combining CLI arguments and environment variables
-    pub fn new(cli_args: CliArgs, env_vars: EnvVars) -> Self {
+    /// Create a new configuration by combining CLI arguments, environment variables,
+    /// the selected hardware profile and settings stored by the web interface
+    pub fn new(
+        cli_args: CliArgs,
+        env_vars: EnvVars,
+        profile: Option<(String, HardwareProfile)>,
+        stored: StoredConfig,
+    ) -> Self {
+        let (profile_name, profile) = match profile {
+            Some((name, profile)) => {
+                info!("Using hardware profile: {}", name);
+                (Some(name), profile)
+            }
+            None => (None, HardwareProfile::default()),
+        };
+
+        // Record where each setting comes from before the layers are merged
+        let provided = CliArgs::provided_options();
+        let cli = |name: &str| provided.contains(name);
+        let mut sources = BTreeMap::new();
+        let mut track = |key: &'static str, env: bool, cli: bool, profile: bool, file: bool| {
+            sources.insert(key, ConfigSource::resolve(env, cli, profile, file));
+        };
+        track(
+            "driver",
+            env_vars.driver.is_some(),
+            cli("driver"),
+            false,
+            stored.driver.is_some(),
+        );
+        track(
+            "driver_opts",
+            env_vars.driver_opts.is_some(),
+            cli("driver_opt"),
+            false,
+            false,
+        );
+        track(
+            "rows",
+            env_vars.rows.is_some(),
+            cli("rows"),
+            false,
+            stored.rows.is_some(),
+        );
+        track(
+            "cols",
+            env_vars.cols.is_some(),
+            cli("cols"),
+            false,
+            stored.cols.is_some(),
+        );
+        track(
+            "chain_length",
+            env_vars.chain_length.is_some(),
+            cli("chain_length"),
+            false,
+            stored.chain_length.is_some(),
+        );
+        track(
+            "parallel",
+            env_vars.parallel.is_some(),
+            cli("parallel"),
+            false,
+            stored.parallel.is_some(),
+        );
+        track(
+            "hardware_mapping",
+            env_vars.hardware_mapping.is_some(),
+            cli("hardware_mapping"),
+            false,
+            false,
+        );
+        track(
+            "pwm_bits",
+            env_vars.pwm_bits.is_some(),
+            cli("pwm_bits"),
+            false,
+            false,
+        );
+        track(
+            "pwm_lsb_nanoseconds",
+            env_vars.pwm_lsb_nanoseconds.is_some(),
+            cli("pwm_lsb_nanoseconds"),
+            false,
+            false,
+        );
+        track(
+            "gpio_slowdown",
+            env_vars.gpio_slowdown.is_some(),
+            cli("gpio_slowdown"),
+            profile.gpio_slowdown.is_some(),
+            false,
+        );
+        track(
+            "interlaced",
+            env_vars.interlaced.is_some(),
+            cli("interlaced"),
+            false,
+            false,
+        );
+        track(
+            "dither_bits",
+            env_vars.dither_bits.is_some(),
+            cli("dither_bits"),
+            false,
+            false,
+        );
+        track(
+            "panel_type",
+            env_vars.panel_type.is_some(),
+            cli("panel_type"),
+            profile.panel_type.is_some(),
+            false,
+        );
+        track(
+            "multiplexing",
+            env_vars.multiplexing.is_some(),
+            cli("multiplexing"),
+            profile.multiplexing.is_some(),
+            false,
+        );
+        track(
+            "pixel_mapper",
+            env_vars.pixel_mapper.is_some(),
+            cli("pixel_mapper"),
+            profile.pixel_mapper.is_some(),
+            false,
+        );
+        track(
+            "row_setter",
+            env_vars.row_setter.is_some(),
+            cli("row_setter"),
+            false,
+            false,
+        );
+        track(
+            "led_sequence",
+            env_vars.led_sequence.is_some(),
+            cli("led_sequence"),
+            false,
+            stored.led_sequence.is_some(),
+        );
+        track(
+            "pi_chip",
+            env_vars.pi_chip.is_some(),
+            cli("pi_chip"),
+            false,
+            false,
+        );
+        track(
+            "hardware_pulsing",
+            env_vars.hardware_pulsing.is_some(),
+            cli("no_hardware_pulse"),
+            false,
+            false,
+        );
+        track(
+            "show_refresh",
+            env_vars.show_refresh.is_some(),
+            cli("show_refresh"),
+            false,
+            false,
+        );
+        track(
+            "inverse_colors",
+            env_vars.inverse_colors.is_some(),
+            cli("inverse_colors"),
+            false,
+            false,
+        );
+        track(
+            "limit_refresh_rate",
+            env_vars.limit_refresh_rate.is_some(),
+            cli("limit_refresh_rate"),
+            false,
+            false,
+        );
+        track(
+            "limit_max_brightness",
+            env_vars.limit_max_brightness.is_some(),
+            cli("limit_max_brightness"),
+            false,
+            false,
+        );
+        track(
+            "power_limit_amps",
+            env_vars.power_limit_amps.is_some(),
+            cli("power_limit_amps"),
+            false,
+            false,
+        );
+        track(
+            "power_ma_per_channel",
+            env_vars.power_ma_per_channel.is_some(),
+            cli("power_ma_per_channel"),
+            false,
+            false,
+        );
+        track(
+            "thermal_throttle_c",
+            env_vars.thermal_throttle_c.is_some(),
+            cli("thermal_throttle_c"),
+            false,
+            false,
+        );
+        track(
+            "thermal_resume_c",
+            env_vars.thermal_resume_c.is_some(),
+            cli("thermal_resume_c"),
+            false,
+            false,
+        );
+        track("port", env_vars.port.is_some(), cli("port"), false, false);
+        track(
+            "interface",
+            env_vars.interface.is_some(),
+            cli("interface"),
+            false,
+            false,
+        );
+        track(
+            "unix_socket",
+            env_vars.unix_socket.is_some(),
+            cli("unix_socket"),
+            false,
+            false,
+        );
+        track(
+            "base_path",
+            env_vars.base_path.is_some(),
+            cli("base_path"),
+            false,
+            false,
+        );
+        track(
+            "allow_local_urls",
+            env_vars.allow_local_urls.is_some(),
+            cli("allow_local_urls"),
+            false,
+            false,
+        );
+        track(
+            "ambient_udp_port",
+            env_vars.ambient_udp_port.is_some(),
+            cli("ambient_udp_port"),
+            false,
+            false,
+        );
+        track(
+            "wall_tiles",
+            env_vars.wall_tiles.is_some(),
+            cli("wall_tile"),
+            false,
+            false,
+        );
+        track(
+            "audio_device",
+            env_vars.audio_device.is_some(),
+            cli("audio_device"),
+            false,
+            false,
+        );
+        track(
+            "sync_mode",
+            env_vars.sync_mode.is_some(),
+            cli("sync_mode"),
+            false,
+            false,
+        );
+        track(
+            "sync_group",
+            env_vars.sync_group.is_some(),
+            cli("sync_group"),
+            false,
+            false,
+        );
+        track(
+            "burn_in_shift_minutes",
+            env_vars.burn_in_shift_minutes.is_some(),
+            cli("burn_in_shift_minutes"),
+            false,
+            false,
+        );
+        track(
+            "max_item_seconds",
+            env_vars.max_item_seconds.is_some(),
+            cli("max_item_seconds"),
+            false,
+            false,
+        );
+        track(
+            "delete_expired_items",
+            env_vars.delete_expired_items.is_some(),
+            cli("delete_expired_items"),
+            false,
+            false,
+        );
+        track(
+            "trash_days",
+            env_vars.trash_days.is_some(),
+            cli("trash_days"),
+            false,
+            false,
+        );
+        track(
+            "outro_ms",
+            env_vars.outro_ms.is_some(),
+            cli("outro_ms"),
+            false,
+            false,
+        );
+        track(
+            "save_interval_seconds",
+            env_vars.save_interval_seconds.is_some(),
+            cli("save_interval_seconds"),
+            false,
+            false,
+        );
+        track(
+            "low_wear",
+            env_vars.low_wear.is_some(),
+            cli("low_wear"),
+            false,
+            false,
+        );
+        track(
+            "cache_dir",
+            env_vars.cache_dir.is_some(),
+            cli("cache_dir"),
+            false,
+            false,
+        );
+        track(
+            "cleanup_interval_minutes",
+            env_vars.cleanup_interval_minutes.is_some(),
+            cli("cleanup_interval_minutes"),
+            false,
+            false,
+        );
+        track(
+            "cleanup_grace_minutes",
+            env_vars.cleanup_grace_minutes.is_some(),
+            cli("cleanup_grace_minutes"),
+            false,
+            false,
+        );
+        track(
+            "timezone",
+            env_vars.timezone.is_some(),
+            cli("timezone"),
+            false,
+            stored.timezone.is_some(),
+        );
+        track(
+            "locale",
+            env_vars.locale.is_some(),
+            cli("locale"),
+            false,
+            false,
+        );
+        track(
+            "render_seed",
+            env_vars.render_seed.is_some(),
+            cli("render_seed"),
+            false,
+            false,
+        );
+        track(
+            "max_image_mb",
+            env_vars.max_image_mb.is_some(),
+            cli("max_image_mb"),
+            false,
+            false,
+        );
+        track(
+            "max_image_dimension",
+            env_vars.max_image_dimension.is_some(),
+            cli("max_image_dimension"),
+            false,
+            false,
+        );
+        track(
+            "min_free_space_mb",
+            env_vars.min_free_space_mb.is_some(),
+            cli("min_free_space_mb"),
+            false,
+            false,
+        );
+        track(
+            "plugin_dir",
+            env_vars.plugin_dir.is_some(),
+            cli("plugin_dir"),
+            false,
+            false,
+        );
+        track(
+            "allowed_commands",
+            env_vars.allowed_commands.is_some(),
+            cli("allow_command"),
+            false,
+            false,
+        );
+        track(
+            "buttons",
+            env_vars.buttons.is_some(),
+            cli("button"),
+            false,
+            false,
+        );
+        track(
+            "button_debounce_ms",
+            env_vars.button_debounce_ms.is_some(),
+            cli("button_debounce_ms"),
+            false,
+            false,
+        );
+        track(
+            "gpio_chip",
+            env_vars.gpio_chip.is_some(),
+            cli("gpio_chip"),
+            false,
+            false,
+        );
+        track(
+            "profile",
+            env_vars.profile.is_some(),
+            cli("profile"),
+            false,
+            false,
+        );
+        track(
+            "headless",
+            env_vars.headless.is_some(),
+            cli("headless"),
+            false,
+            false,
+        );
+        track("demo", env_vars.demo.is_some(), cli("demo"), false, false);
+
         // Determine driver type from CLI argument or environment variable
-        let driver_arg = env_vars.driver.or_else(|| cli_args.driver.clone());
+        let driver_arg = env_vars
+            .driver
+            .or_else(|| cli_args.driver.clone())
+            .or(stored.driver);
 
         let driver_type = match &driver_arg {
+            _ if cli_args.bench => {
+                info!("Benchmark mode: using the null driver, no hardware is accessed");
+                DriverType::Null
+            }
+            _ if cli_args.golden_frames.is_some() => {
+                info!("Golden frame mode: using the null driver, no hardware is accessed");
+                DriverType::Null
+            }
             Some(driver) if driver == "binding" => {
                 info!("Selected driver: C++ binding for rpi-rgb-led-matrix (@https://github.com/hzeller/rpi-rgb-led-matrix)");
                 DriverType::RpiLedMatrix
@@ -53,6 +611,18 @@ impl DisplayConfig {
                 info!("Selected driver: Native library rpi_led_panel (@https://github.com/EmbersArc/rpi_led_panel)");
                 DriverType::RpiLedPanel
             }
+            Some(driver) if driver == "wall" => {
+                info!(
+                    "Selected driver: display wall, frames are sent to the Pis of the wall tiles"
+                );
+                DriverType::Wall
+            }
+            // Without any driver configured, start without hardware so the setup
+            // wizard in the web interface can be used to pick one
+            None if !stored.setup_complete => {
+                warn!("No driver configured: starting without hardware until setup is completed in the web interface (or pass --driver native|binding)");
+                DriverType::Null
+            }
             None => {
                 println!("ERROR: You must specify a driver type (--driver native|binding or LED_DRIVER=native|binding)");
                 println!(
@@ -65,7 +635,7 @@ impl DisplayConfig {
             }
             _ => {
                 println!(
-                    "ERROR: Invalid driver type: {:?}. Must be 'native' or 'binding'",
+                    "ERROR: Invalid driver type: {:?}. Must be 'native', 'binding' or 'wall'",
                     driver_arg
                 );
                 println!(
@@ -78,17 +648,46 @@ impl DisplayConfig {
             }
         };
 
-        // Apply settings from CLI args, then override with environment variables if present
-        let rows = env_vars.rows.unwrap_or(cli_args.rows);
-        let cols = env_vars.cols.unwrap_or(cli_args.cols);
-        let chain_length = env_vars.chain_length.unwrap_or(cli_args.chain_length);
-        let parallel = env_vars.parallel.unwrap_or(cli_args.parallel);
+        // Apply settings from CLI args, then override with environment variables if present.
+        // Geometry saved by the setup wizard replaces the CLI defaults
+        let rows = env_vars
+            .rows
+            .or(cli("rows").then_some(cli_args.rows))
+            .or(stored.rows)
+            .unwrap_or(cli_args.rows);
+        let cols = env_vars
+            .cols
+            .or(cli("cols").then_some(cli_args.cols))
+            .or(stored.cols)
+            .unwrap_or(cli_args.cols);
+        let chain_length = env_vars
+            .chain_length
+            .or(cli("chain_length").then_some(cli_args.chain_length))
+            .or(stored.chain_length)
+            .unwrap_or(cli_args.chain_length);
+        let parallel = env_vars
+            .parallel
+            .or(cli("parallel").then_some(cli_args.parallel))
+            .or(stored.parallel)
+            .unwrap_or(cli_args.parallel);
 
         let limit_max_brightness = env_vars
             .limit_max_brightness
             .unwrap_or(cli_args.limit_max_brightness)
             .clamp(0, 100);
 
+        let power_limit_amps = env_vars.power_limit_amps.or(cli_args.power_limit_amps);
+        let power_ma_per_channel = env_vars
+            .power_ma_per_channel
+            .unwrap_or(cli_args.power_ma_per_channel);
+
+        let thermal_throttle_c = env_vars
+            .thermal_throttle_c
+            .unwrap_or(cli_args.thermal_throttle_c);
+        let thermal_resume_c = env_vars
+            .thermal_resume_c
+            .unwrap_or(cli_args.thermal_resume_c);
+
         // Initialize user brightness to 100% by default
         let user_brightness = 100;
 
@@ -107,11 +706,20 @@ impl DisplayConfig {
             .unwrap_or(cli_args.pwm_lsb_nanoseconds);
 
         // GPU slowdown
-        let gpio_slowdown = env_vars.gpio_slowdown.or(cli_args.gpio_slowdown);
+        let gpio_slowdown = env_vars
+            .gpio_slowdown
+            .or(cli_args.gpio_slowdown)
+            .or(profile.gpio_slowdown);
 
         // Panel configuration
-        let multiplexing = env_vars.multiplexing.or(cli_args.multiplexing);
-        let pixel_mapper = env_vars.pixel_mapper.or(cli_args.pixel_mapper);
+        let multiplexing = env_vars
+            .multiplexing
+            .or(cli_args.multiplexing)
+            .or(profile.multiplexing);
+        let pixel_mapper = env_vars
+            .pixel_mapper
+            .or(cli_args.pixel_mapper)
+            .or(profile.pixel_mapper);
 
         // Other settings from environment variables
         let limit_refresh_rate = env_vars
@@ -119,11 +727,16 @@ impl DisplayConfig {
             .unwrap_or(cli_args.limit_refresh_rate);
         let interlaced = env_vars.interlaced.unwrap_or(cli_args.interlaced);
         let dither_bits = env_vars.dither_bits.unwrap_or(cli_args.dither_bits);
-        let panel_type = env_vars.panel_type.or(cli_args.panel_type);
+        let panel_type = env_vars
+            .panel_type
+            .or(cli_args.panel_type)
+            .or(profile.panel_type);
         let row_setter = env_vars.row_setter.unwrap_or_else(|| cli_args.row_setter);
         let led_sequence = env_vars
             .led_sequence
-            .unwrap_or_else(|| cli_args.led_sequence);
+            .or(cli_args.led_sequence)
+            .or(stored.led_sequence)
+            .unwrap_or_else(|| "RGB".to_string());
         let pi_chip = env_vars.pi_chip.or(cli_args.pi_chip);
 
         let hardware_pulsing = env_vars
@@ -146,6 +759,82 @@ impl DisplayConfig {
             interface
         };
 
+        let unix_socket = env_vars.unix_socket.or(cli_args.unix_socket);
+        let base_path = normalize_base_path(
+            env_vars
+                .base_path
+                .or(cli_args.base_path)
+                .as_deref()
+                .unwrap_or(""),
+        );
+        let allow_local_urls = env_vars
+            .allow_local_urls
+            .unwrap_or(cli_args.allow_local_urls);
+        let ambient_udp_port = env_vars
+            .ambient_udp_port
+            .unwrap_or(cli_args.ambient_udp_port);
+        let audio_device = env_vars
+            .audio_device
+            .or(cli_args.audio_device)
+            .filter(|device| !device.is_empty());
+        let sync_mode = env_vars.sync_mode.or(cli_args.sync_mode);
+        let sync_group = env_vars.sync_group.unwrap_or(cli_args.sync_group);
+
+        let headless = env_vars.headless.unwrap_or(cli_args.headless);
+        let demo = env_vars.demo.unwrap_or(cli_args.demo);
+        let burn_in_shift_minutes = env_vars
+            .burn_in_shift_minutes
+            .unwrap_or(cli_args.burn_in_shift_minutes);
+        let max_item_seconds = env_vars
+            .max_item_seconds
+            .unwrap_or(cli_args.max_item_seconds);
+        let delete_expired_items = env_vars
+            .delete_expired_items
+            .unwrap_or(cli_args.delete_expired_items);
+        let trash_days = env_vars.trash_days.unwrap_or(cli_args.trash_days);
+        let outro_ms = env_vars.outro_ms.unwrap_or(cli_args.outro_ms);
+        let save_interval_seconds = env_vars
+            .save_interval_seconds
+            .unwrap_or(cli_args.save_interval_seconds);
+        let low_wear = env_vars.low_wear.unwrap_or(cli_args.low_wear);
+        let cache_dir = env_vars.cache_dir.or(cli_args.cache_dir);
+        let cleanup_interval_minutes = env_vars
+            .cleanup_interval_minutes
+            .unwrap_or(cli_args.cleanup_interval_minutes);
+        let cleanup_grace_minutes = env_vars
+            .cleanup_grace_minutes
+            .unwrap_or(cli_args.cleanup_grace_minutes);
+        let timezone = env_vars.timezone.or(cli_args.timezone).or(stored.timezone);
+        let locale = env_vars.locale.or(cli_args.locale);
+        let render_seed = env_vars
+            .render_seed
+            .or(cli_args.render_seed)
+            .unwrap_or_else(rand::random);
+
+        // Image upload limits
+        let max_image_mb = env_vars.max_image_mb.unwrap_or(cli_args.max_image_mb);
+        let max_image_dimension = env_vars
+            .max_image_dimension
+            .unwrap_or(cli_args.max_image_dimension);
+        let min_free_space_mb = env_vars
+            .min_free_space_mb
+            .unwrap_or(cli_args.min_free_space_mb);
+
+        // Plugin settings
+        let plugin_dir = env_vars.plugin_dir.or(cli_args.plugin_dir);
+        let allowed_commands = env_vars.allowed_commands.unwrap_or(cli_args.allow_command);
+
+        // Button settings
+        let buttons = env_vars.buttons.unwrap_or(cli_args.button);
+        let button_debounce_ms = env_vars
+            .button_debounce_ms
+            .unwrap_or(cli_args.button_debounce_ms);
+        let gpio_chip = env_vars.gpio_chip.unwrap_or(cli_args.gpio_chip);
+
+        // Driver pass-through options
+        let driver_opts = env_vars.driver_opts.unwrap_or(cli_args.driver_opt);
+        let wall_tiles = env_vars.wall_tiles.unwrap_or(cli_args.wall_tile);
+
         Self {
             rows,
             cols,
@@ -153,6 +842,10 @@ impl DisplayConfig {
             parallel,
             user_brightness,
             limit_max_brightness,
+            power_limit_amps,
+            power_ma_per_channel,
+            thermal_throttle_c,
+            thermal_resume_c,
             driver_type,
 
             hardware_mapping,
@@ -171,11 +864,173 @@ impl DisplayConfig {
             show_refresh,
             inverse_colors,
             limit_refresh_rate,
+            driver_opts,
+            wall_tiles,
             port,
             interface,
+            unix_socket,
+            base_path,
+            allow_local_urls,
+            ambient_udp_port,
+            audio_device,
+            sync_mode,
+            sync_group,
+            headless,
+            demo,
+            burn_in_shift_minutes,
+            max_item_seconds,
+            delete_expired_items,
+            trash_days,
+            outro_ms,
+            save_interval_seconds,
+            low_wear,
+            cache_dir,
+            cleanup_interval_minutes,
+            cleanup_grace_minutes,
+            timezone,
+            locale,
+            render_seed,
+            max_image_mb,
+            max_image_dimension,
+            min_free_space_mb,
+            plugin_dir,
+            allowed_commands,
+            buttons,
+            button_debounce_ms,
+            gpio_chip,
+            profile: profile_name,
+            bench_seconds: cli_args.bench.then_some(cli_args.bench_seconds),
+            golden_frames: cli_args.golden_frames,
+            update_golden: cli_args.update_golden,
+            sources,
         }
     }
 
+    /// Resolved value of every setting together with the layer it came from
+    pub fn effective_settings(&self) -> BTreeMap<&'static str, EffectiveSetting> {
+        let values = [
+            ("driver", json!(self.driver_type.name())),
+            ("rows", json!(self.rows)),
+            ("cols", json!(self.cols)),
+            ("chain_length", json!(self.chain_length)),
+            ("parallel", json!(self.parallel)),
+            ("hardware_mapping", json!(self.hardware_mapping)),
+            ("pwm_bits", json!(self.pwm_bits)),
+            ("pwm_lsb_nanoseconds", json!(self.pwm_lsb_nanoseconds)),
+            ("gpio_slowdown", json!(self.gpio_slowdown)),
+            ("interlaced", json!(self.interlaced)),
+            ("dither_bits", json!(self.dither_bits)),
+            ("panel_type", json!(self.panel_type)),
+            ("multiplexing", json!(self.multiplexing)),
+            ("pixel_mapper", json!(self.pixel_mapper)),
+            ("row_setter", json!(self.row_setter)),
+            ("led_sequence", json!(self.led_sequence)),
+            ("pi_chip", json!(self.pi_chip)),
+            ("hardware_pulsing", json!(self.hardware_pulsing)),
+            ("show_refresh", json!(self.show_refresh)),
+            ("inverse_colors", json!(self.inverse_colors)),
+            ("limit_refresh_rate", json!(self.limit_refresh_rate)),
+            ("limit_max_brightness", json!(self.limit_max_brightness)),
+            ("power_limit_amps", json!(self.power_limit_amps)),
+            ("power_ma_per_channel", json!(self.power_ma_per_channel)),
+            ("thermal_throttle_c", json!(self.thermal_throttle_c)),
+            ("thermal_resume_c", json!(self.thermal_resume_c)),
+            ("driver_opts", json!(self.driver_opts)),
+            ("wall_tiles", json!(self.wall_tiles)),
+            ("port", json!(self.port)),
+            ("interface", json!(self.interface)),
+            ("unix_socket", json!(self.unix_socket)),
+            ("base_path", json!(self.base_path)),
+            ("allow_local_urls", json!(self.allow_local_urls)),
+            ("ambient_udp_port", json!(self.ambient_udp_port)),
+            ("audio_device", json!(self.audio_device)),
+            ("sync_mode", json!(self.sync_mode)),
+            ("sync_group", json!(self.sync_group)),
+            ("headless", json!(self.headless)),
+            ("demo", json!(self.demo)),
+            ("burn_in_shift_minutes", json!(self.burn_in_shift_minutes)),
+            ("max_item_seconds", json!(self.max_item_seconds)),
+            ("delete_expired_items", json!(self.delete_expired_items)),
+            ("trash_days", json!(self.trash_days)),
+            ("outro_ms", json!(self.outro_ms)),
+            ("save_interval_seconds", json!(self.save_interval_seconds)),
+            ("low_wear", json!(self.low_wear)),
+            ("cache_dir", json!(self.cache_dir)),
+            (
+                "cleanup_interval_minutes",
+                json!(self.cleanup_interval_minutes),
+            ),
+            ("cleanup_grace_minutes", json!(self.cleanup_grace_minutes)),
+            ("timezone", json!(self.timezone)),
+            ("locale", json!(self.locale)),
+            ("render_seed", json!(self.render_seed)),
+            ("max_image_mb", json!(self.max_image_mb)),
+            ("max_image_dimension", json!(self.max_image_dimension)),
+            ("min_free_space_mb", json!(self.min_free_space_mb)),
+            ("plugin_dir", json!(self.plugin_dir)),
+            ("allowed_commands", json!(self.allowed_commands)),
+            ("buttons", json!(self.buttons)),
+            ("button_debounce_ms", json!(self.button_debounce_ms)),
+            ("gpio_chip", json!(self.gpio_chip)),
+            ("profile", json!(self.profile)),
+        ];
+
+        values
+            .into_iter()
+            .map(|(key, value)| {
+                let source = self
+                    .sources
+                    .get(key)
+                    .copied()
+                    .unwrap_or(ConfigSource::Default);
+                (key, EffectiveSetting { value, source })
+            })
+            .collect()
+    }
+
+    /// Allowed commands by name, split into program and arguments
+    pub fn command_allowlist(&self) -> BTreeMap<String, Vec<String>> {
+        self.allowed_commands
+            .iter()
+            .filter_map(|spec| crate::utils::commands::parse_entry(spec).ok())
+            .collect()
+    }
+
+    /// Driver pass-through options split into key and value
+    pub fn driver_options(&self) -> Vec<(String, String)> {
+        self.driver_opts
+            .iter()
+            .filter_map(|opt| opt.split_once('='))
+            .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+            .collect()
+    }
+
+    /// Seconds playlist and brightness changes may wait before they are written, 0 writes
+    /// every change
+    pub fn save_interval_seconds(&self) -> u64 {
+        if self.low_wear {
+            self.save_interval_seconds
+                .max(LOW_WEAR_SAVE_INTERVAL_SECONDS)
+        } else {
+            self.save_interval_seconds
+        }
+    }
+
+    /// Time an unused image is kept after its upload
+    pub fn cleanup_grace(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.cleanup_grace_minutes as u64 * 60)
+    }
+
+    /// Largest accepted image upload in bytes
+    pub fn max_image_bytes(&self) -> usize {
+        (self.max_image_mb as usize).saturating_mul(1024 * 1024)
+    }
+
+    /// Free space in bytes the data directory keeps before uploads are rejected
+    pub fn min_free_space_bytes(&self) -> u64 {
+        self.min_free_space_mb as u64 * 1024 * 1024
+    }
+
     /// Calculate the total display width in pixels
     pub fn display_width(&self) -> i32 {
         (self.cols * self.chain_length) as i32
@@ -206,7 +1061,8 @@ impl DisplayConfig {
             errors.push("Parallel chains must be greater than 0".to_string());
         }
 
-        if self.parallel > 3 {
+        // The wall driver has no hardware, its canvas may be any number of panels high
+        if self.parallel > 3 && self.driver_type != DriverType::Wall {
             errors.push(
                 "Parallel chains must be between 1 and 3 (limitation of both drivers)".to_string(),
             );
@@ -233,10 +1089,145 @@ impl DisplayConfig {
             ));
         }
 
+        for spec in &self.wall_tiles {
+            match WallTile::parse(spec) {
+                Ok(tile)
+                    if tile.x + tile.width > self.display_width()
+                        || tile.y + tile.height > self.display_height() =>
+                {
+                    errors.push(format!(
+                        "Wall tile '{}' reaches outside the {}x{} canvas",
+                        spec,
+                        self.display_width(),
+                        self.display_height()
+                    ))
+                }
+                Ok(_) => {}
+                Err(e) => errors.push(e),
+            }
+        }
+        if self.driver_type == DriverType::Wall && self.wall_tiles.is_empty() {
+            errors.push("The wall driver needs at least one --wall-tile".to_string());
+        }
+
+        let mut command_names = std::collections::HashSet::new();
+        for spec in &self.allowed_commands {
+            match crate::utils::commands::parse_entry(spec) {
+                Ok((name, _)) if !command_names.insert(name.clone()) => {
+                    errors.push(format!("Command '{}' is allowed more than once", name))
+                }
+                Ok(_) => {}
+                Err(e) => errors.push(e),
+            }
+        }
+
+        let mut button_pins = std::collections::HashSet::new();
+        for spec in &self.buttons {
+            match crate::display::buttons::parse_button(spec) {
+                Ok((pin, _)) if !button_pins.insert(pin) => {
+                    errors.push(format!("Pin {} has more than one button", pin))
+                }
+                Ok(_) => {}
+                Err(e) => errors.push(e),
+            }
+        }
+
+        if let Some(mode) = &self.sync_mode {
+            if SyncMode::parse(mode).is_none() {
+                errors.push(format!(
+                    "Invalid sync mode '{}': use 'master' or 'follower'",
+                    mode
+                ));
+            }
+        }
+
+        if let Err(e) = self.sync_group.parse::<std::net::SocketAddr>() {
+            errors.push(format!(
+                "Invalid sync group '{}': {}. Use an address and port such as '{}'",
+                self.sync_group,
+                e,
+                crate::display::sync::DEFAULT_SYNC_GROUP
+            ));
+        }
+
+        for opt in &self.driver_opts {
+            match opt.split_once('=') {
+                Some((key, _)) if !key.trim().is_empty() => {}
+                _ => errors.push(format!(
+                    "Invalid driver option '{}': expected key=value",
+                    opt
+                )),
+            }
+        }
+
+        if let Some(timezone) = &self.timezone {
+            if timezone.parse::<chrono_tz::Tz>().is_err() {
+                errors.push(format!(
+                    "Invalid time zone '{}': use an IANA name such as 'Europe/Berlin'",
+                    timezone
+                ));
+            }
+        }
+
+        if let Some(locale) = &self.locale {
+            if crate::utils::locale::lookup(locale).is_none() {
+                errors.push(format!(
+                    "Unsupported locale '{}': use one of {}",
+                    locale,
+                    crate::utils::locale::supported()
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+        }
+
+        if self.bench_seconds == Some(0) {
+            errors.push("Benchmark duration must be greater than 0 seconds".to_string());
+        }
+
+        if self.max_image_mb == 0 || self.max_image_mb > MAX_IMAGE_MB {
+            errors.push(format!(
+                "Maximum image size must be between 1 and {} MB",
+                MAX_IMAGE_MB
+            ));
+        }
+
+        if self.max_image_dimension == 0 {
+            errors.push("Maximum image dimension must be greater than 0 pixels".to_string());
+        }
+
         if self.limit_max_brightness > 100 {
             errors.push("Maximum brightness limit must be between 0 and 100".to_string());
         }
 
+        if self
+            .power_limit_amps
+            .is_some_and(|amps| !amps.is_finite() || amps <= 0.0)
+        {
+            errors.push("Power limit must be greater than 0 A".to_string());
+        }
+
+        if !self.power_ma_per_channel.is_finite() || self.power_ma_per_channel <= 0.0 {
+            errors.push("Power calibration must be greater than 0 mA per channel".to_string());
+        }
+
+        if self.thermal_throttle_c > 0.0 && self.thermal_resume_c >= self.thermal_throttle_c {
+            errors.push(
+                "Thermal resume temperature must be below the throttle temperature".to_string(),
+            );
+        }
+
+        if self
+            .base_path
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '?' | '#' | '*' | ':' | '%'))
+        {
+            errors.push(format!(
+                "Base path '{}' may only contain plain path segments, e.g. /led",
+                self.base_path
+            ));
+        }
+
         if errors.is_empty() {
             Ok(())
         } else {
@@ -244,3 +1235,13 @@ impl DisplayConfig {
         }
     }
 }
+
+// "/led", "led/" and "/led/" all mean "/led", an empty path or "/" the root
+fn normalize_base_path(path: &str) -> String {
+    let path = path.trim().trim_matches('/');
+    if path.is_empty() {
+        String::new()
+    } else {
+        format!("/{}", path)
+    }
+}
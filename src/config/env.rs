@@ -26,7 +26,44 @@ pub struct EnvVars {
     pub limit_refresh_rate: Option<u32>,
     pub port: Option<u16>,
     pub interface: Option<String>,
+    pub unix_socket: Option<String>,
+    pub base_path: Option<String>,
+    pub allow_local_urls: Option<bool>,
+    pub ambient_udp_port: Option<u16>,
+    pub audio_device: Option<String>,
+    pub sync_mode: Option<String>,
+    pub sync_group: Option<String>,
+    pub headless: Option<bool>,
+    pub demo: Option<bool>,
+    pub burn_in_shift_minutes: Option<u32>,
+    pub max_item_seconds: Option<u64>,
+    pub delete_expired_items: Option<bool>,
+    pub trash_days: Option<u32>,
+    pub outro_ms: Option<u32>,
+    pub save_interval_seconds: Option<u64>,
+    pub low_wear: Option<bool>,
+    pub cache_dir: Option<String>,
+    pub cleanup_interval_minutes: Option<u32>,
+    pub cleanup_grace_minutes: Option<u32>,
+    pub timezone: Option<String>,
+    pub locale: Option<String>,
+    pub render_seed: Option<u64>,
+    pub max_image_mb: Option<u32>,
+    pub max_image_dimension: Option<u32>,
+    pub min_free_space_mb: Option<u32>,
     pub limit_max_brightness: Option<u8>,
+    pub power_limit_amps: Option<f32>,
+    pub power_ma_per_channel: Option<f32>,
+    pub thermal_throttle_c: Option<f32>,
+    pub thermal_resume_c: Option<f32>,
+    pub plugin_dir: Option<String>,
+    pub profile: Option<String>,
+    pub driver_opts: Option<Vec<String>>,
+    pub wall_tiles: Option<Vec<String>>,
+    pub allowed_commands: Option<Vec<String>>,
+    pub buttons: Option<Vec<String>>,
+    pub button_debounce_ms: Option<u64>,
+    pub gpio_chip: Option<String>,
 }
 
 /// Load configuration from environment variables
@@ -169,11 +206,250 @@ pub fn load_env_vars() -> EnvVars {
         env.interface = Some(value);
     }
 
+    if let Ok(value) = std::env::var("LED_UNIX_SOCKET") {
+        env.unix_socket = Some(value);
+    }
+
+    if let Ok(value) = std::env::var("LED_BASE_PATH") {
+        env.base_path = Some(value);
+    }
+
+    if let Ok(value) = std::env::var("LED_ALLOW_LOCAL_URLS") {
+        if let Ok(enabled) = value.parse::<bool>() {
+            env.allow_local_urls = Some(enabled);
+        } else if let Ok(enabled) = value.parse::<u8>() {
+            env.allow_local_urls = Some(enabled != 0);
+        }
+    }
+
+    if let Ok(value) = std::env::var("LED_AMBIENT_UDP_PORT") {
+        if let Ok(port) = value.parse() {
+            env.ambient_udp_port = Some(port);
+        }
+    }
+
+    if let Ok(value) = std::env::var("LED_AUDIO_DEVICE") {
+        env.audio_device = Some(value);
+    }
+
+    if let Ok(value) = std::env::var("LED_SYNC_MODE") {
+        env.sync_mode = Some(value);
+    }
+
+    if let Ok(value) = std::env::var("LED_SYNC_GROUP") {
+        env.sync_group = Some(value);
+    }
+
+    if let Ok(value) = std::env::var("LED_HEADLESS") {
+        if let Ok(enabled) = value.parse::<bool>() {
+            env.headless = Some(enabled);
+        } else if let Ok(enabled) = value.parse::<u8>() {
+            env.headless = Some(enabled != 0);
+        }
+    }
+
+    if let Ok(value) = std::env::var("LED_DEMO") {
+        if let Ok(enabled) = value.parse::<bool>() {
+            env.demo = Some(enabled);
+        } else if let Ok(enabled) = value.parse::<u8>() {
+            env.demo = Some(enabled != 0);
+        }
+    }
+
+    if let Ok(value) = std::env::var("LED_BURN_IN_SHIFT_MINUTES") {
+        if let Ok(minutes) = value.parse() {
+            env.burn_in_shift_minutes = Some(minutes);
+        }
+    }
+
+    if let Ok(value) = std::env::var("LED_MAX_ITEM_SECONDS") {
+        if let Ok(seconds) = value.parse() {
+            env.max_item_seconds = Some(seconds);
+        }
+    }
+
+    if let Ok(value) = std::env::var("LED_DELETE_EXPIRED_ITEMS") {
+        if let Ok(enabled) = value.parse::<bool>() {
+            env.delete_expired_items = Some(enabled);
+        } else if let Ok(enabled) = value.parse::<u8>() {
+            env.delete_expired_items = Some(enabled != 0);
+        }
+    }
+
+    if let Ok(value) = std::env::var("LED_TRASH_DAYS") {
+        if let Ok(days) = value.parse() {
+            env.trash_days = Some(days);
+        }
+    }
+
+    if let Ok(value) = std::env::var("LED_OUTRO_MS") {
+        if let Ok(ms) = value.parse() {
+            env.outro_ms = Some(ms);
+        }
+    }
+
+    if let Ok(value) = std::env::var("LED_SAVE_INTERVAL_SECONDS") {
+        if let Ok(seconds) = value.parse() {
+            env.save_interval_seconds = Some(seconds);
+        }
+    }
+
+    if let Ok(value) = std::env::var("LED_LOW_WEAR") {
+        if let Ok(enabled) = value.parse::<bool>() {
+            env.low_wear = Some(enabled);
+        } else if let Ok(enabled) = value.parse::<u8>() {
+            env.low_wear = Some(enabled != 0);
+        }
+    }
+
+    if let Ok(value) = std::env::var("LED_CACHE_DIR") {
+        env.cache_dir = Some(value);
+    }
+
+    if let Ok(value) = std::env::var("LED_CLEANUP_INTERVAL_MINUTES") {
+        if let Ok(minutes) = value.parse() {
+            env.cleanup_interval_minutes = Some(minutes);
+        }
+    }
+
+    if let Ok(value) = std::env::var("LED_CLEANUP_GRACE_MINUTES") {
+        if let Ok(minutes) = value.parse() {
+            env.cleanup_grace_minutes = Some(minutes);
+        }
+    }
+
+    if let Ok(value) = std::env::var("LED_TIMEZONE") {
+        env.timezone = Some(value);
+    }
+
+    if let Ok(value) = std::env::var("LED_LOCALE") {
+        env.locale = Some(value);
+    }
+
+    if let Ok(value) = std::env::var("LED_RENDER_SEED") {
+        if let Ok(seed) = value.parse() {
+            env.render_seed = Some(seed);
+        }
+    }
+
+    // Image upload limits
+    if let Ok(value) = std::env::var("LED_MAX_IMAGE_MB") {
+        if let Ok(megabytes) = value.parse() {
+            env.max_image_mb = Some(megabytes);
+        }
+    }
+
+    if let Ok(value) = std::env::var("LED_MAX_IMAGE_DIMENSION") {
+        if let Ok(pixels) = value.parse() {
+            env.max_image_dimension = Some(pixels);
+        }
+    }
+
+    if let Ok(value) = std::env::var("LED_MIN_FREE_SPACE_MB") {
+        if let Ok(megabytes) = value.parse() {
+            env.min_free_space_mb = Some(megabytes);
+        }
+    }
+
     if let Ok(value) = std::env::var("LED_LIMIT_MAX_BRIGHTNESS") {
         if let Ok(brightness_limit) = value.parse::<u8>() {
             env.limit_max_brightness = Some(brightness_limit.clamp(0, 100));
         }
     }
 
+    // Power guard
+    if let Ok(value) = std::env::var("LED_POWER_LIMIT_AMPS") {
+        if let Ok(amps) = value.parse() {
+            env.power_limit_amps = Some(amps);
+        }
+    }
+
+    if let Ok(value) = std::env::var("LED_POWER_MA_PER_CHANNEL") {
+        if let Ok(milliamps) = value.parse() {
+            env.power_ma_per_channel = Some(milliamps);
+        }
+    }
+
+    // Thermal throttling
+    if let Ok(value) = std::env::var("LED_THERMAL_THROTTLE_C") {
+        if let Ok(celsius) = value.parse() {
+            env.thermal_throttle_c = Some(celsius);
+        }
+    }
+
+    if let Ok(value) = std::env::var("LED_THERMAL_RESUME_C") {
+        if let Ok(celsius) = value.parse() {
+            env.thermal_resume_c = Some(celsius);
+        }
+    }
+
+    // Plugins
+    if let Ok(value) = std::env::var("LED_PLUGIN_DIR") {
+        env.plugin_dir = Some(value);
+    }
+
+    // Hardware profile
+    if let Ok(value) = std::env::var("LED_PROFILE") {
+        env.profile = Some(value);
+    }
+
+    // Driver option pass-through, comma-separated key=value pairs
+    if let Ok(value) = std::env::var("LED_DRIVER_OPTS") {
+        env.driver_opts = Some(
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|opt| !opt.is_empty())
+                .map(String::from)
+                .collect(),
+        );
+    }
+
+    // Wall tiles contain commas, so they are separated by semicolons
+    if let Ok(value) = std::env::var("LED_WALL_TILES") {
+        env.wall_tiles = Some(
+            value
+                .split(';')
+                .map(str::trim)
+                .filter(|tile| !tile.is_empty())
+                .map(String::from)
+                .collect(),
+        );
+    }
+
+    // Commands may contain commas, so they are separated by semicolons as well
+    if let Ok(value) = std::env::var("LED_ALLOW_COMMANDS") {
+        env.allowed_commands = Some(
+            value
+                .split(';')
+                .map(str::trim)
+                .filter(|command| !command.is_empty())
+                .map(String::from)
+                .collect(),
+        );
+    }
+
+    // Buttons
+    if let Ok(value) = std::env::var("LED_BUTTONS") {
+        env.buttons = Some(
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|button| !button.is_empty())
+                .map(String::from)
+                .collect(),
+        );
+    }
+
+    if let Ok(value) = std::env::var("LED_BUTTON_DEBOUNCE_MS") {
+        if let Ok(ms) = value.parse() {
+            env.button_debounce_ms = Some(ms);
+        }
+    }
+
+    if let Ok(value) = std::env::var("LED_GPIO_CHIP") {
+        env.gpio_chip = Some(value);
+    }
+
     env
 }
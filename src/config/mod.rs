@@ -3,19 +3,50 @@
 mod cli;
 mod display;
 mod env;
+mod profile;
+mod source;
+mod stored;
 
 pub use cli::CliArgs;
 pub use display::DisplayConfig;
 pub use env::{load_env_vars, EnvVars};
+pub use profile::{is_valid_profile_name, HardwareProfile};
+pub use source::{ConfigSource, EffectiveSetting};
+use std::collections::BTreeMap;
+pub use stored::StoredConfig;
 
-/// Initialize configuration from all sources (CLI, environment, etc.)
-pub fn init_config() -> DisplayConfig {
+/// Initialize configuration from all sources (CLI, environment, hardware profile, stored settings)
+pub fn init_config(
+    stored: StoredConfig,
+    profiles: &BTreeMap<String, HardwareProfile>,
+) -> DisplayConfig {
     // Parse CLI args first
     let cli_args = CliArgs::parse();
 
     // Load environment variables
     let env_vars = load_env_vars();
 
-    // Create DisplayConfig by combining CLI args and environment variables
-    DisplayConfig::new(cli_args, env_vars)
+    // Look up the selected hardware profile
+    let profile_name = env_vars
+        .profile
+        .clone()
+        .or_else(|| cli_args.profile.clone());
+    let profile = profile_name.as_ref().map(|name| match profiles.get(name) {
+        Some(profile) => profile.clone(),
+        None => {
+            println!("ERROR: Unknown hardware profile: {:?}", name);
+            if profiles.is_empty() {
+                println!(
+                    "\nNo profiles have been saved yet. Create one with PUT /api/profiles/NAME"
+                );
+            } else {
+                let names: Vec<&str> = profiles.keys().map(String::as_str).collect();
+                println!("\nAvailable profiles: {}", names.join(", "));
+            }
+            std::process::exit(1);
+        }
+    });
+
+    // Create DisplayConfig by combining CLI args, environment variables and stored settings
+    DisplayConfig::new(cli_args, env_vars, profile_name.zip(profile), stored)
 }
@@ -0,0 +1,38 @@
+//! Named hardware profiles stored in app storage
+
+use serde::{Deserialize, Serialize};
+
+/// Panel settings that differ between panel models, selected at startup with `--profile NAME`.
+/// Values set through CLI arguments or environment variables take precedence.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct HardwareProfile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub panel_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multiplexing: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pixel_mapper: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpio_slowdown: Option<u32>,
+}
+
+impl HardwareProfile {
+    /// Validate the profile values
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(slowdown) = self.gpio_slowdown {
+            if slowdown > 4 {
+                return Err("GPIO slowdown must be between 0 and 4".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Check that a profile name is usable on the command line and in URLs
+pub fn is_valid_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
@@ -0,0 +1,43 @@
+//! Tracking where each configuration value came from
+
+use serde::Serialize;
+
+/// Configuration layer a setting was resolved from, in order of precedence
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigSource {
+    /// Environment variable
+    Env,
+    /// Command-line argument
+    Cli,
+    /// Hardware profile selected with --profile
+    Profile,
+    /// Settings stored by the web interface (config.json)
+    File,
+    /// Built-in default
+    Default,
+}
+
+impl ConfigSource {
+    /// Pick the highest-precedence layer that provides a value
+    pub fn resolve(env: bool, cli: bool, profile: bool, file: bool) -> Self {
+        if env {
+            ConfigSource::Env
+        } else if cli {
+            ConfigSource::Cli
+        } else if profile {
+            ConfigSource::Profile
+        } else if file {
+            ConfigSource::File
+        } else {
+            ConfigSource::Default
+        }
+    }
+}
+
+/// A resolved setting together with its source
+#[derive(Clone, Debug, Serialize)]
+pub struct EffectiveSetting {
+    pub value: serde_json::Value,
+    pub source: ConfigSource,
+}
@@ -0,0 +1,34 @@
+//! Settings persisted in app storage by the web interface
+
+use serde::{Deserialize, Serialize};
+
+/// Display settings saved to `config.json` in the storage directory.
+/// They only apply when neither a CLI argument nor an environment variable sets the same option.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct StoredConfig {
+    /// Whether the first-boot setup wizard has been completed
+    #[serde(default)]
+    pub setup_complete: bool,
+
+    /// Driver type ("native" or "binding") chosen in the setup wizard
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub driver: Option<String>,
+
+    /// Panel geometry chosen in the setup wizard
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rows: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cols: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_length: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parallel: Option<usize>,
+
+    /// LED color sequence, e.g. the result of color-order detection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub led_sequence: Option<String>,
+
+    /// IANA time zone chosen in the setup wizard
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
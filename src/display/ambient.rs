@@ -0,0 +1,101 @@
+//! Ambient light input: color frames received over UDP (e.g. from Hyperion or other
+//! ambilight senders using the raw UDP protocol) replace the playlist while they keep arriving
+
+use crate::display::driver::LedCanvas;
+use crate::display::manager::DisplayManager;
+use crate::display::renderer::RenderContext;
+use log::{debug, error, info, warn};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::UdpSocket;
+
+// Without new frames for this long the playlist is shown again
+pub const AMBIENT_TIMEOUT: Duration = Duration::from_secs(2);
+
+// Largest accepted datagram, enough for a full 128x128 frame
+pub const MAX_PACKET_BYTES: usize = 128 * 128 * 3;
+
+/// One received frame: either a color per panel pixel or one color for the whole panel
+#[derive(Clone, Debug)]
+pub enum AmbientFrame {
+    Solid([u8; 3]),
+    Pixels { width: usize, pixels: Vec<[u8; 3]> },
+}
+
+impl AmbientFrame {
+    /// Parse a raw RGB packet. A packet with exactly one color per panel pixel is drawn as is,
+    /// any other number of LED colors is averaged into a single ambient color.
+    pub fn from_packet(data: &[u8], width: i32, height: i32) -> Option<Self> {
+        if data.len() < 3 {
+            return None;
+        }
+        let colors = data.chunks_exact(3).map(|rgb| [rgb[0], rgb[1], rgb[2]]);
+
+        let (width, height) = (width.max(0) as usize, height.max(0) as usize);
+        if data.len() == width * height * 3 {
+            return Some(Self::Pixels {
+                width,
+                pixels: colors.collect(),
+            });
+        }
+
+        let mut sum = [0u32; 3];
+        let mut count = 0;
+        for color in colors {
+            for (total, channel) in sum.iter_mut().zip(color) {
+                *total += channel as u32;
+            }
+            count += 1;
+        }
+        Some(Self::Solid(sum.map(|total| (total / count) as u8)))
+    }
+
+    pub fn draw(&self, canvas: &mut Box<dyn LedCanvas>, ctx: &RenderContext) {
+        match self {
+            Self::Solid(color) => {
+                let [r, g, b] = ctx.apply_brightness(*color);
+                canvas.fill(r, g, b);
+            }
+            Self::Pixels { width, pixels } => {
+                for (index, color) in pixels.iter().enumerate() {
+                    let [r, g, b] = ctx.apply_brightness(*color);
+                    canvas.set_pixel(index % width, index / width, r, g, b);
+                }
+            }
+        }
+    }
+}
+
+/// Receive ambient frames on `addr` and hand them to the display manager
+pub async fn ambient_listener(display: Arc<tokio::sync::Mutex<DisplayManager>>, addr: SocketAddr) {
+    let socket = match UdpSocket::bind(addr).await {
+        Ok(socket) => socket,
+        Err(e) => {
+            error!("Failed to bind ambient UDP listener to {}: {}", addr, e);
+            return;
+        }
+    };
+    info!("Listening for ambient color frames on udp://{}", addr);
+
+    let mut buffer = vec![0u8; MAX_PACKET_BYTES];
+    loop {
+        let len = match socket.recv(&mut buffer).await {
+            Ok(len) => len,
+            Err(e) => {
+                warn!("Failed to receive ambient frame: {}", e);
+                continue;
+            }
+        };
+
+        let mut display = display.lock().await;
+        match AmbientFrame::from_packet(
+            &buffer[..len],
+            display.display_width,
+            display.display_height,
+        ) {
+            Some(frame) => display.set_ambient_frame(frame),
+            None => debug!("Ignoring ambient frame of {} bytes", len),
+        }
+    }
+}
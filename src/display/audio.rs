@@ -0,0 +1,130 @@
+//! Audio input: the sound level of an ALSA capture device, recorded with `arecord`, drives
+//! animations marked reactive
+
+use log::{debug, info, warn};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use tokio::process::Command;
+
+// Samples per second recorded, plenty for a level meter
+const SAMPLE_RATE: u32 = 16_000;
+
+// Bytes of 16-bit mono samples read per level update, 20 ms of audio
+const CHUNK_BYTES: usize = (SAMPLE_RATE as usize / 50) * 2;
+
+// How long to wait before starting arecord again after it failed or exited
+const RESTART_DELAY: Duration = Duration::from_secs(5);
+
+// Quietest level treated as full scale, so silence and hum stay dark instead of being
+// amplified by the automatic gain
+const MIN_PEAK: f32 = 0.02;
+
+// Level of the envelope (0-1) as f32 bits
+static LEVEL: AtomicU32 = AtomicU32::new(0);
+
+// Whether a capture device is configured and whether it is delivering samples right now
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static CAPTURING: AtomicBool = AtomicBool::new(false);
+
+/// Whether audio capture was configured with --audio-device
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Current sound level from 0 (silent) to 1 (loudest recent sound), None while no audio
+/// is being captured
+pub fn level() -> Option<f32> {
+    if !CAPTURING.load(Ordering::Relaxed) {
+        return None;
+    }
+    Some(f32::from_bits(LEVEL.load(Ordering::Relaxed)))
+}
+
+/// Follow the sound level of `device`, restarting the recording whenever it ends
+pub async fn audio_capture(device: String) {
+    ENABLED.store(true, Ordering::Relaxed);
+    // Only the first of several failed attempts in a row is logged as a warning
+    let mut failing = false;
+    loop {
+        if !failing {
+            info!("Capturing the sound level of ALSA device {}", device);
+        }
+        let result = capture(&device).await;
+        let captured = CAPTURING.load(Ordering::Relaxed);
+        let message = match result {
+            Ok(()) => format!("Audio capture from {} ended", device),
+            Err(e) => format!("Audio capture from {} failed: {}", device, e),
+        };
+        if captured || !failing {
+            warn!("{}, retrying every {}s", message, RESTART_DELAY.as_secs());
+        } else {
+            debug!("{}", message);
+        }
+        failing = !captured;
+        CAPTURING.store(false, Ordering::Relaxed);
+        LEVEL.store(0f32.to_bits(), Ordering::Relaxed);
+        tokio::time::sleep(RESTART_DELAY).await;
+    }
+}
+
+// Record until arecord exits, updating the level envelope every chunk
+async fn capture(device: &str) -> std::io::Result<()> {
+    let mut child = Command::new("arecord")
+        .args(["-q", "-D", device, "-f", "S16_LE", "-c", "1", "-t", "raw"])
+        .args(["-r", &SAMPLE_RATE.to_string()])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()?;
+    let Some(mut stdout) = child.stdout.take() else {
+        return Ok(());
+    };
+
+    let mut chunk = vec![0u8; CHUNK_BYTES];
+    let mut envelope = Envelope::default();
+    while stdout.read_exact(&mut chunk).await.is_ok() {
+        let level = envelope.update(rms(&chunk));
+        LEVEL.store(level.to_bits(), Ordering::Relaxed);
+        CAPTURING.store(true, Ordering::Relaxed);
+    }
+    child.wait().await?;
+    Ok(())
+}
+
+// Root mean square of little-endian 16-bit samples, 1.0 at full scale
+fn rms(chunk: &[u8]) -> f32 {
+    let samples = chunk.len() / 2;
+    if samples == 0 {
+        return 0.0;
+    }
+    let sum: f64 = chunk
+        .chunks_exact(2)
+        .map(|bytes| {
+            let sample = i16::from_le_bytes([bytes[0], bytes[1]]) as f64 / 32_768.0;
+            sample * sample
+        })
+        .sum();
+    (sum / samples as f64).sqrt() as f32
+}
+
+// Smoothed level relative to the loudest recent sound. Rises quickly so beats register and
+// falls slowly so animations do not flicker.
+#[derive(Default)]
+struct Envelope {
+    peak: f32,
+    level: f32,
+}
+
+impl Envelope {
+    fn update(&mut self, rms: f32) -> f32 {
+        // The reference peak halves within about 15 seconds once the sound gets quieter
+        self.peak = rms.max(self.peak * 0.999).max(MIN_PEAK);
+        let target = (rms / self.peak).min(1.0);
+        let rate = if target > self.level { 0.6 } else { 0.08 };
+        self.level += (target - self.level) * rate;
+        self.level
+    }
+}
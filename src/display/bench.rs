@@ -0,0 +1,402 @@
+//! Renderer benchmark used by `--bench`.
+//!
+//! Runs one sample item per renderer type through the regular display pipeline on the
+//! null driver and reports how fast frames can be produced at the configured panel size.
+
+use crate::config::DisplayConfig;
+use crate::display::driver::create_driver;
+use crate::display::manager::DisplayManager;
+use crate::models::animation::{AnimationContent, MeteorDirection, WeatherKind};
+use crate::models::barcode::{BarcodeContent, BarcodeFormat};
+use crate::models::big_number::BigNumberContent;
+use crate::models::border_effects::BorderEffect;
+use crate::models::clock::{ClockContent, ClockFormat, ClockTransition};
+use crate::models::content::{ContentData, ContentDetails, ContentType};
+use crate::models::countdown::{CountdownContent, CountdownThreshold, ThresholdEffect};
+use crate::models::playlist::{PlayListItem, Playlist};
+use crate::models::qrcode::{QrCodeContent, QrErrorCorrection};
+use crate::models::text::{TextContent, TextOverflow};
+use std::time::{Duration, Instant};
+
+const PALETTE: [[u8; 3]; 3] = [[255, 0, 0], [0, 255, 0], [0, 0, 255]];
+
+/// Frame timings collected for one sample
+struct BenchResult {
+    label: &'static str,
+    frames: usize,
+    elapsed: Duration,
+    frame_times: Vec<Duration>,
+}
+
+impl BenchResult {
+    fn fps(&self) -> f64 {
+        self.frames as f64 / self.elapsed.as_secs_f64()
+    }
+
+    /// Frame time at the given percentile (0-100) in microseconds
+    fn percentile_us(&self, percentile: f64) -> f64 {
+        if self.frame_times.is_empty() {
+            return 0.0;
+        }
+        let rank = (percentile / 100.0 * (self.frame_times.len() - 1) as f64).round() as usize;
+        self.frame_times[rank].as_secs_f64() * 1_000_000.0
+    }
+}
+
+fn sample_item(content_type: ContentType, data: ContentDetails) -> PlayListItem {
+    PlayListItem {
+        id: format!("bench-{}", content_type.as_str().to_lowercase()),
+        duration: None,
+        repeat_count: Some(0),
+        border_effect: None,
+        border_reactive: false,
+        outro_ms: None,
+        content: ContentData { content_type, data },
+        expires_at: None,
+        interstitial: None,
+        tags: Vec::new(),
+        enabled: true,
+        conditions: Vec::new(),
+        created_for: None,
+        created_at: None,
+        updated_at: None,
+        author: None,
+    }
+}
+
+fn text_item(scroll: bool) -> PlayListItem {
+    sample_item(
+        ContentType::Text,
+        ContentDetails::Text(TextContent {
+            text: "The quick brown fox jumps over the lazy dog 0123456789".to_string(),
+            scroll,
+            color: [255, 255, 255],
+            speed: 50.0,
+            text_segments: None,
+            pixels_per_second: None,
+            read_time_ms: None,
+            scroll_ease_ms: None,
+            antialias: false,
+            paging: None,
+            overflow: TextOverflow::default(),
+        }),
+    )
+}
+
+fn animation_item(animation: AnimationContent) -> PlayListItem {
+    sample_item(ContentType::Animation, ContentDetails::Animation(animation))
+}
+
+/// One sample item per renderer type, labelled for the report
+fn samples() -> Vec<(&'static str, PlayListItem)> {
+    let colors = PALETTE.to_vec();
+
+    let mut bordered = text_item(true);
+    bordered.border_effect = Some(BorderEffect::Rainbow);
+
+    vec![
+        ("Text (static)", text_item(false)),
+        ("Text (scrolling)", text_item(true)),
+        ("Text + rainbow border", bordered),
+        (
+            "Clock (flip)",
+            sample_item(
+                ContentType::Clock,
+                ContentDetails::Clock(ClockContent {
+                    format: ClockFormat::TwentyFourHour,
+                    show_seconds: true,
+                    color: [255, 255, 255],
+                    transition: ClockTransition::Flip,
+                    transition_ms: 400,
+                    show_date: false,
+                }),
+            ),
+        ),
+        (
+            "QR code + caption",
+            sample_item(
+                ContentType::QrCode,
+                ContentDetails::QrCode(QrCodeContent {
+                    data: "https://example.com".to_string(),
+                    error_correction: QrErrorCorrection::M,
+                    foreground: [0, 0, 0],
+                    background: [255, 255, 255],
+                    quiet_zone: 4,
+                    caption: Some("Scan for the menu".to_string()),
+                    caption_color: [255, 255, 255],
+                }),
+            ),
+        ),
+        (
+            "Barcode (Code 128)",
+            sample_item(
+                ContentType::Barcode,
+                ContentDetails::Barcode(BarcodeContent {
+                    format: BarcodeFormat::Code128,
+                    data: "1234".to_string(),
+                    foreground: [0, 0, 0],
+                    background: [255, 255, 255],
+                    quiet_zone: 10,
+                    show_text: true,
+                    text_color: [255, 255, 255],
+                }),
+            ),
+        ),
+        (
+            "Big number + unit",
+            sample_item(
+                ContentType::BigNumber,
+                ContentDetails::BigNumber(BigNumberContent {
+                    value: "21.5".to_string(),
+                    unit: Some("°C".to_string()),
+                    decimals: None,
+                    color: [255, 160, 0],
+                    unit_color: None,
+                }),
+            ),
+        ),
+        (
+            "Countdown (pulsing)",
+            sample_item(
+                ContentType::Countdown,
+                ContentDetails::Countdown(CountdownContent {
+                    target: (chrono::Utc::now() + chrono::Duration::minutes(3)).to_rfc3339(),
+                    color: [255, 255, 255],
+                    finished_text: "GO!".to_string(),
+                    thresholds: vec![CountdownThreshold {
+                        remaining_seconds: 300,
+                        color: [255, 0, 0],
+                        effect: ThresholdEffect::Pulse,
+                    }],
+                }),
+            ),
+        ),
+        (
+            "Animation: Pulse",
+            animation_item(AnimationContent::Pulse {
+                colors: colors.clone(),
+                palette: None,
+                reactive: false,
+                cycle_ms: 2_000,
+            }),
+        ),
+        (
+            "Animation: Palette Wave",
+            animation_item(AnimationContent::PaletteWave {
+                colors: colors.clone(),
+                palette: None,
+                reactive: false,
+                cycle_ms: 2_000,
+                wave_count: 3,
+            }),
+        ),
+        (
+            "Animation: Dual Pulse",
+            animation_item(AnimationContent::DualPulse {
+                colors: colors.clone(),
+                palette: None,
+                reactive: false,
+                cycle_ms: 2_000,
+                phase_offset: 0.5,
+            }),
+        ),
+        (
+            "Animation: Color Fade",
+            animation_item(AnimationContent::ColorFade {
+                colors: colors.clone(),
+                palette: None,
+                reactive: false,
+                drift_speed: 0.25,
+            }),
+        ),
+        (
+            "Animation: Strobe",
+            animation_item(AnimationContent::Strobe {
+                colors: colors.clone(),
+                palette: None,
+                reactive: false,
+                flash_ms: 180,
+                fade_ms: 220,
+                randomize: true,
+                randomization_factor: 0.35,
+            }),
+        ),
+        (
+            "Animation: Sparkle",
+            animation_item(AnimationContent::Sparkle {
+                colors: colors.clone(),
+                palette: None,
+                reactive: false,
+                density: 0.12,
+                twinkle_ms: 600,
+            }),
+        ),
+        (
+            "Animation: Mosaic Twinkle",
+            animation_item(AnimationContent::MosaicTwinkle {
+                colors: colors.clone(),
+                palette: None,
+                reactive: false,
+                tile_size: 1,
+                flow_speed: 0.35,
+                border_size: 0,
+                border_color: [50, 0, 0],
+            }),
+        ),
+        (
+            "Animation: Plasma Flow",
+            animation_item(AnimationContent::Plasma {
+                colors: colors.clone(),
+                palette: None,
+                reactive: false,
+                flow_speed: 1.85,
+                noise_scale: 1.75,
+            }),
+        ),
+        (
+            "Animation: Meteor",
+            animation_item(AnimationContent::Meteor {
+                colors: colors.clone(),
+                palette: None,
+                reactive: false,
+                direction: MeteorDirection::DownLeft,
+                density: 0.15,
+                speed: 40.0,
+                trail_ms: 700,
+            }),
+        ),
+        (
+            "Animation: Fireworks",
+            animation_item(AnimationContent::Fireworks {
+                colors: colors.clone(),
+                palette: None,
+                reactive: false,
+                burst_rate: 0.8,
+                spark_count: 28,
+                gravity: 40.0,
+            }),
+        ),
+        (
+            "Animation: Noise Field",
+            animation_item(AnimationContent::NoiseField {
+                colors,
+                palette: None,
+                reactive: false,
+                octaves: 3,
+                speed: 0.15,
+                cycle_ms: 30_000,
+            }),
+        ),
+        (
+            "Animation: Pixel Weather (Rain)",
+            animation_item(AnimationContent::PixelWeather {
+                colors: Vec::new(),
+                palette: None,
+                reactive: false,
+                weather: WeatherKind::Rain,
+                intensity: 0.5,
+            }),
+        ),
+        (
+            "Animation: Pixel Weather (Snow)",
+            animation_item(AnimationContent::PixelWeather {
+                colors: Vec::new(),
+                palette: None,
+                reactive: false,
+                weather: WeatherKind::Snow,
+                intensity: 0.5,
+            }),
+        ),
+        (
+            "Animation: Pixel Weather (Sun)",
+            animation_item(AnimationContent::PixelWeather {
+                colors: Vec::new(),
+                palette: None,
+                reactive: false,
+                weather: WeatherKind::Sun,
+                intensity: 0.5,
+            }),
+        ),
+    ]
+}
+
+/// Render `item` as fast as possible for `duration`, timing every frame
+fn bench_item(
+    config: &DisplayConfig,
+    label: &'static str,
+    item: PlayListItem,
+    duration: Duration,
+) -> Result<BenchResult, String> {
+    let driver = create_driver(config)?;
+    let playlist = Playlist {
+        items: vec![item],
+        active_index: 0,
+        repeat: true,
+    };
+    let mut display = DisplayManager::with_playlist_config_and_driver(playlist, config, driver);
+
+    let mut frame_times = Vec::new();
+    let start = Instant::now();
+    let mut last_frame = start;
+
+    while start.elapsed() < duration {
+        let frame_start = Instant::now();
+        let dt = frame_start.duration_since(last_frame).as_secs_f32();
+        last_frame = frame_start;
+
+        display.update_renderer(dt);
+        display.update_display();
+
+        frame_times.push(frame_start.elapsed());
+    }
+
+    let elapsed = start.elapsed();
+    frame_times.sort_unstable();
+
+    Ok(BenchResult {
+        label,
+        frames: frame_times.len(),
+        elapsed,
+        frame_times,
+    })
+}
+
+/// Run the benchmark and print a report to stdout
+pub fn run_bench(config: &DisplayConfig, seconds: u64) -> Result<(), String> {
+    let duration = Duration::from_secs(seconds);
+    let samples = samples();
+
+    println!(
+        "Benchmarking {} renderers at {}x{} for {}s each",
+        samples.len(),
+        config.display_width(),
+        config.display_height(),
+        seconds
+    );
+
+    let mut results = Vec::with_capacity(samples.len());
+    for (label, item) in samples {
+        results.push(bench_item(config, label, item, duration)?);
+    }
+
+    println!();
+    println!(
+        "{:<28} {:>10} {:>10} {:>10} {:>10} {:>10}",
+        "Renderer", "FPS", "p50 (us)", "p95 (us)", "p99 (us)", "max (us)"
+    );
+    for result in &results {
+        println!(
+            "{:<28} {:>10.1} {:>10.1} {:>10.1} {:>10.1} {:>10.1}",
+            result.label,
+            result.fps(),
+            result.percentile_us(50.0),
+            result.percentile_us(95.0),
+            result.percentile_us(99.0),
+            result.percentile_us(100.0)
+        );
+    }
+    println!();
+    println!("Images are not included: they depend on uploaded files");
+
+    Ok(())
+}
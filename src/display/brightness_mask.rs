@@ -0,0 +1,50 @@
+//! Brightness mask: a per-pixel multiplier map applied to every frame, so enclosures that
+//! diffuse unevenly can dim hotspots and bright edges. The mask is a grayscale PNG where
+//! white keeps a pixel's brightness and black turns it off, stretched to the panel size.
+
+use crate::display::driver::LedCanvas;
+use crate::display::graphics::frame_buffer::FrameBuffer;
+use image::imageops::FilterType;
+
+#[derive(Debug)]
+pub struct BrightnessMask {
+    width: i32,
+    height: i32,
+    // One multiplier per panel pixel, 0-255 for 0-100%
+    levels: Vec<u8>,
+}
+
+impl BrightnessMask {
+    /// Decode a mask image and scale it to a `width` x `height` panel. Colored images are
+    /// converted to grayscale.
+    pub fn from_image(bytes: &[u8], width: i32, height: i32) -> Result<Self, String> {
+        let decoded = image::load_from_memory(bytes)
+            .map_err(|e| format!("failed to decode mask image: {}", e))?
+            .to_luma8();
+        let (panel_width, panel_height) = (width.max(1) as u32, height.max(1) as u32);
+        let scaled = if decoded.dimensions() == (panel_width, panel_height) {
+            decoded
+        } else {
+            image::imageops::resize(&decoded, panel_width, panel_height, FilterType::Triangle)
+        };
+
+        Ok(Self {
+            width: panel_width as i32,
+            height: panel_height as i32,
+            levels: scaled.into_raw(),
+        })
+    }
+
+    /// Scale every pixel of `frame` by its mask level
+    pub fn apply(&self, frame: &mut FrameBuffer) {
+        let (width, height) = frame.size();
+        if (width, height) != (self.width, self.height) {
+            return;
+        }
+        for (pixel, level) in frame.pixels_mut().iter_mut().zip(&self.levels) {
+            if *level != u8::MAX {
+                *pixel = pixel.map(|c| (c as u16 * *level as u16 / 255) as u8);
+            }
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! Anti burn-in protection: moves the whole picture by up to one pixel every few minutes
+//! and briefly exercises the edge pixels that the shifts leave dark
+
+use crate::display::driver::LedCanvas;
+use crate::display::graphics::frame_buffer::FrameBuffer;
+use log::debug;
+use std::time::{Duration, Instant};
+
+// Offsets visited in turn, circling around the original position
+const ORBIT: [(i32, i32); 9] = [
+    (0, 0),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+// How long the edge pixels stay inverted after each shift
+const EXERCISE_DURATION: Duration = Duration::from_secs(1);
+
+#[derive(Debug)]
+pub struct BurnInGuard {
+    interval: Duration,
+    step: usize,
+    last_shift: Instant,
+    exercise_until: Option<Instant>,
+}
+
+impl BurnInGuard {
+    pub fn new(interval_minutes: u32) -> Self {
+        Self {
+            interval: Duration::from_secs(interval_minutes as u64 * 60),
+            step: 0,
+            last_shift: Instant::now(),
+            exercise_until: None,
+        }
+    }
+
+    /// Move on to the next offset once the interval has passed
+    pub fn update(&mut self) {
+        if self.last_shift.elapsed() < self.interval {
+            return;
+        }
+
+        self.step = (self.step + 1) % ORBIT.len();
+        self.last_shift = Instant::now();
+        self.exercise_until = Some(self.last_shift + EXERCISE_DURATION);
+        debug!("Burn-in shift: offset now {:?}", ORBIT[self.step]);
+    }
+
+    /// Copy `frame` onto `canvas` at the current offset. While the edges are exercised the
+    /// outermost ring of pixels is inverted against `exercise_color`.
+    pub fn draw(
+        &self,
+        frame: &FrameBuffer,
+        canvas: &mut Box<dyn LedCanvas>,
+        exercise_color: [u8; 3],
+    ) {
+        let (dx, dy) = ORBIT[self.step];
+        let exercising = self
+            .exercise_until
+            .is_some_and(|until| Instant::now() < until);
+        let (width, height) = frame.size();
+
+        for y in 0..height {
+            for x in 0..width {
+                let mut color = frame.pixel(x - dx, y - dy).unwrap_or([0, 0, 0]);
+                if exercising && (x == 0 || y == 0 || x == width - 1 || y == height - 1) {
+                    color = [0, 1, 2].map(|i| exercise_color[i].saturating_sub(color[i]));
+                }
+                if color != [0, 0, 0] {
+                    canvas.set_pixel(x as usize, y as usize, color[0], color[1], color[2]);
+                }
+            }
+        }
+    }
+}
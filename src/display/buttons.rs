@@ -0,0 +1,186 @@
+//! Physical push buttons on GPIO pins (--button), so a wall-mounted sign can be operated
+//! without a phone. Buttons connect their pin to ground; the pins are read on a background
+//! thread and debounced before the mapped action runs.
+
+use crate::models::action::Action;
+use crate::models::settings::MAX_BRIGHTNESS;
+use crate::web::api::actions::dispatch;
+use crate::web::api::CombinedState;
+use gpio_cdev::{Chip, LineHandle, LineRequestFlags};
+use log::{debug, info, warn};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+// How often the pins are read
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+// Brightness change per press of brightness-up or brightness-down, in percent
+const BRIGHTNESS_STEP: u8 = 10;
+
+/// What pressing a button does
+#[derive(Clone, Debug, PartialEq)]
+pub enum ButtonAction {
+    Next,
+    BrightnessUp,
+    BrightnessDown,
+    PowerToggle,
+    // Show the playlist item with this ID
+    ShowItem(String),
+}
+
+impl ButtonAction {
+    fn parse(action: &str) -> Option<Self> {
+        match action {
+            "next" => Some(Self::Next),
+            "brightness-up" => Some(Self::BrightnessUp),
+            "brightness-down" => Some(Self::BrightnessDown),
+            "power" => Some(Self::PowerToggle),
+            _ => action
+                .strip_prefix("show:")
+                .filter(|id| !id.is_empty())
+                .map(|id| Self::ShowItem(id.to_string())),
+        }
+    }
+}
+
+/// Parse a button mapping "PIN=ACTION", where PIN is the line offset on the GPIO chip
+/// (the BCM number on a Raspberry Pi)
+pub fn parse_button(spec: &str) -> Result<(u32, ButtonAction), String> {
+    let invalid = || {
+        format!(
+            "Invalid button '{}': expected PIN=ACTION with ACTION one of next, brightness-up, \
+             brightness-down, power or show:ITEM_ID",
+            spec
+        )
+    };
+    let (pin, action) = spec.split_once('=').ok_or_else(invalid)?;
+    let pin = pin.trim().parse().map_err(|_| invalid())?;
+    let action = ButtonAction::parse(action.trim()).ok_or_else(invalid)?;
+    Ok((pin, action))
+}
+
+pub struct Button {
+    pin: u32,
+    action: ButtonAction,
+    line: LineHandle,
+    // Debounced state and the raw reading it may change to
+    pressed: bool,
+    raw: bool,
+    raw_since: Instant,
+    // Whether the last read failed, so a broken pin warns once rather than on every poll
+    failing: bool,
+}
+
+/// Watch the buttons and run their actions until the program exits
+pub async fn monitor(combined_state: CombinedState, buttons: Vec<Button>, debounce: Duration) {
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    std::thread::spawn(move || poll_buttons(buttons, debounce, sender));
+
+    while let Some((pin, action)) = receiver.recv().await {
+        debug!("Button on pin {} pressed: {:?}", pin, action);
+        let action = resolve(&combined_state, action).await;
+        if let Err(status) = dispatch(&combined_state, action).await {
+            warn!("Button on pin {} failed: {}", pin, status);
+        }
+    }
+}
+
+/// Claim the pins of the buttons given as PIN=ACTION. Needs access to the GPIO chip, so
+/// this runs before privileges are dropped.
+pub fn open(chip_path: &str, specs: &[String]) -> Result<Vec<Button>, String> {
+    let mut chip =
+        Chip::new(chip_path).map_err(|e| format!("failed to open {}: {}", chip_path, e))?;
+    let mut buttons = Vec::new();
+    for spec in specs {
+        let (pin, action) = parse_button(spec)?;
+        // Active low: the pin reads 1 while the button pulls it to ground
+        let line = chip
+            .get_line(pin)
+            .and_then(|line| {
+                line.request(
+                    LineRequestFlags::INPUT | LineRequestFlags::ACTIVE_LOW,
+                    0,
+                    "led-sign-button",
+                )
+            })
+            .map_err(|e| format!("failed to claim pin {}: {}", pin, e))?;
+        buttons.push(Button {
+            pin,
+            action,
+            line,
+            pressed: false,
+            raw: false,
+            raw_since: Instant::now(),
+            failing: false,
+        });
+    }
+    info!("Listening to {} button(s) on {}", buttons.len(), chip_path);
+    Ok(buttons)
+}
+
+// Read the pins and send the action of every button that has been held down for the
+// debounce time. Runs until the receiving side is gone.
+fn poll_buttons(
+    mut buttons: Vec<Button>,
+    debounce: Duration,
+    sender: mpsc::UnboundedSender<(u32, ButtonAction)>,
+) {
+    loop {
+        for button in &mut buttons {
+            let raw = match button.line.get_value() {
+                Ok(value) => {
+                    if button.failing {
+                        info!("Button on pin {} can be read again", button.pin);
+                        button.failing = false;
+                    }
+                    value == 1
+                }
+                Err(e) => {
+                    if !button.failing {
+                        warn!("Failed to read button on pin {}: {}", button.pin, e);
+                        button.failing = true;
+                    }
+                    continue;
+                }
+            };
+            if raw != button.raw {
+                button.raw = raw;
+                button.raw_since = Instant::now();
+            }
+            if button.raw != button.pressed && button.raw_since.elapsed() >= debounce {
+                button.pressed = button.raw;
+                if button.pressed && sender.send((button.pin, button.action.clone())).is_err() {
+                    return;
+                }
+            }
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+// Turn a press into the action it stands for, relative actions are resolved against the
+// current state
+async fn resolve(combined_state: &CombinedState, action: ButtonAction) -> Action {
+    let ((display, _), _) = combined_state;
+    match action {
+        ButtonAction::Next => Action::NextItem,
+        ButtonAction::ShowItem(id) => Action::ShowItem(id),
+        ButtonAction::PowerToggle => Action::Power(!display.lock().await.is_powered_on()),
+        ButtonAction::BrightnessUp => {
+            let display = display.lock().await;
+            Action::SetBrightness(
+                display
+                    .get_brightness()
+                    .saturating_add(BRIGHTNESS_STEP)
+                    .min(MAX_BRIGHTNESS),
+            )
+        }
+        ButtonAction::BrightnessDown => Action::SetBrightness(
+            display
+                .lock()
+                .await
+                .get_brightness()
+                .saturating_sub(BRIGHTNESS_STEP),
+        ),
+    }
+}
@@ -0,0 +1,109 @@
+//! Interactive detection of the panel's LED color sequence.
+//!
+//! The display shows a pure test color and the user reports which color they actually see.
+//! Each answer rules out the `led_sequence` candidates that could not have produced it;
+//! red and green are enough to single out one of the six possible sequences.
+
+use serde::{Deserialize, Serialize};
+
+/// All sequences supported by the drivers
+const CANDIDATES: [&str; 6] = ["RGB", "RBG", "GRB", "GBR", "BRG", "BGR"];
+
+/// Colors shown to the user, in order
+const TEST_COLORS: [ColorChannel; 2] = [ColorChannel::Red, ColorChannel::Green];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChannel {
+    Red,
+    Green,
+    Blue,
+}
+
+impl ColorChannel {
+    fn letter(self) -> char {
+        match self {
+            ColorChannel::Red => 'R',
+            ColorChannel::Green => 'G',
+            ColorChannel::Blue => 'B',
+        }
+    }
+
+    /// Pure RGB value for this channel
+    pub fn rgb(self) -> [u8; 3] {
+        match self {
+            ColorChannel::Red => [255, 0, 0],
+            ColorChannel::Green => [0, 255, 0],
+            ColorChannel::Blue => [0, 0, 255],
+        }
+    }
+}
+
+/// Outcome of reporting an observed color
+pub enum DetectionStep {
+    /// More answers are needed, the next test color is shown
+    Continue,
+    /// Exactly one sequence matches all answers
+    Detected(String),
+    /// The answers contradict each other
+    Inconsistent,
+}
+
+/// State of a running color-order detection
+pub struct ColorOrderDetection {
+    /// Sequence the driver was started with; the colors the user sees are produced through it
+    active_sequence: String,
+    candidates: Vec<&'static str>,
+    step: usize,
+}
+
+impl ColorOrderDetection {
+    pub fn new(active_sequence: &str) -> Self {
+        let active_sequence = active_sequence.to_uppercase();
+        // Unknown sequences are treated as the driver default
+        let active_sequence = if CANDIDATES.contains(&active_sequence.as_str()) {
+            active_sequence
+        } else {
+            "RGB".to_string()
+        };
+
+        Self {
+            active_sequence,
+            candidates: CANDIDATES.to_vec(),
+            step: 0,
+        }
+    }
+
+    /// Color currently shown on the panel
+    pub fn current_color(&self) -> ColorChannel {
+        TEST_COLORS[self.step.min(TEST_COLORS.len() - 1)]
+    }
+
+    /// Sequences still consistent with the answers so far
+    pub fn candidates(&self) -> Vec<String> {
+        self.candidates.iter().map(|c| c.to_string()).collect()
+    }
+
+    /// Record the color the user saw for the current test color
+    pub fn report(&mut self, seen: ColorChannel) -> DetectionStep {
+        // The driver puts the shown channel on the pin at its position in the active sequence,
+        // and a panel wired as `candidate` lights that pin in the candidate's color there
+        let shown = self.current_color().letter();
+        let pin = self
+            .active_sequence
+            .chars()
+            .position(|c| c == shown)
+            .unwrap_or(0);
+
+        self.candidates
+            .retain(|candidate| candidate.chars().nth(pin) == Some(seen.letter()));
+        self.step += 1;
+
+        match self.candidates.as_slice() {
+            [] => DetectionStep::Inconsistent,
+            [sequence] => DetectionStep::Detected(sequence.to_string()),
+            _ if self.step >= TEST_COLORS.len() => DetectionStep::Inconsistent,
+            _ => DetectionStep::Continue,
+        }
+    }
+}
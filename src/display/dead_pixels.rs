@@ -0,0 +1,76 @@
+//! Dead-pixel map: corrects registered dead or stuck pixels in every frame, either by turning
+//! them off or by showing the average of their working neighbors
+
+use crate::display::driver::LedCanvas;
+use crate::display::graphics::frame_buffer::FrameBuffer;
+use crate::models::settings::{DeadPixel, DeadPixelFix};
+use std::collections::HashSet;
+
+// Neighbors averaged for blended pixels
+const NEIGHBORS: [(i32, i32); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+#[derive(Debug, Default)]
+pub struct DeadPixelMap {
+    pixels: Vec<DeadPixel>,
+    // Positions of all registered pixels, never used as blend sources
+    positions: HashSet<(i32, i32)>,
+}
+
+impl DeadPixelMap {
+    pub fn new(pixels: Vec<DeadPixel>) -> Self {
+        let positions = pixels.iter().map(|pixel| (pixel.x, pixel.y)).collect();
+        Self { pixels, positions }
+    }
+
+    pub fn pixels(&self) -> &[DeadPixel] {
+        &self.pixels
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pixels.is_empty()
+    }
+
+    /// Correct the registered pixels of `frame`, blends use the colors from before correcting
+    pub fn apply(&self, frame: &mut FrameBuffer) {
+        let corrections: Vec<(i32, i32, [u8; 3])> = self
+            .pixels
+            .iter()
+            .filter(|pixel| frame.pixel(pixel.x, pixel.y).is_some())
+            .map(|pixel| {
+                let color = match pixel.fix {
+                    DeadPixelFix::Off => [0, 0, 0],
+                    DeadPixelFix::Blend => self.blend(frame, pixel.x, pixel.y),
+                };
+                (pixel.x, pixel.y, color)
+            })
+            .collect();
+
+        let (width, _) = frame.size();
+        let pixels = frame.pixels_mut();
+        for (x, y, color) in corrections {
+            pixels[(y * width + x) as usize] = color;
+        }
+    }
+
+    // Average color of the working neighbors, black when there are none
+    fn blend(&self, frame: &FrameBuffer, x: i32, y: i32) -> [u8; 3] {
+        let mut sum = [0u32; 3];
+        let mut count = 0;
+        for (dx, dy) in NEIGHBORS {
+            let (nx, ny) = (x + dx, y + dy);
+            if self.positions.contains(&(nx, ny)) {
+                continue;
+            }
+            if let Some(color) = frame.pixel(nx, ny) {
+                for (total, channel) in sum.iter_mut().zip(color) {
+                    *total += channel as u32;
+                }
+                count += 1;
+            }
+        }
+        if count == 0 {
+            return [0, 0, 0];
+        }
+        sum.map(|total| (total / count) as u8)
+    }
+}
@@ -0,0 +1,30 @@
+//! Bundled showcase playlist shown by `--demo` while the playlist is empty
+
+use crate::models::playlist::Playlist;
+
+const DEMO_PLAYLIST: &str = include_str!("demo_playlist.json");
+
+/// Sample playlist with text effects, a clock and animations
+pub fn demo_playlist() -> Playlist {
+    serde_json::from_str(DEMO_PLAYLIST).expect("Bundled demo playlist is invalid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    // Deserializing runs the same checks as the playlist API
+    #[test]
+    fn bundled_playlist_is_valid() {
+        let playlist = serde_json::from_str::<Playlist>(DEMO_PLAYLIST)
+            .unwrap_or_else(|e| panic!("Bundled demo playlist is invalid: {}", e));
+        assert!(!playlist.items.is_empty());
+        assert!(playlist.items.iter().all(|item| item.is_playable()));
+
+        let mut ids = HashSet::new();
+        for item in &playlist.items {
+            assert!(ids.insert(&item.id), "Duplicate item ID {}", item.id);
+        }
+    }
+}
@@ -0,0 +1,190 @@
+// In-memory canvas for unit tests and, with the "test-util" feature, a driver for the golden
+// frame check. The canvas keeps every pixel write and the driver records each presented frame,
+// so renderer output such as scroll positions, borders and brightness can be checked without
+// LED hardware.
+
+use std::any::Any;
+#[cfg(feature = "test-util")]
+use std::sync::{Arc, Mutex};
+
+use super::LedCanvas;
+#[cfg(feature = "test-util")]
+use super::LedDriver;
+#[cfg(feature = "test-util")]
+use crate::config::DisplayConfig;
+#[cfg(test)]
+use crate::display::renderer::Renderer;
+
+/// Frames presented through a `MockLedDriver`, shared so they can be read after the
+/// driver has been handed to a `DisplayManager`
+#[cfg(feature = "test-util")]
+pub type FrameLog = Arc<Mutex<Vec<MockLedCanvas>>>;
+
+/// Canvas that records pixel writes into a buffer
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockLedCanvas {
+    pixels: Vec<[u8; 3]>,
+    width: i32,
+    height: i32,
+}
+
+impl MockLedCanvas {
+    pub fn new(width: i32, height: i32) -> Self {
+        Self {
+            pixels: vec![[0, 0, 0]; (width * height).max(0) as usize],
+            width,
+            height,
+        }
+    }
+
+    /// Snapshot as an RGB image, e.g. to save a failing frame for inspection
+    #[cfg(feature = "test-util")]
+    pub fn to_image(&self) -> image::RgbImage {
+        image::RgbImage::from_fn(self.width as u32, self.height as u32, |x, y| {
+            image::Rgb(self.pixels[(y * self.width as u32 + x) as usize])
+        })
+    }
+}
+
+// Inspection helpers for unit tests
+#[cfg(test)]
+impl MockLedCanvas {
+    /// Color at a position, None outside the canvas
+    pub fn pixel(&self, x: i32, y: i32) -> Option<[u8; 3]> {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return None;
+        }
+        Some(self.pixels[(y * self.width + x) as usize])
+    }
+
+    /// Number of pixels that are not black
+    pub fn lit_pixels(&self) -> usize {
+        self.pixels
+            .iter()
+            .filter(|pixel| **pixel != [0, 0, 0])
+            .count()
+    }
+
+    /// Smallest rectangle containing every lit pixel as (left, top, right, bottom),
+    /// None if the canvas is black. Useful to follow scrolling content between frames.
+    pub fn lit_bounds(&self) -> Option<(i32, i32, i32, i32)> {
+        let mut bounds: Option<(i32, i32, i32, i32)> = None;
+        for (index, pixel) in self.pixels.iter().enumerate() {
+            if *pixel == [0, 0, 0] {
+                continue;
+            }
+            let (x, y) = (index as i32 % self.width, index as i32 / self.width);
+            bounds = Some(match bounds {
+                None => (x, y, x, y),
+                Some((left, top, right, bottom)) => {
+                    (left.min(x), top.min(y), right.max(x), bottom.max(y))
+                }
+            });
+        }
+        bounds
+    }
+
+    /// Brightest channel value on the canvas, e.g. to check brightness scaling
+    pub fn max_channel(&self) -> u8 {
+        self.pixels
+            .iter()
+            .flat_map(|pixel| pixel.iter().copied())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Text snapshot with one line per row, '#' for lit and '.' for black pixels
+    pub fn to_ascii(&self) -> String {
+        let mut snapshot = String::with_capacity(self.pixels.len() + self.height as usize);
+        for row in self.pixels.chunks(self.width.max(1) as usize) {
+            for pixel in row {
+                snapshot.push(if *pixel == [0, 0, 0] { '.' } else { '#' });
+            }
+            snapshot.push('\n');
+        }
+        snapshot
+    }
+}
+
+impl LedCanvas for MockLedCanvas {
+    fn set_pixel(&mut self, x: usize, y: usize, r: u8, g: u8, b: u8) {
+        if x < self.width as usize && y < self.height as usize {
+            self.pixels[y * self.width as usize + x] = [r, g, b];
+        }
+    }
+
+    fn fill(&mut self, r: u8, g: u8, b: u8) {
+        self.pixels.fill([r, g, b]);
+    }
+
+    fn size(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any
+    where
+        Self: 'static,
+    {
+        self
+    }
+}
+
+/// Draw one frame of a renderer onto a black canvas of the given size
+#[cfg(test)]
+pub fn capture_frame(renderer: &dyn Renderer, width: i32, height: i32) -> MockLedCanvas {
+    let mut canvas: Box<dyn LedCanvas> = Box::new(MockLedCanvas::new(width, height));
+    renderer.render(&mut canvas);
+    canvas
+        .as_any_mut()
+        .downcast_mut::<MockLedCanvas>()
+        .expect("canvas is a MockLedCanvas")
+        .clone()
+}
+
+/// Driver that keeps a copy of every frame it is asked to show
+#[cfg(feature = "test-util")]
+#[derive(Debug)]
+pub struct MockLedDriver {
+    canvas: Option<MockLedCanvas>,
+    frames: FrameLog,
+}
+
+#[cfg(feature = "test-util")]
+impl MockLedDriver {
+    pub fn new(width: i32, height: i32) -> Self {
+        Self {
+            canvas: Some(MockLedCanvas::new(width, height)),
+            frames: FrameLog::default(),
+        }
+    }
+
+    /// Handle to the recorded frames, oldest first
+    pub fn frames(&self) -> FrameLog {
+        self.frames.clone()
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl LedDriver for MockLedDriver {
+    fn initialize(config: &DisplayConfig) -> Result<Self, String>
+    where
+        Self: Sized,
+    {
+        Ok(Self::new(config.display_width(), config.display_height()))
+    }
+
+    fn take_canvas(&mut self) -> Option<Box<dyn LedCanvas>> {
+        self.canvas
+            .take()
+            .map(|canvas| Box::new(canvas) as Box<dyn LedCanvas>)
+    }
+
+    fn update_canvas(&mut self, mut canvas: Box<dyn LedCanvas>) -> Box<dyn LedCanvas> {
+        if let Some(frame) = canvas.as_any_mut().downcast_mut::<MockLedCanvas>() {
+            self.frames.lock().unwrap().push(frame.clone());
+        }
+        canvas
+    }
+
+    fn shutdown(&mut self) {}
+}
@@ -1,12 +1,23 @@
 use crate::config::DisplayConfig;
 use std::fmt::Debug;
 
+// Test fixture, the controller itself never uses it
+#[cfg(any(test, feature = "test-util"))]
+mod mock_driver;
+mod null_driver;
 mod options;
 mod rpi_led_matrix_driver;
 mod rpi_led_panel_driver;
+mod wall_driver;
 
+#[cfg(test)]
+pub use mock_driver::capture_frame;
+#[cfg(feature = "test-util")]
+pub use mock_driver::MockLedDriver;
+pub use null_driver::NullDriver;
 pub use rpi_led_matrix_driver::RpiLedMatrixDriver;
 pub use rpi_led_panel_driver::RpiLedPanelDriver;
+pub use wall_driver::{WallDriver, WallTile};
 
 // Core traits
 pub trait LedCanvas: Debug + Send {
@@ -34,6 +45,20 @@ pub trait LedDriver: Debug + Send {
 pub enum DriverType {
     RpiLedPanel,  // Native Rust driver
     RpiLedMatrix, // C++ binding driver
+    Null,         // No hardware, used by --bench
+    Wall,         // Streams regions of the canvas to other Pis
+}
+
+impl DriverType {
+    /// Name used for this driver on the command line and in the API
+    pub fn name(&self) -> &'static str {
+        match self {
+            DriverType::RpiLedPanel => "native",
+            DriverType::RpiLedMatrix => "binding",
+            DriverType::Null => "null",
+            DriverType::Wall => "wall",
+        }
+    }
 }
 
 // Factory function to create the appropriate driver
@@ -47,5 +72,13 @@ pub fn create_driver(config: &DisplayConfig) -> Result<Box<dyn LedDriver>, Strin
             Ok(driver) => Ok(Box::new(driver)),
             Err(e) => Err(e),
         },
+        DriverType::Null => match NullDriver::initialize(config) {
+            Ok(driver) => Ok(Box::new(driver)),
+            Err(e) => Err(e),
+        },
+        DriverType::Wall => match WallDriver::initialize(config) {
+            Ok(driver) => Ok(Box::new(driver)),
+            Err(e) => Err(e),
+        },
     }
 }
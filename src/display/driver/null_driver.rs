@@ -0,0 +1,71 @@
+use std::any::Any;
+
+use super::{LedCanvas, LedDriver};
+use crate::config::DisplayConfig;
+
+// Canvas that draws into an in-memory frame buffer
+#[derive(Debug)]
+pub struct NullCanvas {
+    pixels: Vec<[u8; 3]>,
+    width: i32,
+    height: i32,
+}
+
+impl LedCanvas for NullCanvas {
+    fn set_pixel(&mut self, x: usize, y: usize, r: u8, g: u8, b: u8) {
+        if x < self.width as usize && y < self.height as usize {
+            self.pixels[y * self.width as usize + x] = [r, g, b];
+        }
+    }
+
+    fn fill(&mut self, r: u8, g: u8, b: u8) {
+        self.pixels.fill([r, g, b]);
+    }
+
+    fn size(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any
+    where
+        Self: 'static,
+    {
+        self
+    }
+}
+
+// Driver without hardware, used for benchmarks. Frames are drawn but never shown.
+#[derive(Debug)]
+pub struct NullDriver {
+    canvas: Option<NullCanvas>,
+}
+
+impl LedDriver for NullDriver {
+    fn initialize(config: &DisplayConfig) -> Result<Self, String>
+    where
+        Self: Sized,
+    {
+        let width = config.display_width();
+        let height = config.display_height();
+
+        Ok(Self {
+            canvas: Some(NullCanvas {
+                pixels: vec![[0, 0, 0]; (width * height).max(0) as usize],
+                width,
+                height,
+            }),
+        })
+    }
+
+    fn take_canvas(&mut self) -> Option<Box<dyn LedCanvas>> {
+        self.canvas
+            .take()
+            .map(|canvas| Box::new(canvas) as Box<dyn LedCanvas>)
+    }
+
+    fn update_canvas(&mut self, canvas: Box<dyn LedCanvas>) -> Box<dyn LedCanvas> {
+        canvas
+    }
+
+    fn shutdown(&mut self) {}
+}
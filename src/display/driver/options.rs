@@ -29,6 +29,9 @@ pub struct MatrixOptions {
     pub inverse_colors: bool,
     pub limit_refresh_rate: u32,
     pub pi_chip: Option<String>,
+
+    // Extra key=value options passed through to the driver unmodified
+    pub driver_opts: Vec<(String, String)>,
 }
 
 impl Default for MatrixOptions {
@@ -55,6 +58,7 @@ impl Default for MatrixOptions {
             inverse_colors: false,
             limit_refresh_rate: 0,
             pi_chip: None,
+            driver_opts: Vec::new(),
         }
     }
 }
@@ -84,6 +88,7 @@ impl MatrixOptions {
             inverse_colors: config.inverse_colors,
             limit_refresh_rate: config.limit_refresh_rate,
             pi_chip: config.pi_chip.clone(),
+            driver_opts: config.driver_options(),
         };
 
         // Apply any environment variable overrides
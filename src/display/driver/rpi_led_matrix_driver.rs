@@ -291,6 +291,15 @@ impl RpiLedMatrixDriver {
             matrix_options.set_limit_refresh(options.limit_refresh_rate);
         }
 
+        // Apply pass-through options last so they override the modeled ones
+        for (key, value) in &options.driver_opts {
+            if let Err(e) = Self::apply_driver_opt(&mut matrix_options, &mut rt_options, key, value)
+            {
+                error!("{}", e);
+                unsupported_options.push(format!("driver-opt {}={}", key, value));
+            }
+        }
+
         // Check for driver-specific unsupported options
         if let Some(chip) = &options.pi_chip {
             unsupported_options.push(format!("pi_chip={}", chip));
@@ -307,6 +316,67 @@ impl RpiLedMatrixDriver {
         Ok((matrix_options, rt_options))
     }
 
+    // Apply a raw key=value option to the matching rpi-rgb-led-matrix setter.
+    // Keys follow the library's option names, dashes and underscores are interchangeable.
+    fn apply_driver_opt(
+        matrix_options: &mut LedMatrixOptions,
+        rt_options: &mut LedRuntimeOptions,
+        key: &str,
+        value: &str,
+    ) -> Result<(), String> {
+        fn parse<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, String> {
+            value
+                .parse()
+                .map_err(|_| format!("Invalid value '{}' for driver option '{}'", value, key))
+        }
+
+        fn parse_bool(key: &str, value: &str) -> Result<bool, String> {
+            match value.to_lowercase().as_str() {
+                "true" | "1" | "yes" | "on" => Ok(true),
+                "false" | "0" | "no" | "off" => Ok(false),
+                _ => Err(format!(
+                    "Invalid value '{}' for driver option '{}'",
+                    value, key
+                )),
+            }
+        }
+
+        debug!("Applying driver option {}={}", key, value);
+
+        match key.replace('-', "_").as_str() {
+            "hardware_mapping" => matrix_options.set_hardware_mapping(value),
+            "rows" => matrix_options.set_rows(parse(key, value)?),
+            "cols" => matrix_options.set_cols(parse(key, value)?),
+            "chain_length" => matrix_options.set_chain_length(parse(key, value)?),
+            "parallel" => matrix_options.set_parallel(parse(key, value)?),
+            "pwm_bits" => matrix_options
+                .set_pwm_bits(parse(key, value)?)
+                .map_err(|e| format!("Driver option '{}': {}", key, e))?,
+            "pwm_lsb_nanoseconds" => matrix_options.set_pwm_lsb_nanoseconds(parse(key, value)?),
+            "pwm_dither_bits" => matrix_options.set_pwm_dither_bits(parse(key, value)?),
+            "brightness" => matrix_options
+                .set_brightness(parse(key, value)?)
+                .map_err(|e| format!("Driver option '{}': {}", key, e))?,
+            "scan_mode" => matrix_options.set_scan_mode(parse(key, value)?),
+            "row_addr_type" => matrix_options.set_row_addr_type(parse(key, value)?),
+            "multiplexing" => matrix_options.set_multiplexing(parse(key, value)?),
+            "led_rgb_sequence" => matrix_options.set_led_rgb_sequence(value),
+            "pixel_mapper_config" => matrix_options.set_pixel_mapper_config(value),
+            "panel_type" => matrix_options.set_panel_type(value),
+            "hardware_pulsing" => matrix_options.set_hardware_pulsing(parse_bool(key, value)?),
+            "show_refresh_rate" => matrix_options.set_refresh_rate(parse_bool(key, value)?),
+            "inverse_colors" => matrix_options.set_inverse_colors(parse_bool(key, value)?),
+            "limit_refresh_rate_hz" => matrix_options.set_limit_refresh(parse(key, value)?),
+            "gpio_slowdown" => rt_options.set_gpio_slowdown(parse(key, value)?),
+            "daemon" => rt_options.set_daemon(parse_bool(key, value)?),
+            "drop_privileges" => rt_options.set_drop_privileges(parse_bool(key, value)?),
+            "do_gpio_init" => rt_options.set_do_gpio_init(parse_bool(key, value)?),
+            _ => return Err(format!("Unknown driver option '{}'", key)),
+        }
+
+        Ok(())
+    }
+
     // Helper to map multiplexing string to numeric value
     fn map_multiplexing(multiplex_str: &str) -> u32 {
         match multiplex_str.to_lowercase().as_str() {
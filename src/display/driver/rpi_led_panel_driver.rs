@@ -274,6 +274,15 @@ impl RpiLedPanelDriver {
             unsupported_options.push("inverse-colors".to_string());
         }
 
+        // Pass-through options target rpi-rgb-led-matrix and have no equivalent here
+        for (key, value) in &options.driver_opts {
+            warn!(
+                "Driver option '{}' is not supported by the native driver",
+                key
+            );
+            unsupported_options.push(format!("driver-opt {}={}", key, value));
+        }
+
         // Check if we encountered any unsupported options
         if !unsupported_options.is_empty() {
             return Err(format!(
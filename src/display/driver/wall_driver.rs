@@ -0,0 +1,255 @@
+use std::net::{SocketAddr, UdpSocket};
+use std::time::{Duration, Instant};
+
+use super::{LedCanvas, LedDriver};
+use crate::config::DisplayConfig;
+use crate::display::ambient::MAX_PACKET_BYTES;
+use crate::display::graphics::frame_buffer::FrameBuffer;
+
+// Frame rate towards the tiles unless --limit-refresh-rate sets a lower one
+const MAX_FPS: u32 = 60;
+
+// Unchanged frames are sent again this often, well within the tiles' ambient timeout
+const KEEPALIVE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Region of the virtual canvas shown by one Pi of a display wall
+#[derive(Debug, Clone, PartialEq)]
+pub struct WallTile {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+    pub addr: SocketAddr,
+}
+
+impl WallTile {
+    /// Parse a tile as "X,Y,WIDTHxHEIGHT@HOST:PORT", e.g. "64,0,64x32@192.168.1.21:19446"
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let invalid = || {
+            format!(
+                "Invalid wall tile '{}': expected X,Y,WIDTHxHEIGHT@HOST:PORT",
+                spec
+            )
+        };
+        let (region, addr) = spec.trim().split_once('@').ok_or_else(invalid)?;
+        let mut parts = region.split(',').map(str::trim);
+        let (Some(x), Some(y), Some(size), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+        let (width, height) = size.split_once('x').ok_or_else(invalid)?;
+
+        let tile = Self {
+            x: x.parse().map_err(|_| invalid())?,
+            y: y.parse().map_err(|_| invalid())?,
+            width: width.parse().map_err(|_| invalid())?,
+            height: height.parse().map_err(|_| invalid())?,
+            addr: addr
+                .trim()
+                .parse()
+                .map_err(|e| format!("Invalid wall tile address '{}': {}", addr, e))?,
+        };
+        if tile.x < 0 || tile.y < 0 || tile.width <= 0 || tile.height <= 0 {
+            return Err(invalid());
+        }
+        if tile.x.checked_add(tile.width).is_none() || tile.y.checked_add(tile.height).is_none() {
+            return Err(invalid());
+        }
+        let packet_len = (tile.width as u64)
+            .checked_mul(tile.height as u64)
+            .and_then(|pixels| pixels.checked_mul(3));
+        if packet_len.is_none_or(|len| len > MAX_PACKET_BYTES as u64) {
+            return Err(format!(
+                "Wall tile '{}' is too large: at most {} pixels fit into one frame",
+                spec,
+                MAX_PACKET_BYTES / 3
+            ));
+        }
+        Ok(tile)
+    }
+
+    // Bytes of one frame for this tile, parse made sure it fits into a packet
+    fn packet_len(&self) -> usize {
+        self.width as usize * self.height as usize * 3
+    }
+
+    // Raw RGB bytes of this tile's region, row by row
+    fn crop(&self, frame: &FrameBuffer) -> Vec<u8> {
+        let mut packet = Vec::with_capacity(self.packet_len());
+        for y in self.y..self.y + self.height {
+            for x in self.x..self.x + self.width {
+                packet.extend(frame.pixel(x, y).unwrap_or([0, 0, 0]));
+            }
+        }
+        packet
+    }
+}
+
+// Coordinator of a display wall: renders the whole virtual canvas and sends each tile its
+// region as a raw RGB frame over UDP. The Pis behind the tiles receive them with
+// --ambient-udp-port, which draws a frame of exactly the panel's size as is.
+#[derive(Debug)]
+pub struct WallDriver {
+    canvas: Option<FrameBuffer>,
+    tiles: Vec<WallTile>,
+    socket: UdpSocket,
+    frame_interval: Duration,
+    last_sent: Option<Instant>,
+    // Last frame sent to each tile, so unchanged tiles are only refreshed occasionally
+    sent_frames: Vec<(Vec<u8>, Instant)>,
+}
+
+impl WallDriver {
+    fn send_tiles(&mut self, frame: &FrameBuffer) {
+        for (tile, (sent, sent_at)) in self.tiles.iter().zip(&mut self.sent_frames) {
+            let packet = tile.crop(frame);
+            if packet == *sent && sent_at.elapsed() < KEEPALIVE_INTERVAL {
+                continue;
+            }
+            // The socket does not block, a busy network drops frames instead of the render loop
+            if let Err(e) = self.socket.send_to(&packet, tile.addr) {
+                log::debug!("Failed to send wall frame to {}: {}", tile.addr, e);
+            }
+            *sent = packet;
+            *sent_at = Instant::now();
+        }
+    }
+}
+
+impl LedDriver for WallDriver {
+    fn initialize(config: &DisplayConfig) -> Result<Self, String>
+    where
+        Self: Sized,
+    {
+        let tiles = config
+            .wall_tiles
+            .iter()
+            .map(|spec| WallTile::parse(spec))
+            .collect::<Result<Vec<_>, _>>()?;
+        if tiles.is_empty() {
+            return Err("The wall driver needs at least one --wall-tile".to_string());
+        }
+        // All tiles are sent to from one socket, which only reaches one address family
+        if let Some(tile) = tiles
+            .iter()
+            .find(|tile| tile.addr.is_ipv4() != tiles[0].addr.is_ipv4())
+        {
+            return Err(format!(
+                "Wall tiles must all use IPv4 or all use IPv6 addresses, {} does not match {}",
+                tile.addr, tiles[0].addr
+            ));
+        }
+
+        let bind_addr = if tiles[0].addr.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        let socket = UdpSocket::bind(bind_addr)
+            .map_err(|e| format!("Failed to create wall socket: {}", e))?;
+        socket
+            .set_nonblocking(true)
+            .map_err(|e| format!("Failed to configure wall socket: {}", e))?;
+        socket
+            .set_broadcast(true)
+            .map_err(|e| format!("Failed to configure wall socket: {}", e))?;
+
+        let fps = match config.limit_refresh_rate {
+            0 => MAX_FPS,
+            limit => limit.min(MAX_FPS),
+        };
+        for tile in &tiles {
+            log::info!(
+                "Wall tile {}x{} at ({}, {}) is sent to udp://{}",
+                tile.width,
+                tile.height,
+                tile.x,
+                tile.y,
+                tile.addr
+            );
+        }
+
+        Ok(Self {
+            canvas: Some(FrameBuffer::new(
+                config.display_width(),
+                config.display_height(),
+            )),
+            sent_frames: vec![(Vec::new(), Instant::now()); tiles.len()],
+            tiles,
+            socket,
+            frame_interval: Duration::from_secs(1) / fps,
+            last_sent: None,
+        })
+    }
+
+    fn take_canvas(&mut self) -> Option<Box<dyn LedCanvas>> {
+        self.canvas
+            .take()
+            .map(|canvas| Box::new(canvas) as Box<dyn LedCanvas>)
+    }
+
+    fn update_canvas(&mut self, mut canvas: Box<dyn LedCanvas>) -> Box<dyn LedCanvas> {
+        if self
+            .last_sent
+            .is_some_and(|sent| sent.elapsed() < self.frame_interval)
+        {
+            return canvas;
+        }
+        self.last_sent = Some(Instant::now());
+
+        if let Some(frame) = canvas.as_any_mut().downcast_mut::<FrameBuffer>() {
+            self.send_tiles(frame);
+        }
+        canvas
+    }
+
+    fn shutdown(&mut self) {
+        // Blank the tiles instead of leaving the last frame up until they time out
+        for tile in &self.tiles {
+            let black = vec![0u8; tile.packet_len()];
+            let _ = self.socket.send_to(&black, tile.addr);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_tile() {
+        assert_eq!(
+            WallTile::parse(" 64, 0, 64x32@192.168.1.21:19446 "),
+            Ok(WallTile {
+                x: 64,
+                y: 0,
+                width: 64,
+                height: 32,
+                addr: "192.168.1.21:19446".parse().unwrap(),
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_tiles() {
+        for spec in [
+            "64,0,64x32",
+            "64,0@192.168.1.21:19446",
+            "64,0,64x32,1@192.168.1.21:19446",
+            "-1,0,64x32@192.168.1.21:19446",
+            "0,0,0x32@192.168.1.21:19446",
+            "0,0,64by32@192.168.1.21:19446",
+            "0,0,64x32@pi-tile:19446",
+            "2147483647,0,64x32@192.168.1.21:19446",
+        ] {
+            assert!(WallTile::parse(spec).is_err(), "{} was accepted", spec);
+        }
+    }
+
+    #[test]
+    fn rejects_tiles_larger_than_a_packet() {
+        let error = WallTile::parse("0,0,4096x4096@192.168.1.21:19446").unwrap_err();
+        assert!(error.contains("too large"), "{}", error);
+    }
+}
@@ -0,0 +1,441 @@
+//! Golden-frame regression check used by `--golden-frames` (built with the "test-util" feature).
+//!
+//! Renders a sample of every built-in content type through the regular display pipeline on the
+//! mock driver at fixed timestamps and compares each frame with a PNG in the golden directory.
+//! The panel size, wall clock and time zone are pinned so frames are identical on every run.
+
+use crate::config::DisplayConfig;
+use crate::display::driver::MockLedDriver;
+use crate::display::manager::DisplayManager;
+use crate::models::animation::{AnimationContent, MeteorDirection, WeatherKind};
+use crate::models::barcode::{BarcodeContent, BarcodeFormat};
+use crate::models::big_number::BigNumberContent;
+use crate::models::border_effects::BorderEffect;
+use crate::models::clock::{ClockContent, ClockFormat, ClockTransition};
+use crate::models::content::{ContentData, ContentDetails, ContentType};
+use crate::models::countdown::{CountdownContent, CountdownThreshold, ThresholdEffect};
+use crate::models::playlist::{PlayListItem, Playlist};
+use crate::models::qrcode::{QrCodeContent, QrErrorCorrection};
+use crate::models::text::{TextContent, TextOverflow};
+use crate::utils::timezone;
+use chrono::{DateTime, TimeDelta, Utc};
+use std::path::Path;
+
+// Golden frames are always rendered on a single 64x32 panel
+const PANEL_ROWS: usize = 32;
+const PANEL_COLS: usize = 64;
+
+// Wall clock at the first frame of every sample
+const START_TIME: &str = "2025-03-14T09:26:53Z";
+
+// Simulated frame interval; capture times are multiples of it
+const FRAME_MS: u64 = 20;
+
+// Milliseconds after the start at which each sample is captured
+const CAPTURE_TIMES_MS: [u64; 3] = [0, 500, 1500];
+
+const PALETTE: [[u8; 3]; 3] = [[255, 0, 0], [0, 255, 0], [0, 0, 255]];
+
+fn sample_item(content_type: ContentType, data: ContentDetails) -> PlayListItem {
+    PlayListItem {
+        id: format!("golden-{}", content_type.as_str().to_lowercase()),
+        duration: None,
+        repeat_count: Some(0),
+        border_effect: None,
+        border_reactive: false,
+        outro_ms: None,
+        content: ContentData { content_type, data },
+        expires_at: None,
+        interstitial: None,
+        tags: Vec::new(),
+        enabled: true,
+        conditions: Vec::new(),
+        created_for: None,
+        created_at: None,
+        updated_at: None,
+        author: None,
+    }
+}
+
+fn text_item(text: &str, scroll: bool, antialias: bool) -> PlayListItem {
+    sample_item(
+        ContentType::Text,
+        ContentDetails::Text(TextContent {
+            text: text.to_string(),
+            scroll,
+            color: [255, 255, 255],
+            speed: 50.0,
+            text_segments: None,
+            pixels_per_second: None,
+            read_time_ms: None,
+            scroll_ease_ms: None,
+            antialias,
+            paging: None,
+            overflow: TextOverflow::default(),
+        }),
+    )
+}
+
+fn clock_item(format: ClockFormat, show_date: bool) -> PlayListItem {
+    sample_item(
+        ContentType::Clock,
+        ContentDetails::Clock(ClockContent {
+            format,
+            show_seconds: false,
+            color: [255, 255, 255],
+            transition: ClockTransition::None,
+            transition_ms: 400,
+            show_date,
+        }),
+    )
+}
+
+fn animation_item(animation: AnimationContent) -> PlayListItem {
+    sample_item(ContentType::Animation, ContentDetails::Animation(animation))
+}
+
+/// One or more samples per content type, named after their golden files
+fn samples(start: DateTime<Utc>) -> Vec<(&'static str, PlayListItem)> {
+    let colors = PALETTE.to_vec();
+
+    let mut rainbow_border = text_item("Hi", false, false);
+    rainbow_border.border_effect = Some(BorderEffect::Rainbow);
+    let mut sparkle_border = text_item("Hi", false, false);
+    sparkle_border.border_effect = Some(BorderEffect::Sparkle {
+        colors: colors.clone(),
+        palette: None,
+    });
+
+    vec![
+        ("text-static", text_item("Hello", false, false)),
+        (
+            "text-scrolling",
+            text_item("The quick brown fox 0123456789", true, false),
+        ),
+        (
+            "text-antialias",
+            text_item("The quick brown fox 0123456789", true, true),
+        ),
+        ("border-rainbow", rainbow_border),
+        ("border-sparkle", sparkle_border),
+        (
+            "clock-24h-date",
+            clock_item(ClockFormat::TwentyFourHour, true),
+        ),
+        ("clock-12h", clock_item(ClockFormat::TwelveHour, false)),
+        (
+            "countdown",
+            sample_item(
+                ContentType::Countdown,
+                ContentDetails::Countdown(CountdownContent {
+                    target: (start + TimeDelta::minutes(3)).to_rfc3339(),
+                    color: [255, 255, 255],
+                    finished_text: "GO!".to_string(),
+                    thresholds: vec![CountdownThreshold {
+                        remaining_seconds: 300,
+                        color: [255, 0, 0],
+                        effect: ThresholdEffect::Pulse,
+                    }],
+                }),
+            ),
+        ),
+        (
+            "qrcode",
+            sample_item(
+                ContentType::QrCode,
+                ContentDetails::QrCode(QrCodeContent {
+                    data: "https://example.com".to_string(),
+                    error_correction: QrErrorCorrection::M,
+                    foreground: [0, 0, 0],
+                    background: [255, 255, 255],
+                    quiet_zone: 4,
+                    caption: Some("Scan me".to_string()),
+                    caption_color: [255, 255, 255],
+                }),
+            ),
+        ),
+        (
+            "barcode",
+            sample_item(
+                ContentType::Barcode,
+                ContentDetails::Barcode(BarcodeContent {
+                    format: BarcodeFormat::Code128,
+                    data: "1234".to_string(),
+                    foreground: [0, 0, 0],
+                    background: [255, 255, 255],
+                    quiet_zone: 10,
+                    show_text: true,
+                    text_color: [255, 255, 255],
+                }),
+            ),
+        ),
+        (
+            "big-number",
+            sample_item(
+                ContentType::BigNumber,
+                ContentDetails::BigNumber(BigNumberContent {
+                    value: "21.5".to_string(),
+                    unit: Some("°C".to_string()),
+                    decimals: None,
+                    color: [255, 160, 0],
+                    unit_color: None,
+                }),
+            ),
+        ),
+        (
+            "animation-pulse",
+            animation_item(AnimationContent::Pulse {
+                colors: colors.clone(),
+                palette: None,
+                reactive: false,
+                cycle_ms: 2_000,
+            }),
+        ),
+        (
+            "animation-palette-wave",
+            animation_item(AnimationContent::PaletteWave {
+                colors: colors.clone(),
+                palette: None,
+                reactive: false,
+                cycle_ms: 2_000,
+                wave_count: 3,
+            }),
+        ),
+        (
+            "animation-dual-pulse",
+            animation_item(AnimationContent::DualPulse {
+                colors: colors.clone(),
+                palette: None,
+                reactive: false,
+                cycle_ms: 2_000,
+                phase_offset: 0.5,
+            }),
+        ),
+        (
+            "animation-color-fade",
+            animation_item(AnimationContent::ColorFade {
+                colors: colors.clone(),
+                palette: None,
+                reactive: false,
+                drift_speed: 0.25,
+            }),
+        ),
+        (
+            "animation-strobe",
+            animation_item(AnimationContent::Strobe {
+                colors: colors.clone(),
+                palette: None,
+                reactive: false,
+                flash_ms: 180,
+                fade_ms: 220,
+                randomize: true,
+                randomization_factor: 0.35,
+            }),
+        ),
+        (
+            "animation-sparkle",
+            animation_item(AnimationContent::Sparkle {
+                colors: colors.clone(),
+                palette: None,
+                reactive: false,
+                density: 0.12,
+                twinkle_ms: 600,
+            }),
+        ),
+        (
+            "animation-mosaic-twinkle",
+            animation_item(AnimationContent::MosaicTwinkle {
+                colors: colors.clone(),
+                palette: None,
+                reactive: false,
+                tile_size: 1,
+                flow_speed: 0.35,
+                border_size: 0,
+                border_color: [50, 0, 0],
+            }),
+        ),
+        (
+            "animation-plasma",
+            animation_item(AnimationContent::Plasma {
+                colors: colors.clone(),
+                palette: None,
+                reactive: false,
+                flow_speed: 1.85,
+                noise_scale: 1.75,
+            }),
+        ),
+        (
+            "animation-meteor",
+            animation_item(AnimationContent::Meteor {
+                colors: colors.clone(),
+                palette: None,
+                reactive: false,
+                direction: MeteorDirection::DownLeft,
+                density: 0.15,
+                speed: 40.0,
+                trail_ms: 700,
+            }),
+        ),
+        (
+            "animation-fireworks",
+            animation_item(AnimationContent::Fireworks {
+                colors: colors.clone(),
+                palette: None,
+                reactive: false,
+                burst_rate: 0.8,
+                spark_count: 28,
+                gravity: 40.0,
+            }),
+        ),
+        (
+            "animation-noise-field",
+            animation_item(AnimationContent::NoiseField {
+                colors,
+                palette: None,
+                reactive: false,
+                octaves: 3,
+                speed: 0.15,
+                cycle_ms: 30_000,
+            }),
+        ),
+        (
+            "animation-weather-rain",
+            animation_item(AnimationContent::PixelWeather {
+                colors: Vec::new(),
+                palette: None,
+                reactive: false,
+                weather: WeatherKind::Rain,
+                intensity: 0.5,
+            }),
+        ),
+        (
+            "animation-weather-snow",
+            animation_item(AnimationContent::PixelWeather {
+                colors: Vec::new(),
+                palette: None,
+                reactive: false,
+                weather: WeatherKind::Snow,
+                intensity: 0.5,
+            }),
+        ),
+        (
+            "animation-weather-sun",
+            animation_item(AnimationContent::PixelWeather {
+                colors: Vec::new(),
+                palette: None,
+                reactive: false,
+                weather: WeatherKind::Sun,
+                intensity: 0.5,
+            }),
+        ),
+    ]
+}
+
+/// Render `item` and return the frames shown at each capture time
+fn capture_item(
+    config: &DisplayConfig,
+    item: PlayListItem,
+    start: DateTime<Utc>,
+) -> Vec<(u64, image::RgbImage)> {
+    let driver = MockLedDriver::new(config.display_width(), config.display_height());
+    let frames = driver.frames();
+    let playlist = Playlist {
+        items: vec![item],
+        active_index: 0,
+        repeat: true,
+    };
+    let mut display =
+        DisplayManager::with_playlist_config_and_driver(playlist, config, Box::new(driver));
+
+    let mut captured = Vec::with_capacity(CAPTURE_TIMES_MS.len());
+    let mut elapsed_ms = 0;
+    for capture_ms in CAPTURE_TIMES_MS {
+        while elapsed_ms < capture_ms {
+            elapsed_ms += FRAME_MS;
+            timezone::freeze(Some(start + TimeDelta::milliseconds(elapsed_ms as i64)));
+            display.update_renderer(FRAME_MS as f32 / 1000.0);
+        }
+        display.update_display();
+        let frame = frames.lock().unwrap().pop().expect("a frame was presented");
+        captured.push((capture_ms, frame.to_image()));
+    }
+    captured
+}
+
+/// Render every sample and compare it with the golden files in `dir`, or overwrite them
+/// when `update` is set. Mismatching frames are saved next to the golden file as
+/// `<name>.actual.png` for inspection.
+pub fn run_golden_frames(config: &DisplayConfig, dir: &Path, update: bool) -> Result<(), String> {
+    let mut config = config.clone();
+    config.rows = PANEL_ROWS;
+    config.cols = PANEL_COLS;
+    config.chain_length = 1;
+    config.parallel = 1;
+    config.user_brightness = 100;
+    config.limit_max_brightness = 100;
+    config.burn_in_shift_minutes = 0;
+    config.render_seed = 0;
+
+    timezone::set(chrono_tz::UTC);
+    let start = DateTime::parse_from_rfc3339(START_TIME)
+        .expect("valid start time")
+        .with_timezone(&Utc);
+
+    if update {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+
+    let mut checked = 0;
+    let mut failures = Vec::new();
+    for (name, item) in samples(start) {
+        timezone::freeze(Some(start));
+        for (capture_ms, frame) in capture_item(&config, item, start) {
+            let file_name = format!("{}-{:04}ms", name, capture_ms);
+            let golden_path = dir.join(format!("{}.png", file_name));
+            checked += 1;
+
+            if update {
+                frame
+                    .save(&golden_path)
+                    .map_err(|e| format!("Failed to write {}: {}", golden_path.display(), e))?;
+                continue;
+            }
+
+            let matches = match image::open(&golden_path) {
+                Ok(golden) => golden.to_rgb8() == frame,
+                Err(e) => {
+                    println!("{}: cannot read golden file: {}", file_name, e);
+                    false
+                }
+            };
+            if !matches {
+                let actual_path = dir.join(format!("{}.actual.png", file_name));
+                if let Err(e) = frame.save(&actual_path) {
+                    println!("{}: failed to save actual frame: {}", file_name, e);
+                }
+                failures.push(file_name);
+            }
+        }
+    }
+    timezone::freeze(None);
+
+    if update {
+        println!("Wrote {} golden frames to {}", checked, dir.display());
+        return Ok(());
+    }
+
+    if failures.is_empty() {
+        println!("All {} golden frames match", checked);
+        Ok(())
+    } else {
+        for name in &failures {
+            println!("MISMATCH {}", name);
+        }
+        Err(format!(
+            "{} of {} frames differ from the golden files",
+            failures.len(),
+            checked
+        ))
+    }
+}
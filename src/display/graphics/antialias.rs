@@ -0,0 +1,50 @@
+//! Softens hard bitmap glyph edges when an off-screen frame is drawn onto the panel
+
+use crate::display::driver::LedCanvas;
+use crate::display::graphics::frame_buffer::FrameBuffer;
+
+// Coverage given to an empty pixel in the inside corner of a diagonal step
+const CORNER_COVERAGE: f32 = 0.45;
+
+/// Color of a lit pixel, None for black or out-of-bounds pixels
+fn lit(buffer: &FrameBuffer, x: i32, y: i32) -> Option<[u8; 3]> {
+    buffer.pixel(x, y).filter(|color| *color != [0, 0, 0])
+}
+
+/// Pixel after smoothing, with its coverage already applied to the color
+fn smoothed(buffer: &FrameBuffer, x: i32, y: i32) -> [f32; 3] {
+    if let Some(color) = lit(buffer, x, y) {
+        return color.map(f32::from);
+    }
+
+    // Fill the inside corner of staircase diagonals: both orthogonal neighbours
+    // towards one diagonal are lit
+    for (dx, dy) in [(-1, -1), (1, -1), (-1, 1), (1, 1)] {
+        if let (Some(a), Some(b)) = (lit(buffer, x + dx, y), lit(buffer, x, y + dy)) {
+            return [0, 1, 2].map(|i| (a[i] as f32 + b[i] as f32) / 2.0 * CORNER_COVERAGE);
+        }
+    }
+    [0.0; 3]
+}
+
+/// Draw `buffer` onto `canvas` with smoothed edges, shifted right by `shift` (0-1)
+/// pixels so fractional positions blend between neighbouring columns
+pub fn draw_smoothed(buffer: &FrameBuffer, canvas: &mut Box<dyn LedCanvas>, shift: f32) {
+    let shift = shift.clamp(0.0, 1.0);
+    let (width, height) = buffer.size();
+    for y in 0..height {
+        for x in 0..width {
+            let current = smoothed(buffer, x, y);
+            let previous = if shift > 0.0 {
+                smoothed(buffer, x - 1, y)
+            } else {
+                [0.0; 3]
+            };
+            let [r, g, b] =
+                [0, 1, 2].map(|i| (current[i] * (1.0 - shift) + previous[i] * shift).round() as u8);
+            if r > 0 || g > 0 || b > 0 {
+                canvas.set_pixel(x as usize, y as usize, r, g, b);
+            }
+        }
+    }
+}
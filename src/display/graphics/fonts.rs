@@ -0,0 +1,30 @@
+//! Named bitmap fonts shared by the renderers and the text measurement API
+
+use embedded_graphics::mono_font::iso_8859_1::{FONT_10X20, FONT_5X8, FONT_6X10};
+use embedded_graphics::mono_font::MonoFont;
+
+/// Font used by text content
+pub const DEFAULT_FONT: &str = "10x20";
+
+const FONTS: [(&str, &MonoFont<'static>); 3] = [
+    ("10x20", &FONT_10X20),
+    ("6x10", &FONT_6X10),
+    ("5x8", &FONT_5X8),
+];
+
+/// Look up a font by its "WIDTHxHEIGHT" name
+pub fn font_by_name(name: &str) -> Option<&'static MonoFont<'static>> {
+    FONTS
+        .iter()
+        .find(|(font_name, _)| font_name.eq_ignore_ascii_case(name))
+        .map(|(_, font)| *font)
+}
+
+/// Fonts from largest to smallest, for shrinking text until it fits
+pub fn fonts_largest_first() -> impl Iterator<Item = &'static MonoFont<'static>> {
+    FONTS.iter().map(|(_, font)| *font)
+}
+
+pub fn font_names() -> Vec<&'static str> {
+    FONTS.iter().map(|(name, _)| *name).collect()
+}
@@ -0,0 +1,71 @@
+//! Off-screen canvas that renderers can draw into before the result is copied to the panel
+
+use crate::display::driver::LedCanvas;
+use std::any::Any;
+
+#[derive(Debug)]
+pub struct FrameBuffer {
+    width: i32,
+    height: i32,
+    pixels: Vec<[u8; 3]>,
+}
+
+impl FrameBuffer {
+    pub fn new(width: i32, height: i32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![[0, 0, 0]; (width * height).max(0) as usize],
+        }
+    }
+
+    /// Color of the pixel at (x, y), None when out of bounds
+    pub fn pixel(&self, x: i32, y: i32) -> Option<[u8; 3]> {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return None;
+        }
+        Some(self.pixels[(y * self.width + x) as usize])
+    }
+
+    /// All pixels, row by row
+    pub fn pixels(&self) -> &[[u8; 3]] {
+        &self.pixels
+    }
+
+    pub fn pixels_mut(&mut self) -> &mut [[u8; 3]] {
+        &mut self.pixels
+    }
+
+    /// Copy the lit pixels onto `canvas`, which is expected to be cleared already
+    pub fn copy_to(&self, canvas: &mut Box<dyn LedCanvas>) {
+        let width = self.width.max(1) as usize;
+        for (index, [r, g, b]) in self.pixels.iter().enumerate() {
+            if [*r, *g, *b] != [0, 0, 0] {
+                canvas.set_pixel(index % width, index / width, *r, *g, *b);
+            }
+        }
+    }
+}
+
+impl LedCanvas for FrameBuffer {
+    fn set_pixel(&mut self, x: usize, y: usize, r: u8, g: u8, b: u8) {
+        if x < self.width as usize && y < self.height as usize {
+            self.pixels[y * self.width as usize + x] = [r, g, b];
+        }
+    }
+
+    fn fill(&mut self, r: u8, g: u8, b: u8) {
+        self.pixels.fill([r, g, b]);
+    }
+
+    fn size(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any
+    where
+        Self: 'static,
+    {
+        self
+    }
+}
@@ -1 +1,4 @@
+pub mod antialias;
 pub mod embedded_graphics_support;
+pub mod fonts;
+pub mod frame_buffer;
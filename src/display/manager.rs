@@ -1,15 +1,34 @@
 use crate::config::DisplayConfig;
+use crate::display::ambient::{AmbientFrame, AMBIENT_TIMEOUT};
+use crate::display::brightness_mask::BrightnessMask;
+use crate::display::burn_in::BurnInGuard;
+use crate::display::color_order::{ColorChannel, ColorOrderDetection, DetectionStep};
+use crate::display::dead_pixels::DeadPixelMap;
 use crate::display::driver::{LedCanvas, LedDriver};
-use crate::display::renderer::{create_border_renderer, create_renderer, RenderContext, Renderer};
+use crate::display::graphics::frame_buffer::FrameBuffer;
+use crate::display::power_guard::{PowerGuard, PowerThrottle};
+use crate::display::recorder::{Recorder, Recording};
+use crate::display::renderer::{
+    create_border_renderer, create_renderer, BorderLink, ErrorRenderer, ItemTiming, RenderContext,
+    Renderer,
+};
+use crate::display::suspend::{Suspension, SUSPENDED_FRAME_INTERVAL};
+use crate::display::sync::{SYNC_TIMEOUT, SYNC_TOLERANCE};
 use crate::models::animation::AnimationContent;
-use crate::models::border_effects::BorderEffect;
-use crate::models::clock::ClockFormat;
+use crate::models::clock::{ClockContent, ClockFormat, ClockTransition};
+use crate::models::collection::Collection;
 use crate::models::content::{ContentData, ContentDetails, ContentType};
-use crate::models::playlist::{PlayListItem, Playlist};
-use crate::models::text::TextContent;
-use log::{debug, info};
+use crate::models::panel::PanelSize;
+use crate::models::playlist::{HealthStatus, ItemHealth, PlayListItem, Playlist};
+use crate::models::settings::{DeadPixel, IdleMode, IdleSettings, WelcomeSettings, MAX_BRIGHTNESS};
+use crate::models::text::{TextContent, TextOverflow};
+use crate::utils::{locale, palettes};
+use log::{debug, info, warn};
 use once_cell::sync::Lazy;
-use std::time::Instant;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
 // Structure to manage LED matrix state
@@ -29,8 +48,144 @@ pub struct DisplayManager {
     border_renderer: Option<Box<dyn Renderer>>,
     preview_renderer: Option<Box<dyn Renderer>>,
     preview_border_renderer: Option<Box<dyn Renderer>>,
+    // Shared by the content and border renderer of the active and the previewed item
+    border_link: BorderLink,
+    preview_border_link: BorderLink,
     render_context: RenderContext,
     preview_session_id: Option<String>,
+    // Open event streams of the preview session, which keep it from timing out
+    preview_connections: Arc<AtomicUsize>,
+    brightness_fade: Option<BrightnessFade>,
+    // Render brightness before the outro envelope, what render_context shows outside outros
+    base_brightness: u8,
+    // Fade to black of the finished active item before the playlist moves on
+    outro: Option<Outro>,
+    color_order_detection: Option<ColorOrderDetection>,
+    idle_settings: IdleSettings,
+    idle_state: IdleState,
+    // The operating hours say closed and ask for the idle screen
+    closed: bool,
+    welcome_settings: WelcomeSettings,
+    // Welcome message built from welcome_settings
+    welcome_item: PlayListItem,
+    burn_in: Option<BurnInGuard>,
+    // Dims frames that would draw more current than the power supply provides
+    power_guard: Option<PowerGuard>,
+    // Per-pixel dimming uploaded through /api/display/brightness-mask
+    brightness_mask: Option<BrightnessMask>,
+    // Dead or stuck pixels registered through /api/display/dead-pixels
+    dead_pixels: DeadPixelMap,
+    // Recording started through /api/display/record
+    recorder: Option<Recorder>,
+    // Share of the brightness kept and frame rate cap while the SoC is too hot
+    thermal_percent: u8,
+    max_fps: Option<u32>,
+    item_health: HashMap<String, ItemHealth>,
+    // Every playlist item has expired or is disabled, so the welcome screen shows instead
+    nothing_playable: bool,
+    interstitial_progress: HashMap<String, InterstitialProgress>,
+    // Regular item to continue after while an interstitial is showing
+    interrupted_index: Option<usize>,
+    // Switched off through /api/simple/state, the panel stays black while playback continues
+    powered_on: bool,
+    // Set while switched off once the black frame is shown, the driver gets no frames then
+    suspension: Option<Suspension>,
+    // Latest ambient frame received over UDP and when it arrived
+    ambient: Option<(AmbientFrame, Instant)>,
+    // When the sync master was last heard from, transitions follow it while this is recent
+    sync_heard: Option<Instant>,
+    // Flash or notification shown on top of everything else until it has finished
+    overlay: Option<Overlay>,
+    // Seasonal collections and the items of those currently active, which play after the
+    // playlist's own items
+    collections: Vec<Collection>,
+    collection_items: Vec<PlayListItem>,
+}
+
+// Short-lived content started by an action, see flash and notify
+enum Overlay {
+    Flash { color: [u8; 3], started: Instant },
+    Notify(Box<dyn Renderer>),
+}
+
+// A flash blinks FLASH_BLINKS times, each FLASH_PHASE on and FLASH_PHASE off
+const FLASH_BLINKS: u32 = 3;
+const FLASH_PHASE: Duration = Duration::from_millis(250);
+
+// Largest time step when fast-forwarding an item to the sync master's position
+const SYNC_FAST_FORWARD_STEP: Duration = Duration::from_millis(20);
+
+// Most time replayed when fast-forwarding, so an item that has been repeating for hours does
+// not hold the display lock while millions of frames are replayed
+const SYNC_MAX_FAST_FORWARD: Duration = Duration::from_secs(5);
+
+// Extra time a duration based item gets before the watchdog steps in
+const WATCHDOG_GRACE: Duration = Duration::from_secs(5);
+
+// Playlist item the watchdog skipped because its renderer never finished
+pub struct WatchdogTrip {
+    pub item_id: String,
+    pub elapsed: Duration,
+}
+
+// Playlist item whose renderer failed and was replaced by the error placeholder
+pub struct RenderFailure {
+    pub item_id: String,
+    pub error: String,
+}
+
+// Result of an expiry check that changed the playlist or the active item
+pub struct ExpiredItems {
+    // IDs removed from the playlist, only with --delete-expired-items
+    pub deleted: Vec<String>,
+    // The active item expired or was disabled and playback moved on
+    pub skipped_active: bool,
+}
+
+// When an interstitial item was last shown and how many regular items played since
+struct InterstitialProgress {
+    last_shown: Instant,
+    items_since: u32,
+}
+
+// Idle screen state machine, advanced by the update loop
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum IdleState {
+    // Playlist content is showing
+    Playing,
+    // Playlist is empty, the welcome message is showing since the given time
+    Welcome { since: Instant },
+    // The configured idle behavior is showing
+    Idle,
+}
+
+// In-progress brightness transition, advanced by the update loop
+struct BrightnessFade {
+    from: u8,
+    to: u8,
+    started: Instant,
+    duration: Duration,
+}
+
+// Finished item fading to black, the envelope scales the render brightness independently of
+// the renderer, which keeps running until the next item starts
+#[derive(Clone, Copy)]
+struct Outro {
+    started: Instant,
+    duration: Duration,
+}
+
+impl Outro {
+    fn is_finished(&self) -> bool {
+        self.started.elapsed() >= self.duration
+    }
+
+    // Share of `brightness` left at this point of the fade
+    fn scale(&self, brightness: u8) -> u8 {
+        let progress =
+            (self.started.elapsed().as_secs_f32() / self.duration.as_secs_f32()).min(1.0);
+        (brightness as f32 * (1.0 - progress)).round() as u8
+    }
 }
 
 impl DisplayManager {
@@ -57,8 +212,9 @@ impl DisplayManager {
         let default_playlist = Playlist::default();
 
         // Create render context
-        let render_context =
-            RenderContext::new(display_width, display_height, config.user_brightness);
+        let base_brightness = config.user_brightness;
+        let render_context = RenderContext::new(display_width, display_height, base_brightness)
+            .with_seed(config.render_seed);
 
         let mut display_manager = Self {
             playlist: default_playlist,
@@ -78,8 +234,47 @@ impl DisplayManager {
             border_renderer: None,
             preview_renderer: None,
             preview_border_renderer: None,
+            border_link: BorderLink::default(),
+            preview_border_link: BorderLink::default(),
             render_context,
             preview_session_id: None,
+            preview_connections: Arc::default(),
+            brightness_fade: None,
+            base_brightness,
+            outro: None,
+            color_order_detection: None,
+            idle_settings: IdleSettings::default(),
+            idle_state: IdleState::Welcome {
+                since: Instant::now(),
+            },
+            closed: false,
+            welcome_settings: WelcomeSettings::default(),
+            welcome_item: welcome_item(&WelcomeSettings::default()),
+            burn_in: (config.burn_in_shift_minutes > 0)
+                .then(|| BurnInGuard::new(config.burn_in_shift_minutes)),
+            thermal_percent: 100,
+            max_fps: None,
+            brightness_mask: None,
+            dead_pixels: DeadPixelMap::default(),
+            recorder: None,
+            power_guard: config.power_limit_amps.map(|limit| {
+                PowerGuard::new(
+                    limit,
+                    config.power_ma_per_channel,
+                    config.limit_max_brightness,
+                )
+            }),
+            item_health: HashMap::new(),
+            nothing_playable: false,
+            interstitial_progress: HashMap::new(),
+            interrupted_index: None,
+            powered_on: true,
+            suspension: None,
+            ambient: None,
+            sync_heard: None,
+            overlay: None,
+            collections: Vec::new(),
+            collection_items: Vec::new(),
         };
 
         // Initialize renderer if we have content
@@ -133,9 +328,43 @@ impl DisplayManager {
                         AnimationContent::Sparkle { .. } => "Sparkle",
                         AnimationContent::MosaicTwinkle { .. } => "Mosaic Twinkle",
                         AnimationContent::Plasma { .. } => "Plasma Flow",
+                        AnimationContent::Meteor { .. } => "Meteor",
+                        AnimationContent::Fireworks { .. } => "Fireworks",
+                        AnimationContent::NoiseField { .. } => "Noise Field",
+                        AnimationContent::PixelWeather { .. } => "Pixel Weather",
                     };
                     format!("Animation: {}", preset)
                 }
+                ContentDetails::Plugin(plugin_content) => {
+                    format!("Plugin: {}", plugin_content.plugin)
+                }
+                ContentDetails::QrCode(qr_content) => {
+                    format!("QR code: {}", qr_content.data)
+                }
+                ContentDetails::Barcode(barcode_content) => {
+                    format!(
+                        "Barcode: {} ({:?})",
+                        barcode_content.data, barcode_content.format
+                    )
+                }
+                ContentDetails::BigNumber(number_content) => {
+                    format!("Big number: {}", number_content.value)
+                }
+                ContentDetails::Countdown(countdown_content) => {
+                    format!("Countdown to {}", countdown_content.target)
+                }
+                ContentDetails::Command(command_content) => {
+                    format!("Command: {}", command_content.command)
+                }
+                ContentDetails::Slideshow(slideshow_content) => {
+                    format!("Slideshow: {} images", slideshow_content.images.len())
+                }
+                ContentDetails::Video(video_content) => {
+                    format!("Video: {}", video_content.video_id)
+                }
+                ContentDetails::Stream(stream_content) => {
+                    format!("Stream: {}", stream_content.url)
+                }
             };
             info!("  Item {}: {}", i + 1, content_desc);
         }
@@ -149,8 +378,10 @@ impl DisplayManager {
         // IMPORTANT: Ensure we always start with the first item
         display_manager.playlist.active_index = 0;
 
-        // Initialize renderer
-        display_manager.setup_active_renderer();
+        // Initialize renderer, leaving the welcome state if the playlist has content
+        if !display_manager.update_idle_state() {
+            display_manager.setup_active_renderer();
+        }
 
         display_manager
     }
@@ -161,35 +392,8 @@ impl DisplayManager {
             return self.preview_content.as_ref().unwrap();
         }
 
-        if self.playlist.items.is_empty() {
-            // Store the default message item
-            static DEFAULT_ITEM: Lazy<PlayListItem> = Lazy::new(|| {
-                // Get the local IP for a more helpful message
-                let ip = get_local_ip().unwrap_or_else(|| "localhost".to_string());
-
-                PlayListItem {
-                    id: Uuid::new_v4().to_string(),
-                    duration: None,                   // Updated to use None
-                    repeat_count: Some(0),            // Infinite repeat with Some(0)
-                    border_effect: Some(BorderEffect::Pulse {
-                        colors: vec![[0, 255, 0], [0, 200, 0]]
-                    }),
-                    content: ContentData {
-                        content_type: ContentType::Text,
-                        data: ContentDetails::Text(TextContent {
-                            text: format!("LED Matrix Controller | Web interface: http://{}:3000 | Use web UI to configure display", ip),
-                            scroll: true,
-                            color: [0, 255, 0],  // Green color for visibility
-                            speed: 30.0,         // Slower for better readability
-                            text_segments: None,
-                        }),
-                    },
-                }
-            });
-            &DEFAULT_ITEM
-        } else {
-            &self.playlist.items[self.playlist.active_index]
-        }
+        self.rotation_item(self.playlist.active_index)
+            .unwrap_or(&self.welcome_item)
     }
 
     pub fn check_transition(&mut self) -> bool {
@@ -199,10 +403,28 @@ impl DisplayManager {
         }
 
         // If playlist is empty, no transitions needed
-        if self.playlist.items.is_empty() {
+        if self.rotation_len() == 0 {
             return false;
         }
 
+        // Followers switch items when the sync master does
+        if let Some(heard) = self.sync_heard {
+            if heard.elapsed() < SYNC_TIMEOUT {
+                return false;
+            }
+            info!("Lost the sync master, continuing the playlist on our own");
+            self.sync_heard = None;
+        }
+
+        // A running outro moves on once the panel has faded to black
+        if let Some(outro) = self.outro {
+            if !outro.is_finished() {
+                return false;
+            }
+            self.advance_playlist();
+            return true;
+        }
+
         // Check if the current content is complete based on renderer state
         let should_transition = self
             .active_renderer
@@ -210,6 +432,14 @@ impl DisplayManager {
             .map_or(false, |renderer| renderer.is_complete());
 
         if should_transition {
+            let duration = Duration::from_millis(self.outro_ms() as u64);
+            if !duration.is_zero() {
+                self.outro = Some(Outro {
+                    started: Instant::now(),
+                    duration,
+                });
+                return false;
+            }
             self.advance_playlist();
             return true;
         }
@@ -217,21 +447,254 @@ impl DisplayManager {
         false
     }
 
+    // Outro of the active item, its own or the --outro-ms default
+    fn outro_ms(&self) -> u32 {
+        self.active_item()
+            .and_then(|item| item.outro_ms)
+            .unwrap_or(self.config.outro_ms)
+    }
+
+    // Step the outro envelope, called once per frame by the update loop. Previews are never
+    // dimmed, so entering preview mode cancels a running outro.
+    pub fn update_outro(&mut self) {
+        let Some(outro) = self.outro else {
+            return;
+        };
+        if self.preview_mode {
+            self.cancel_outro();
+            return;
+        }
+        if outro.scale(self.base_brightness) != self.render_context.brightness {
+            self.apply_render_brightness(self.base_brightness);
+        }
+    }
+
+    // Stop a running outro and show the full brightness again
+    fn cancel_outro(&mut self) {
+        if self.outro.take().is_some() {
+            self.apply_render_brightness(self.base_brightness);
+        }
+    }
+
+    // Swap a failed active renderer for the error placeholder so the failure is visible
+    // instead of a black panel. The placeholder completes after a few seconds.
+    pub fn check_renderer_health(&mut self) -> Option<RenderFailure> {
+        let error = self.active_renderer.as_ref()?.error()?;
+        let item = self.active_item()?.clone();
+        warn!("Playlist item {} failed to render: {}", item.id, error);
+
+        let health = self.item_health.entry(item.id.clone()).or_default();
+        health.status = HealthStatus::Error;
+        health.error = Some(error.clone());
+        health.last_error_at = Some(chrono::Utc::now().to_rfc3339());
+        health.error_count += 1;
+
+        self.active_renderer = Some(Box::new(ErrorRenderer::new(
+            &item,
+            self.render_context.clone(),
+        )));
+        self.border_renderer = None;
+
+        Some(RenderFailure {
+            item_id: item.id,
+            error,
+        })
+    }
+
+    // Health of a playlist item, Unknown if it has not been shown yet
+    pub fn item_health(&self, item_id: &str) -> ItemHealth {
+        self.item_health.get(item_id).cloned().unwrap_or_default()
+    }
+
+    // Force-advance the active item when its renderer has not finished long after it should have,
+    // e.g. because an image went missing or an animation produced NaN
+    pub fn check_watchdog(&mut self) -> Option<WatchdogTrip> {
+        if self.preview_mode || self.config.max_item_seconds == 0 {
+            return None;
+        }
+
+        let item = self.active_item()?;
+        // Items repeating forever never finish by design
+        if item.repeat_count == Some(0) {
+            return None;
+        }
+
+        let limit = Duration::from_secs(self.config.max_item_seconds).max(
+            item.duration.map_or(Duration::ZERO, |seconds| {
+                Duration::from_secs(seconds) + WATCHDOG_GRACE
+            }),
+        );
+        let elapsed = self.last_transition.elapsed();
+        if elapsed < limit {
+            return None;
+        }
+
+        let item_id = item.id.clone();
+        warn!(
+            "Watchdog: playlist item {} did not finish after {}s, skipping it",
+            item_id,
+            elapsed.as_secs()
+        );
+        self.advance_playlist();
+        Some(WatchdogTrip { item_id, elapsed })
+    }
+
+    // Skip or delete playlist items whose 'expires_at' time has passed and skip disabled
+    // items, called periodically by the update loop
+    pub fn check_expired_items(&mut self) -> Option<ExpiredItems> {
+        let mut deleted = Vec::new();
+        let mut skipped_active = self.refresh_collections(false);
+
+        if self.config.delete_expired_items {
+            let expired: Vec<bool> = self
+                .playlist
+                .items
+                .iter()
+                .map(PlayListItem::is_expired)
+                .collect();
+            if expired.contains(&true) {
+                let active_index = self.playlist.active_index;
+                let mut index = 0;
+                self.playlist.items.retain(|item| {
+                    let keep = !expired[index];
+                    if !keep {
+                        deleted.push(item.id.clone());
+                    }
+                    index += 1;
+                    keep
+                });
+                info!("Deleted {} expired playlist item(s)", deleted.len());
+
+                // Keep the active item, or continue with the item that followed it
+                let removed_before = expired[..active_index.min(expired.len())]
+                    .iter()
+                    .filter(|e| **e)
+                    .count();
+                self.playlist.active_index = active_index - removed_before;
+                if self.playlist.active_index >= self.rotation_len() {
+                    self.playlist.active_index = 0;
+                }
+                if expired.get(active_index).copied().unwrap_or(false) {
+                    self.reset_display_state();
+                    skipped_active = true;
+                }
+            }
+        }
+
+        let playable: Vec<bool> = self.rotation().map(PlayListItem::is_playable).collect();
+        // Conditions only decide which item comes next, but when none hold there is
+        // nothing to show
+        self.nothing_playable = !playable.is_empty()
+            && !self
+                .rotation()
+                .any(|item| item.is_playable() && item.conditions_met());
+        let active_playable = playable
+            .get(self.playlist.active_index)
+            .copied()
+            .unwrap_or(true);
+        if !active_playable && !self.nothing_playable {
+            info!(
+                "Playlist item {} expired or was disabled, skipping it",
+                self.get_current_content().id
+            );
+            self.advance_playlist();
+            skipped_active = true;
+        }
+
+        (skipped_active || !deleted.is_empty()).then_some(ExpiredItems {
+            deleted,
+            skipped_active,
+        })
+    }
+
+    // Count the item that just finished and pick an interstitial that is due to be shown
+    fn due_interstitial(&mut self, finished_index: usize) -> Option<usize> {
+        let now = Instant::now();
+        let finished_regular = self
+            .rotation_item(finished_index)
+            .is_some_and(|item| item.interstitial.is_none());
+
+        let mut due = None;
+        let rotation = self.playlist.items.iter().chain(&self.collection_items);
+        for (index, item) in rotation.enumerate() {
+            let Some(interstitial) = &item.interstitial else {
+                continue;
+            };
+            // Intervals start when the item is first seen
+            let progress =
+                self.interstitial_progress
+                    .entry(item.id.clone())
+                    .or_insert(InterstitialProgress {
+                        last_shown: now,
+                        items_since: 0,
+                    });
+            if finished_regular {
+                progress.items_since += 1;
+            }
+
+            let is_due = match (interstitial.every_minutes, interstitial.every_items) {
+                (Some(minutes), _) => {
+                    now.duration_since(progress.last_shown)
+                        >= Duration::from_secs(minutes as u64 * 60)
+                }
+                (None, Some(items)) => progress.items_since >= items,
+                (None, None) => false,
+            };
+            if is_due && due.is_none() && item.is_playable() && item.conditions_met() {
+                due = Some(index);
+            }
+        }
+
+        let index = due?;
+        let item_id = self.rotation_item(index)?.id.clone();
+        if let Some(progress) = self.interstitial_progress.get_mut(&item_id) {
+            progress.last_shown = now;
+            progress.items_since = 0;
+        }
+        info!("Showing interstitial item {}", item_id);
+        Some(index)
+    }
+
     fn advance_playlist(&mut self) {
         // If playlist is empty, nothing to advance
-        if self.playlist.items.is_empty() {
+        let length = self.rotation_len();
+        if length == 0 {
             return;
         }
 
-        // Save current index
-        let old_index = self.playlist.active_index;
-
-        // Change to next item
-        let length = self.playlist.items.len();
-        if old_index + 1 < length {
-            self.playlist.active_index = old_index + 1;
-        } else if self.playlist.repeat {
-            self.playlist.active_index = 0;
+        // Save current index, or the regular item an interstitial interrupted
+        let finished_index = self.playlist.active_index;
+        let old_index = self
+            .interrupted_index
+            .take()
+            .unwrap_or(finished_index)
+            .min(length - 1);
+
+        if let Some(index) = self.due_interstitial(finished_index) {
+            // Resume the regular rotation after the interstitial
+            self.interrupted_index = Some(old_index);
+            self.playlist.active_index = index;
+        } else {
+            // Change to the next regular item that is enabled, has not expired and whose
+            // conditions hold. Interstitials only take part in the rotation when there is
+            // nothing else to show.
+            let has_regular = self.rotation().any(|item| {
+                item.interstitial.is_none() && item.is_playable() && item.conditions_met()
+            });
+            for step in 1..=length {
+                if old_index + step >= length && !self.playlist.repeat {
+                    break;
+                }
+                let index = (old_index + step) % length;
+                if self.rotation_item(index).is_some_and(|item| {
+                    item.is_playable()
+                        && item.conditions_met()
+                        && (item.interstitial.is_none() || !has_regular)
+                }) {
+                    self.playlist.active_index = index;
+                    break;
+                }
+            }
         }
 
         // Reset transition timestamp and counters
@@ -253,9 +716,127 @@ impl DisplayManager {
     }
 
     pub fn update_display(&mut self) {
+        // A switched off panel keeps showing the last (black) frame without the driver's help,
+        // unless a recording needs frames
+        if self.suspension.is_some() && self.recorder.is_none() {
+            return;
+        }
+
         let mut canvas = self.canvas.take().expect("Canvas missing");
         canvas.fill(0, 0, 0); // Clear the canvas
 
+        // Dead-pixel fixes, the brightness mask, recordings and the power guard work on the
+        // finished frame off-screen before it reaches the panel, the guard measuring the
+        // corrected frame
+        if self.power_guard.is_some()
+            || self.brightness_mask.is_some()
+            || !self.dead_pixels.is_empty()
+            || self.recorder.is_some()
+        {
+            let mut frame: Box<dyn LedCanvas> =
+                Box::new(FrameBuffer::new(self.display_width, self.display_height));
+            self.compose_frame(&mut frame);
+            if let Some(frame) = frame.as_any_mut().downcast_mut::<FrameBuffer>() {
+                self.dead_pixels.apply(frame);
+                if let Some(mask) = &self.brightness_mask {
+                    mask.apply(frame);
+                }
+                if let Some(recorder) = &mut self.recorder {
+                    recorder.capture(frame);
+                }
+                match &mut self.power_guard {
+                    Some(guard) => guard.draw(frame, &mut canvas),
+                    None => frame.copy_to(&mut canvas),
+                }
+            }
+        } else {
+            self.compose_frame(&mut canvas);
+        }
+
+        // Update the canvas using the driver
+        let updated_canvas = self.driver.update_canvas(canvas);
+        self.canvas = Some(updated_canvas);
+
+        if !self.powered_on && self.suspension.is_none() {
+            info!("Display driver suspended until the display is switched on");
+            self.suspension = Some(Suspension::release_priority());
+        }
+
+        if self
+            .recorder
+            .as_ref()
+            .is_some_and(|recorder| recorder.is_finished())
+        {
+            if let Some(recorder) = self.recorder.take() {
+                recorder.finish();
+            }
+        }
+    }
+
+    // Draw everything the panel shows right now onto a cleared canvas
+    fn compose_frame(&mut self, canvas: &mut Box<dyn LedCanvas>) {
+        // A switched off display only shows black
+        if !self.powered_on {
+            return;
+        }
+
+        // Color-order detection shows a solid test color instead of any content
+        if let Some(detection) = &self.color_order_detection {
+            let [r, g, b] = self
+                .render_context
+                .apply_brightness(detection.current_color().rgb());
+            canvas.fill(r, g, b);
+            return;
+        }
+
+        // Flashes and notifications interrupt whatever else is showing
+        match &self.overlay {
+            Some(Overlay::Flash { color, started }) => {
+                let phase = started.elapsed().as_millis() / FLASH_PHASE.as_millis();
+                if phase.is_multiple_of(2) {
+                    let [r, g, b] = self.render_context.apply_brightness(*color);
+                    canvas.fill(r, g, b);
+                }
+                return;
+            }
+            Some(Overlay::Notify(renderer)) => {
+                renderer.render(canvas);
+                return;
+            }
+            None => {}
+        }
+
+        // Ambient frames replace the playlist while they keep arriving
+        if let Some((frame, received)) = &self.ambient {
+            if received.elapsed() < AMBIENT_TIMEOUT {
+                frame.draw(canvas, &self.render_context);
+                return;
+            }
+            info!("Ambient frames stopped, returning to the playlist");
+            self.ambient = None;
+        }
+
+        // Anti burn-in shifts the composed frame, so it is rendered off-screen first
+        if let Some(burn_in) = &mut self.burn_in {
+            burn_in.update();
+            let mut frame: Box<dyn LedCanvas> =
+                Box::new(FrameBuffer::new(self.display_width, self.display_height));
+            self.render_frame(&mut frame);
+
+            let exercise_color = self.render_context.apply_brightness([255, 255, 255]);
+            if let (Some(burn_in), Some(frame)) = (
+                &self.burn_in,
+                frame.as_any_mut().downcast_mut::<FrameBuffer>(),
+            ) {
+                burn_in.draw(frame, canvas, exercise_color);
+            }
+        } else {
+            self.render_frame(canvas);
+        }
+    }
+
+    // Render the current content with its border on top
+    fn render_frame(&self, canvas: &mut Box<dyn LedCanvas>) {
         // Use the appropriate content renderer
         let content_renderer = if self.preview_mode && self.preview_renderer.is_some() {
             self.preview_renderer.as_ref()
@@ -265,7 +846,7 @@ impl DisplayManager {
 
         // Render content first
         if let Some(renderer) = content_renderer {
-            renderer.render(&mut canvas);
+            renderer.render(canvas);
         }
 
         // Use the appropriate border renderer
@@ -277,40 +858,384 @@ impl DisplayManager {
 
         // Render border on top
         if let Some(renderer) = border_renderer {
-            renderer.render(&mut canvas);
+            renderer.render(canvas);
         }
-
-        // Update the canvas using the driver
-        let updated_canvas = self.driver.update_canvas(canvas);
-        self.canvas = Some(updated_canvas);
     }
 
     // Set up the renderer for the active content
     pub fn setup_active_renderer(&mut self) {
-        if self.playlist.items.is_empty() {
-            self.active_renderer = None;
-            self.border_renderer = None;
-            return;
-        }
-
-        let current = self.get_current_content().clone();
-
         // Drop existing renderers first to avoid borrow conflicts
         self.active_renderer = None;
         self.border_renderer = None;
 
-        // Then create new renderers
-        self.active_renderer = Some(create_renderer(&current, self.render_context.clone()));
+        // The new item starts at full brightness, whatever ended the previous one's outro
+        self.cancel_outro();
+
+        // An empty or expired playlist shows the welcome message or the idle behavior
+        let current = if self.rotation_len() == 0 || self.idle_state != IdleState::Playing {
+            match self.idle_item() {
+                Some(item) => item,
+                None => return,
+            }
+        } else {
+            self.get_current_content().clone()
+        };
+
+        // Then create new renderers, linked so the border can follow the content
+        self.border_link = BorderLink::default();
+        let renderer = create_renderer(&current, self.active_render_context());
+        if self.active_item().is_some() && renderer.error().is_none() {
+            self.item_health
+                .entry(current.id.clone())
+                .or_default()
+                .status = HealthStatus::Ok;
+        }
+        self.active_renderer = Some(renderer);
 
         // Create border renderer if border effect is specified
         if current.border_effect.is_some() {
             self.border_renderer = Some(create_border_renderer(
                 &current,
-                self.render_context.clone(),
+                self.border_render_context(),
             ));
         }
     }
 
+    // Playlist item currently scheduled, ignoring preview content and the welcome screen
+    pub fn active_item(&self) -> Option<&PlayListItem> {
+        if self.idle_state != IdleState::Playing {
+            return None;
+        }
+        self.rotation_item(self.playlist.active_index)
+    }
+
+    pub fn panel_size(&self) -> PanelSize {
+        PanelSize::new(self.display_width, self.display_height)
+    }
+
+    // Number of items in the rotation, see rotation
+    pub fn rotation_len(&self) -> usize {
+        self.playlist.items.len() + self.collection_items.len()
+    }
+
+    // Items in the order they play: the playlist's own items, then those of the active
+    // collections. Indices within the playlist are the same as in the rotation.
+    fn rotation(&self) -> impl Iterator<Item = &PlayListItem> {
+        self.playlist.items.iter().chain(&self.collection_items)
+    }
+
+    fn rotation_item(&self, index: usize) -> Option<&PlayListItem> {
+        self.playlist.items.get(index).or_else(|| {
+            self.collection_items
+                .get(index.checked_sub(self.playlist.items.len())?)
+        })
+    }
+
+    pub fn collections(&self) -> &[Collection] {
+        &self.collections
+    }
+
+    // Replace the collections, restarting the active item if it belongs to one
+    pub fn set_collections(&mut self, collections: Vec<Collection>) {
+        self.collections = collections;
+        self.refresh_collections(true);
+    }
+
+    // Take the items of the collections active today into the rotation. Returns true if the
+    // active item's collection closed and playback moved on. Without `force` nothing
+    // happens while the same items are active.
+    fn refresh_collections(&mut self, force: bool) -> bool {
+        let items: Vec<PlayListItem> = self
+            .collections
+            .iter()
+            .filter(|collection| collection.is_active())
+            .flat_map(|collection| collection.items.iter().cloned())
+            .collect();
+        let unchanged = items.len() == self.collection_items.len()
+            && items
+                .iter()
+                .zip(&self.collection_items)
+                .all(|(item, current)| item.id == current.id);
+        if unchanged && !force {
+            return false;
+        }
+
+        // Collection items come last, so only an active collection item can move
+        let active_index = self.playlist.active_index;
+        let active_id = (active_index >= self.playlist.items.len())
+            .then(|| self.rotation_item(active_index).map(|item| item.id.clone()))
+            .flatten();
+        let changed = items.len() != self.collection_items.len();
+        self.collection_items = items;
+        if changed {
+            info!(
+                "{} collection item(s) are now part of the rotation",
+                self.collection_items.len()
+            );
+        }
+
+        let Some(active_id) = active_id else {
+            return false;
+        };
+        let position = self.rotation().position(|item| item.id == active_id);
+        match position {
+            Some(index) => {
+                self.playlist.active_index = index;
+                if force {
+                    self.reset_display_state();
+                }
+                false
+            }
+            None => {
+                // Continue with the item that followed it, or start over
+                if active_index >= self.rotation_len() {
+                    self.playlist.active_index = 0;
+                }
+                self.interrupted_index = None;
+                self.reset_display_state();
+                true
+            }
+        }
+    }
+
+    // Time the active playlist item has been on screen
+    pub fn active_item_elapsed(&self) -> Duration {
+        self.last_transition.elapsed()
+    }
+
+    // Repeat cycles the active renderer has completed, if it counts them
+    pub fn active_completed_cycles(&self) -> Option<u32> {
+        // The welcome and idle screens are not playlist items
+        self.active_item()?;
+        self.active_renderer
+            .as_ref()
+            .and_then(|renderer| renderer.completed_cycles())
+    }
+
+    // Share of the active item's display time completed, if its renderer can tell
+    pub fn active_progress(&self) -> Option<f32> {
+        self.active_item()?;
+        self.active_renderer
+            .as_ref()
+            .and_then(|renderer| renderer.progress())
+    }
+
+    // Throttling by the power guard that started since the last check, for the update loop
+    // to report
+    pub fn check_power_guard(&mut self) -> Option<PowerThrottle> {
+        self.power_guard.as_mut()?.take_warning()
+    }
+
+    // Configuration the controller was started with
+    pub fn has_brightness_mask(&self) -> bool {
+        self.brightness_mask.is_some()
+    }
+
+    // Replace the brightness mask applied to every frame, None removes it
+    pub fn set_brightness_mask(&mut self, mask: Option<BrightnessMask>) {
+        self.brightness_mask = mask;
+    }
+
+    pub fn dead_pixels(&self) -> &[DeadPixel] {
+        self.dead_pixels.pixels()
+    }
+
+    // Replace the registered dead or stuck pixels
+    pub fn set_dead_pixels(&mut self, pixels: Vec<DeadPixel>) {
+        self.dead_pixels = DeadPixelMap::new(pixels);
+    }
+
+    // Record the frames sent to the panel for `duration`, None while a recording is running
+    pub fn start_recording(
+        &mut self,
+        duration: Duration,
+    ) -> Option<tokio::sync::oneshot::Receiver<Recording>> {
+        if self.recorder.is_some() {
+            return None;
+        }
+        let (recorder, receiver) = Recorder::new(duration, self.display_width, self.display_height);
+        self.recorder = Some(recorder);
+        Some(receiver)
+    }
+
+    pub fn config(&self) -> &DisplayConfig {
+        &self.config
+    }
+
+    // Hardware profile the controller was started with
+    pub fn profile_name(&self) -> Option<&str> {
+        self.config.profile.as_deref()
+    }
+
+    // LED color sequence the driver was started with
+    pub fn led_sequence(&self) -> &str {
+        &self.config.led_sequence
+    }
+
+    // Start (or restart) color-order detection, returning the first test color
+    pub fn start_color_order_detection(&mut self) -> &ColorOrderDetection {
+        info!(
+            "Starting color-order detection (current sequence: {})",
+            self.config.led_sequence
+        );
+        self.color_order_detection
+            .insert(ColorOrderDetection::new(&self.config.led_sequence))
+    }
+
+    pub fn color_order_detection(&self) -> Option<&ColorOrderDetection> {
+        self.color_order_detection.as_ref()
+    }
+
+    // Record the color the user saw. Returns None when no detection is running.
+    // Detection ends once a sequence is found or the answers contradict each other.
+    pub fn report_color_order(&mut self, seen: ColorChannel) -> Option<DetectionStep> {
+        let step = self.color_order_detection.as_mut()?.report(seen);
+        if !matches!(step, DetectionStep::Continue) {
+            self.color_order_detection = None;
+        }
+        Some(step)
+    }
+
+    // Stop color-order detection and return to normal content
+    pub fn cancel_color_order_detection(&mut self) -> bool {
+        self.color_order_detection.take().is_some()
+    }
+
+    // Blink the whole panel in `color`, interrupting the current content
+    pub fn flash(&mut self, color: [u8; 3]) {
+        self.overlay = Some(Overlay::Flash {
+            color,
+            started: Instant::now(),
+        });
+    }
+
+    // Scroll `text` across the panel once on top of the current content
+    pub fn notify(&mut self, text: &str) {
+        let item = PlayListItem {
+            duration: None,
+            repeat_count: Some(1),
+            content: ContentData {
+                content_type: ContentType::Text,
+                data: ContentDetails::Text(TextContent {
+                    text: text.to_string(),
+                    scroll: true,
+                    color: [255, 255, 255],
+                    speed: 50.0,
+                    text_segments: None,
+                    pixels_per_second: None,
+                    read_time_ms: None,
+                    scroll_ease_ms: None,
+                    antialias: false,
+                    paging: None,
+                    overflow: TextOverflow::default(),
+                }),
+            },
+            ..PlayListItem::default()
+        };
+        let renderer = create_renderer(&item, self.render_context.clone());
+        self.overlay = Some(Overlay::Notify(renderer));
+    }
+
+    // Show an ambient frame instead of the playlist until frames stop arriving
+    pub fn set_ambient_frame(&mut self, frame: AmbientFrame) {
+        if self.ambient.is_none() {
+            info!("Receiving ambient frames, pausing the playlist display");
+        }
+        self.ambient = Some((frame, Instant::now()));
+    }
+
+    // Align playback with the sync master, which shows `item_id` (or the item at `index` if
+    // this playlist has no such ID) for `elapsed`. The item is restarted and fast-forwarded
+    // when it is not the active one or has drifted too far. Returns true if it was.
+    pub fn follow_sync(&mut self, item_id: &str, index: usize, elapsed: Duration) -> bool {
+        if self.sync_heard.is_none() {
+            info!("Following the sync master");
+        }
+        self.sync_heard = Some(Instant::now());
+
+        if self.preview_mode || self.active_item().is_none() {
+            return false;
+        }
+        let Some(index) = self
+            .rotation()
+            .position(|item| item.id == item_id)
+            .or((index < self.rotation_len()).then_some(index))
+        else {
+            return false;
+        };
+        if index == self.playlist.active_index
+            && self.active_item_elapsed().abs_diff(elapsed) <= SYNC_TOLERANCE
+        {
+            return false;
+        }
+
+        self.playlist.active_index = index;
+        self.interrupted_index = None;
+        self.current_repeat = 0;
+        self.last_transition = Instant::now()
+            .checked_sub(elapsed)
+            .unwrap_or_else(Instant::now);
+        self.setup_active_renderer();
+        if let Some(renderer) = &mut self.active_renderer {
+            renderer.reset();
+        }
+
+        // Step in frame-sized increments so scrolling wraps the same way it does live
+        let mut remaining = elapsed.min(SYNC_MAX_FAST_FORWARD);
+        while !remaining.is_zero() {
+            let step = remaining.min(SYNC_FAST_FORWARD_STEP);
+            let dt = step.as_secs_f32();
+            if let Some(renderer) = &mut self.active_renderer {
+                renderer.update(dt);
+            }
+            if let Some(renderer) = &mut self.border_renderer {
+                renderer.update(dt);
+            }
+            remaining -= step;
+        }
+        true
+    }
+
+    // Move on to the next playable item now, as if the current one had finished
+    pub fn skip_to_next(&mut self) {
+        if self.preview_mode {
+            return;
+        }
+        self.advance_playlist();
+    }
+
+    // Show the item with the given ID now, the rotation continues from there.
+    // Returns false if there is no such playable item.
+    pub fn show_item(&mut self, item_id: &str) -> bool {
+        if self.preview_mode {
+            return false;
+        }
+        let Some(index) = self
+            .rotation()
+            .position(|item| item.id == item_id && item.is_playable())
+        else {
+            return false;
+        };
+        self.playlist.active_index = index;
+        self.interrupted_index = None;
+        self.reset_display_state();
+        true
+    }
+
+    pub fn is_powered_on(&self) -> bool {
+        self.powered_on
+    }
+
+    pub fn set_powered_on(&mut self, on: bool) {
+        if self.powered_on != on {
+            info!("Display switched {}", if on { "on" } else { "off" });
+        }
+        self.powered_on = on;
+        if on && self.suspension.take().is_some() {
+            info!("Display driver resumed");
+        }
+    }
+
     // Add a method to get the current brightness
     pub fn get_brightness(&self) -> u8 {
         self.config.user_brightness
@@ -332,9 +1257,15 @@ impl DisplayManager {
         self.driver.shutdown();
     }
 
+    // Panel brightness MAX_BRIGHTNESS maps to, the configured --limit-max-brightness. The
+    // driver applies it, so user brightness is scaled below it.
+    pub fn brightness_limit(&self) -> u8 {
+        self.config.limit_max_brightness
+    }
+
     // Set brightness now updates the render context without resetting animations
     pub fn set_brightness(&mut self, brightness: u8) {
-        let brightness = brightness.clamp(0, 100);
+        let brightness = brightness.min(MAX_BRIGHTNESS);
 
         // Only log at debug level for continuous updates
         // This won't show up unless RUST_LOG=debug is set
@@ -343,31 +1274,126 @@ impl DisplayManager {
         // Update the brightness in the config
         self.config.user_brightness = brightness;
 
+        // An explicit brightness cancels any running fade
+        self.brightness_fade = None;
+        self.apply_render_brightness(self.target_render_brightness(brightness));
+    }
+
+    // Render brightness for a user brightness, lowered while thermal throttling
+    fn target_render_brightness(&self, brightness: u8) -> u8 {
+        (brightness as u32 * self.thermal_percent as u32 / 100) as u8
+    }
+
+    // Keep only `percent` of the brightness and cap the frame rate, used by the thermal
+    // monitor. The user brightness stays as it is.
+    pub fn set_thermal_limit(&mut self, percent: u8, max_fps: Option<u32>) {
+        self.max_fps = max_fps;
+        if percent == self.thermal_percent {
+            return;
+        }
+        self.thermal_percent = percent;
+
+        let target = self.target_render_brightness(self.config.user_brightness);
+        match &mut self.brightness_fade {
+            Some(fade) => fade.to = target,
+            None => self.apply_render_brightness(target),
+        }
+    }
+
+    // Shortest time between two frames, zero when the frame rate is not capped
+    pub fn min_frame_interval(&self) -> Duration {
+        if self.suspension.is_some() {
+            return SUSPENDED_FRAME_INTERVAL;
+        }
+        self.max_fps
+            .map(|fps| Duration::from_secs(1) / fps.max(1))
+            .unwrap_or_default()
+    }
+
+    // Fade from the brightness currently shown to the target over `fade_ms` milliseconds.
+    // The target is reported by get_brightness right away so it is saved and broadcast as usual.
+    pub fn fade_brightness(&mut self, brightness: u8, fade_ms: u64) {
+        let brightness = brightness.min(MAX_BRIGHTNESS);
+        debug!(
+            "Fading display brightness: {} -> {} over {}ms",
+            self.config.user_brightness, brightness, fade_ms
+        );
+
+        self.config.user_brightness = brightness;
+        self.brightness_fade = Some(BrightnessFade {
+            from: self.base_brightness,
+            to: self.target_render_brightness(brightness),
+            started: Instant::now(),
+            duration: Duration::from_millis(fade_ms),
+        });
+    }
+
+    // Advance a running brightness fade, called once per frame by the update loop
+    pub fn update_brightness_fade(&mut self) {
+        let Some(fade) = &self.brightness_fade else {
+            return;
+        };
+
+        let progress =
+            (fade.started.elapsed().as_secs_f32() / fade.duration.as_secs_f32()).min(1.0);
+        let brightness =
+            (fade.from as f32 + (fade.to as f32 - fade.from as f32) * progress).round() as u8;
+        if progress >= 1.0 {
+            self.brightness_fade = None;
+        }
+
+        // Brightness only has 101 steps, so most frames leave the context untouched
+        if brightness != self.base_brightness {
+            self.apply_render_brightness(brightness);
+        }
+    }
+
+    // Rebuild the render context with the given brightness and push it to all renderers. A
+    // running outro dims it further.
+    fn apply_render_brightness(&mut self, brightness: u8) {
+        self.base_brightness = brightness;
+        let brightness = self
+            .outro
+            .map_or(brightness, |outro| outro.scale(brightness));
+
         // Update the render context brightness
         self.render_context =
-            RenderContext::new(self.display_width, self.display_height, brightness);
+            RenderContext::new(self.display_width, self.display_height, brightness)
+                .with_seed(self.config.render_seed);
 
         // Update context in all active renderers without resetting animation state
+        let active_context = self.active_render_context();
         if let Some(renderer) = &mut self.active_renderer {
-            renderer.update_context(self.render_context.clone());
+            renderer.update_context(active_context);
         }
 
+        let border_context = self.border_render_context();
         if let Some(renderer) = &mut self.border_renderer {
+            renderer.update_context(border_context);
+        }
+
+        if let Some(Overlay::Notify(renderer)) = &mut self.overlay {
             renderer.update_context(self.render_context.clone());
         }
 
         // Update preview renderers if in preview mode
         if self.preview_mode {
+            let preview_context = self.preview_render_context();
             if let Some(renderer) = &mut self.preview_renderer {
-                renderer.update_context(self.render_context.clone());
+                renderer.update_context(preview_context.clone());
             }
 
             if let Some(renderer) = &mut self.preview_border_renderer {
-                renderer.update_context(self.render_context.clone());
+                renderer.update_context(preview_context);
             }
         }
     }
 
+    // Content shown by the active preview session
+    pub fn preview_content(&self) -> Option<&PlayListItem> {
+        self.preview_content.as_ref().filter(|_| self.preview_mode)
+    }
+
     // Private helper method to handle common preview content update logic
     fn update_preview_renderers(&mut self, content: &PlayListItem) {
         // Determine if the content type changed between the previous and new content
@@ -382,22 +1408,30 @@ impl DisplayManager {
         // Otherwise, update the existing renderer in place to preserve animation state where possible
         match (&mut self.preview_renderer, content_type_changed) {
             (Some(renderer), false) => {
-                renderer.update_content(content);
+                renderer.update_content(&palettes::resolve(content));
             }
             _ => {
-                // Create new renderer if none exists or if the type changed
-                self.preview_renderer = Some(create_renderer(content, self.render_context.clone()));
+                // Create new renderer if none exists or if the type changed, with a fresh link
+                // so the border does not keep following the previous content
+                self.preview_border_link = BorderLink::default();
+                let preview_context = self.preview_render_context();
+                self.preview_renderer = Some(create_renderer(content, preview_context.clone()));
+                if let Some(renderer) = &mut self.preview_border_renderer {
+                    renderer.update_context(preview_context);
+                }
             }
         };
 
         // Update border renderer or create new one if needed
         if content.border_effect.is_some() {
             if let Some(renderer) = &mut self.preview_border_renderer {
-                renderer.update_content(content);
+                renderer.update_content(&palettes::resolve(content));
             } else {
                 // Create new border renderer if none exists
-                self.preview_border_renderer =
-                    Some(create_border_renderer(content, self.render_context.clone()));
+                self.preview_border_renderer = Some(create_border_renderer(
+                    content,
+                    self.preview_render_context(),
+                ));
             }
         } else {
             // Remove border renderer if no longer needed
@@ -420,6 +1454,7 @@ impl DisplayManager {
         if !already_in_preview {
             // First-time preview mode setup
             info!("Entering preview mode with session_id: {}", session_id);
+            self.preview_connections = Arc::default();
         }
 
         // Use the common helper method
@@ -438,6 +1473,21 @@ impl DisplayManager {
 
     // Update renderer state
     pub fn update_renderer(&mut self, dt: f32) {
+        // Drop a flash or notification once it has finished
+        let overlay_done = match &mut self.overlay {
+            Some(Overlay::Flash { started, .. }) => {
+                started.elapsed() >= FLASH_PHASE * (2 * FLASH_BLINKS)
+            }
+            Some(Overlay::Notify(renderer)) => {
+                renderer.update(dt);
+                renderer.is_complete()
+            }
+            None => false,
+        };
+        if overlay_done {
+            self.overlay = None;
+        }
+
         // Update renderers with the elapsed time
         if let Some(renderer) = &mut self.active_renderer {
             renderer.update(dt);
@@ -463,6 +1513,12 @@ impl DisplayManager {
     // Check if preview mode has timed out from inactivity
     pub fn check_preview_timeout(&mut self, timeout_seconds: u64) -> Option<String> {
         if self.preview_mode {
+            // A connected event stream counts as a ping, the timeout starts once it closes
+            if self.preview_connections.load(Ordering::SeqCst) > 0 {
+                self.last_preview_ping = Instant::now();
+                return None;
+            }
+
             let elapsed = self.last_preview_ping.elapsed().as_secs();
             if elapsed > timeout_seconds {
                 info!(
@@ -483,6 +1539,15 @@ impl DisplayManager {
         self.preview_mode
     }
 
+    // Keep the preview session alive while the returned connection is held
+    pub fn connect_preview(&self, session_id: &str) -> Option<PreviewConnection> {
+        if !self.is_preview_session_owner(session_id) {
+            return None;
+        }
+        self.preview_connections.fetch_add(1, Ordering::SeqCst);
+        Some(PreviewConnection(self.preview_connections.clone()))
+    }
+
     // Update the ping time and return whether the operation was successful
     pub fn update_preview_ping(&mut self) -> bool {
         if self.preview_mode {
@@ -511,6 +1576,232 @@ impl DisplayManager {
         self.setup_active_renderer();
     }
 
+    pub fn idle_settings(&self) -> &IdleSettings {
+        &self.idle_settings
+    }
+
+    // Apply new idle settings, restarting the idle timeout if the welcome message is showing
+    pub fn set_idle_settings(&mut self, settings: IdleSettings) {
+        self.idle_settings = settings;
+        if self.idle_state != IdleState::Playing {
+            self.idle_state = IdleState::Welcome {
+                since: Instant::now(),
+            };
+            if !self.update_idle_state() {
+                self.setup_active_renderer();
+            }
+        }
+    }
+
+    pub fn welcome_settings(&self) -> &WelcomeSettings {
+        &self.welcome_settings
+    }
+
+    // Apply a new welcome message look, shown right away if the welcome message is on screen
+    pub fn set_welcome_settings(&mut self, settings: WelcomeSettings) {
+        self.welcome_item = welcome_item(&settings);
+        self.welcome_settings = settings;
+        let showing = match self.idle_state {
+            IdleState::Playing => false,
+            IdleState::Welcome { .. } => true,
+            IdleState::Idle => self.idle_settings.mode == IdleMode::Welcome,
+        };
+        if showing {
+            self.setup_active_renderer();
+        }
+    }
+
+    // Show the idle screen instead of the playlist while the operating hours say closed
+    pub fn set_closed(&mut self, closed: bool) {
+        if self.closed != closed {
+            info!(
+                "Operating hours: {}",
+                if closed {
+                    "closed, showing the idle screen"
+                } else {
+                    "showing the playlist again"
+                }
+            );
+        }
+        self.closed = closed;
+    }
+
+    // Whether the idle behavior (rather than playlist content or the welcome message) is showing
+    pub fn is_idle(&self) -> bool {
+        self.idle_state == IdleState::Idle
+    }
+
+    // Advance the idle state machine, called once per frame by the update loop.
+    // Returns true when the state changed and the renderers were rebuilt.
+    pub fn update_idle_state(&mut self) -> bool {
+        let has_content = self.rotation_len() > 0 && !self.nothing_playable && !self.closed;
+        let mut next = match self.idle_state {
+            _ if has_content => IdleState::Playing,
+            // Closing hours go to the idle behavior without the welcome message first
+            _ if self.closed => IdleState::Idle,
+            IdleState::Playing => IdleState::Welcome {
+                since: Instant::now(),
+            },
+            state => state,
+        };
+
+        if let IdleState::Welcome { since } = next {
+            let timeout = Duration::from_secs(self.idle_settings.after_minutes as u64 * 60);
+            if self.idle_settings.mode != IdleMode::Welcome && since.elapsed() >= timeout {
+                next = IdleState::Idle;
+            }
+        }
+
+        if next == self.idle_state {
+            return false;
+        }
+
+        match next {
+            IdleState::Playing => info!("Playlist has content, leaving idle screen"),
+            IdleState::Welcome { .. } => {
+                info!("Playlist is empty or expired, showing welcome message")
+            }
+            IdleState::Idle => info!("Display idle, switching to {:?}", self.idle_settings.mode),
+        }
+        self.idle_state = next;
+        self.last_transition = Instant::now();
+        self.current_repeat = 0;
+
+        // Resume with an item whose conditions hold, the one playing before may not qualify
+        if next == IdleState::Playing
+            && !self
+                .active_item()
+                .is_some_and(|item| item.is_playable() && item.conditions_met())
+        {
+            self.advance_playlist();
+            return true;
+        }
+        self.setup_active_renderer();
+        true
+    }
+
+    // Content shown while the playlist is empty, None leaves the panel blank
+    fn idle_item(&self) -> Option<PlayListItem> {
+        if self.idle_state != IdleState::Idle {
+            return Some(self.welcome_item.clone());
+        }
+
+        let (content_type, data) = match self.idle_settings.mode {
+            IdleMode::Welcome => return Some(self.welcome_item.clone()),
+            IdleMode::Blank => return None,
+            IdleMode::Clock => (
+                ContentType::Clock,
+                ContentDetails::Clock(ClockContent {
+                    format: ClockFormat::default(),
+                    show_seconds: false,
+                    color: [255, 255, 255],
+                    transition: ClockTransition::None,
+                    transition_ms: 400,
+                    show_date: false,
+                }),
+            ),
+            IdleMode::Animation => (
+                ContentType::Animation,
+                ContentDetails::Animation(self.idle_settings.animation.clone()?),
+            ),
+        };
+
+        Some(PlayListItem {
+            id: "idle".to_string(),
+            duration: None,
+            repeat_count: Some(0),
+            border_effect: None,
+            border_reactive: false,
+            outro_ms: None,
+            content: ContentData { content_type, data },
+            expires_at: None,
+            interstitial: None,
+            tags: Vec::new(),
+            enabled: true,
+            conditions: Vec::new(),
+            created_for: None,
+            created_at: None,
+            updated_at: None,
+            author: None,
+        })
+    }
+
+    // Render context for the active renderer, the idle clock stays below the user brightness
+    fn active_render_context(&self) -> RenderContext {
+        if self.idle_state == IdleState::Idle && self.idle_settings.mode == IdleMode::Clock {
+            let brightness = self
+                .render_context
+                .brightness
+                .min(self.idle_settings.clock_brightness);
+            return RenderContext::new(self.display_width, self.display_height, brightness)
+                .with_seed(self.config.render_seed)
+                .with_border_link(self.border_link.clone());
+        }
+        self.render_context
+            .clone()
+            .with_border_link(self.border_link.clone())
+    }
+
+    // Render context for the preview renderers, linked to each other but not to the active ones
+    // Render context for the active item's border, with the item's timing for Progress borders
+    fn border_render_context(&self) -> RenderContext {
+        let timing = self.active_item().map(|item| ItemTiming {
+            started: self.last_transition,
+            duration: item.duration.map(Duration::from_secs),
+        });
+        self.render_context
+            .clone()
+            .with_border_link(self.border_link.clone())
+            .with_item_timing(timing)
+    }
+
+    fn preview_render_context(&self) -> RenderContext {
+        self.render_context
+            .clone()
+            .with_border_link(self.preview_border_link.clone())
+    }
+
+    // Pass edited content of the active item to its renderer without restarting it
+    pub fn refresh_active_content(&mut self) {
+        let Some(current) = self.active_item().cloned() else {
+            return;
+        };
+        if let Some(renderer) = &mut self.active_renderer {
+            renderer.update_content(&palettes::resolve(&current));
+        }
+        // An edited duration moves the Progress border along
+        let border_context = self.border_render_context();
+        if let Some(renderer) = &mut self.border_renderer {
+            renderer.update_context(border_context);
+        }
+    }
+
+    // Restyle the active and previewed item when a palette they name has changed
+    pub fn refresh_palette(&mut self, name: &str) {
+        let items = [self.active_item(), self.preview_content()];
+        let [active, preview] = items.map(|item| {
+            item.filter(|item| palettes::names_used_by(item).contains(&name))
+                .map(palettes::resolve)
+        });
+
+        if let Some(item) = active {
+            if let Some(renderer) = &mut self.active_renderer {
+                renderer.update_content(&item);
+            }
+            if let Some(renderer) = &mut self.border_renderer {
+                renderer.update_content(&item);
+            }
+        }
+        if let Some(item) = preview {
+            if let Some(renderer) = &mut self.preview_renderer {
+                renderer.update_content(&item);
+            }
+            if let Some(renderer) = &mut self.preview_border_renderer {
+                renderer.update_content(&item);
+            }
+        }
+    }
+
     // Add a method to check if a session owns the preview
     pub fn is_preview_session_owner(&self, session_id: &str) -> bool {
         if !self.preview_mode {
@@ -537,6 +1828,66 @@ impl DisplayManager {
     }
 }
 
+// Open event stream of a preview session, see DisplayManager::connect_preview
+pub struct PreviewConnection(Arc<AtomicUsize>);
+
+impl Drop for PreviewConnection {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// Welcome message shown while the playlist is empty
+fn welcome_item(settings: &WelcomeSettings) -> PlayListItem {
+    // Same id for every look, so clients see one welcome item
+    static WELCOME_ID: Lazy<String> = Lazy::new(|| Uuid::new_v4().to_string());
+    // Get the local IP for a more helpful message
+    static WELCOME_URL: Lazy<String> = Lazy::new(|| {
+        let ip = get_local_ip().unwrap_or_else(|| "localhost".to_string());
+        format!("http://{}:3000", ip)
+    });
+
+    // The built-in message is in the configured locale
+    let text = match &settings.text {
+        Some(text) => text.replace("{url}", &WELCOME_URL),
+        None => locale::strings().welcome_message(&WELCOME_URL),
+    };
+
+    PlayListItem {
+        id: WELCOME_ID.clone(),
+        duration: None,
+        repeat_count: Some(0), // Infinite repeat with Some(0)
+        border_effect: settings.border_effect.clone(),
+        border_reactive: false,
+        outro_ms: None,
+        content: ContentData {
+            content_type: ContentType::Text,
+            data: ContentDetails::Text(TextContent {
+                text,
+                scroll: true,
+                color: settings.color,
+                speed: settings.speed,
+                text_segments: None,
+                pixels_per_second: None,
+                read_time_ms: None,
+                scroll_ease_ms: None,
+                antialias: false,
+                paging: None,
+                overflow: TextOverflow::default(),
+            }),
+        },
+        expires_at: None,
+        interstitial: None,
+        tags: Vec::new(),
+        enabled: true,
+        conditions: Vec::new(),
+        created_for: None,
+        created_at: None,
+        updated_at: None,
+        author: None,
+    }
+}
+
 // Add this helper function to get the local IP address
 fn get_local_ip() -> Option<String> {
     use std::net::UdpSocket;
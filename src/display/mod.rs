@@ -1,5 +1,21 @@
+pub mod ambient;
+pub mod audio;
+pub mod bench;
+pub mod brightness_mask;
+pub mod burn_in;
+pub mod buttons;
+pub mod color_order;
+pub mod dead_pixels;
+pub mod demo;
 pub mod driver;
+#[cfg(feature = "test-util")]
+pub mod golden;
 pub mod graphics;
 pub mod manager;
+pub mod power_guard;
+pub mod recorder;
 pub mod renderer;
+pub mod suspend;
+pub mod sync;
+pub mod thermal;
 pub mod update_loop;
@@ -0,0 +1,104 @@
+//! Power guard: estimates the current a frame draws from the sum of its pixel values and dims
+//! frames that would draw more than the power supply is rated for
+
+use crate::display::driver::LedCanvas;
+use crate::display::graphics::frame_buffer::FrameBuffer;
+use log::warn;
+use std::time::{Duration, Instant};
+
+// Throttling that starts again within this time after the last warning is not reported again
+const WARNING_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Throttling reported to API clients when it starts
+#[derive(Debug, Clone)]
+pub struct PowerThrottle {
+    // Current the unthrottled frame would have drawn
+    pub estimated_amps: f32,
+    pub limit_amps: f32,
+    // Share of the frame's brightness that was kept, 0-100
+    pub percent: u8,
+}
+
+#[derive(Debug)]
+pub struct PowerGuard {
+    limit_amps: f32,
+    // Amps drawn per unit of channel value, including the hardware brightness
+    amps_per_unit: f32,
+    throttling: bool,
+    last_warning: Option<Instant>,
+    pending: Option<PowerThrottle>,
+}
+
+impl PowerGuard {
+    /// `ma_per_channel` is the current of one LED channel at value 255 and full hardware
+    /// brightness, `hardware_brightness` the brightness the driver runs the panel at (0-100)
+    pub fn new(limit_amps: f32, ma_per_channel: f32, hardware_brightness: u8) -> Self {
+        Self {
+            limit_amps,
+            amps_per_unit: ma_per_channel / 1000.0 / 255.0 * hardware_brightness as f32 / 100.0,
+            throttling: false,
+            last_warning: None,
+            pending: None,
+        }
+    }
+
+    /// Estimated current drawn by showing `frame`, in amps
+    pub fn estimate(&self, frame: &FrameBuffer) -> f32 {
+        let sum: u64 = frame
+            .pixels()
+            .iter()
+            .map(|[r, g, b]| *r as u64 + *g as u64 + *b as u64)
+            .sum();
+        sum as f32 * self.amps_per_unit
+    }
+
+    /// Copy `frame` onto `canvas`, scaled down when it would exceed the current limit
+    pub fn draw(&mut self, frame: &FrameBuffer, canvas: &mut Box<dyn LedCanvas>) {
+        let estimated_amps = self.estimate(frame);
+        let scale = if estimated_amps > self.limit_amps {
+            self.limit_amps / estimated_amps
+        } else {
+            1.0
+        };
+        self.update_state(estimated_amps, scale);
+
+        let (width, height) = frame.size();
+        for y in 0..height {
+            for x in 0..width {
+                let color = frame.pixel(x, y).unwrap_or([0, 0, 0]);
+                if color != [0, 0, 0] {
+                    let [r, g, b] = color.map(|c| (c as f32 * scale) as u8);
+                    canvas.set_pixel(x as usize, y as usize, r, g, b);
+                }
+            }
+        }
+    }
+
+    /// Throttling that started since the last call, at most once per WARNING_INTERVAL
+    pub fn take_warning(&mut self) -> Option<PowerThrottle> {
+        self.pending.take()
+    }
+
+    fn update_state(&mut self, estimated_amps: f32, scale: f32) {
+        let throttling = scale < 1.0;
+        if throttling && !self.throttling {
+            let recently_warned = self
+                .last_warning
+                .is_some_and(|warned| warned.elapsed() < WARNING_INTERVAL);
+            if !recently_warned {
+                let throttle = PowerThrottle {
+                    estimated_amps,
+                    limit_amps: self.limit_amps,
+                    percent: (scale * 100.0) as u8,
+                };
+                warn!(
+                    "Frame would draw an estimated {:.2} A, above the {:.2} A limit; dimming to {}%",
+                    throttle.estimated_amps, throttle.limit_amps, throttle.percent
+                );
+                self.last_warning = Some(Instant::now());
+                self.pending = Some(throttle);
+            }
+        }
+        self.throttling = throttling;
+    }
+}
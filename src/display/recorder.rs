@@ -0,0 +1,133 @@
+//! Recorder: captures the frames sent to the panel for a few seconds and encodes them as an
+//! animated GIF, to share a configuration or look into animation issues remotely
+
+use crate::display::graphics::frame_buffer::FrameBuffer;
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, Rgba, RgbaImage};
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+
+// Frames are captured at most this often, GIF delays are counted in 10 ms steps
+const CAPTURE_INTERVAL: Duration = Duration::from_millis(50);
+
+// Palette quantization speed of the GIF encoder, 1 (best) to 30 (fastest)
+const GIF_SPEED: i32 = 10;
+
+/// A captured frame and how long it stayed on the panel
+#[derive(Debug)]
+pub struct RecordedFrame {
+    pub pixels: Vec<[u8; 3]>,
+    pub delay: Duration,
+}
+
+/// Frames captured by a finished recording
+#[derive(Debug)]
+pub struct Recording {
+    pub width: i32,
+    pub height: i32,
+    pub frames: Vec<RecordedFrame>,
+}
+
+#[derive(Debug)]
+pub struct Recorder {
+    started: Instant,
+    duration: Duration,
+    width: i32,
+    height: i32,
+    // Captured frames with the time they were captured, unchanged frames are skipped
+    frames: Vec<(Instant, Vec<[u8; 3]>)>,
+    last_capture: Option<Instant>,
+    done: oneshot::Sender<Recording>,
+}
+
+impl Recorder {
+    pub fn new(
+        duration: Duration,
+        width: i32,
+        height: i32,
+    ) -> (Self, oneshot::Receiver<Recording>) {
+        let (done, receiver) = oneshot::channel();
+        let recorder = Self {
+            started: Instant::now(),
+            duration,
+            width,
+            height,
+            frames: Vec::new(),
+            last_capture: None,
+            done,
+        };
+        (recorder, receiver)
+    }
+
+    /// Capture `frame` if the capture interval has passed and it differs from the last one
+    pub fn capture(&mut self, frame: &FrameBuffer) {
+        let now = Instant::now();
+        if self
+            .last_capture
+            .is_some_and(|last| now.duration_since(last) < CAPTURE_INTERVAL)
+        {
+            return;
+        }
+        self.last_capture = Some(now);
+
+        if self
+            .frames
+            .last()
+            .is_some_and(|(_, pixels)| pixels.as_slice() == frame.pixels())
+        {
+            return;
+        }
+        self.frames.push((now, frame.pixels().to_vec()));
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.started.elapsed() >= self.duration
+    }
+
+    /// Hand the captured frames to whoever started the recording
+    pub fn finish(self) {
+        let end = self.started + self.duration;
+        let mut frames = Vec::with_capacity(self.frames.len());
+        let mut captured = self.frames.into_iter().peekable();
+        while let Some((at, pixels)) = captured.next() {
+            let until = captured.peek().map_or(end, |(next, _)| *next);
+            frames.push(RecordedFrame {
+                pixels,
+                delay: until.saturating_duration_since(at).max(CAPTURE_INTERVAL),
+            });
+        }
+
+        // The receiver is gone when the request was cancelled
+        let _ = self.done.send(Recording {
+            width: self.width,
+            height: self.height,
+            frames,
+        });
+    }
+}
+
+/// Encode a recording as a looping GIF, each panel pixel drawn as a `scale` x `scale` block
+pub fn encode_gif(recording: &Recording, scale: u32) -> Result<Vec<u8>, String> {
+    let width = recording.width.max(1) as u32;
+    let height = recording.height.max(1) as u32;
+
+    let mut bytes = Vec::new();
+    {
+        let mut encoder = GifEncoder::new_with_speed(&mut bytes, GIF_SPEED);
+        encoder
+            .set_repeat(Repeat::Infinite)
+            .map_err(|e| e.to_string())?;
+        for recorded in &recording.frames {
+            let image = RgbaImage::from_fn(width * scale, height * scale, |x, y| {
+                let index = ((y / scale) * width + x / scale) as usize;
+                let [r, g, b] = recorded.pixels.get(index).copied().unwrap_or([0, 0, 0]);
+                Rgba([r, g, b, 255])
+            });
+            let delay = Delay::from_saturating_duration(recorded.delay);
+            encoder
+                .encode_frame(Frame::from_parts(image, 0, 0, delay))
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(bytes)
+}
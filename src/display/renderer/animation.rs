@@ -1,6 +1,7 @@
+use crate::display::audio;
 use crate::display::driver::LedCanvas;
-use crate::display::renderer::{RenderContext, Renderer};
-use crate::models::animation::AnimationContent;
+use crate::display::renderer::{EffectRng, RenderContext, Renderer};
+use crate::models::animation::{AnimationContent, MeteorDirection, WeatherKind};
 use crate::models::content::ContentDetails;
 use crate::models::playlist::PlayListItem;
 use std::f32::consts::TAU;
@@ -9,9 +10,63 @@ use std::time::Instant;
 pub struct AnimationRenderer {
     content: AnimationContent,
     ctx: RenderContext,
+    // Brightness of the context before reactive animations scale it with the sound level
+    base_brightness: u8,
     elapsed: f32,
     duration: Option<u64>,
     start_time: Instant,
+    // Randomness for sparkle, strobe and mosaic effects, seeded from the context
+    rng: EffectRng,
+    // Meteor preset: glow left behind on every pixel, fading a little each frame
+    trail: Vec<[f32; 3]>,
+    meteors: Vec<Meteor>,
+    meteors_spawned: u32,
+    next_meteor_in: f32,
+    // Fireworks preset: fixed pool of rockets and sparks reused instead of allocated per frame
+    particles: Vec<Particle>,
+    // Rockets that reached their apex this frame, kept to reuse its capacity
+    bursts: Vec<Particle>,
+    rockets_launched: u32,
+    next_rocket_in: f32,
+}
+
+// Meteor in flight, positions in pixels and velocities in pixels per second
+struct Meteor {
+    x: f32,
+    y: f32,
+    dx: f32,
+    dy: f32,
+    color: [u8; 3],
+}
+
+// Side of the blocks the weather presets divide the panel into, each block holds at most one
+// raindrop or snowflake at a time so precipitation spreads evenly
+const WEATHER_BLOCK: usize = 8;
+
+// Colors of the weather presets when the item gives none
+const RAIN_COLOR: [u8; 3] = [70, 130, 255];
+const SNOW_COLOR: [u8; 3] = [225, 235, 255];
+const SUN_COLOR: [u8; 3] = [255, 200, 40];
+const SUN_RAY_COLOR: [u8; 3] = [255, 130, 0];
+
+// Most rockets and sparks alive at once, later sparks are dropped while the pool is full
+const MAX_PARTICLES: usize = 512;
+
+// Rocket rising to its burst or spark falling from it, a free pool slot when not alive
+#[derive(Clone, Copy, Default)]
+struct Particle {
+    alive: bool,
+    rocket: bool,
+    x: f32,
+    y: f32,
+    dx: f32,
+    dy: f32,
+    age: f32,
+    // Seconds a spark glows, or until a rocket bursts
+    life: f32,
+    color: [u8; 3],
+    // Launch number of the rocket, seeds the sparks of its burst
+    launch: u32,
 }
 
 impl Renderer for AnimationRenderer {
@@ -24,26 +79,74 @@ impl Renderer for AnimationRenderer {
 
         Self {
             content: animation_content,
+            rng: EffectRng::new(ctx.seed),
+            base_brightness: ctx.brightness,
             ctx,
             elapsed: 0.0,
             duration: content.duration,
             start_time: Instant::now(),
+            trail: Vec::new(),
+            meteors: Vec::new(),
+            meteors_spawned: 0,
+            next_meteor_in: 0.0,
+            particles: Vec::new(),
+            bursts: Vec::new(),
+            rockets_launched: 0,
+            next_rocket_in: 0.0,
         }
     }
 
     fn update(&mut self, dt: f32) {
+        // Louder sound speeds reactive animations up and brightens them, silence slows and
+        // dims them
+        let level = if self.content.reactive() {
+            audio::level()
+        } else {
+            None
+        };
+        let dt = match level {
+            Some(level) => {
+                self.ctx.brightness =
+                    (self.base_brightness as f32 * (0.3 + 0.7 * level)).round() as u8;
+                dt * (0.4 + 1.6 * level)
+            }
+            None => {
+                self.ctx.brightness = self.base_brightness;
+                dt
+            }
+        };
+
         self.elapsed += dt;
+        match self.content {
+            AnimationContent::Meteor {
+                direction,
+                density,
+                speed,
+                trail_ms,
+                ..
+            } => self.update_meteors(dt, direction, density, speed, trail_ms),
+            AnimationContent::Fireworks {
+                burst_rate,
+                spark_count,
+                gravity,
+                ..
+            } => self.update_fireworks(dt, burst_rate, spark_count, gravity),
+            _ => {}
+        }
     }
 
     fn render(&self, canvas: &mut Box<dyn LedCanvas>) {
         match &self.content {
-            AnimationContent::Pulse { colors, cycle_ms } => {
+            AnimationContent::Pulse {
+                colors, cycle_ms, ..
+            } => {
                 self.render_pulse(canvas, colors, *cycle_ms as f32 / 1000.0);
             }
             AnimationContent::PaletteWave {
                 colors,
                 cycle_ms,
                 wave_count,
+                ..
             } => {
                 self.render_palette_wave(canvas, colors, *cycle_ms as f32 / 1000.0, *wave_count);
             }
@@ -51,12 +154,14 @@ impl Renderer for AnimationRenderer {
                 colors,
                 cycle_ms,
                 phase_offset,
+                ..
             } => {
                 self.render_dual_pulse(canvas, colors, *cycle_ms as f32 / 1000.0, *phase_offset);
             }
             AnimationContent::ColorFade {
                 colors,
                 drift_speed,
+                ..
             } => {
                 self.render_color_fade(canvas, colors, *drift_speed);
             }
@@ -66,6 +171,7 @@ impl Renderer for AnimationRenderer {
                 fade_ms,
                 randomize,
                 randomization_factor,
+                ..
             } => {
                 self.render_strobe(
                     canvas,
@@ -80,6 +186,7 @@ impl Renderer for AnimationRenderer {
                 colors,
                 density,
                 twinkle_ms,
+                ..
             } => {
                 self.render_sparkle(canvas, colors, *density, *twinkle_ms);
             }
@@ -89,6 +196,7 @@ impl Renderer for AnimationRenderer {
                 flow_speed,
                 border_size,
                 border_color,
+                ..
             } => {
                 self.render_mosaic_twinkle(
                     canvas,
@@ -103,9 +211,35 @@ impl Renderer for AnimationRenderer {
                 colors,
                 flow_speed,
                 noise_scale,
+                ..
             } => {
                 self.render_plasma(canvas, colors, *flow_speed, *noise_scale);
             }
+            AnimationContent::Meteor { .. } => {
+                self.render_meteors(canvas);
+            }
+            AnimationContent::Fireworks { .. } => {
+                self.render_fireworks(canvas);
+            }
+            AnimationContent::NoiseField {
+                colors,
+                octaves,
+                speed,
+                cycle_ms,
+                ..
+            } => {
+                self.render_noise_field(canvas, colors, *octaves, *speed, *cycle_ms);
+            }
+            AnimationContent::PixelWeather {
+                colors,
+                weather,
+                intensity,
+                ..
+            } => match weather {
+                WeatherKind::Rain => self.render_precipitation(canvas, colors, false, *intensity),
+                WeatherKind::Snow => self.render_precipitation(canvas, colors, true, *intensity),
+                WeatherKind::Sun => self.render_sun(canvas, colors, *intensity),
+            },
         }
     }
 
@@ -119,9 +253,25 @@ impl Renderer for AnimationRenderer {
     fn reset(&mut self) {
         self.elapsed = 0.0;
         self.start_time = Instant::now();
+        self.trail.clear();
+        self.meteors.clear();
+        self.meteors_spawned = 0;
+        self.next_meteor_in = 0.0;
+        for particle in &mut self.particles {
+            particle.alive = false;
+        }
+        self.rockets_launched = 0;
+        self.next_rocket_in = 0.0;
+    }
+
+    fn progress(&self) -> Option<f32> {
+        let duration = self.duration?;
+        Some((self.start_time.elapsed().as_secs_f32() / duration.max(1) as f32).min(1.0))
     }
 
     fn update_context(&mut self, ctx: RenderContext) {
+        self.rng = EffectRng::new(ctx.seed);
+        self.base_brightness = ctx.brightness;
         self.ctx = ctx;
     }
 
@@ -285,15 +435,14 @@ impl AnimationRenderer {
 
         for y in 0..height {
             for x in 0..width {
-                let seed = Self::tile_seed(y as u32, x as u32);
-                if Self::pseudo_random_f32(seed) > active_density {
+                let seed = self.rng.key(y as u32, x as u32);
+                if EffectRng::unit(seed) > active_density {
                     continue;
                 }
 
                 let palette_index = (seed as usize) % palette_len;
-                let speed_variation =
-                    0.6 + 1.2 * Self::pseudo_random_f32(seed.wrapping_mul(31_415_927));
-                let phase_offset = Self::pseudo_random_f32(seed.wrapping_mul(97_531));
+                let speed_variation = 0.6 + 1.2 * EffectRng::unit(seed.wrapping_mul(31_415_927));
+                let phase_offset = EffectRng::unit(seed.wrapping_mul(97_531));
                 let twinkle_phase = (phase_base * speed_variation + phase_offset).fract();
                 let brightness = Self::sparkle_brightness(twinkle_phase);
 
@@ -339,13 +488,12 @@ impl AnimationRenderer {
 
         for row in 0..rows {
             for col in 0..cols {
-                let seed = Self::tile_seed(row as u32, col as u32);
+                let seed = self.rng.key(row as u32, col as u32);
                 let color_idx = (seed as usize) % colors.len();
                 let base_color = colors[color_idx];
 
-                let speed_variation =
-                    0.6 + 0.6 * Self::pseudo_random_f32(seed.wrapping_mul(31_415_927));
-                let phase_offset = Self::pseudo_random_f32(seed.wrapping_mul(97_531));
+                let speed_variation = 0.6 + 0.6 * EffectRng::unit(seed.wrapping_mul(31_415_927));
+                let phase_offset = EffectRng::unit(seed.wrapping_mul(97_531));
                 let phase = (self.elapsed * flow_speed * speed_variation + phase_offset).fract();
                 let shimmer = 0.65 + 0.35 * (TAU * phase).sin();
 
@@ -467,16 +615,505 @@ impl AnimationRenderer {
         }
     }
 
-    fn pseudo_random_f32(seed: u32) -> f32 {
-        let mut x = seed;
-        x ^= x << 13;
-        x ^= x >> 17;
-        x ^= x << 5;
-        (x as f32 / u32::MAX as f32).fract()
+    fn update_meteors(
+        &mut self,
+        dt: f32,
+        direction: MeteorDirection,
+        density: f32,
+        speed: f32,
+        trail_ms: u32,
+    ) {
+        let width = self.width();
+        let height = self.height();
+        if width == 0 || height == 0 || trail_ms == 0 || !speed.is_finite() || speed <= 0.0 {
+            return;
+        }
+        if self.trail.len() != width * height {
+            self.trail = vec![[0.0; 3]; width * height];
+            self.meteors.clear();
+        }
+
+        // Trails fade to 1/255 of their brightness, i.e. off, over trail_ms
+        let fade = (1.0f32 / 255.0).powf(dt / (trail_ms as f32 / 1000.0));
+        for pixel in &mut self.trail {
+            for channel in pixel.iter_mut() {
+                *channel *= fade;
+            }
+        }
+
+        // Paint every pixel a meteor crossed, so fast meteors leave unbroken trails
+        for meteor in &mut self.meteors {
+            let (from_x, from_y) = (meteor.x, meteor.y);
+            meteor.x += meteor.dx * dt;
+            meteor.y += meteor.dy * dt;
+            let distance = (meteor.x - from_x).abs().max((meteor.y - from_y).abs());
+            let steps = distance.ceil().max(1.0) as usize;
+            for step in 1..=steps {
+                let t = step as f32 / steps as f32;
+                let x = (from_x + (meteor.x - from_x) * t).round();
+                let y = (from_y + (meteor.y - from_y) * t).round();
+                if x < 0.0 || y < 0.0 || x >= width as f32 || y >= height as f32 {
+                    continue;
+                }
+                let pixel = &mut self.trail[y as usize * width + x as usize];
+                for (channel, value) in pixel.iter_mut().zip(meteor.color) {
+                    *channel = channel.max(value as f32);
+                }
+            }
+        }
+        self.meteors.retain(|meteor| {
+            meteor.x >= -2.0
+                && meteor.y >= -2.0
+                && meteor.x <= width as f32 + 1.0
+                && meteor.y <= height as f32 + 1.0
+        });
+
+        // Density is the share of lanes across the direction of travel with a meteor in flight
+        let lanes = match direction {
+            MeteorDirection::Left | MeteorDirection::Right => height,
+            MeteorDirection::Up | MeteorDirection::Down => width,
+            _ => (width + height) / 2,
+        };
+        let target = ((lanes as f32 * density).round() as usize).max(1);
+        self.next_meteor_in -= dt;
+        let colors = self.content.palette();
+        if self.meteors.len() >= target || self.next_meteor_in > 0.0 || colors.is_empty() {
+            return;
+        }
+
+        let key = |salt: u32| EffectRng::unit(self.rng.key(self.meteors_spawned, salt));
+        let heading = direction.resolve(key(1));
+        let (step_x, step_y) = heading.step();
+        let meteor_speed = speed * (0.7 + 0.6 * key(2)) / step_x.hypot(step_y);
+
+        // Enter just outside the edge the meteor travels away from
+        let entry_x = if step_x > 0.0 { -1.0 } else { width as f32 };
+        let entry_y = if step_y > 0.0 { -1.0 } else { height as f32 };
+        let (x, y) = if step_y == 0.0 {
+            (entry_x, key(3) * height as f32)
+        } else if step_x == 0.0 {
+            (key(3) * width as f32, entry_y)
+        } else {
+            let along = key(3) * (width + height) as f32;
+            if along < width as f32 {
+                (along, entry_y)
+            } else {
+                (entry_x, along - width as f32)
+            }
+        };
+
+        let color = colors[self.rng.key(self.meteors_spawned, 4) as usize % colors.len()];
+        self.meteors.push(Meteor {
+            x,
+            y,
+            dx: step_x * meteor_speed,
+            dy: step_y * meteor_speed,
+            color,
+        });
+
+        // Stagger launches over the time a meteor takes to cross the panel
+        let crossing_s = width.max(height) as f32 / speed;
+        self.next_meteor_in = crossing_s / target as f32 * (0.5 + key(5));
+        self.meteors_spawned = self.meteors_spawned.wrapping_add(1);
+    }
+
+    fn render_meteors(&self, canvas: &mut Box<dyn LedCanvas>) {
+        let width = self.width();
+        let height = self.height();
+        if self.trail.len() != width * height {
+            canvas.fill(0, 0, 0);
+            return;
+        }
+
+        for y in 0..height {
+            for x in 0..width {
+                let [r, g, b] = self.trail[y * width + x];
+                let color = [r as u8, g as u8, b as u8];
+                let [r, g, b] = self.ctx.apply_brightness(color);
+                canvas.set_pixel(x, y, r, g, b);
+            }
+        }
+
+        // Heads burn hotter than their trails, most of the way to white
+        for meteor in &self.meteors {
+            let (x, y) = (meteor.x.round(), meteor.y.round());
+            if x < 0.0 || y < 0.0 || x >= width as f32 || y >= height as f32 {
+                continue;
+            }
+            let head = meteor.color.map(|channel| Self::lerp(channel, 255, 0.6));
+            let [r, g, b] = self.ctx.apply_brightness(head);
+            canvas.set_pixel(x as usize, y as usize, r, g, b);
+        }
+    }
+
+    fn update_fireworks(&mut self, dt: f32, burst_rate: f32, spark_count: u8, gravity: f32) {
+        let width = self.width() as f32;
+        let height = self.height() as f32;
+        if width < 1.0
+            || height < 1.0
+            || !burst_rate.is_finite()
+            || burst_rate <= 0.0
+            || !gravity.is_finite()
+            || gravity <= 0.0
+        {
+            return;
+        }
+        if self.particles.is_empty() {
+            self.particles = vec![Particle::default(); MAX_PARTICLES];
+        }
+
+        // Rockets fly freely, sparks also lose speed to drag so bursts hang in the air
+        let drag = (1.0 - 1.8 * dt).max(0.0);
+        self.bursts.clear();
+        for particle in self.particles.iter_mut().filter(|particle| particle.alive) {
+            particle.age += dt;
+            if particle.rocket && particle.age >= particle.life {
+                particle.alive = false;
+                self.bursts.push(*particle);
+                continue;
+            }
+            if !particle.rocket {
+                particle.dx *= drag;
+                particle.dy *= drag;
+            }
+            particle.dy += gravity * dt;
+            particle.x += particle.dx * dt;
+            particle.y += particle.dy * dt;
+            if particle.age >= particle.life || particle.y >= height + 1.0 {
+                particle.alive = false;
+            }
+        }
+
+        // Sparks fly out evenly around the burst, with some jitter in angle, speed and life
+        let burst_speed = height.max(width * 0.5) * 0.8;
+        for burst in 0..self.bursts.len() {
+            let rocket = self.bursts[burst];
+            for spark in 0..spark_count as u32 {
+                let key = |salt: u32| {
+                    EffectRng::unit(self.rng.key(rocket.launch, spark.wrapping_mul(8) + salt))
+                };
+                let angle = TAU * (spark as f32 + key(1) * 0.6) / spark_count as f32;
+                let speed = burst_speed * (0.55 + 0.45 * key(2));
+                let life = 0.8 + 0.7 * key(3);
+                let Some(slot) = self.particles.iter_mut().find(|particle| !particle.alive) else {
+                    break;
+                };
+                *slot = Particle {
+                    alive: true,
+                    rocket: false,
+                    dx: angle.cos() * speed,
+                    dy: angle.sin() * speed,
+                    age: 0.0,
+                    life,
+                    ..rocket
+                };
+            }
+        }
+
+        self.next_rocket_in -= dt;
+        let colors = self.content.palette();
+        if self.next_rocket_in > 0.0 || colors.is_empty() {
+            return;
+        }
+        let launch = self.rockets_launched;
+        let key = |salt: u32| EffectRng::unit(self.rng.key(launch, 1_000 + salt));
+
+        // Launch from the bottom edge towards a burst in the upper half of the panel
+        let x = width * (0.15 + 0.7 * key(1));
+        let apex = height * (0.15 + 0.3 * key(2));
+        let rise = height - apex;
+        let speed = (2.0 * gravity * rise).sqrt();
+        let color = colors[self.rng.key(launch, 1_003) as usize % colors.len()];
+        let drift = width * 0.1 * (key(4) - 0.5);
+        let next_rocket_in = (0.5 + key(5)) / burst_rate;
+        if let Some(slot) = self.particles.iter_mut().find(|particle| !particle.alive) {
+            *slot = Particle {
+                alive: true,
+                rocket: true,
+                x,
+                y: height,
+                dx: drift,
+                dy: -speed,
+                age: 0.0,
+                life: speed / gravity,
+                color,
+                launch,
+            };
+        }
+        self.next_rocket_in = next_rocket_in;
+        self.rockets_launched = launch.wrapping_add(1);
+    }
+
+    fn render_fireworks(&self, canvas: &mut Box<dyn LedCanvas>) {
+        canvas.fill(0, 0, 0);
+        let width = self.width() as f32;
+        let height = self.height() as f32;
+
+        for particle in self.particles.iter().filter(|particle| particle.alive) {
+            let (x, y) = (particle.x.round(), particle.y.round());
+            if x < 0.0 || y < 0.0 || x >= width || y >= height {
+                continue;
+            }
+            let color = if particle.rocket {
+                // Rockets climb as a warm spark, tinted towards their burst color
+                let warm = [255, 190, 120];
+                [
+                    Self::lerp(warm[0], particle.color[0], 0.3),
+                    Self::lerp(warm[1], particle.color[1], 0.3),
+                    Self::lerp(warm[2], particle.color[2], 0.3),
+                ]
+            } else {
+                // Sparks start white hot, then fade out in their color
+                let t = (particle.age / particle.life).clamp(0.0, 1.0);
+                let hot = particle
+                    .color
+                    .map(|channel| Self::lerp(channel, 255, (1.0 - t * 6.0).max(0.0)));
+                Self::scale_color(hot, (1.0 - t).powf(1.5))
+            };
+            let [r, g, b] = self.ctx.apply_brightness(color);
+            canvas.set_pixel(x as usize, y as usize, r, g, b);
+        }
+    }
+
+    fn render_noise_field(
+        &self,
+        canvas: &mut Box<dyn LedCanvas>,
+        colors: &[[u8; 3]],
+        octaves: u8,
+        speed: f32,
+        cycle_ms: u32,
+    ) {
+        if colors.is_empty() || octaves == 0 || !speed.is_finite() || speed <= 0.0 {
+            return;
+        }
+
+        let width = self.width();
+        let height = self.height();
+        // Features span about half the panel height whatever its resolution
+        let cell = (height as f32 / 2.0).max(4.0);
+        let time = self.elapsed * speed;
+        let cycle = if cycle_ms == 0 {
+            0.0
+        } else {
+            self.loop_progress(cycle_ms as f32 / 1000.0)
+        };
+        let salt = self.rng.key(0, 271_828);
+
+        for y in 0..height {
+            let ny = y as f32 / cell;
+            for x in 0..width {
+                // The field drifts sideways a little while it evolves, like slow currents
+                let nx = x as f32 / cell + time * 0.25;
+
+                let mut amplitude = 1.0;
+                let mut frequency = 1.0;
+                let mut total = 0.0;
+                let mut max_amplitude = 0.0;
+                for octave in 0..octaves {
+                    total += Self::perlin_noise(
+                        nx * frequency,
+                        ny * frequency,
+                        time * frequency,
+                        salt.wrapping_add(octave as u32),
+                    ) * amplitude;
+                    max_amplitude += amplitude;
+                    amplitude *= 0.5;
+                    frequency *= 2.0;
+                }
+
+                // Perlin noise rarely comes near its bounds, stretch it to use the whole palette
+                let value = (total / max_amplitude * 1.4).clamp(-1.0, 1.0) * 0.5 + 0.5;
+                let color = self.sample_palette(colors, Self::wrap01(value + cycle));
+                let [r, g, b] = self.ctx.apply_brightness(color);
+                canvas.set_pixel(x, y, r, g, b);
+            }
+        }
+    }
+
+    fn render_precipitation(
+        &self,
+        canvas: &mut Box<dyn LedCanvas>,
+        colors: &[[u8; 3]],
+        snow: bool,
+        intensity: f32,
+    ) {
+        canvas.fill(0, 0, 0);
+        let width = self.width();
+        let height = self.height();
+        if width == 0 || height == 0 || !intensity.is_finite() {
+            return;
+        }
+
+        let cols = width.div_ceil(WEATHER_BLOCK);
+        let rows = height.div_ceil(WEATHER_BLOCK);
+        // Raindrops are short streaks falling fast, snowflakes single pixels drifting down
+        let (length, fall_speed) = if snow {
+            (1, height as f32 * 0.3)
+        } else {
+            (4, height as f32 * 1.6)
+        };
+        let span = (rows * WEATHER_BLOCK + length) as f32;
+
+        for col in 0..cols {
+            for row in 0..rows {
+                let lane = self.rng.key(col as u32, row as u32 + 7_919);
+                let speed = fall_speed * (0.8 + 0.4 * EffectRng::unit(lane));
+                let travel = self.elapsed * speed
+                    + ((row as f32 + EffectRng::unit(lane.wrapping_mul(31)))
+                        * WEATHER_BLOCK as f32);
+
+                // Every pass through the panel rolls whether the block has a drop and where
+                let pass = (travel / span).floor() as u32;
+                let drop = self.rng.key(lane, pass);
+                if EffectRng::unit(drop) >= intensity {
+                    continue;
+                }
+                let head_y = (travel % span).floor() as i32 - 1;
+                let mut x = (col * WEATHER_BLOCK) as f32
+                    + (EffectRng::unit(drop.wrapping_mul(7)) * WEATHER_BLOCK as f32).floor();
+                if snow {
+                    let sway = EffectRng::unit(drop.wrapping_mul(13)) * TAU;
+                    x += (self.elapsed * 1.3 + sway).sin() * 1.5;
+                }
+                let x = x.round() as i32;
+
+                let base = if colors.is_empty() {
+                    if snow {
+                        SNOW_COLOR
+                    } else {
+                        RAIN_COLOR
+                    }
+                } else {
+                    colors[drop as usize % colors.len()]
+                };
+                // Nearer flakes and drops are brighter
+                let depth = 0.55 + 0.45 * EffectRng::unit(drop.wrapping_mul(17));
+
+                for segment in 0..length {
+                    let y = head_y - segment as i32;
+                    let fade = 1.0 - segment as f32 / length as f32 * 0.8;
+                    let color = Self::scale_color(base, depth * fade);
+                    self.set_weather_pixel(canvas, x, y, color);
+                }
+
+                // Large flakes in heavy snow get dim arms
+                if snow && intensity > 0.5 && depth > 0.85 {
+                    let arm = Self::scale_color(base, depth * 0.35);
+                    for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+                        self.set_weather_pixel(canvas, x + dx, head_y + dy, arm);
+                    }
+                }
+            }
+        }
+    }
+
+    fn render_sun(&self, canvas: &mut Box<dyn LedCanvas>, colors: &[[u8; 3]], intensity: f32) {
+        let width = self.width();
+        let height = self.height();
+        if width == 0 || height == 0 || !intensity.is_finite() {
+            return;
+        }
+        let core = colors.first().copied().unwrap_or(SUN_COLOR);
+        let ray = colors.get(1).copied().unwrap_or(if colors.is_empty() {
+            SUN_RAY_COLOR
+        } else {
+            core
+        });
+
+        // The sun sits in the top-left corner and grows brighter rays with the intensity
+        let size = height.min(width) as f32;
+        let center = (size * 0.4, size * 0.4);
+        let radius = (size * 0.15 * (0.8 + 0.4 * intensity)).max(2.0);
+        let pulse = 0.85 + 0.15 * (self.elapsed * 1.5).sin();
+        let ray_length = size * (0.3 + 0.6 * intensity) * pulse;
+        let rotation = self.elapsed * 0.15;
+
+        for y in 0..height {
+            for x in 0..width {
+                let dx = x as f32 - center.0;
+                let dy = y as f32 - center.1;
+                let distance = (dx * dx + dy * dy).sqrt();
+                let color = if distance <= radius {
+                    core
+                } else {
+                    let out = distance - radius;
+                    let angle = dy.atan2(dx) - rotation;
+                    let beam = (((angle * 8.0).cos() + 1.0) * 0.5).powi(6);
+                    let reach = (1.0 - out / ray_length).max(0.0);
+                    let glow = (-out / (ray_length * 0.35)).exp() * 0.3 * intensity;
+                    Self::scale_color(ray, (beam * reach).max(glow))
+                };
+                let [r, g, b] = self.ctx.apply_brightness(color);
+                canvas.set_pixel(x, y, r, g, b);
+            }
+        }
+    }
+
+    fn set_weather_pixel(&self, canvas: &mut Box<dyn LedCanvas>, x: i32, y: i32, color: [u8; 3]) {
+        if x < 0 || y < 0 || x >= self.width() as i32 || y >= self.height() as i32 {
+            return;
+        }
+        let [r, g, b] = self.ctx.apply_brightness(color);
+        canvas.set_pixel(x as usize, y as usize, r, g, b);
+    }
+
+    // Gradient noise from -1 to 1 on a 3D lattice, time being the third axis so the field
+    // evolves smoothly instead of scrolling
+    fn perlin_noise(x: f32, y: f32, z: f32, salt: u32) -> f32 {
+        let (x0, y0, z0) = (x.floor(), y.floor(), z.floor());
+        let (fx, fy, fz) = (x - x0, y - y0, z - z0);
+        let (ix, iy, iz) = (x0 as i32, y0 as i32, z0 as i32);
+        let (u, v, w) = (Self::fade(fx), Self::fade(fy), Self::fade(fz));
+
+        let corner = |dx: i32, dy: i32, dz: i32| {
+            Self::gradient_dot(
+                Self::lattice_hash(ix + dx, iy + dy, iz + dz, salt),
+                fx - dx as f32,
+                fy - dy as f32,
+                fz - dz as f32,
+            )
+        };
+
+        let x00 = Self::lerp_f32(corner(0, 0, 0), corner(1, 0, 0), u);
+        let x10 = Self::lerp_f32(corner(0, 1, 0), corner(1, 1, 0), u);
+        let x01 = Self::lerp_f32(corner(0, 0, 1), corner(1, 0, 1), u);
+        let x11 = Self::lerp_f32(corner(0, 1, 1), corner(1, 1, 1), u);
+        let y0 = Self::lerp_f32(x00, x10, v);
+        let y1 = Self::lerp_f32(x01, x11, v);
+        Self::lerp_f32(y0, y1, w)
     }
 
-    fn tile_seed(row: u32, col: u32) -> u32 {
-        row.wrapping_mul(738_560_93) ^ col.wrapping_mul(193_496_63)
+    fn fade(t: f32) -> f32 {
+        t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+    }
+
+    fn lattice_hash(x: i32, y: i32, z: i32, salt: u32) -> u32 {
+        let mut h = (x as u32).wrapping_mul(73_856_093)
+            ^ (y as u32).wrapping_mul(19_349_663)
+            ^ (z as u32).wrapping_mul(83_492_791)
+            ^ salt;
+        h ^= h >> 15;
+        h = h.wrapping_mul(0x2c1b_3c6d);
+        h ^= h >> 12;
+        h
+    }
+
+    // Dot product with one of the 12 cube edge directions of improved Perlin noise
+    fn gradient_dot(hash: u32, x: f32, y: f32, z: f32) -> f32 {
+        match hash % 12 {
+            0 => x + y,
+            1 => -x + y,
+            2 => x - y,
+            3 => -x - y,
+            4 => x + z,
+            5 => -x + z,
+            6 => x - z,
+            7 => -x - z,
+            8 => y + z,
+            9 => -y + z,
+            10 => y - z,
+            _ => -y - z,
+        }
     }
 
     fn sparkle_brightness(phase: f32) -> f32 {
@@ -501,8 +1138,8 @@ impl AnimationRenderer {
 
         while accumulated_ms <= elapsed_ms && cycle_index < max_cycles {
             // Calculate randomized duration for this cycle
-            let seed = Self::tile_seed(cycle_index as u32, 531_441);
-            let random_value = Self::pseudo_random_f32(seed);
+            let seed = self.rng.key(cycle_index as u32, 531_441);
+            let random_value = EffectRng::unit(seed);
             let multiplier = min_multiplier + (max_multiplier - min_multiplier) * random_value;
             let cycle_duration = (base_cycle_ms as f32 * multiplier).round() as u32;
             let cycle_duration = cycle_duration.max(1); // Ensure at least 1ms
@@ -520,8 +1157,8 @@ impl AnimationRenderer {
         // Fallback: if we hit the limit, use approximate calculation
         let approximate_cycle =
             (elapsed_ms as f32 / (base_cycle_ms as f32 * (1.0 + clamped * 0.5))) as usize;
-        let seed = Self::tile_seed(approximate_cycle as u32, 531_441);
-        let random_value = Self::pseudo_random_f32(seed);
+        let seed = self.rng.key(approximate_cycle as u32, 531_441);
+        let random_value = EffectRng::unit(seed);
         let multiplier = min_multiplier + (max_multiplier - min_multiplier) * random_value;
         let cycle_duration = (base_cycle_ms as f32 * multiplier).round() as u32;
         let cycle_duration = cycle_duration.max(1);
@@ -0,0 +1,187 @@
+use crate::display::driver::LedCanvas;
+use crate::display::graphics::embedded_graphics_support::EmbeddedGraphicsCanvas;
+use crate::display::renderer::{RenderContext, Renderer};
+use crate::models::barcode::BarcodeContent;
+use crate::models::content::ContentDetails;
+use crate::models::playlist::PlayListItem;
+use embedded_graphics::geometry::Point;
+use embedded_graphics::mono_font::iso_8859_1::FONT_5X8 as FONT_5X8_LATIN1;
+use embedded_graphics::mono_font::MonoTextStyle;
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::text::Text;
+use embedded_graphics::Drawable;
+use log::warn;
+use std::time::Instant;
+
+// Height of the text line under the bars, including one pixel of spacing
+const TEXT_HEIGHT: i32 = 9;
+// Bars need at least this much height left before the text line is added
+const MIN_BAR_HEIGHT: i32 = 16;
+
+/// Position and size of the code on the panel
+struct BarcodeLayout {
+    x: i32,
+    module_width: i32,
+    quiet_zone: i32,
+    bar_height: i32,
+}
+
+pub struct BarcodeRenderer {
+    content: BarcodeContent,
+    ctx: RenderContext,
+    duration: Option<u64>,
+    start_time: Instant,
+    modules: Vec<bool>,
+    layout: Option<BarcodeLayout>,
+}
+
+impl Renderer for BarcodeRenderer {
+    fn new(content: &PlayListItem, ctx: RenderContext) -> Self {
+        let barcode_content = match &content.content.data {
+            ContentDetails::Barcode(barcode) => barcode.clone(),
+            #[allow(unreachable_patterns)]
+            _ => panic!("Expected barcode content"),
+        };
+
+        let mut renderer = Self {
+            content: barcode_content,
+            ctx,
+            duration: content.duration,
+            start_time: Instant::now(),
+            modules: Vec::new(),
+            layout: None,
+        };
+        renderer.encode();
+        renderer
+    }
+
+    fn update(&mut self, _dt: f32) {
+        // Static content
+    }
+
+    fn render(&self, canvas: &mut Box<dyn LedCanvas>) {
+        let Some(layout) = &self.layout else {
+            return;
+        };
+
+        let [fr, fg, fb] = self.ctx.apply_brightness(self.content.foreground);
+        let [br, bg, bb] = self.ctx.apply_brightness(self.content.background);
+
+        let total_modules = self.modules.len() as i32 + 2 * layout.quiet_zone;
+        for module in 0..total_modules {
+            let bar = module
+                .checked_sub(layout.quiet_zone)
+                .and_then(|index| self.modules.get(index as usize))
+                .copied()
+                .unwrap_or(false);
+            let (r, g, b) = if bar { (fr, fg, fb) } else { (br, bg, bb) };
+
+            let x_start = layout.x + module * layout.module_width;
+            for x in x_start..x_start + layout.module_width {
+                for y in 0..layout.bar_height {
+                    if x >= 0 && x < self.ctx.display_width {
+                        canvas.set_pixel(x as usize, y as usize, r, g, b);
+                    }
+                }
+            }
+        }
+
+        if layout.bar_height < self.ctx.display_height {
+            self.render_text(canvas);
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        if let Some(duration) = self.duration {
+            return Instant::now().duration_since(self.start_time).as_secs() >= duration;
+        }
+        false
+    }
+
+    fn reset(&mut self) {
+        self.start_time = Instant::now();
+    }
+
+    fn update_context(&mut self, ctx: RenderContext) {
+        let resized = ctx.display_width != self.ctx.display_width
+            || ctx.display_height != self.ctx.display_height;
+        self.ctx = ctx;
+        if resized {
+            self.layout = self.compute_layout();
+        }
+    }
+
+    fn update_content(&mut self, content: &PlayListItem) {
+        if let ContentDetails::Barcode(barcode) = &content.content.data {
+            self.content = barcode.clone();
+            self.duration = content.duration;
+            self.start_time = Instant::now();
+            self.encode();
+        } else {
+            warn!("BarcodeRenderer received non-barcode content during update");
+        }
+    }
+}
+
+impl BarcodeRenderer {
+    fn encode(&mut self) {
+        self.modules = match self.content.encode() {
+            Ok(modules) => modules,
+            Err(e) => {
+                warn!("Failed to encode barcode: {}", e);
+                Vec::new()
+            }
+        };
+        self.layout = self.compute_layout();
+    }
+
+    /// Use the widest whole-pixel modules that fit, shrinking the quiet zone before giving up
+    fn compute_layout(&self) -> Option<BarcodeLayout> {
+        if self.modules.is_empty() {
+            return None;
+        }
+
+        let bar_height =
+            if self.content.show_text && self.ctx.display_height - TEXT_HEIGHT >= MIN_BAR_HEIGHT {
+                self.ctx.display_height - TEXT_HEIGHT
+            } else {
+                self.ctx.display_height
+            };
+
+        let preferred = self.content.quiet_zone.max(1) as i32;
+        for quiet_zone in (1..=preferred).rev() {
+            let span = self.modules.len() as i32 + 2 * quiet_zone;
+            let module_width = self.ctx.display_width / span;
+            if module_width >= 1 {
+                return Some(BarcodeLayout {
+                    x: (self.ctx.display_width - span * module_width) / 2,
+                    module_width,
+                    quiet_zone,
+                    bar_height,
+                });
+            }
+        }
+
+        warn!(
+            "Barcode for '{}' needs at least {} pixels but the panel is only {} pixels wide",
+            self.content.data,
+            self.modules.len() + 2,
+            self.ctx.display_width
+        );
+        None
+    }
+
+    fn render_text(&self, canvas: &mut Box<dyn LedCanvas>) {
+        let text = self.content.display_text();
+        let [r, g, b] = self.ctx.apply_brightness(self.content.text_color);
+        let style = MonoTextStyle::new(&FONT_5X8_LATIN1, Rgb888::new(r, g, b));
+
+        let text_width = text.chars().count() as i32 * 5;
+        let x = ((self.ctx.display_width - text_width) / 2).max(0);
+        // Baseline sits one pixel above the bottom edge
+        let y = self.ctx.display_height - 2;
+
+        let mut eg_canvas = EmbeddedGraphicsCanvas::new(canvas);
+        let _ = Text::new(&text, Point::new(x, y), style).draw(&mut eg_canvas);
+    }
+}
@@ -0,0 +1,301 @@
+use crate::display::driver::LedCanvas;
+use crate::display::graphics::embedded_graphics_support::EmbeddedGraphicsCanvas;
+use crate::display::renderer::{RenderContext, Renderer};
+use crate::models::big_number::BigNumberContent;
+use crate::models::content::ContentDetails;
+use crate::models::playlist::PlayListItem;
+use embedded_graphics::geometry::Point;
+use embedded_graphics::mono_font::iso_8859_1::{
+    FONT_5X8 as FONT_5X8_LATIN1, FONT_6X10 as FONT_6X10_LATIN1,
+};
+use embedded_graphics::mono_font::{MonoFont, MonoTextStyle};
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::text::Text;
+use embedded_graphics::Drawable;
+use log::warn;
+use std::time::Instant;
+
+// Smallest digit height that still reads as seven-segment digits
+const MIN_DIGIT_HEIGHT: i32 = 5;
+
+// Segment bits, in the usual a-g order
+const SEG_A: u8 = 1 << 0;
+const SEG_B: u8 = 1 << 1;
+const SEG_C: u8 = 1 << 2;
+const SEG_D: u8 = 1 << 3;
+const SEG_E: u8 = 1 << 4;
+const SEG_F: u8 = 1 << 5;
+const SEG_G: u8 = 1 << 6;
+
+const DIGIT_SEGMENTS: [u8; 10] = [
+    SEG_A | SEG_B | SEG_C | SEG_D | SEG_E | SEG_F,
+    SEG_B | SEG_C,
+    SEG_A | SEG_B | SEG_D | SEG_E | SEG_G,
+    SEG_A | SEG_B | SEG_C | SEG_D | SEG_G,
+    SEG_B | SEG_C | SEG_F | SEG_G,
+    SEG_A | SEG_C | SEG_D | SEG_F | SEG_G,
+    SEG_A | SEG_C | SEG_D | SEG_E | SEG_F | SEG_G,
+    SEG_A | SEG_B | SEG_C,
+    SEG_A | SEG_B | SEG_C | SEG_D | SEG_E | SEG_F | SEG_G,
+    SEG_A | SEG_B | SEG_C | SEG_D | SEG_F | SEG_G,
+];
+
+#[derive(Clone, Copy)]
+enum Glyph {
+    /// Full-width cell with the given lit segments ('-' lights only g, ' ' none)
+    Cell(u8),
+    Dot,
+    Colon,
+}
+
+/// Digit size and position chosen to fill the panel
+struct BigNumberLayout {
+    x: i32,
+    y: i32,
+    digit_height: i32,
+    digit_width: i32,
+    thickness: i32,
+    unit_font: &'static MonoFont<'static>,
+}
+
+impl BigNumberLayout {
+    fn sized(digit_height: i32) -> Self {
+        let thickness = ((digit_height + 5) / 10).max(1);
+        Self {
+            x: 0,
+            y: 0,
+            digit_height,
+            digit_width: (digit_height * 5 / 9).max(2 * thickness + 1),
+            thickness,
+            unit_font: if digit_height >= 10 {
+                &FONT_6X10_LATIN1
+            } else {
+                &FONT_5X8_LATIN1
+            },
+        }
+    }
+
+    fn glyph_width(&self, glyph: Glyph) -> i32 {
+        match glyph {
+            Glyph::Cell(_) => self.digit_width,
+            Glyph::Dot | Glyph::Colon => self.thickness,
+        }
+    }
+
+    fn unit_width(&self, unit: &str) -> i32 {
+        unit.chars().count() as i32 * self.unit_font.character_size.width as i32
+    }
+
+    /// Total width of the glyphs, the spacing between them and the unit suffix
+    fn total_width(&self, glyphs: &[Glyph], unit: Option<&str>) -> i32 {
+        let gap = self.thickness;
+        let digits: i32 = glyphs
+            .iter()
+            .map(|glyph| self.glyph_width(*glyph))
+            .sum::<i32>()
+            + gap * (glyphs.len() as i32 - 1).max(0);
+        match unit {
+            Some(unit) => digits + gap + 1 + self.unit_width(unit),
+            None => digits,
+        }
+    }
+}
+
+pub struct BigNumberRenderer {
+    content: BigNumberContent,
+    ctx: RenderContext,
+    duration: Option<u64>,
+    start_time: Instant,
+    glyphs: Vec<Glyph>,
+    layout: BigNumberLayout,
+}
+
+impl Renderer for BigNumberRenderer {
+    fn new(content: &PlayListItem, ctx: RenderContext) -> Self {
+        let number_content = match &content.content.data {
+            ContentDetails::BigNumber(number) => number.clone(),
+            #[allow(unreachable_patterns)]
+            _ => panic!("Expected big number content"),
+        };
+
+        let mut renderer = Self {
+            content: number_content,
+            ctx,
+            duration: content.duration,
+            start_time: Instant::now(),
+            glyphs: Vec::new(),
+            layout: BigNumberLayout::sized(MIN_DIGIT_HEIGHT),
+        };
+        renderer.prepare();
+        renderer
+    }
+
+    fn update(&mut self, _dt: f32) {
+        // Static content
+    }
+
+    fn render(&self, canvas: &mut Box<dyn LedCanvas>) {
+        let layout = &self.layout;
+        let color = self.ctx.apply_brightness(self.content.color);
+
+        let mut x = layout.x;
+        for glyph in &self.glyphs {
+            match glyph {
+                Glyph::Cell(segments) => self.draw_segments(canvas, x, *segments, color),
+                Glyph::Dot => {
+                    let y = layout.y + layout.digit_height - layout.thickness;
+                    self.fill_rect(canvas, x, y, layout.thickness, layout.thickness, color);
+                }
+                Glyph::Colon => {
+                    let half = layout.thickness / 2;
+                    for y in [
+                        layout.y + layout.digit_height / 3 - half,
+                        layout.y + layout.digit_height * 2 / 3 - half,
+                    ] {
+                        self.fill_rect(canvas, x, y, layout.thickness, layout.thickness, color);
+                    }
+                }
+            }
+            x += layout.glyph_width(*glyph) + layout.thickness;
+        }
+
+        if let Some(unit) = self.unit() {
+            let [r, g, b] = self
+                .ctx
+                .apply_brightness(self.content.unit_color.unwrap_or(self.content.color));
+            let style = MonoTextStyle::new(layout.unit_font, Rgb888::new(r, g, b));
+            // `x` already includes the gap after the last glyph; one extra pixel sets the unit apart
+            let baseline = layout.y + layout.digit_height - 1;
+            let mut eg_canvas = EmbeddedGraphicsCanvas::new(canvas);
+            let _ = Text::new(unit, Point::new(x + 1, baseline), style).draw(&mut eg_canvas);
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        if let Some(duration) = self.duration {
+            return Instant::now().duration_since(self.start_time).as_secs() >= duration;
+        }
+        false
+    }
+
+    fn reset(&mut self) {
+        self.start_time = Instant::now();
+    }
+
+    fn update_context(&mut self, ctx: RenderContext) {
+        let resized = ctx.display_width != self.ctx.display_width
+            || ctx.display_height != self.ctx.display_height;
+        self.ctx = ctx;
+        if resized {
+            self.prepare();
+        }
+    }
+
+    fn update_content(&mut self, content: &PlayListItem) {
+        if let ContentDetails::BigNumber(number) = &content.content.data {
+            // Value updates keep the item's schedule, so the start time is left alone
+            self.content = number.clone();
+            self.duration = content.duration;
+            self.prepare();
+        } else {
+            warn!("BigNumberRenderer received non-big-number content during update");
+        }
+    }
+}
+
+impl BigNumberRenderer {
+    fn unit(&self) -> Option<&str> {
+        self.content
+            .unit
+            .as_deref()
+            .filter(|unit| !unit.trim().is_empty())
+    }
+
+    /// Convert the value to glyphs and pick the largest digit height that fits the panel
+    fn prepare(&mut self) {
+        self.glyphs = self
+            .content
+            .display_value()
+            .chars()
+            .filter_map(|c| match c {
+                '0'..='9' => Some(Glyph::Cell(DIGIT_SEGMENTS[c as usize - '0' as usize])),
+                '-' => Some(Glyph::Cell(SEG_G)),
+                ' ' => Some(Glyph::Cell(0)),
+                '.' => Some(Glyph::Dot),
+                ':' => Some(Glyph::Colon),
+                _ => None,
+            })
+            .collect();
+
+        // Leave a one pixel margin above and below on panels tall enough to spare it
+        let max_height = if self.ctx.display_height > 8 {
+            self.ctx.display_height - 2
+        } else {
+            self.ctx.display_height
+        }
+        .max(MIN_DIGIT_HEIGHT);
+
+        let unit = self.unit();
+        let mut layout = (MIN_DIGIT_HEIGHT..=max_height)
+            .rev()
+            .map(BigNumberLayout::sized)
+            .find(|layout| layout.total_width(&self.glyphs, unit) <= self.ctx.display_width)
+            .unwrap_or_else(|| {
+                warn!(
+                    "Value '{}' does not fit the panel width of {} pixels",
+                    self.content.value, self.ctx.display_width
+                );
+                BigNumberLayout::sized(MIN_DIGIT_HEIGHT)
+            });
+
+        layout.x = (self.ctx.display_width - layout.total_width(&self.glyphs, unit)) / 2;
+        layout.y = (self.ctx.display_height - layout.digit_height) / 2;
+        self.layout = layout;
+    }
+
+    fn draw_segments(&self, canvas: &mut Box<dyn LedCanvas>, x: i32, segments: u8, color: [u8; 3]) {
+        let layout = &self.layout;
+        let (y, w, h, t) = (
+            layout.y,
+            layout.digit_width,
+            layout.digit_height,
+            layout.thickness,
+        );
+        // Top edge of the middle segment
+        let mid = (h - t) / 2;
+        let upper = mid - t;
+        let lower = h - mid - 2 * t;
+
+        let rects = [
+            (SEG_A, x + t, y, w - 2 * t, t),
+            (SEG_B, x + w - t, y + t, t, upper),
+            (SEG_C, x + w - t, y + mid + t, t, lower),
+            (SEG_D, x + t, y + h - t, w - 2 * t, t),
+            (SEG_E, x, y + mid + t, t, lower),
+            (SEG_F, x, y + t, t, upper),
+            (SEG_G, x + t, y + mid, w - 2 * t, t),
+        ];
+        for (segment, rx, ry, rw, rh) in rects {
+            if segments & segment != 0 {
+                self.fill_rect(canvas, rx, ry, rw, rh, color);
+            }
+        }
+    }
+
+    fn fill_rect(
+        &self,
+        canvas: &mut Box<dyn LedCanvas>,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        color: [u8; 3],
+    ) {
+        let [r, g, b] = color;
+        for py in y.max(0)..(y + height).min(self.ctx.display_height) {
+            for px in x.max(0)..(x + width).min(self.ctx.display_width) {
+                canvas.set_pixel(px as usize, py as usize, r, g, b);
+            }
+        }
+    }
+}
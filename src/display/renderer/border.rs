@@ -1,10 +1,13 @@
 use crate::display::driver::LedCanvas;
-use crate::display::renderer::{RenderContext, Renderer};
+use crate::display::renderer::{EffectRng, RenderContext, Renderer};
 use crate::models::border_effects::BorderEffect;
 use crate::models::playlist::PlayListItem;
 use std::f32::consts::TAU;
 use std::time::Instant;
 
+// How long a reactive border flashes after a content event
+const FLASH_SECONDS: f32 = 0.4;
+
 pub struct BorderRenderer {
     /// The border effect to render
     effect: BorderEffect,
@@ -17,6 +20,15 @@ pub struct BorderRenderer {
 
     /// Timestamp when rendering started
     start_time: Instant,
+
+    /// Randomness for sparkling borders, seeded from the context
+    rng: EffectRng,
+
+    /// Whether content events flash the border
+    reactive: bool,
+
+    /// Color and age in seconds of the running flash
+    flash: Option<([u8; 3], f32)>,
 }
 
 impl Renderer for BorderRenderer {
@@ -29,6 +41,9 @@ impl Renderer for BorderRenderer {
             ctx: ctx.clone(), // Clone to avoid move issues
             animation_state: 0.0,
             start_time: Instant::now(),
+            rng: EffectRng::new(ctx.seed),
+            reactive: content.border_reactive,
+            flash: None,
         }
     }
 
@@ -41,6 +56,19 @@ impl Renderer for BorderRenderer {
                 self.animation_state += dt;
             }
         }
+
+        if let Some((_, age)) = &mut self.flash {
+            *age += dt;
+        }
+        if self.flash.is_some_and(|(_, age)| age >= FLASH_SECONDS) {
+            self.flash = None;
+        }
+        // Always drain events so a border turned reactive does not flash for old ones
+        if let Some(color) = self.ctx.border_link.take_pulse() {
+            if self.reactive {
+                self.flash = Some((color, 0.0));
+            }
+        }
     }
 
     fn render(&self, canvas: &mut Box<dyn LedCanvas>) {
@@ -51,15 +79,25 @@ impl Renderer for BorderRenderer {
             BorderEffect::Rainbow => {
                 self.render_rainbow_border(canvas);
             }
-            BorderEffect::Pulse { colors } => {
+            BorderEffect::Pulse { colors, .. } => {
                 self.render_pulse_border(canvas, colors);
             }
-            BorderEffect::Sparkle { colors } => {
+            BorderEffect::Sparkle { colors, .. } => {
                 self.render_sparkle_border(canvas, colors);
             }
-            BorderEffect::Gradient { colors } => {
+            BorderEffect::Gradient { colors, .. } => {
                 self.render_gradient_border(canvas, colors);
             }
+            BorderEffect::Progress { colors, .. } => {
+                self.render_progress_border(canvas, colors);
+            }
+        }
+
+        // Flash on top of the effect, fading towards the end
+        if let Some((color, age)) = self.flash {
+            let level = 1.0 - (age / FLASH_SECONDS).powi(2);
+            let [r, g, b] = self.ctx.apply_brightness(Self::scale_color(color, level));
+            self.draw_solid_border(canvas, r, g, b);
         }
     }
 
@@ -71,10 +109,12 @@ impl Renderer for BorderRenderer {
     fn reset(&mut self) {
         self.animation_state = 0.0;
         self.start_time = Instant::now();
+        self.flash = None;
     }
 
     fn update_context(&mut self, ctx: RenderContext) {
         // Update the context without changing animation state
+        self.rng = EffectRng::new(ctx.seed);
         self.ctx = ctx;
     }
 
@@ -84,6 +124,7 @@ impl Renderer for BorderRenderer {
 
         // Only update the effect, preserving animation state
         self.effect = new_effect;
+        self.reactive = content.border_reactive;
     }
 }
 
@@ -202,15 +243,14 @@ impl BorderRenderer {
                     continue;
                 }
 
-                let seed = Self::tile_seed(y as u32, x as u32);
-                if Self::pseudo_random_f32(seed) > density {
+                let seed = self.rng.key(y as u32, x as u32);
+                if EffectRng::unit(seed) > density {
                     continue;
                 }
 
                 let palette_index = (seed as usize) % colors.len();
-                let speed_variation =
-                    0.6 + 1.2 * Self::pseudo_random_f32(seed.wrapping_mul(31_415_927));
-                let phase_offset = Self::pseudo_random_f32(seed.wrapping_mul(97_531));
+                let speed_variation = 0.6 + 1.2 * EffectRng::unit(seed.wrapping_mul(31_415_927));
+                let phase_offset = EffectRng::unit(seed.wrapping_mul(97_531));
                 let twinkle_phase = (phase_base * speed_variation + phase_offset).fract();
                 let brightness = Self::sparkle_brightness(twinkle_phase);
 
@@ -286,14 +326,14 @@ impl BorderRenderer {
                 // Right border (excluding corners)
                 canvas.set_pixel(
                     (width - 1) as usize,
-                    pos - (width as usize) * 2 - (height as usize) + 2 + 1,
+                    pos + 3 - (width as usize) * 2 - (height as usize),
                     r,
                     g,
                     b,
                 );
                 canvas.set_pixel(
                     (width - 2) as usize,
-                    pos - (width as usize) * 2 - (height as usize) + 2 + 1,
+                    pos + 3 - (width as usize) * 2 - (height as usize),
                     r,
                     g,
                     b,
@@ -302,6 +342,55 @@ impl BorderRenderer {
         }
     }
 
+    // Render a border that fills clockwise from the top-left corner with the content's progress,
+    // or with the elapsed share of the item's duration for content that publishes none
+    fn render_progress_border(&self, canvas: &mut Box<dyn LedCanvas>, colors: &[[u8; 3]]) {
+        let width = self.ctx.display_width as usize;
+        let height = self.ctx.display_height as usize;
+        if colors.is_empty() || width < 2 || height < 2 {
+            return;
+        }
+        let Some(progress) = self
+            .ctx
+            .border_link
+            .progress()
+            .or_else(|| self.ctx.item_timing.and_then(|timing| timing.progress()))
+        else {
+            return;
+        };
+
+        let perimeter = 2 * (width + height) - 4;
+        let filled = (progress * perimeter as f32).round() as usize;
+        let fill = self.ctx.apply_brightness(colors[0]);
+        let track = colors.get(1).map(|color| self.ctx.apply_brightness(*color));
+
+        for pos in 0..perimeter {
+            let [r, g, b] = if pos < filled {
+                fill
+            } else if let Some(track) = track {
+                track
+            } else {
+                continue;
+            };
+
+            // Outer pixel and the one next to it towards the center (2 pixels thick)
+            let (outer, inner) = if pos < width {
+                ((pos, 0), (pos, 1))
+            } else if pos < width + height - 2 {
+                let y = pos - width + 1;
+                ((width - 1, y), (width - 2, y))
+            } else if pos < 2 * width + height - 2 {
+                let x = width - 1 - (pos - (width + height - 2));
+                ((x, height - 1), (x, height - 2))
+            } else {
+                let y = height - 2 - (pos - (2 * width + height - 2));
+                ((0, y), (1, y))
+            };
+            canvas.set_pixel(outer.0, outer.1, r, g, b);
+            canvas.set_pixel(inner.0, inner.1, r, g, b);
+        }
+    }
+
     // Helper to draw a solid border with the given color
     fn draw_solid_border(&self, canvas: &mut Box<dyn LedCanvas>, r: u8, g: u8, b: u8) {
         let height = self.ctx.display_height;
@@ -353,18 +442,6 @@ impl BorderRenderer {
         (r, g, b)
     }
 
-    fn pseudo_random_f32(seed: u32) -> f32 {
-        let mut x = seed;
-        x ^= x << 13;
-        x ^= x >> 17;
-        x ^= x << 5;
-        (x as f32 / u32::MAX as f32).fract()
-    }
-
-    fn tile_seed(row: u32, col: u32) -> u32 {
-        row.wrapping_mul(738_560_93) ^ col.wrapping_mul(193_496_63)
-    }
-
     fn sparkle_brightness(phase: f32) -> f32 {
         let wave = (TAU * phase).sin() * 0.5 + 0.5;
         0.1 + 0.9 * wave.powf(2.2)
@@ -379,3 +456,42 @@ impl BorderRenderer {
         ]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::driver::capture_frame;
+
+    fn item_with_border(border: serde_json::Value) -> PlayListItem {
+        serde_json::from_value(serde_json::json!({
+            "duration": 10,
+            "border_effect": border,
+            "content": {
+                "type": "Text",
+                "data": { "type": "Text", "text": "Hi", "scroll": false, "color": [255, 255, 255], "speed": 50.0 }
+            }
+        }))
+        .expect("valid item")
+    }
+
+    #[test]
+    fn gradient_border_lights_only_the_edge() {
+        let item = item_with_border(serde_json::json!({ "Gradient": { "colors": [[255, 0, 0]] } }));
+        let renderer = BorderRenderer::new(&item, RenderContext::new(16, 8, 100));
+        let frame = capture_frame(&renderer, 16, 8);
+
+        // Two pixels thick all around
+        assert_eq!(frame.lit_pixels(), 16 * 8 - 12 * 4);
+        assert_eq!(frame.pixel(0, 0), Some([255, 0, 0]));
+        assert_eq!(frame.pixel(14, 6), Some([255, 0, 0]));
+        assert_eq!(frame.pixel(2, 2), Some([0, 0, 0]));
+        assert_eq!(frame.pixel(13, 5), Some([0, 0, 0]));
+    }
+
+    #[test]
+    fn no_border_draws_nothing() {
+        let item = item_with_border(serde_json::Value::Null);
+        let renderer = BorderRenderer::new(&item, RenderContext::new(16, 8, 100));
+        assert_eq!(capture_frame(&renderer, 16, 8).lit_pixels(), 0);
+    }
+}
@@ -1,24 +1,87 @@
 use crate::display::driver::LedCanvas;
 use crate::display::graphics::embedded_graphics_support::EmbeddedGraphicsCanvas;
-use crate::display::renderer::{RenderContext, Renderer};
-use crate::models::clock::{ClockContent, ClockFormat};
+use crate::display::renderer::{draw_time_sync_indicator, RenderContext, Renderer};
+use crate::models::clock::{ClockContent, ClockFormat, ClockTransition};
 use crate::models::content::ContentDetails;
 use crate::models::playlist::PlayListItem;
-use chrono::Local;
-use embedded_graphics::geometry::Point;
-use embedded_graphics::mono_font::iso_8859_1::FONT_10X20 as FONT_10X20_LATIN1;
+use crate::utils::{locale, timezone};
+use chrono::Timelike;
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::geometry::{OriginDimensions, Point, Size};
+use embedded_graphics::mono_font::iso_8859_1::{
+    FONT_10X20 as FONT_10X20_LATIN1, FONT_5X8 as FONT_5X8_LATIN1, FONT_6X10 as FONT_6X10_LATIN1,
+};
 use embedded_graphics::mono_font::MonoTextStyle;
-use embedded_graphics::pixelcolor::Rgb888;
-use embedded_graphics::text::Text;
-use embedded_graphics::Drawable;
+use embedded_graphics::pixelcolor::{BinaryColor, Rgb888};
+use embedded_graphics::text::{Baseline, Text};
+use embedded_graphics::{Drawable, Pixel};
 use log::warn;
 use std::time::Instant;
 
+/// Monochrome bitmap of a single character, used to draw partial glyphs during transitions
+struct GlyphMask {
+    width: i32,
+    height: i32,
+    pixels: Vec<bool>,
+}
+
+impl GlyphMask {
+    fn new(c: char, style: MonoTextStyle<'_, BinaryColor>, width: i32, height: i32) -> Self {
+        let mut mask = Self {
+            width,
+            height,
+            pixels: vec![false; (width * height) as usize],
+        };
+        let mut buffer = [0u8; 4];
+        let _ = Text::with_baseline(
+            c.encode_utf8(&mut buffer),
+            Point::zero(),
+            style,
+            Baseline::Top,
+        )
+        .draw(&mut mask);
+        mask
+    }
+
+    fn is_set(&self, x: i32, y: i32) -> bool {
+        self.pixels[(y * self.width + x) as usize]
+    }
+}
+
+impl DrawTarget for GlyphMask {
+    type Color = BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if point.x >= 0 && point.y >= 0 && point.x < self.width && point.y < self.height {
+                self.pixels[(point.y * self.width + point.x) as usize] = color.is_on();
+            }
+        }
+        Ok(())
+    }
+}
+
+impl OriginDimensions for GlyphMask {
+    fn size(&self) -> Size {
+        Size::new(self.width as u32, self.height as u32)
+    }
+}
+
 pub struct ClockRenderer {
     content: ClockContent,
     ctx: RenderContext,
     duration: Option<u64>,
     start_time: Instant,
+    // Time string currently shown, refreshed in update()
+    time_str: String,
+    // Date line below the time, empty unless show_date is set
+    date_str: String,
+    // Time string being replaced and when the transition started
+    previous: Option<(String, Instant)>,
 }
 
 impl Renderer for ClockRenderer {
@@ -29,32 +92,102 @@ impl Renderer for ClockRenderer {
             _ => panic!("Expected clock content"),
         };
 
-        Self {
+        let mut renderer = Self {
             content: clock_content,
             ctx: ctx.clone(),
             duration: content.duration,
             start_time: Instant::now(),
-        }
+            time_str: String::new(),
+            date_str: String::new(),
+            previous: None,
+        };
+        renderer.time_str = renderer.format_time_string();
+        renderer.date_str = renderer.format_date_string();
+        renderer
     }
 
     fn update(&mut self, _dt: f32) {
-        // No animation state required; rendering uses current system time
+        // Progress through the current minute, drawn by a Progress border
+        let now = timezone::now();
+        self.ctx.border_link.set_progress(
+            (now.second() as f32 + now.nanosecond().min(999_999_999) as f32 / 1e9) / 60.0,
+        );
+
+        self.date_str = self.format_date_string();
+        let time_str = self.format_time_string();
+        if time_str != self.time_str {
+            // Digits can only be animated in place when the layout stays the same
+            if self.content.transition != ClockTransition::None
+                && time_str.chars().count() == self.time_str.chars().count()
+            {
+                let previous = std::mem::replace(&mut self.time_str, time_str);
+                self.previous = Some((previous, Instant::now()));
+            } else {
+                self.time_str = time_str;
+                self.previous = None;
+            }
+        }
+
+        if self.transition_progress().is_none() {
+            self.previous = None;
+        }
     }
 
     fn render(&self, canvas: &mut Box<dyn LedCanvas>) {
-        let mut eg_canvas = EmbeddedGraphicsCanvas::new(canvas);
-        let time_str = self.format_time_string();
+        draw_time_sync_indicator(canvas, &self.ctx);
 
         let font = &FONT_10X20_LATIN1;
         let char_width = font.character_size.width as i32;
         let font_height = font.character_size.height as i32;
-        let text_width = (time_str.chars().count() as i32) * char_width;
+        let text_width = (self.time_str.chars().count() as i32) * char_width;
         let x = (self.ctx.display_width - text_width) / 2;
-        let y = self.ctx.calculate_centered_text_position(font_height);
+        let mut y = self.ctx.calculate_centered_text_position(font_height);
         let [r, g, b] = self.ctx.apply_brightness(self.content.color);
-        let text_style = MonoTextStyle::new(font, Rgb888::new(r, g, b));
 
-        let _ = Text::new(&time_str, Point::new(x, y), text_style).draw(&mut eg_canvas);
+        if !self.date_str.is_empty() {
+            // Center time and date together, the date sits right below the digits. Dates too
+            // wide for the panel use the small font.
+            let chars = self.date_str.chars().count() as i32;
+            let date_font =
+                if chars * FONT_6X10_LATIN1.character_size.width as i32 <= self.ctx.display_width {
+                    &FONT_6X10_LATIN1
+                } else {
+                    &FONT_5X8_LATIN1
+                };
+            y -= date_font.character_size.height as i32 / 2;
+            let date_width = chars * date_font.character_size.width as i32;
+            let date_top = y - font.baseline as i32 + font_height - 1;
+            let mut eg_canvas = EmbeddedGraphicsCanvas::new(canvas);
+            let style = MonoTextStyle::new(date_font, Rgb888::new(r, g, b));
+            let _ = Text::with_baseline(
+                &self.date_str,
+                Point::new((self.ctx.display_width - date_width) / 2, date_top),
+                style,
+                Baseline::Top,
+            )
+            .draw(&mut eg_canvas);
+        }
+
+        if let (Some((previous, _)), Some(progress)) = (&self.previous, self.transition_progress())
+        {
+            let top = y - font.baseline as i32;
+            let mask_style = MonoTextStyle::new(font, BinaryColor::On);
+            for (index, (old, new)) in previous.chars().zip(self.time_str.chars()).enumerate() {
+                let cell_x = x + index as i32 * char_width;
+                let new_mask = GlyphMask::new(new, mask_style, char_width, font_height);
+                if old == new {
+                    self.draw_mask_rows(canvas, &new_mask, cell_x, top, [r, g, b], Some);
+                    continue;
+                }
+                let old_mask = GlyphMask::new(old, mask_style, char_width, font_height);
+                self.draw_transition(canvas, &old_mask, &new_mask, cell_x, top, progress);
+            }
+            return;
+        }
+
+        let mut eg_canvas = EmbeddedGraphicsCanvas::new(canvas);
+        let text_style = MonoTextStyle::new(font, Rgb888::new(r, g, b));
+        let _ = Text::new(&self.time_str, Point::new(x, y), text_style).draw(&mut eg_canvas);
     }
 
     fn is_complete(&self) -> bool {
@@ -66,6 +199,12 @@ impl Renderer for ClockRenderer {
 
     fn reset(&mut self) {
         self.start_time = Instant::now();
+        self.previous = None;
+    }
+
+    fn progress(&self) -> Option<f32> {
+        let duration = self.duration?;
+        Some((self.start_time.elapsed().as_secs_f32() / duration.max(1) as f32).min(1.0))
     }
 
     fn update_context(&mut self, ctx: RenderContext) {
@@ -77,6 +216,9 @@ impl Renderer for ClockRenderer {
             self.content = clock.clone();
             self.duration = content.duration;
             self.start_time = Instant::now();
+            self.time_str = self.format_time_string();
+            self.date_str = self.format_date_string();
+            self.previous = None;
         } else {
             warn!("ClockRenderer received non-clock content during update");
         }
@@ -84,8 +226,112 @@ impl Renderer for ClockRenderer {
 }
 
 impl ClockRenderer {
+    /// Eased progress (0-1) of the running digit transition, if any
+    fn transition_progress(&self) -> Option<f32> {
+        let (_, started) = self.previous.as_ref()?;
+        let linear =
+            started.elapsed().as_secs_f32() * 1000.0 / self.content.transition_ms.max(1) as f32;
+        if linear >= 1.0 {
+            return None;
+        }
+        // Ease out so digits settle gently
+        Some(1.0 - (1.0 - linear) * (1.0 - linear))
+    }
+
+    /// Draw one changing character cell part way through the transition
+    fn draw_transition(
+        &self,
+        canvas: &mut Box<dyn LedCanvas>,
+        old: &GlyphMask,
+        new: &GlyphMask,
+        x: i32,
+        top: i32,
+        progress: f32,
+    ) {
+        let color = self.ctx.apply_brightness(self.content.color);
+        let height = new.height;
+        match self.content.transition {
+            ClockTransition::Roll => {
+                // Both glyphs move up together; the cell acts as a window
+                let offset = (progress * height as f32) as i32;
+                self.draw_mask_rows(canvas, old, x, top, color, |row| {
+                    Some(row + offset).filter(|source| *source < height)
+                });
+                self.draw_mask_rows(canvas, new, x, top, color, |row| {
+                    Some(row + offset - height).filter(|source| *source >= 0)
+                });
+            }
+            ClockTransition::Flip => {
+                let mid = height / 2;
+                if progress < 0.5 {
+                    // The old top half folds down towards the hinge, uncovering the new top half
+                    let flap = ((1.0 - progress * 2.0) * mid as f32).round() as i32;
+                    self.draw_mask_rows(canvas, new, x, top, color, |row| {
+                        Some(row).filter(|row| *row < mid - flap)
+                    });
+                    self.draw_mask_rows(canvas, old, x, top, color, |row| {
+                        if row >= mid {
+                            Some(row)
+                        } else if row >= mid - flap {
+                            Some((row - (mid - flap)) * mid / flap)
+                        } else {
+                            None
+                        }
+                    });
+                } else {
+                    // The new bottom half unfolds from the hinge over the old bottom half
+                    let lower = height - mid;
+                    let flap = ((progress * 2.0 - 1.0) * lower as f32).round() as i32;
+                    self.draw_mask_rows(canvas, new, x, top, color, |row| {
+                        if row < mid {
+                            Some(row)
+                        } else if row < mid + flap {
+                            Some(mid + (row - mid) * lower / flap)
+                        } else {
+                            None
+                        }
+                    });
+                    self.draw_mask_rows(canvas, old, x, top, color, |row| {
+                        Some(row).filter(|row| *row >= mid + flap)
+                    });
+                }
+            }
+            ClockTransition::None => {
+                self.draw_mask_rows(canvas, new, x, top, color, Some);
+            }
+        }
+    }
+
+    /// Draw each cell row from the mask row chosen by `source_row`, skipping rows it rejects
+    fn draw_mask_rows(
+        &self,
+        canvas: &mut Box<dyn LedCanvas>,
+        mask: &GlyphMask,
+        x: i32,
+        top: i32,
+        color: [u8; 3],
+        source_row: impl Fn(i32) -> Option<i32>,
+    ) {
+        let [r, g, b] = color;
+        for row in 0..mask.height {
+            let Some(source) = source_row(row) else {
+                continue;
+            };
+            let y = top + row;
+            if source < 0 || source >= mask.height || y < 0 || y >= self.ctx.display_height {
+                continue;
+            }
+            for column in 0..mask.width {
+                let px = x + column;
+                if mask.is_set(column, source) && px >= 0 && px < self.ctx.display_width {
+                    canvas.set_pixel(px as usize, y as usize, r, g, b);
+                }
+            }
+        }
+    }
+
     fn format_time_string(&self) -> String {
-        let now = Local::now();
+        let now = timezone::now();
         let show_seconds = self.content.show_seconds;
 
         let raw = match self.content.format {
@@ -97,12 +343,17 @@ impl ClockRenderer {
                 }
             }
             ClockFormat::TwelveHour => {
-                let formatted = if show_seconds {
-                    now.format("%I:%M:%S %p").to_string()
+                let strings = locale::strings();
+                let period = if now.hour() < 12 {
+                    strings.am
                 } else {
-                    now.format("%I:%M %p").to_string()
+                    strings.pm
                 };
-                formatted
+                if show_seconds {
+                    format!("{} {}", now.format("%I:%M:%S"), period)
+                } else {
+                    format!("{} {}", now.format("%I:%M"), period)
+                }
             }
         };
 
@@ -112,4 +363,20 @@ impl ClockRenderer {
             raw
         }
     }
+
+    fn format_date_string(&self) -> String {
+        if !self.content.show_date {
+            return String::new();
+        }
+        // Leave out the weekday when the date does not fit even in the small font
+        let strings = locale::strings();
+        let now = timezone::now();
+        let date = strings.format_date(&now);
+        let small_width = FONT_5X8_LATIN1.character_size.width as i32;
+        if date.chars().count() as i32 * small_width > self.ctx.display_width {
+            strings.format_day_month(&now)
+        } else {
+            date
+        }
+    }
 }
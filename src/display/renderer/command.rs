@@ -0,0 +1,144 @@
+use crate::display::driver::LedCanvas;
+use crate::display::renderer::{RenderContext, Renderer, TextRenderer};
+use crate::models::command::CommandContent;
+use crate::models::content::{ContentData, ContentDetails, ContentType};
+use crate::models::playlist::PlayListItem;
+use crate::models::text::{TextContent, TextOverflow};
+use crate::utils::commands::{self, CommandOutput};
+use log::warn;
+use std::time::{Duration, Instant};
+
+// Width of one character of the text font, see TextRenderer
+const CHAR_WIDTH: i32 = 10;
+
+/// Shows the output of an allowed local command as text, refreshed on the item's interval.
+/// The output is drawn by a TextRenderer and scrolls when it does not fit the panel.
+pub struct CommandRenderer {
+    content: CommandContent,
+    ctx: RenderContext,
+    duration: Option<u64>,
+    start_time: Instant,
+    output: CommandOutput,
+    text: TextRenderer,
+}
+
+impl Renderer for CommandRenderer {
+    fn new(content: &PlayListItem, ctx: RenderContext) -> Self {
+        let command_content = match &content.content.data {
+            ContentDetails::Command(command) => command.clone(),
+            #[allow(unreachable_patterns)]
+            _ => panic!("Expected command content"),
+        };
+
+        // The command first runs on the first update, not for renderers that are only
+        // built to validate an item
+        let output = CommandOutput::default();
+        let text = TextRenderer::new(&text_item(&command_content, &output, &ctx), ctx.clone());
+        Self {
+            content: command_content,
+            ctx,
+            duration: content.duration,
+            start_time: Instant::now(),
+            output,
+            text,
+        }
+    }
+
+    fn update(&mut self, dt: f32) {
+        let interval = Duration::from_secs(self.content.interval_seconds);
+        let output = commands::latest(&self.content.command, interval);
+        if output != self.output {
+            self.output = output;
+            self.refresh_text();
+        }
+        self.text.update(dt);
+    }
+
+    fn render(&self, canvas: &mut Box<dyn LedCanvas>) {
+        self.text.render(canvas);
+    }
+
+    fn is_complete(&self) -> bool {
+        if let Some(duration) = self.duration {
+            return Instant::now().duration_since(self.start_time).as_secs() >= duration;
+        }
+        false
+    }
+
+    fn reset(&mut self) {
+        self.start_time = Instant::now();
+        self.text.reset();
+    }
+
+    fn update_context(&mut self, ctx: RenderContext) {
+        self.ctx = ctx.clone();
+        self.text.update_context(ctx);
+    }
+
+    fn update_content(&mut self, content: &PlayListItem) {
+        if let ContentDetails::Command(command) = &content.content.data {
+            self.content = command.clone();
+            self.duration = content.duration;
+            self.refresh_text();
+        } else {
+            warn!("CommandRenderer received non-command content during update");
+        }
+    }
+
+    fn error(&self) -> Option<String> {
+        if !commands::is_allowed(&self.content.command) {
+            return Some(format!(
+                "Command '{}' is not allowed on this controller",
+                self.content.command
+            ));
+        }
+        // Failures after a successful run keep showing the last output
+        match &self.output.text {
+            Some(_) => None,
+            None => self
+                .output
+                .error
+                .as_ref()
+                .map(|e| format!("Command '{}' {}", self.content.command, e)),
+        }
+    }
+}
+
+impl CommandRenderer {
+    // Hand new output or settings to the text renderer, keeping its scroll position
+    fn refresh_text(&mut self) {
+        let item = text_item(&self.content, &self.output, &self.ctx);
+        self.text.update_content(&item);
+    }
+}
+
+// Text item showing the command output, scrolling if it is wider than the panel
+fn text_item(
+    command: &CommandContent,
+    output: &CommandOutput,
+    ctx: &RenderContext,
+) -> PlayListItem {
+    let text = output.text.clone().unwrap_or_default();
+    let scroll = text.chars().count() as i32 * CHAR_WIDTH > ctx.display_width;
+    PlayListItem {
+        duration: None,
+        repeat_count: None,
+        content: ContentData {
+            content_type: ContentType::Text,
+            data: ContentDetails::Text(TextContent {
+                text,
+                scroll,
+                color: command.color,
+                speed: command.speed,
+                text_segments: None,
+                pixels_per_second: None,
+                read_time_ms: None,
+                scroll_ease_ms: None,
+                antialias: false,
+                paging: None,
+                overflow: TextOverflow::default(),
+            }),
+        },
+        ..PlayListItem::default()
+    }
+}
@@ -1,3 +1,67 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Channel from a content renderer to the border renderer drawn around it. The display
+/// manager hands both renderers of an item the same link, so borders can follow state the
+/// content keeps, e.g. how far the current minute of a clock has elapsed, and react to events
+/// such as a countdown crossing a threshold.
+#[derive(Clone, Default)]
+pub struct BorderLink(Arc<Mutex<BorderSignals>>);
+
+#[derive(Default)]
+struct BorderSignals {
+    progress: Option<f32>,
+    // Color of the last event not yet picked up by the border
+    pulse: Option<[u8; 3]>,
+}
+
+impl BorderLink {
+    /// Publish how far the content has progressed (0-1), drawn by the Progress border
+    pub fn set_progress(&self, progress: f32) {
+        if let Ok(mut signals) = self.0.lock() {
+            signals.progress = Some(progress.clamp(0.0, 1.0));
+        }
+    }
+
+    /// Last progress published by the content, None if it never published any
+    pub fn progress(&self) -> Option<f32> {
+        self.0.lock().ok().and_then(|signals| signals.progress)
+    }
+
+    /// Signal an event, reactive borders flash in `color`
+    pub fn pulse(&self, color: [u8; 3]) {
+        if let Ok(mut signals) = self.0.lock() {
+            signals.pulse = Some(color);
+        }
+    }
+
+    /// Event signaled since the last call, if any
+    pub fn take_pulse(&self) -> Option<[u8; 3]> {
+        self.0
+            .lock()
+            .ok()
+            .and_then(|mut signals| signals.pulse.take())
+    }
+}
+
+/// Timing of the playlist item being drawn, so borders can follow how long it has been on
+/// screen
+#[derive(Clone, Copy, Debug)]
+pub struct ItemTiming {
+    /// When the item went on screen
+    pub started: Instant,
+    /// How long the item is shown, None when it repeats instead of running for a duration
+    pub duration: Option<Duration>,
+}
+
+impl ItemTiming {
+    /// Share (0-1) of the item's duration that has elapsed, None without a duration
+    pub fn progress(&self) -> Option<f32> {
+        let duration = self.duration.filter(|duration| !duration.is_zero())?;
+        Some((self.started.elapsed().as_secs_f32() / duration.as_secs_f32()).min(1.0))
+    }
+}
+
 /// Provides shared configuration and helpers for all renderers
 #[derive(Clone)]
 pub struct RenderContext {
@@ -9,6 +73,15 @@ pub struct RenderContext {
 
     /// User-defined brightness (0-100)
     pub brightness: u8,
+
+    /// Seed for random effects, displays with the same seed show identical effects
+    pub seed: u64,
+
+    /// Link between the content and border renderer of the same item
+    pub border_link: BorderLink,
+
+    /// Timing of the playlist item, None for the welcome and idle screens and previews
+    pub item_timing: Option<ItemTiming>,
 }
 
 impl RenderContext {
@@ -18,9 +91,30 @@ impl RenderContext {
             display_width,
             display_height,
             brightness,
+            seed: 0,
+            border_link: BorderLink::default(),
+            item_timing: None,
         }
     }
 
+    /// Use `seed` for random effects
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Share `link` between the renderers created with this context
+    pub fn with_border_link(mut self, link: BorderLink) -> Self {
+        self.border_link = link;
+        self
+    }
+
+    /// Draw for an item with the given timing
+    pub fn with_item_timing(mut self, timing: Option<ItemTiming>) -> Self {
+        self.item_timing = timing;
+        self
+    }
+
     /// Apply brightness scaling to a color
     pub fn apply_brightness(&self, color: [u8; 3]) -> [u8; 3] {
         let brightness_scale = self.brightness as f32 / 100.0;
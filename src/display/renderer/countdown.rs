@@ -0,0 +1,201 @@
+use crate::display::driver::LedCanvas;
+use crate::display::graphics::embedded_graphics_support::EmbeddedGraphicsCanvas;
+use crate::display::renderer::{draw_time_sync_indicator, RenderContext, Renderer};
+use crate::models::content::ContentDetails;
+use crate::models::countdown::{CountdownContent, ThresholdEffect};
+use crate::models::playlist::PlayListItem;
+use crate::utils::{locale, timezone};
+use chrono::{DateTime, FixedOffset, Utc};
+use embedded_graphics::geometry::Point;
+use embedded_graphics::mono_font::iso_8859_1::{
+    FONT_10X20 as FONT_10X20_LATIN1, FONT_6X10 as FONT_6X10_LATIN1,
+};
+use embedded_graphics::mono_font::MonoTextStyle;
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::text::Text;
+use embedded_graphics::Drawable;
+use log::warn;
+use std::time::Instant;
+
+pub struct CountdownRenderer {
+    content: CountdownContent,
+    ctx: RenderContext,
+    duration: Option<u64>,
+    start_time: Instant,
+    target: Option<DateTime<FixedOffset>>,
+    // Threshold (or 0 once finished) last seen by update(), crossing another one is an event
+    // for reactive borders
+    last_milestone: Option<u64>,
+}
+
+impl Renderer for CountdownRenderer {
+    fn new(content: &PlayListItem, ctx: RenderContext) -> Self {
+        let countdown_content = match &content.content.data {
+            ContentDetails::Countdown(countdown) => countdown.clone(),
+            #[allow(unreachable_patterns)]
+            _ => panic!("Expected countdown content"),
+        };
+
+        let mut renderer = Self {
+            content: countdown_content,
+            ctx,
+            duration: content.duration,
+            start_time: Instant::now(),
+            target: None,
+            last_milestone: None,
+        };
+        renderer.parse_target();
+        renderer.last_milestone = renderer.milestone();
+        renderer
+    }
+
+    fn update(&mut self, _dt: f32) {
+        // No animation state required; rendering uses current system time
+        let milestone = self.milestone();
+        if milestone != self.last_milestone {
+            self.last_milestone = milestone;
+            let color = self
+                .remaining_seconds()
+                .and_then(|remaining| self.content.active_threshold(remaining))
+                .map_or(self.content.color, |threshold| threshold.color);
+            if milestone.is_some() {
+                self.ctx.border_link.pulse(color);
+            }
+        }
+    }
+
+    fn render(&self, canvas: &mut Box<dyn LedCanvas>) {
+        draw_time_sync_indicator(canvas, &self.ctx);
+
+        let Some(target) = self.target else {
+            return;
+        };
+
+        let now = timezone::utc_now();
+        let remaining_ms = (target.with_timezone(&Utc) - now).num_milliseconds();
+        let remaining_seconds = round_up_seconds(remaining_ms);
+
+        let text = if remaining_ms > 0 {
+            format_remaining(remaining_seconds)
+        } else {
+            self.content.finished_text.clone()
+        };
+
+        let threshold = self.content.active_threshold(remaining_seconds);
+        let mut color = threshold.map_or(self.content.color, |threshold| threshold.color);
+        let subsec = now.timestamp_subsec_millis() as f32 / 1000.0;
+        match threshold.map(|threshold| threshold.effect) {
+            Some(ThresholdEffect::Blink) if subsec % 0.5 >= 0.25 => return,
+            Some(ThresholdEffect::Pulse) => {
+                let level = 0.2 + 0.8 * (0.5 + 0.5 * (subsec * std::f32::consts::TAU).cos());
+                color = color.map(|channel| (channel as f32 * level) as u8);
+            }
+            _ => {}
+        }
+
+        // Fall back to the small font when the large one does not fit
+        let char_count = text.chars().count() as i32;
+        let font = if char_count * FONT_10X20_LATIN1.character_size.width as i32
+            <= self.ctx.display_width
+        {
+            &FONT_10X20_LATIN1
+        } else {
+            &FONT_6X10_LATIN1
+        };
+        let text_width = char_count * font.character_size.width as i32;
+        let x = (self.ctx.display_width - text_width) / 2;
+        let y = self
+            .ctx
+            .calculate_centered_text_position(font.character_size.height as i32);
+
+        let [r, g, b] = self.ctx.apply_brightness(color);
+        let style = MonoTextStyle::new(font, Rgb888::new(r, g, b));
+        let mut eg_canvas = EmbeddedGraphicsCanvas::new(canvas);
+        let _ = Text::new(&text, Point::new(x, y), style).draw(&mut eg_canvas);
+    }
+
+    fn is_complete(&self) -> bool {
+        if let Some(duration) = self.duration {
+            return Instant::now().duration_since(self.start_time).as_secs() >= duration;
+        }
+        false
+    }
+
+    fn reset(&mut self) {
+        self.start_time = Instant::now();
+    }
+
+    fn update_context(&mut self, ctx: RenderContext) {
+        self.ctx = ctx;
+    }
+
+    fn update_content(&mut self, content: &PlayListItem) {
+        if let ContentDetails::Countdown(countdown) = &content.content.data {
+            self.content = countdown.clone();
+            self.duration = content.duration;
+            self.start_time = Instant::now();
+            self.parse_target();
+            self.last_milestone = self.milestone();
+        } else {
+            warn!("CountdownRenderer received non-countdown content during update");
+        }
+    }
+}
+
+impl CountdownRenderer {
+    fn remaining_seconds(&self) -> Option<u64> {
+        let target = self.target?;
+        Some(round_up_seconds(
+            (target.with_timezone(&Utc) - timezone::utc_now()).num_milliseconds(),
+        ))
+    }
+
+    /// `remaining_seconds` of the active threshold, 0 once the target has passed
+    fn milestone(&self) -> Option<u64> {
+        let remaining = self.remaining_seconds()?;
+        if remaining == 0 {
+            return Some(0);
+        }
+        self.content
+            .active_threshold(remaining)
+            .map(|threshold| threshold.remaining_seconds)
+    }
+
+    fn parse_target(&mut self) {
+        self.target = match self.content.target_time() {
+            Ok(target) => Some(target),
+            Err(e) => {
+                warn!("{}", e);
+                None
+            }
+        };
+    }
+}
+
+/// Seconds left, rounded up so the display only reads zero once the target is reached
+fn round_up_seconds(remaining_ms: i64) -> u64 {
+    (remaining_ms.max(0) as u64).div_ceil(1000)
+}
+
+/// Format the time left as "MM:SS", "H:MM:SS" or "Dd HH:MM" depending on its size,
+/// with the day suffix of the configured locale
+fn format_remaining(seconds: u64) -> String {
+    let days = seconds / 86_400;
+    let hours = seconds % 86_400 / 3_600;
+    let minutes = seconds % 3_600 / 60;
+    let secs = seconds % 60;
+
+    if days > 0 {
+        format!(
+            "{}{} {:02}:{:02}",
+            days,
+            locale::strings().days_suffix,
+            hours,
+            minutes
+        )
+    } else if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, secs)
+    } else {
+        format!("{:02}:{:02}", minutes, secs)
+    }
+}
@@ -0,0 +1,105 @@
+use crate::display::driver::LedCanvas;
+use crate::display::graphics::embedded_graphics_support::EmbeddedGraphicsCanvas;
+use crate::display::renderer::{RenderContext, Renderer};
+use crate::models::content::{ContentDetails, ContentType};
+use crate::models::playlist::PlayListItem;
+use crate::utils::locale;
+use embedded_graphics::draw_target::DrawTargetExt;
+use embedded_graphics::geometry::{Point, Size};
+use embedded_graphics::mono_font::iso_8859_1::{FONT_5X8, FONT_6X10};
+use embedded_graphics::mono_font::MonoTextStyle;
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::Text;
+use embedded_graphics::Drawable;
+use std::time::Instant;
+
+// How long the placeholder stays up before the playlist moves on
+pub const PLACEHOLDER_SECONDS: u64 = 5;
+
+const BORDER_COLOR: [u8; 3] = [255, 0, 0];
+const LABEL_COLOR: [u8; 3] = [255, 255, 255];
+
+/// Placeholder shown instead of an item whose renderer failed at runtime:
+/// a red frame with the item's translated content type (or plugin name) in the middle
+pub struct ErrorRenderer {
+    label: String,
+    ctx: RenderContext,
+    start_time: Instant,
+}
+
+impl Renderer for ErrorRenderer {
+    fn new(content: &PlayListItem, ctx: RenderContext) -> Self {
+        Self {
+            label: label(content),
+            ctx,
+            start_time: Instant::now(),
+        }
+    }
+
+    fn update(&mut self, _dt: f32) {}
+
+    fn render(&self, canvas: &mut Box<dyn LedCanvas>) {
+        let width = self.ctx.display_width;
+        let height = self.ctx.display_height;
+
+        let [r, g, b] = self.ctx.apply_brightness(BORDER_COLOR);
+        for x in 0..width {
+            canvas.set_pixel(x as usize, 0, r, g, b);
+            canvas.set_pixel(x as usize, (height - 1).max(0) as usize, r, g, b);
+        }
+        for y in 0..height {
+            canvas.set_pixel(0, y as usize, r, g, b);
+            canvas.set_pixel((width - 1).max(0) as usize, y as usize, r, g, b);
+        }
+
+        // Fall back to the small font when the label does not fit inside the frame
+        let inner_width = width - 4;
+        let chars = self.label.chars().count() as i32;
+        let (font, char_width, font_height) = if chars * 6 <= inner_width {
+            (&FONT_6X10, 6, 10)
+        } else {
+            (&FONT_5X8, 5, 8)
+        };
+
+        let [r, g, b] = self.ctx.apply_brightness(LABEL_COLOR);
+        let style = MonoTextStyle::new(font, Rgb888::new(r, g, b));
+        let x = 2 + ((inner_width - chars * char_width) / 2).max(0);
+        let baseline = (height - font_height) / 2 + font_height - 2;
+
+        let mut eg_canvas = EmbeddedGraphicsCanvas::new(canvas);
+        let mut clipped = eg_canvas.clipped(&Rectangle::new(
+            Point::new(2, 2),
+            Size::new(inner_width.max(0) as u32, (height - 4).max(0) as u32),
+        ));
+        let _ = Text::new(&self.label, Point::new(x, baseline), style).draw(&mut clipped);
+    }
+
+    fn is_complete(&self) -> bool {
+        self.start_time.elapsed().as_secs() >= PLACEHOLDER_SECONDS
+    }
+
+    fn reset(&mut self) {
+        self.start_time = Instant::now();
+    }
+
+    fn update_context(&mut self, ctx: RenderContext) {
+        self.ctx = ctx;
+    }
+
+    fn update_content(&mut self, content: &PlayListItem) {
+        self.label = label(content);
+    }
+}
+
+// Plugins are shown by their own name, built-in content types by their translated name
+fn label(content: &PlayListItem) -> String {
+    match &content.content.data {
+        ContentDetails::Plugin(plugin) if !ContentType::is_builtin(&plugin.plugin) => {
+            plugin.plugin.clone()
+        }
+        _ => locale::strings()
+            .content_type(&content.content.content_type)
+            .to_string(),
+    }
+}
@@ -1,5 +1,7 @@
+use image::imageops::FilterType;
+use image::RgbImage;
 use log::{debug, error, warn};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::display::driver::LedCanvas;
 use crate::display::renderer::{RenderContext, Renderer};
@@ -88,6 +90,12 @@ impl Renderer for ImageRenderer {
         }
     }
 
+    fn error(&self) -> Option<String> {
+        self.decoded
+            .is_none()
+            .then(|| format!("Image {} could not be loaded", self.content.image_id))
+    }
+
     fn update(&mut self, dt: f32) {
         if self.decoded.is_none() {
             self.is_complete = true;
@@ -203,6 +211,27 @@ impl Renderer for ImageRenderer {
             self.reset();
         }
     }
+
+    fn completed_cycles(&self) -> Option<u32> {
+        // Only animated images complete cycles
+        self.content
+            .animation
+            .as_ref()
+            .map(|_| self.completed_iterations)
+    }
+
+    fn progress(&self) -> Option<f32> {
+        if self.is_complete {
+            return Some(1.0);
+        }
+        if let Some(duration) = self.duration_seconds {
+            return Some((self.elapsed_seconds / duration.max(1) as f32).min(1.0));
+        }
+        let max_iterations = self.max_iterations?;
+        let cycle_length = animation_length_ms(self.content.animation.as_ref()?).max(1) as f32;
+        let cycles = self.completed_iterations as f32 + self.animation_elapsed_ms / cycle_length;
+        Some((cycles / max_iterations as f32).min(1.0))
+    }
 }
 
 impl ImageRenderer {
@@ -267,12 +296,33 @@ fn animation_length_ms(animation: &ImageAnimation) -> u32 {
         .unwrap_or(0)
 }
 
-fn load_image(image_id: &str) -> Option<DecodedImage> {
+/// Where the uploaded image with the given ID is stored
+pub(super) fn image_path(image_id: &str) -> PathBuf {
     let base_dir = std::env::var("LED_STORAGE_DIR").unwrap_or_else(|_| DEFAULT_DIR.to_string());
-    let path = Path::new(&base_dir)
+    Path::new(&base_dir)
         .join(paths::IMAGES_DIR)
-        .join(format!("{}.png", image_id));
+        .join(format!("{}.png", image_id))
+}
 
+/// Scale an image to fit a panel of the given size without changing its aspect ratio,
+/// centered on black. Returns one color per panel pixel.
+pub(super) fn fit_to_panel(image: &RgbImage, width: u32, height: u32) -> Vec<[u8; 3]> {
+    let scale = (width as f32 / image.width() as f32).min(height as f32 / image.height() as f32);
+    let scaled_width = ((image.width() as f32 * scale).round() as u32).clamp(1, width);
+    let scaled_height = ((image.height() as f32 * scale).round() as u32).clamp(1, height);
+    let scaled = image::imageops::resize(image, scaled_width, scaled_height, FilterType::Triangle);
+
+    let offset_x = (width - scaled_width) / 2;
+    let offset_y = (height - scaled_height) / 2;
+    let mut pixels = vec![[0, 0, 0]; (width * height) as usize];
+    for (x, y, pixel) in scaled.enumerate_pixels() {
+        pixels[((y + offset_y) * width + x + offset_x) as usize] = pixel.0;
+    }
+    pixels
+}
+
+fn load_image(image_id: &str) -> Option<DecodedImage> {
+    let path = image_path(image_id);
     match image::open(&path) {
         Ok(dynamic) => {
             let rgb = dynamic.to_rgb8();
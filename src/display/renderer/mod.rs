@@ -1,20 +1,45 @@
 mod animation;
+mod barcode;
+mod big_number;
 mod border;
 mod clock;
+mod command;
 mod context;
+mod countdown;
+mod error;
 mod image;
+#[cfg(feature = "dynamic-plugins")]
+pub mod plugin;
+mod qrcode;
+pub mod registry;
+mod rng;
+mod slideshow;
+mod stream;
 mod text;
+mod video;
+#[cfg(feature = "wasm-plugins")]
+pub mod wasm;
 
 pub use animation::AnimationRenderer;
+pub use barcode::BarcodeRenderer;
+pub use big_number::BigNumberRenderer;
 pub use border::BorderRenderer;
 pub use clock::ClockRenderer;
-pub use context::RenderContext;
+pub use command::CommandRenderer;
+pub use context::{BorderLink, ItemTiming, RenderContext};
+pub use countdown::CountdownRenderer;
+pub use error::ErrorRenderer;
 pub use image::ImageRenderer;
+pub use qrcode::QrCodeRenderer;
+pub use rng::EffectRng;
+pub use slideshow::SlideshowRenderer;
+pub use stream::StreamRenderer;
 pub use text::TextRenderer;
+pub use video::VideoRenderer;
 
 use crate::display::driver::LedCanvas;
-use crate::models::content::{ContentDetails, ContentType};
 use crate::models::playlist::PlayListItem;
+use crate::utils::palettes;
 
 /// Core Renderer trait that all content-specific renderers must implement
 pub trait Renderer: Send + Sync {
@@ -42,35 +67,52 @@ pub trait Renderer: Send + Sync {
 
     /// Update the renderer's content without fully resetting animation state
     fn update_content(&mut self, content: &PlayListItem);
+
+    /// Number of repeat cycles completed so far, for renderers that count them
+    fn completed_cycles(&self) -> Option<u32> {
+        None
+    }
+
+    /// Fraction (0-1) of the item's display time completed, for progress bars in the UI.
+    /// None when the renderer cannot tell, e.g. for content that repeats forever.
+    fn progress(&self) -> Option<f32> {
+        None
+    }
+
+    /// Runtime failure that keeps the renderer from showing its content, e.g. a missing image.
+    /// The display manager shows an error placeholder instead while this returns Some.
+    fn error(&self) -> Option<String> {
+        None
+    }
 }
 
-/// Factory function to create the appropriate content renderer based on content type
+/// Factory function to create the appropriate content renderer based on content type.
+/// Renderers are looked up in the registry, so plugins can add content types at runtime.
+/// Named palettes are replaced with their colors first.
 pub fn create_renderer(content: &PlayListItem, ctx: RenderContext) -> Box<dyn Renderer> {
-    match content.content.content_type {
-        ContentType::Text => match &content.content.data {
-            ContentDetails::Text(_) => Box::new(TextRenderer::new(content, ctx)),
-            #[allow(unreachable_patterns)]
-            _ => panic!("Content type mismatch: expected Text content details"),
-        },
-        ContentType::Image => match &content.content.data {
-            ContentDetails::Image(_) => Box::new(ImageRenderer::new(content, ctx)),
-            #[allow(unreachable_patterns)]
-            _ => panic!("Content type mismatch: expected Image content details"),
-        },
-        ContentType::Animation => match &content.content.data {
-            ContentDetails::Animation(_) => Box::new(AnimationRenderer::new(content, ctx)),
-            #[allow(unreachable_patterns)]
-            _ => panic!("Content type mismatch: expected Animation content details"),
-        },
-        ContentType::Clock => match &content.content.data {
-            ContentDetails::Clock(_) => Box::new(ClockRenderer::new(content, ctx)),
-            #[allow(unreachable_patterns)]
-            _ => panic!("Content type mismatch: expected Clock content details"),
-        },
-    }
+    registry::create_registered_renderer(&palettes::resolve(content), ctx)
 }
 
 /// Create a border renderer for the given content
 pub fn create_border_renderer(content: &PlayListItem, ctx: RenderContext) -> Box<dyn Renderer> {
-    Box::new(BorderRenderer::new(content, ctx))
+    Box::new(BorderRenderer::new(&palettes::resolve(content), ctx))
+}
+
+// Amber marker drawn by clock and countdown content while the system time is not synchronized
+const TIME_UNSYNCED_COLOR: [u8; 3] = [255, 140, 0];
+
+/// Blink a small marker in the top-right corner if the system clock has not been synchronized,
+/// so a wrong time on the panel is recognizable as such
+pub fn draw_time_sync_indicator(canvas: &mut Box<dyn LedCanvas>, ctx: &RenderContext) {
+    if crate::utils::time_sync::is_synced()
+        || crate::utils::timezone::utc_now().timestamp_subsec_millis() >= 500
+    {
+        return;
+    }
+    let [r, g, b] = ctx.apply_brightness(TIME_UNSYNCED_COLOR);
+    for y in 0..2 {
+        for x in (ctx.display_width - 2).max(0)..ctx.display_width {
+            canvas.set_pixel(x as usize, y, r, g, b);
+        }
+    }
 }
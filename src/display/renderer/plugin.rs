@@ -0,0 +1,305 @@
+//! Dynamic library renderer plugins (enabled with the `dynamic-plugins` feature).
+//!
+//! A plugin is a shared library exporting the following C ABI:
+//!
+//! ```c
+//! const char *led_sign_plugin_name(void);
+//! void *led_sign_plugin_create(const char *config_json, int32_t width, int32_t height);
+//! void led_sign_plugin_update(void *instance, float dt);
+//! void led_sign_plugin_render(void *instance, uint8_t *rgb, size_t len);
+//! bool led_sign_plugin_is_complete(void *instance);
+//! void led_sign_plugin_destroy(void *instance);
+//! ```
+//!
+//! `render` fills a `width * height * 3` RGB buffer which is copied to the canvas with
+//! the user brightness applied. Playlist items use `Plugin` content with `plugin` set to
+//! the name returned by `led_sign_plugin_name`.
+
+use crate::display::driver::LedCanvas;
+use crate::display::renderer::registry::register_renderer_type;
+use crate::display::renderer::{RenderContext, Renderer};
+use crate::models::content::{ContentDetails, ContentType};
+use crate::models::playlist::PlayListItem;
+use libloading::Library;
+use log::{error, info, warn};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::ffi::{c_char, c_void, CStr, CString};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+type NameFn = unsafe extern "C" fn() -> *const c_char;
+type CreateFn = unsafe extern "C" fn(*const c_char, i32, i32) -> *mut c_void;
+type UpdateFn = unsafe extern "C" fn(*mut c_void, f32);
+type RenderFn = unsafe extern "C" fn(*mut c_void, *mut u8, usize);
+type IsCompleteFn = unsafe extern "C" fn(*mut c_void) -> bool;
+type DestroyFn = unsafe extern "C" fn(*mut c_void);
+
+/// A loaded plugin library together with its resolved entry points
+struct PluginLibrary {
+    name: String,
+    create: CreateFn,
+    update: UpdateFn,
+    render: RenderFn,
+    is_complete: IsCompleteFn,
+    destroy: DestroyFn,
+    // Keeps the function pointers above valid; must be dropped last
+    _library: Library,
+}
+
+impl PluginLibrary {
+    fn load(path: &Path) -> Result<Self, String> {
+        // Safety: loading a library runs its initialisers; plugins are trusted by the admin
+        let library = unsafe { Library::new(path) }.map_err(|e| e.to_string())?;
+
+        unsafe {
+            let name_fn = *library
+                .get::<NameFn>(b"led_sign_plugin_name\0")
+                .map_err(|e| e.to_string())?;
+            let create = *library
+                .get::<CreateFn>(b"led_sign_plugin_create\0")
+                .map_err(|e| e.to_string())?;
+            let update = *library
+                .get::<UpdateFn>(b"led_sign_plugin_update\0")
+                .map_err(|e| e.to_string())?;
+            let render = *library
+                .get::<RenderFn>(b"led_sign_plugin_render\0")
+                .map_err(|e| e.to_string())?;
+            let is_complete = *library
+                .get::<IsCompleteFn>(b"led_sign_plugin_is_complete\0")
+                .map_err(|e| e.to_string())?;
+            let destroy = *library
+                .get::<DestroyFn>(b"led_sign_plugin_destroy\0")
+                .map_err(|e| e.to_string())?;
+
+            let name_ptr = name_fn();
+            if name_ptr.is_null() {
+                return Err("plugin returned a null name".to_string());
+            }
+            let name = CStr::from_ptr(name_ptr).to_string_lossy().into_owned();
+            if name.trim().is_empty() {
+                return Err("plugin returned an empty name".to_string());
+            }
+
+            Ok(Self {
+                name,
+                create,
+                update,
+                render,
+                is_complete,
+                destroy,
+                _library: library,
+            })
+        }
+    }
+}
+
+/// Libraries loaded so far, keyed by the content type string they registered
+static LOADED_PLUGINS: Lazy<Mutex<HashMap<String, Arc<PluginLibrary>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Renderer backed by an instance created by a plugin library
+pub struct DynamicPluginRenderer {
+    library: Arc<PluginLibrary>,
+    instance: *mut c_void,
+    ctx: RenderContext,
+    config_json: String,
+    duration: Option<u64>,
+    start_time: Instant,
+}
+
+// The plugin instance is only ever touched while the display manager lock is held
+unsafe impl Send for DynamicPluginRenderer {}
+unsafe impl Sync for DynamicPluginRenderer {}
+
+impl DynamicPluginRenderer {
+    fn create_instance(
+        library: &PluginLibrary,
+        config_json: &str,
+        ctx: &RenderContext,
+    ) -> *mut c_void {
+        let config = CString::new(config_json).unwrap_or_default();
+        let instance =
+            unsafe { (library.create)(config.as_ptr(), ctx.display_width, ctx.display_height) };
+        if instance.is_null() {
+            error!("Plugin '{}' failed to create an instance", library.name);
+        }
+        instance
+    }
+
+    fn recreate_instance(&mut self) {
+        self.destroy_instance();
+        self.instance = Self::create_instance(&self.library, &self.config_json, &self.ctx);
+    }
+
+    fn destroy_instance(&mut self) {
+        if !self.instance.is_null() {
+            unsafe { (self.library.destroy)(self.instance) };
+            self.instance = std::ptr::null_mut();
+        }
+    }
+}
+
+impl Drop for DynamicPluginRenderer {
+    fn drop(&mut self) {
+        self.destroy_instance();
+    }
+}
+
+impl Renderer for DynamicPluginRenderer {
+    fn new(content: &PlayListItem, ctx: RenderContext) -> Self {
+        let name = match &content.content.data {
+            ContentDetails::Plugin(plugin_content) => plugin_content.plugin.clone(),
+            #[allow(unreachable_patterns)]
+            _ => panic!("Expected plugin content"),
+        };
+        let library = LOADED_PLUGINS
+            .lock()
+            .ok()
+            .and_then(|plugins| plugins.get(&name).cloned())
+            .unwrap_or_else(|| panic!("Plugin '{}' is registered but not loaded", name));
+
+        let config_json = plugin_config_json(content);
+        let instance = Self::create_instance(&library, &config_json, &ctx);
+
+        Self {
+            library,
+            instance,
+            ctx,
+            config_json,
+            duration: content.duration,
+            start_time: Instant::now(),
+        }
+    }
+
+    fn update(&mut self, dt: f32) {
+        if !self.instance.is_null() {
+            unsafe { (self.library.update)(self.instance, dt) };
+        }
+    }
+
+    fn render(&self, canvas: &mut Box<dyn LedCanvas>) {
+        if self.instance.is_null() {
+            return;
+        }
+
+        let width = self.ctx.display_width.max(0) as usize;
+        let height = self.ctx.display_height.max(0) as usize;
+        let mut buffer = vec![0u8; width * height * 3];
+        unsafe { (self.library.render)(self.instance, buffer.as_mut_ptr(), buffer.len()) };
+
+        for y in 0..height {
+            for x in 0..width {
+                let idx = (y * width + x) * 3;
+                let [r, g, b] =
+                    self.ctx
+                        .apply_brightness([buffer[idx], buffer[idx + 1], buffer[idx + 2]]);
+                canvas.set_pixel(x, y, r, g, b);
+            }
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        if let Some(duration) = self.duration {
+            if self.start_time.elapsed().as_secs() >= duration {
+                return true;
+            }
+        }
+        !self.instance.is_null() && unsafe { (self.library.is_complete)(self.instance) }
+    }
+
+    fn reset(&mut self) {
+        self.start_time = Instant::now();
+        self.recreate_instance();
+    }
+
+    fn update_context(&mut self, ctx: RenderContext) {
+        let size_changed = ctx.display_width != self.ctx.display_width
+            || ctx.display_height != self.ctx.display_height;
+        self.ctx = ctx;
+        if size_changed {
+            self.recreate_instance();
+        }
+    }
+
+    fn update_content(&mut self, content: &PlayListItem) {
+        self.duration = content.duration;
+        let config_json = plugin_config_json(content);
+        if config_json != self.config_json {
+            self.config_json = config_json;
+            self.recreate_instance();
+        }
+    }
+
+    fn error(&self) -> Option<String> {
+        self.instance.is_null().then(|| {
+            format!(
+                "Plugin '{}' failed to create an instance",
+                self.library.name
+            )
+        })
+    }
+}
+
+fn plugin_config_json(content: &PlayListItem) -> String {
+    match &content.content.data {
+        ContentDetails::Plugin(plugin_content) => plugin_content.config.to_string(),
+        _ => "null".to_string(),
+    }
+}
+
+/// Load a single plugin library and register its renderer
+pub fn load_plugin(path: &Path) -> Result<String, String> {
+    let library = Arc::new(PluginLibrary::load(path)?);
+    let name = library.name.clone();
+    if ContentType::is_builtin(&name) {
+        return Err(format!(
+            "Plugin name '{}' is reserved for a built-in content type",
+            name
+        ));
+    }
+
+    LOADED_PLUGINS
+        .lock()
+        .map_err(|e| e.to_string())?
+        .insert(name.clone(), library);
+    register_renderer_type::<DynamicPluginRenderer>(&name);
+
+    Ok(name)
+}
+
+/// Load every shared library in `dir` as a renderer plugin. Returns the number loaded.
+pub fn load_plugins_from_dir(dir: &Path) -> usize {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) => {
+            warn!("Could not read plugin directory {:?}: {}", dir, err);
+            return 0;
+        }
+    };
+
+    let mut loaded = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_library = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext == std::env::consts::DLL_EXTENSION)
+            .unwrap_or(false);
+
+        if !path.is_file() || !is_library {
+            continue;
+        }
+
+        match load_plugin(&path) {
+            Ok(name) => {
+                info!("Loaded renderer plugin '{}' from {:?}", name, path);
+                loaded += 1;
+            }
+            Err(err) => error!("Failed to load renderer plugin {:?}: {}", path, err),
+        }
+    }
+
+    loaded
+}
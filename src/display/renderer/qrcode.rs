@@ -0,0 +1,263 @@
+use crate::display::driver::LedCanvas;
+use crate::display::graphics::embedded_graphics_support::EmbeddedGraphicsCanvas;
+use crate::display::renderer::{RenderContext, Renderer};
+use crate::models::content::ContentDetails;
+use crate::models::playlist::PlayListItem;
+use crate::models::qrcode::QrCodeContent;
+use embedded_graphics::draw_target::DrawTargetExt;
+use embedded_graphics::geometry::{Point, Size};
+use embedded_graphics::mono_font::iso_8859_1::FONT_6X10 as FONT_6X10_LATIN1;
+use embedded_graphics::mono_font::MonoTextStyle;
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::primitives::Rectangle;
+use embedded_graphics::text::Text;
+use embedded_graphics::Drawable;
+use log::warn;
+use std::time::Instant;
+
+// Caption scroll speed in pixels per second when it does not fit its zone
+const CAPTION_SCROLL_SPEED: f32 = 20.0;
+// Gap between the end of a scrolling caption and its next repetition
+const CAPTION_GAP: i32 = 12;
+// Space between the code and the caption
+const CAPTION_MARGIN: i32 = 2;
+
+/// Position and size of the code on the panel
+struct QrLayout {
+    x: i32,
+    y: i32,
+    scale: i32,
+    quiet_zone: i32,
+}
+
+pub struct QrCodeRenderer {
+    content: QrCodeContent,
+    ctx: RenderContext,
+    duration: Option<u64>,
+    start_time: Instant,
+    modules: Vec<bool>,
+    modules_width: usize,
+    layout: Option<QrLayout>,
+    caption_offset: f32,
+}
+
+impl Renderer for QrCodeRenderer {
+    fn new(content: &PlayListItem, ctx: RenderContext) -> Self {
+        let qr_content = match &content.content.data {
+            ContentDetails::QrCode(qr) => qr.clone(),
+            #[allow(unreachable_patterns)]
+            _ => panic!("Expected QR code content"),
+        };
+
+        let mut renderer = Self {
+            content: qr_content,
+            ctx,
+            duration: content.duration,
+            start_time: Instant::now(),
+            modules: Vec::new(),
+            modules_width: 0,
+            layout: None,
+            caption_offset: 0.0,
+        };
+        renderer.encode();
+        renderer
+    }
+
+    fn update(&mut self, dt: f32) {
+        let overflow = self.caption_width() - self.caption_zone_width();
+        if overflow > 0 {
+            let cycle = (self.caption_width() + CAPTION_GAP) as f32;
+            self.caption_offset = (self.caption_offset + dt * CAPTION_SCROLL_SPEED) % cycle;
+        } else {
+            self.caption_offset = 0.0;
+        }
+    }
+
+    fn render(&self, canvas: &mut Box<dyn LedCanvas>) {
+        let Some(layout) = &self.layout else {
+            return;
+        };
+
+        let [fr, fg, fb] = self.ctx.apply_brightness(self.content.foreground);
+        let [br, bg, bb] = self.ctx.apply_brightness(self.content.background);
+
+        // Quiet zone and light modules
+        let total = (self.modules_width as i32 + 2 * layout.quiet_zone) * layout.scale;
+        for y in layout.y..layout.y + total {
+            for x in layout.x..layout.x + total {
+                self.set_pixel(canvas, x, y, br, bg, bb);
+            }
+        }
+
+        // Dark modules
+        let origin_x = layout.x + layout.quiet_zone * layout.scale;
+        let origin_y = layout.y + layout.quiet_zone * layout.scale;
+        for (index, dark) in self.modules.iter().enumerate() {
+            if !dark {
+                continue;
+            }
+            let module_x = (index % self.modules_width) as i32;
+            let module_y = (index / self.modules_width) as i32;
+            for dy in 0..layout.scale {
+                for dx in 0..layout.scale {
+                    self.set_pixel(
+                        canvas,
+                        origin_x + module_x * layout.scale + dx,
+                        origin_y + module_y * layout.scale + dy,
+                        fr,
+                        fg,
+                        fb,
+                    );
+                }
+            }
+        }
+
+        self.render_caption(canvas, layout.x + total + CAPTION_MARGIN);
+    }
+
+    fn is_complete(&self) -> bool {
+        if let Some(duration) = self.duration {
+            return Instant::now().duration_since(self.start_time).as_secs() >= duration;
+        }
+        false
+    }
+
+    fn reset(&mut self) {
+        self.start_time = Instant::now();
+        self.caption_offset = 0.0;
+    }
+
+    fn update_context(&mut self, ctx: RenderContext) {
+        let resized = ctx.display_width != self.ctx.display_width
+            || ctx.display_height != self.ctx.display_height;
+        self.ctx = ctx;
+        if resized {
+            self.layout = self.compute_layout();
+        }
+    }
+
+    fn update_content(&mut self, content: &PlayListItem) {
+        if let ContentDetails::QrCode(qr) = &content.content.data {
+            self.content = qr.clone();
+            self.duration = content.duration;
+            self.start_time = Instant::now();
+            self.caption_offset = 0.0;
+            self.encode();
+        } else {
+            warn!("QrCodeRenderer received non-QR content during update");
+        }
+    }
+}
+
+impl QrCodeRenderer {
+    fn encode(&mut self) {
+        match self.content.encode() {
+            Ok((modules, width)) => {
+                self.modules = modules;
+                self.modules_width = width;
+            }
+            Err(e) => {
+                warn!("{}", e);
+                self.modules.clear();
+                self.modules_width = 0;
+            }
+        }
+        self.layout = self.compute_layout();
+    }
+
+    /// Scale the code to the panel height, shrinking the quiet zone before giving up
+    fn compute_layout(&self) -> Option<QrLayout> {
+        if self.modules_width == 0 {
+            return None;
+        }
+
+        let available = self.ctx.display_height.min(self.ctx.display_width);
+        let preferred = self.content.quiet_zone.max(1) as i32;
+
+        for quiet_zone in (1..=preferred).rev() {
+            let span = self.modules_width as i32 + 2 * quiet_zone;
+            let scale = available / span;
+            if scale >= 1 {
+                let size = span * scale;
+                let x = if self.content.caption.is_some() {
+                    0
+                } else {
+                    (self.ctx.display_width - size) / 2
+                };
+                return Some(QrLayout {
+                    x,
+                    y: (self.ctx.display_height - size) / 2,
+                    scale,
+                    quiet_zone,
+                });
+            }
+        }
+
+        warn!(
+            "QR code for '{}' needs {} modules but the panel is only {} pixels high; use less data or a lower error correction level",
+            self.content.data,
+            self.modules_width + 2,
+            self.ctx.display_height
+        );
+        None
+    }
+
+    fn caption_width(&self) -> i32 {
+        self.content
+            .caption
+            .as_ref()
+            .map_or(0, |caption| caption.chars().count() as i32 * 6)
+    }
+
+    fn caption_zone_width(&self) -> i32 {
+        match &self.layout {
+            Some(layout) => {
+                let total = (self.modules_width as i32 + 2 * layout.quiet_zone) * layout.scale;
+                self.ctx.display_width - layout.x - total - CAPTION_MARGIN
+            }
+            None => 0,
+        }
+    }
+
+    /// Draw the caption in the zone right of the code, scrolling it when it is too wide
+    fn render_caption(&self, canvas: &mut Box<dyn LedCanvas>, zone_x: i32) {
+        let Some(caption) = &self.content.caption else {
+            return;
+        };
+        let zone_width = self.ctx.display_width - zone_x;
+        if zone_width <= 0 {
+            return;
+        }
+
+        let [r, g, b] = self.ctx.apply_brightness(self.content.caption_color);
+        let style = MonoTextStyle::new(&FONT_6X10_LATIN1, Rgb888::new(r, g, b));
+        let baseline = self.ctx.display_height / 2 + 3;
+
+        let mut eg_canvas = EmbeddedGraphicsCanvas::new(canvas);
+        let mut clipped = eg_canvas.clipped(&Rectangle::new(
+            Point::new(zone_x, 0),
+            Size::new(zone_width as u32, self.ctx.display_height as u32),
+        ));
+
+        let text_width = self.caption_width();
+        if text_width <= zone_width {
+            let x = zone_x + (zone_width - text_width) / 2;
+            let _ = Text::new(caption, Point::new(x, baseline), style).draw(&mut clipped);
+        } else {
+            let x = zone_x - self.caption_offset as i32;
+            let _ = Text::new(caption, Point::new(x, baseline), style).draw(&mut clipped);
+            let _ = Text::new(
+                caption,
+                Point::new(x + text_width + CAPTION_GAP, baseline),
+                style,
+            )
+            .draw(&mut clipped);
+        }
+    }
+
+    fn set_pixel(&self, canvas: &mut Box<dyn LedCanvas>, x: i32, y: i32, r: u8, g: u8, b: u8) {
+        if x >= 0 && y >= 0 && x < self.ctx.display_width && y < self.ctx.display_height {
+            canvas.set_pixel(x as usize, y as usize, r, g, b);
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use crate::display::driver::LedCanvas;
+use crate::display::renderer::{
+    AnimationRenderer, BarcodeRenderer, BigNumberRenderer, ClockRenderer, CommandRenderer,
+    CountdownRenderer, ImageRenderer, QrCodeRenderer, RenderContext, Renderer, SlideshowRenderer,
+    StreamRenderer, TextRenderer, VideoRenderer,
+};
+use crate::models::content::{ContentDetails, ContentType};
+use crate::models::playlist::PlayListItem;
+use log::{debug, error, warn};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+
+/// Constructor registered for a content type string
+pub type RendererFactory =
+    Arc<dyn Fn(&PlayListItem, RenderContext) -> Box<dyn Renderer> + Send + Sync>;
+
+/// Maps content type strings to the factories that build their renderers
+pub struct RendererRegistry {
+    factories: HashMap<String, RendererFactory>,
+}
+
+impl RendererRegistry {
+    /// Create a registry containing only the built-in renderers
+    pub fn with_builtin_renderers() -> Self {
+        let mut registry = Self {
+            factories: HashMap::new(),
+        };
+
+        registry.register_type::<TextRenderer>("Text");
+        registry.register_type::<ImageRenderer>("Image");
+        registry.register_type::<AnimationRenderer>("Animation");
+        registry.register_type::<ClockRenderer>("Clock");
+        registry.register_type::<QrCodeRenderer>("QrCode");
+        registry.register_type::<BarcodeRenderer>("Barcode");
+        registry.register_type::<BigNumberRenderer>("BigNumber");
+        registry.register_type::<CountdownRenderer>("Countdown");
+        registry.register_type::<CommandRenderer>("Command");
+        registry.register_type::<SlideshowRenderer>("Slideshow");
+        registry.register_type::<VideoRenderer>("Video");
+        registry.register_type::<StreamRenderer>("Stream");
+
+        registry
+    }
+
+    /// Register a factory for a content type, replacing any previous registration
+    pub fn register(&mut self, content_type: &str, factory: RendererFactory) {
+        if self
+            .factories
+            .insert(content_type.to_string(), factory)
+            .is_some()
+        {
+            warn!("Replaced renderer registered for '{}'", content_type);
+        } else {
+            debug!("Registered renderer for '{}'", content_type);
+        }
+    }
+
+    /// Register a renderer type using its `Renderer::new` constructor
+    pub fn register_type<R>(&mut self, content_type: &str)
+    where
+        R: Renderer + 'static,
+    {
+        self.register(
+            content_type,
+            Arc::new(|content: &PlayListItem, ctx: RenderContext| {
+                Box::new(R::new(content, ctx)) as Box<dyn Renderer>
+            }),
+        );
+    }
+
+    /// Remove the factory for a content type. Returns whether one was registered.
+    pub fn unregister(&mut self, content_type: &str) -> bool {
+        let removed = self.factories.remove(content_type).is_some();
+        if removed {
+            debug!("Unregistered renderer for '{}'", content_type);
+        }
+        removed
+    }
+
+    pub fn get(&self, content_type: &str) -> Option<RendererFactory> {
+        self.factories.get(content_type).cloned()
+    }
+
+    pub fn is_registered(&self, content_type: &str) -> bool {
+        self.factories.contains_key(content_type)
+    }
+
+    /// Sorted list of all registered content type strings
+    pub fn content_types(&self) -> Vec<String> {
+        let mut types: Vec<String> = self.factories.keys().cloned().collect();
+        types.sort();
+        types
+    }
+}
+
+static REGISTRY: Lazy<RwLock<RendererRegistry>> =
+    Lazy::new(|| RwLock::new(RendererRegistry::with_builtin_renderers()));
+
+/// Register a renderer type in the global registry using its `Renderer::new` constructor
+#[cfg_attr(
+    not(any(feature = "dynamic-plugins", feature = "wasm-plugins")),
+    allow(dead_code)
+)]
+pub fn register_renderer_type<R>(content_type: &str)
+where
+    R: Renderer + 'static,
+{
+    match REGISTRY.write() {
+        Ok(mut registry) => registry.register_type::<R>(content_type),
+        Err(err) => error!("Renderer registry lock poisoned: {}", err),
+    }
+}
+
+/// Remove a renderer from the global registry. Returns whether one was registered.
+#[cfg_attr(not(feature = "wasm-plugins"), allow(dead_code))]
+pub fn unregister_renderer(content_type: &str) -> bool {
+    match REGISTRY.write() {
+        Ok(mut registry) => registry.unregister(content_type),
+        Err(err) => {
+            error!("Renderer registry lock poisoned: {}", err);
+            false
+        }
+    }
+}
+
+/// Check whether a renderer is available for the given content type string
+pub fn is_renderer_registered(content_type: &str) -> bool {
+    REGISTRY
+        .read()
+        .map(|registry| registry.is_registered(content_type))
+        .unwrap_or(false)
+}
+
+/// Check whether a plugin with this name is loaded. Built-in content types are not plugins,
+/// a Plugin item naming one would be drawn by a renderer expecting other content.
+pub fn is_plugin_registered(name: &str) -> bool {
+    !ContentType::is_builtin(name) && is_renderer_registered(name)
+}
+
+/// List all content type strings that currently have a renderer
+pub fn registered_content_types() -> Vec<String> {
+    REGISTRY
+        .read()
+        .map(|registry| registry.content_types())
+        .unwrap_or_default()
+}
+
+/// Content type string used to look up the renderer for an item.
+/// Plugin content is keyed by the plugin name, everything else by its content type. Plugin
+/// content naming a built-in type, e.g. from an old export, finds no renderer.
+pub fn renderer_key(content: &PlayListItem) -> &str {
+    match &content.content.data {
+        ContentDetails::Plugin(plugin_content)
+            if !ContentType::is_builtin(&plugin_content.plugin) =>
+        {
+            plugin_content.plugin.as_str()
+        }
+        _ => content.content.content_type.as_str(),
+    }
+}
+
+/// Build a renderer for the item using the global registry.
+/// Falls back to a blank renderer when nothing is registered for the content type,
+/// so a missing plugin never takes down the display loop.
+pub fn create_registered_renderer(content: &PlayListItem, ctx: RenderContext) -> Box<dyn Renderer> {
+    let key = renderer_key(content);
+    let factory = REGISTRY.read().ok().and_then(|registry| registry.get(key));
+
+    match factory {
+        Some(factory) => factory(content, ctx),
+        None => {
+            error!(
+                "No renderer registered for content type '{}' (item {})",
+                key, content.id
+            );
+            Box::new(UnsupportedRenderer::new(content, ctx))
+        }
+    }
+}
+
+/// Placeholder used when an item's content type has no registered renderer.
+/// Draws nothing and reports an error, so the display shows the error placeholder instead.
+pub struct UnsupportedRenderer {
+    content_type: String,
+    duration: Option<u64>,
+    start_time: Instant,
+}
+
+impl Renderer for UnsupportedRenderer {
+    fn new(content: &PlayListItem, _ctx: RenderContext) -> Self {
+        Self {
+            content_type: renderer_key(content).to_string(),
+            duration: content.duration,
+            start_time: Instant::now(),
+        }
+    }
+
+    fn update(&mut self, _dt: f32) {}
+
+    fn render(&self, _canvas: &mut Box<dyn LedCanvas>) {}
+
+    fn is_complete(&self) -> bool {
+        match self.duration {
+            Some(duration) => self.start_time.elapsed().as_secs() >= duration,
+            None => true,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.start_time = Instant::now();
+    }
+
+    fn update_context(&mut self, _ctx: RenderContext) {}
+
+    fn update_content(&mut self, content: &PlayListItem) {
+        self.duration = content.duration;
+    }
+
+    fn error(&self) -> Option<String> {
+        Some(format!(
+            "No renderer for content type '{}'",
+            self.content_type
+        ))
+    }
+}
@@ -0,0 +1,33 @@
+/// Seeded randomness for effects such as sparkle and strobe.
+///
+/// Values are hashed from the seed and a caller-provided key (e.g. a pixel position or cycle
+/// number) instead of drawn from a running sequence, so a frame only depends on the seed and
+/// the elapsed time. Displays with the same seed play identical effects; seed 0 gives the
+/// patterns the effects had before they were seedable.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EffectRng {
+    seed: u32,
+}
+
+impl EffectRng {
+    pub fn new(seed: u64) -> Self {
+        // Spread both halves of the seed over all bits; 0 stays 0
+        let low = (seed as u32).wrapping_mul(0x9E37_79B9);
+        let high = ((seed >> 32) as u32).wrapping_mul(0x85EB_CA6B);
+        Self { seed: low ^ high }
+    }
+
+    /// Key for a grid cell or any other pair of numbers, e.g. (cycle, salt)
+    pub fn key(&self, row: u32, col: u32) -> u32 {
+        row.wrapping_mul(73_856_093) ^ col.wrapping_mul(19_349_663) ^ self.seed
+    }
+
+    /// Pseudo-random value in 0..1 derived from a key
+    pub fn unit(key: u32) -> f32 {
+        let mut x = key;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        (x as f32 / u32::MAX as f32).fract()
+    }
+}
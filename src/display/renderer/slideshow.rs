@@ -0,0 +1,250 @@
+use log::{debug, error, warn};
+
+use crate::display::driver::LedCanvas;
+use crate::display::renderer::image::{fit_to_panel, image_path};
+use crate::display::renderer::{RenderContext, Renderer};
+use crate::models::content::ContentDetails;
+use crate::models::playlist::PlayListItem;
+use crate::models::slideshow::{SlideTransition, SlideshowContent};
+
+// Image scaled to fit the panel and centered on black, one color per panel pixel
+struct Slide {
+    // Index into the content's images, for the dwell time
+    image_index: usize,
+    pixels: Vec<[u8; 3]>,
+}
+
+pub struct SlideshowRenderer {
+    ctx: RenderContext,
+    content: SlideshowContent,
+    // Images that could be loaded, the others are left out
+    slides: Vec<Slide>,
+    current: usize,
+    // Slide the current one transitions from
+    previous: Option<usize>,
+    elapsed_ms: f32,
+    completed_passes: u32,
+    max_passes: Option<u32>,
+    is_complete: bool,
+}
+
+impl Renderer for SlideshowRenderer {
+    fn new(content: &PlayListItem, ctx: RenderContext) -> Self
+    where
+        Self: Sized,
+    {
+        let slideshow_content = match &content.content.data {
+            ContentDetails::Slideshow(slideshow_content) => slideshow_content.clone(),
+            _ => unreachable!("SlideshowRenderer can only be created with slideshow content"),
+        };
+
+        let slides = load_slides(&slideshow_content, &ctx);
+        debug!(
+            "Loaded {} of {} slideshow images for playlist item {}",
+            slides.len(),
+            slideshow_content.images.len(),
+            content.id
+        );
+
+        Self {
+            ctx,
+            content: slideshow_content,
+            slides,
+            current: 0,
+            previous: None,
+            elapsed_ms: 0.0,
+            completed_passes: 0,
+            max_passes: repeat_count_to_passes(content.repeat_count),
+            is_complete: false,
+        }
+    }
+
+    fn error(&self) -> Option<String> {
+        self.slides
+            .is_empty()
+            .then(|| "None of the slideshow's images could be loaded".to_string())
+    }
+
+    fn update(&mut self, dt: f32) {
+        if self.slides.is_empty() {
+            self.is_complete = true;
+            return;
+        }
+        if self.is_complete {
+            return;
+        }
+
+        self.elapsed_ms += dt * 1000.0;
+        loop {
+            let dwell_ms = self.dwell_ms(self.current);
+            if self.elapsed_ms < dwell_ms {
+                break;
+            }
+
+            if self.current + 1 == self.slides.len() {
+                self.completed_passes = self.completed_passes.saturating_add(1);
+                // The last image stays on screen once all passes are done
+                if self
+                    .max_passes
+                    .is_some_and(|max_passes| self.completed_passes >= max_passes)
+                {
+                    self.elapsed_ms = dwell_ms;
+                    self.is_complete = true;
+                    break;
+                }
+            }
+            self.elapsed_ms -= dwell_ms;
+            self.previous = Some(self.current);
+            self.current = (self.current + 1) % self.slides.len();
+        }
+    }
+
+    fn render(&self, canvas: &mut Box<dyn LedCanvas>) {
+        let Some(slide) = self.slides.get(self.current) else {
+            return;
+        };
+        let width = self.ctx.display_width.max(0) as usize;
+        let height = self.ctx.display_height.max(0) as usize;
+
+        // Share of the transition from the previous slide that is done, if one is running
+        let transition = self
+            .previous
+            .filter(|_| {
+                self.content.transition != SlideTransition::None
+                    && self.elapsed_ms < self.content.transition_ms as f32
+            })
+            .and_then(|previous| self.slides.get(previous))
+            .map(|previous| {
+                let progress = self.elapsed_ms / self.content.transition_ms.max(1) as f32;
+                (previous, progress.clamp(0.0, 1.0))
+            });
+
+        for y in 0..height {
+            for x in 0..width {
+                let color = match transition {
+                    None => slide.pixels[y * width + x],
+                    Some((previous, progress)) => match self.content.transition {
+                        SlideTransition::Fade => blend(
+                            previous.pixels[y * width + x],
+                            slide.pixels[y * width + x],
+                            progress,
+                        ),
+                        // The new slide pushes in from the right
+                        _ => {
+                            let offset = (progress * width as f32) as usize;
+                            if x + offset < width {
+                                previous.pixels[y * width + x + offset]
+                            } else {
+                                slide.pixels[y * width + x + offset - width]
+                            }
+                        }
+                    },
+                };
+                let [r, g, b] = self.ctx.apply_brightness(color);
+                canvas.set_pixel(x, y, r, g, b);
+            }
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.is_complete
+    }
+
+    fn reset(&mut self) {
+        self.current = 0;
+        self.previous = None;
+        self.elapsed_ms = 0.0;
+        self.completed_passes = 0;
+        self.is_complete = false;
+    }
+
+    fn update_context(&mut self, ctx: RenderContext) {
+        let resized = ctx.display_width != self.ctx.display_width
+            || ctx.display_height != self.ctx.display_height;
+        self.ctx = ctx;
+        if resized {
+            self.slides = load_slides(&self.content, &self.ctx);
+            self.reset();
+        }
+    }
+
+    fn update_content(&mut self, content: &PlayListItem) {
+        if let ContentDetails::Slideshow(slideshow_content) = &content.content.data {
+            let same_images = slideshow_content.images.len() == self.content.images.len()
+                && slideshow_content
+                    .images
+                    .iter()
+                    .zip(&self.content.images)
+                    .all(|(new, old)| new.image_id == old.image_id);
+            self.content = slideshow_content.clone();
+            if !same_images {
+                self.slides = load_slides(&self.content, &self.ctx);
+            }
+            self.max_passes = repeat_count_to_passes(content.repeat_count);
+            self.reset();
+        }
+    }
+
+    fn completed_cycles(&self) -> Option<u32> {
+        Some(self.completed_passes)
+    }
+}
+
+impl SlideshowRenderer {
+    fn dwell_ms(&self, slide: usize) -> f32 {
+        self.slides
+            .get(slide)
+            .map_or(self.content.dwell_ms, |slide| {
+                self.content.dwell_ms(slide.image_index)
+            })
+            .max(1) as f32
+    }
+}
+
+fn repeat_count_to_passes(repeat_count: Option<u32>) -> Option<u32> {
+    match repeat_count {
+        Some(0) | None => None,
+        Some(value) => Some(value),
+    }
+}
+
+fn blend(from: [u8; 3], to: [u8; 3], progress: f32) -> [u8; 3] {
+    let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * progress).round() as u8;
+    [
+        mix(from[0], to[0]),
+        mix(from[1], to[1]),
+        mix(from[2], to[2]),
+    ]
+}
+
+// Load and scale every image of the slideshow, leaving out those that fail
+fn load_slides(content: &SlideshowContent, ctx: &RenderContext) -> Vec<Slide> {
+    let width = ctx.display_width.max(1) as u32;
+    let height = ctx.display_height.max(1) as u32;
+
+    content
+        .images
+        .iter()
+        .enumerate()
+        .filter_map(|(image_index, image)| {
+            let path = image_path(&image.image_id);
+            let decoded = match image::open(&path) {
+                Ok(decoded) => decoded.to_rgb8(),
+                Err(err) => {
+                    error!("Failed to open image {}: {}", path.display(), err);
+                    return None;
+                }
+            };
+            if decoded.width() == 0 || decoded.height() == 0 {
+                warn!("Skipping empty slideshow image {}", image.image_id);
+                return None;
+            }
+
+            let pixels = fit_to_panel(&decoded, width, height);
+            Some(Slide {
+                image_index,
+                pixels,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,118 @@
+use crate::display::driver::LedCanvas;
+use crate::display::renderer::image::fit_to_panel;
+use crate::display::renderer::{RenderContext, Renderer};
+use crate::models::content::ContentDetails;
+use crate::models::playlist::PlayListItem;
+use crate::models::stream::StreamContent;
+use crate::utils::stream_frames::{self, StreamFrame};
+use log::warn;
+use std::time::{Duration, Instant};
+
+/// Shows the latest frame of a camera stream scaled to fit the panel, fetched at most
+/// `max_fps` times per second. The panel stays black until the first frame has arrived.
+pub struct StreamRenderer {
+    content: StreamContent,
+    ctx: RenderContext,
+    duration: Option<u64>,
+    start_time: Instant,
+    frame: StreamFrame,
+    // The frame scaled to the panel, one color per panel pixel
+    pixels: Vec<[u8; 3]>,
+}
+
+impl Renderer for StreamRenderer {
+    fn new(content: &PlayListItem, ctx: RenderContext) -> Self {
+        let stream_content = match &content.content.data {
+            ContentDetails::Stream(stream) => stream.clone(),
+            #[allow(unreachable_patterns)]
+            _ => panic!("Expected stream content"),
+        };
+
+        // The first frame is requested on the first update, not for renderers that are only
+        // built to validate an item
+        Self {
+            content: stream_content,
+            ctx,
+            duration: content.duration,
+            start_time: Instant::now(),
+            frame: StreamFrame::default(),
+            pixels: Vec::new(),
+        }
+    }
+
+    fn update(&mut self, _dt: f32) {
+        let interval = Duration::from_secs_f32(1.0 / self.content.max_fps);
+        let frame = stream_frames::latest(&self.content.url, interval);
+        let is_new = frame.sequence != self.frame.sequence;
+        self.frame = frame;
+        if is_new {
+            self.scale_frame();
+        }
+    }
+
+    fn render(&self, canvas: &mut Box<dyn LedCanvas>) {
+        let width = self.ctx.display_width.max(0) as usize;
+        for (index, color) in self.pixels.iter().enumerate() {
+            let [r, g, b] = self.ctx.apply_brightness(*color);
+            canvas.set_pixel(index % width, index / width, r, g, b);
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        if let Some(duration) = self.duration {
+            return Instant::now().duration_since(self.start_time).as_secs() >= duration;
+        }
+        false
+    }
+
+    fn reset(&mut self) {
+        self.start_time = Instant::now();
+    }
+
+    fn update_context(&mut self, ctx: RenderContext) {
+        let resized = ctx.display_width != self.ctx.display_width
+            || ctx.display_height != self.ctx.display_height;
+        self.ctx = ctx;
+        if resized {
+            self.scale_frame();
+        }
+    }
+
+    fn update_content(&mut self, content: &PlayListItem) {
+        if let ContentDetails::Stream(stream) = &content.content.data {
+            if stream.url != self.content.url {
+                self.frame = StreamFrame::default();
+                self.pixels.clear();
+            }
+            self.content = stream.clone();
+            self.duration = content.duration;
+        } else {
+            warn!("StreamRenderer received non-stream content during update");
+        }
+    }
+
+    fn error(&self) -> Option<String> {
+        // Failures after a frame has arrived keep showing that frame
+        match &self.frame.image {
+            Some(_) => None,
+            None => self
+                .frame
+                .error
+                .as_ref()
+                .map(|e| format!("Stream {} failed: {}", self.content.url, e)),
+        }
+    }
+}
+
+impl StreamRenderer {
+    fn scale_frame(&mut self) {
+        let width = self.ctx.display_width.max(1) as u32;
+        let height = self.ctx.display_height.max(1) as u32;
+        self.pixels = match &self.frame.image {
+            Some(image) if image.width() > 0 && image.height() > 0 => {
+                fit_to_panel(image, width, height)
+            }
+            _ => Vec::new(),
+        };
+    }
+}
@@ -1,36 +1,62 @@
 use crate::display::driver::LedCanvas;
+use crate::display::graphics::antialias;
 use crate::display::graphics::embedded_graphics_support::EmbeddedGraphicsCanvas;
+use crate::display::graphics::fonts;
+use crate::display::graphics::frame_buffer::FrameBuffer;
 use crate::display::renderer::{RenderContext, Renderer};
 use crate::models::content::ContentDetails;
 use crate::models::playlist::PlayListItem;
-use crate::models::text::{TextContent, TextSegment};
+use crate::models::text::{PageTransition, TextContent, TextOverflow, TextPaging, TextSegment};
+use crate::utils::variables;
 use embedded_graphics::geometry::Point;
 use embedded_graphics::mono_font::iso_8859_1::FONT_10X20 as FONT_10X20_LATIN1;
-use embedded_graphics::mono_font::MonoTextStyle;
+use embedded_graphics::mono_font::{MonoFont, MonoTextStyle};
 use embedded_graphics::pixelcolor::Rgb888;
 use embedded_graphics::text::Text;
 use embedded_graphics::Drawable;
 use log::debug;
+use std::ops::Range;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::time::Instant;
 
+// Fastest scroll speed in pixels per second, the limit for 'pixels_per_second' as well
+const MAX_SCROLL_SPEED: f32 = 1000.0;
+
 pub struct TextRenderer {
-    /// The text content to render
+    /// The text content to render, with variable placeholders replaced
     content: TextContent,
 
+    /// The text content as given, with {{name}} variable placeholders
+    template: TextContent,
+
+    /// Variables version the placeholders were last replaced at
+    variables_version: u64,
+
+    /// Minute {until ...} and {open_state} placeholders were last replaced in
+    expanded_minute: i64,
+
     /// Context with display properties
     ctx: RenderContext,
 
+    /// Font the text is drawn with, smaller than the regular one when shrunk to fit
+    font: &'static MonoFont<'static>,
+
+    /// Characters of static text shown before the ellipsis when it is truncated to fit
+    truncated_chars: Option<usize>,
+
     /// Width of the text in pixels
     text_width: i32,
 
-    /// Current scroll position
-    scroll_position: i32,
+    /// Scroll speed derived from the text width and `read_time_ms`, if set
+    read_time_speed: Option<f32>,
+
+    /// Current scroll position, kept fractional so slow scrolls move evenly
+    scroll_position: f32,
 
     /// Counter for completed scroll cycles
     completed_scrolls: u32,
 
-    /// Timing accumulator for scroll animation
+    /// Seconds spent scrolling since the item started, used for easing in
     accumulated_time: f32,
 
     /// Target number of repeats (None for duration-based)
@@ -44,6 +70,15 @@ pub struct TextRenderer {
 
     /// Last reported cycle (to avoid duplicate logging)
     last_reported_cycle: AtomicU32,
+
+    /// Character ranges of each page in paged mode
+    pages: Vec<Range<usize>>,
+
+    /// Page currently shown in paged mode
+    page_index: usize,
+
+    /// Seconds since the current page started, including its outgoing transition
+    page_elapsed: f32,
 }
 
 impl Renderer for TextRenderer {
@@ -58,20 +93,32 @@ impl Renderer for TextRenderer {
         // Create text renderer with clone of ctx
         let ctx_clone = ctx.clone();
         let mut renderer = Self {
-            content: text_content,
+            content: expand_variables(&text_content),
+            template: text_content,
+            variables_version: variables::version(),
+            expanded_minute: current_minute(),
             ctx: ctx_clone,
+            font: &FONT_10X20_LATIN1,
+            truncated_chars: None,
             text_width: 0, // Will calculate on first render
-            scroll_position: ctx.display_width,
+            read_time_speed: None,
+            scroll_position: ctx.display_width as f32,
             completed_scrolls: 0,
             accumulated_time: 0.0,
             repeat_count: content.repeat_count,
             duration: content.duration,
             start_time: Instant::now(),
             last_reported_cycle: AtomicU32::new(0),
+            pages: Vec::new(),
+            page_index: 0,
+            page_elapsed: 0.0,
         };
 
-        // Pre-calculate text width
+        // Pre-calculate the font and text width
+        renderer.calculate_fit();
         renderer.calculate_text_width();
+        renderer.calculate_read_time_speed();
+        renderer.calculate_pages();
 
         // Log the configuration to help diagnose issues
         debug!(
@@ -86,20 +133,50 @@ impl Renderer for TextRenderer {
     }
 
     fn update(&mut self, dt: f32) {
-        if self.content.scroll {
-            self.accumulated_time += dt;
-            let pixels_to_move = (self.accumulated_time * self.content.speed) as i32;
-
-            if pixels_to_move > 0 {
-                self.scroll_position -= pixels_to_move;
-                self.accumulated_time = 0.0;
+        // Show new variable values, and relative times and the open state every minute,
+        // keeping the scroll position
+        let version = variables::version();
+        let minute = current_minute();
+        let minute_passed = minute != self.expanded_minute
+            && variables::has_timed_placeholders(&self.template.text);
+        let variables_changed = version != self.variables_version;
+        if variables_changed || minute_passed {
+            self.variables_version = version;
+            self.expanded_minute = minute;
+            if variables::has_placeholders(&self.template.text) {
+                let previous_text = self.content.text.clone();
+                self.apply_content(expand_variables(&self.template));
+                // A new value, e.g. the next headline, is an event for reactive borders. The
+                // minutely timed placeholders are not.
+                if variables_changed && self.content.text != previous_text {
+                    self.ctx.border_link.pulse(self.content.color);
+                }
+            }
+        }
 
-                // Reset position when text is off screen
-                if self.scroll_position < -self.text_width {
-                    self.scroll_position = self.ctx.display_width;
+        if let Some(paging) = self.paging() {
+            let page_seconds = (paging.dwell_ms + self.page_transition_ms(paging)) as f32 / 1000.0;
+            self.page_elapsed += dt;
+            while self.page_elapsed >= page_seconds {
+                self.page_elapsed -= page_seconds;
+                self.page_index += 1;
+                self.ctx.border_link.pulse(self.content.color);
+                // A pass through all pages counts like one scroll cycle
+                if self.page_index >= self.pages.len() {
+                    self.page_index = 0;
                     self.completed_scrolls += 1;
                 }
             }
+        } else if self.scrolls() {
+            self.accumulated_time += dt;
+            self.scroll_position -= self.scroll_speed() * dt;
+
+            // Reset position when text is off screen
+            if self.scroll_position < -self.text_width as f32 {
+                self.scroll_position = self.ctx.display_width as f32;
+                self.completed_scrolls += 1;
+                self.ctx.border_link.pulse(self.content.color);
+            }
         }
         // For duration-based content, track elapsed time
         else if let Some(_) = self.duration {
@@ -112,25 +189,24 @@ impl Renderer for TextRenderer {
     }
 
     fn render(&self, canvas: &mut Box<dyn LedCanvas>) {
-        // Create embedded graphics wrapper
-        let mut eg_canvas = EmbeddedGraphicsCanvas::new(canvas);
-
-        // Get the vertical position for text
-        let font_height = 20; // Height of FONT_10X20_LATIN1
-        let vertical_position = self.ctx.calculate_centered_text_position(font_height);
-
-        // Apply brightness scaling to the text color
-        let [r, g, b] = self.ctx.apply_brightness(self.content.color);
-        let text_style = MonoTextStyle::new(&FONT_10X20_LATIN1, Rgb888::new(r, g, b));
+        if !self.content.antialias {
+            self.draw_text(canvas);
+            return;
+        }
 
-        if let Some(segments) = &self.content.text_segments {
-            if !segments.is_empty() {
-                self.render_segmented_text(&mut eg_canvas, segments, vertical_position);
-            } else {
-                self.render_simple_text(&mut eg_canvas, vertical_position, &text_style);
-            }
+        // Draw into an off-screen buffer first, then smooth the glyph edges onto the panel
+        let mut buffer: Box<dyn LedCanvas> = Box::new(FrameBuffer::new(
+            self.ctx.display_width,
+            self.ctx.display_height,
+        ));
+        self.draw_text(&mut buffer);
+        let shift = if self.scrolls() && self.paging().is_none() {
+            self.scroll_position - self.scroll_position.floor()
         } else {
-            self.render_simple_text(&mut eg_canvas, vertical_position, &text_style);
+            0.0
+        };
+        if let Some(buffer) = buffer.as_any_mut().downcast_mut::<FrameBuffer>() {
+            antialias::draw_smoothed(buffer, canvas, shift);
         }
     }
 
@@ -152,8 +228,10 @@ impl Renderer for TextRenderer {
     }
 
     fn reset(&mut self) {
-        self.scroll_position = self.ctx.display_width;
+        self.scroll_position = self.ctx.display_width as f32;
         self.completed_scrolls = 0;
+        self.page_index = 0;
+        self.page_elapsed = 0.0;
         self.accumulated_time = 0.0;
         self.start_time = Instant::now();
         self.last_reported_cycle.store(0, Ordering::SeqCst);
@@ -161,7 +239,13 @@ impl Renderer for TextRenderer {
 
     fn update_context(&mut self, ctx: RenderContext) {
         // Update the context without changing animation state
+        let resized = ctx.display_width != self.ctx.display_width;
         self.ctx = ctx;
+        if resized {
+            self.calculate_fit();
+            self.calculate_text_width();
+            self.calculate_pages();
+        }
     }
 
     fn update_content(&mut self, content: &PlayListItem) {
@@ -172,35 +256,410 @@ impl Renderer for TextRenderer {
             _ => panic!("Expected text content"),
         };
 
+        self.repeat_count = content.repeat_count;
+        self.duration = content.duration;
+        self.apply_content(expand_variables(&new_text_content));
+        self.template = new_text_content;
+    }
+
+    fn completed_cycles(&self) -> Option<u32> {
+        // Only scrolling text completes cycles
+        self.scrolls().then_some(self.completed_scrolls)
+    }
+
+    fn progress(&self) -> Option<f32> {
+        if let Some(duration) = self.duration {
+            let elapsed = self.start_time.elapsed().as_secs_f32();
+            return Some((elapsed / duration.max(1) as f32).min(1.0));
+        }
+        let repeat_count = self.repeat_count.filter(|count| *count > 0)?;
+
+        // Share of the current pass, through the pages or across the panel
+        let pass = if let Some(paging) = self.paging() {
+            let page_seconds = (paging.dwell_ms + self.page_transition_ms(paging)) as f32 / 1000.0;
+            (self.page_index as f32 + self.page_elapsed / page_seconds.max(f32::EPSILON))
+                / self.pages.len().max(1) as f32
+        } else {
+            let distance = (self.ctx.display_width + self.text_width).max(1) as f32;
+            (self.ctx.display_width as f32 - self.scroll_position) / distance
+        };
+        Some(
+            ((self.completed_scrolls as f32 + pass.clamp(0.0, 1.0)) / repeat_count as f32).min(1.0),
+        )
+    }
+}
+
+impl TextRenderer {
+    /// Width of the text in pixels, as it scrolls
+    pub fn text_width(&self) -> i32 {
+        self.text_width
+    }
+
+    /// Seconds one pass takes at full speed, from entering on the right to leaving on the left
+    /// or through all pages. None for static text.
+    pub fn pass_seconds(&self) -> Option<f32> {
+        if let Some(paging) = self.paging() {
+            let page_ms = paging.dwell_ms + self.page_transition_ms(paging);
+            return Some((page_ms * self.pages.len() as u64) as f32 / 1000.0);
+        }
+        if !self.scrolls() {
+            return None;
+        }
+        let speed = self
+            .read_time_speed
+            .or(self.content.pixels_per_second)
+            .unwrap_or(self.content.speed);
+        let distance = (self.ctx.display_width + self.text_width) as f32;
+        Some(distance / speed.max(f32::EPSILON))
+    }
+
+    /// Seconds until the item completes, None when it repeats forever
+    pub fn total_seconds(&self) -> Option<f32> {
+        let Some(pass_seconds) = self.pass_seconds() else {
+            return self.duration.map(|seconds| seconds as f32);
+        };
+        let repeat_count = self.repeat_count.filter(|count| *count > 0)?;
+        // Easing in at the start and out at the end each add half the ease time
+        let ease_seconds = match self.paging() {
+            Some(_) => 0.0,
+            None => self.content.scroll_ease_ms.unwrap_or(0) as f32 / 1000.0,
+        };
+        Some(pass_seconds * repeat_count as f32 + ease_seconds)
+    }
+
+    // Switch to new content while preserving the animation state
+    fn apply_content(&mut self, new_text_content: TextContent) {
         // Track if we need to recalculate width
         let text_changed = self.content.text != new_text_content.text;
+        let read_time_changed = self.content.read_time_ms != new_text_content.read_time_ms;
+        let paging_changed = self.content.paging.is_some() != new_text_content.paging.is_some();
+        let fit_changed = text_changed
+            || self.content.scroll != new_text_content.scroll
+            || self.content.overflow != new_text_content.overflow;
 
         // Update content properties
         self.content = new_text_content;
-        self.repeat_count = content.repeat_count;
-        self.duration = content.duration;
 
         // Only recalculate width if text changed
-        if text_changed {
+        if text_changed || paging_changed {
+            self.calculate_pages();
+        }
+        if fit_changed {
+            self.calculate_fit();
             self.calculate_text_width();
 
             // Don't reset scroll position completely, but ensure it's visible
             // if currently off-screen
-            if self.content.scroll && self.scroll_position < -self.text_width {
+            if self.scrolls() && self.scroll_position < -self.text_width as f32 {
                 // Position text just off screen to the right
-                self.scroll_position = self.ctx.display_width;
+                self.scroll_position = self.ctx.display_width as f32;
             }
         }
+        if text_changed || read_time_changed {
+            self.calculate_read_time_speed();
+        }
 
         // Log that we're preserving animation state
         debug!("Updated TextRenderer content while preserving animation state");
     }
-}
 
-impl TextRenderer {
     // Calculate text width based on character count
     fn calculate_text_width(&mut self) {
-        self.text_width = (self.content.text.chars().count() as i32) * 10 + 2;
+        self.text_width = (self.content.text.chars().count() as i32) * self.char_width() + 2;
+    }
+
+    // Pick the font and the characters of static text to show so it fits the panel, see
+    // TextOverflow. Scrolling text always uses the regular font.
+    fn calculate_fit(&mut self) {
+        self.font = &FONT_10X20_LATIN1;
+        self.truncated_chars = None;
+        if self.content.scroll {
+            return;
+        }
+
+        let chars = self.content.text.chars().count();
+        let fits = |font: &MonoFont| glyphs_width(font, chars) <= self.ctx.display_width;
+        match self.content.overflow {
+            TextOverflow::Clip | TextOverflow::ScrollIfTooLong => return,
+            TextOverflow::TruncateEllipsis => {}
+            TextOverflow::ShrinkFont => {
+                let mut smallest = self.font;
+                for font in fonts::fonts_largest_first() {
+                    if fits(font) {
+                        self.font = font;
+                        return;
+                    }
+                    smallest = font;
+                }
+                self.font = smallest;
+            }
+        }
+
+        // The ellipsis takes the place of one more character
+        if !fits(self.font) {
+            let cell = self.char_width();
+            self.truncated_chars = Some((self.ctx.display_width / cell - 1).max(0) as usize);
+        }
+    }
+
+    // Static text scrolls when asked to or when it is too wide for the panel with
+    // 'scroll_if_too_long'
+    fn scrolls(&self) -> bool {
+        self.content.scroll
+            || (self.content.overflow == TextOverflow::ScrollIfTooLong
+                && glyphs_width(self.font, self.content.text.chars().count())
+                    > self.ctx.display_width)
+    }
+
+    // Horizontal distance from one character to the next
+    fn char_width(&self) -> i32 {
+        (self.font.character_size.width + self.font.character_spacing) as i32
+    }
+
+    // Text baseline that centers the font vertically, the same as
+    // calculate_centered_text_position for the regular font
+    fn baseline_y(&self) -> i32 {
+        self.ctx.display_height / 2 + self.font.character_size.height as i32 / 4
+    }
+
+    // Left edge of centered static text, including the ellipsis if it is truncated
+    fn static_x(&self) -> i32 {
+        let width = match self.truncated_chars {
+            Some(chars) => (chars as i32 + 1) * self.char_width(),
+            None => self.text_width,
+        };
+        (self.ctx.display_width - width) / 2
+    }
+
+    // Three dots filling one character cell at `x`, as the fonts have no ellipsis glyph
+    fn draw_ellipsis(&self, canvas: &mut EmbeddedGraphicsCanvas, x: i32, y_pos: i32) {
+        let [r, g, b] = self.ctx.apply_brightness(self.content.color);
+        let cell = self.char_width();
+        let dot = (cell / 5).max(1);
+        let step = ((cell - dot) / 3).max(dot + 1);
+        let canvas = canvas.inner_mut();
+        for i in 0..3 {
+            let left = x + i * step;
+            for dx in left.max(0)..(left + dot).min(self.ctx.display_width) {
+                for dy in (y_pos - dot).max(0)..y_pos.min(self.ctx.display_height) {
+                    canvas.set_pixel(dx as usize, dy as usize, r, g, b);
+                }
+            }
+        }
+    }
+
+    // Speed at which one pass, from entering on the right to leaving on the left, takes
+    // the configured read time. Capped like 'pixels_per_second' for very long text.
+    fn calculate_read_time_speed(&mut self) {
+        self.read_time_speed = self.content.read_time_ms.filter(|ms| *ms > 0).map(|ms| {
+            let distance = (self.ctx.display_width + self.text_width) as f32;
+            (distance / (ms as f32 / 1000.0)).min(MAX_SCROLL_SPEED)
+        });
+    }
+
+    // Draw the text with the regular bitmap font
+    fn draw_text(&self, canvas: &mut Box<dyn LedCanvas>) {
+        // Create embedded graphics wrapper
+        let mut eg_canvas = EmbeddedGraphicsCanvas::new(canvas);
+
+        // Get the vertical position for text
+        let vertical_position = self.baseline_y();
+
+        // Apply brightness scaling to the text color
+        let [r, g, b] = self.ctx.apply_brightness(self.content.color);
+        let text_style = MonoTextStyle::new(self.font, Rgb888::new(r, g, b));
+
+        if let Some(paging) = self.paging() {
+            self.render_pages(&mut eg_canvas, paging, vertical_position, &text_style);
+            return;
+        }
+
+        // Starting X position depends on scroll mode
+        let x_start = if self.scrolls() {
+            self.scroll_x()
+        } else {
+            self.static_x()
+        };
+
+        if let Some(truncated) = self.truncated_chars {
+            self.draw_ellipsis(
+                &mut eg_canvas,
+                x_start + truncated as i32 * self.char_width(),
+                vertical_position,
+            );
+        }
+
+        if let Some(segments) = &self.content.text_segments {
+            if !segments.is_empty() {
+                let range = 0..self
+                    .truncated_chars
+                    .unwrap_or_else(|| self.content.text.chars().count());
+                self.render_segmented_text(
+                    &mut eg_canvas,
+                    segments,
+                    vertical_position,
+                    x_start,
+                    range,
+                );
+            } else {
+                self.render_simple_text(&mut eg_canvas, vertical_position, &text_style);
+            }
+        } else {
+            self.render_simple_text(&mut eg_canvas, vertical_position, &text_style);
+        }
+    }
+
+    // Whole-pixel scroll position; anti-aliased text blends the remainder itself
+    fn scroll_x(&self) -> i32 {
+        if self.content.antialias {
+            self.scroll_position.floor() as i32
+        } else {
+            self.scroll_position.round() as i32
+        }
+    }
+
+    // Current scroll speed in pixels per second, including easing at the start and end
+    fn scroll_speed(&self) -> f32 {
+        let speed = self
+            .read_time_speed
+            .or(self.content.pixels_per_second)
+            .unwrap_or(self.content.speed);
+        let Some(ease_ms) = self.content.scroll_ease_ms.filter(|ms| *ms > 0) else {
+            return speed;
+        };
+        let ease = ease_ms as f32 / 1000.0;
+
+        // Ease in from standstill when the item starts
+        let progress = (self.accumulated_time / ease).min(1.0);
+        let mut factor = progress * progress * (3.0 - 2.0 * progress);
+
+        // Ease out over the distance a steady deceleration would need on the final pass
+        let final_pass = self
+            .repeat_count
+            .is_some_and(|count| count > 0 && self.completed_scrolls + 1 >= count);
+        if final_pass {
+            let braking_distance = speed * ease / 2.0;
+            let remaining = self.scroll_position + self.text_width as f32;
+            if braking_distance > 0.0 && remaining < braking_distance {
+                factor = factor.min((remaining / braking_distance).max(0.0).sqrt());
+            }
+        }
+
+        // Never stop completely, or the item would not finish
+        speed * factor.max(0.05)
+    }
+
+    // Paging settings, when the text is shown page by page
+    fn paging(&self) -> Option<&TextPaging> {
+        self.content
+            .paging
+            .as_ref()
+            .filter(|_| self.content.scroll && !self.pages.is_empty())
+    }
+
+    // Single-page text has nothing to slide to
+    fn page_transition_ms(&self, paging: &TextPaging) -> u64 {
+        if paging.transition == PageTransition::Slide && self.pages.len() > 1 {
+            paging.transition_ms
+        } else {
+            0
+        }
+    }
+
+    // Word-wrap the text into pages that fit the panel width
+    fn calculate_pages(&mut self) {
+        self.pages.clear();
+        self.page_index = 0;
+        self.page_elapsed = 0.0;
+        if self.content.paging.is_none() {
+            return;
+        }
+
+        let chars: Vec<char> = self.content.text.chars().collect();
+        let per_page = (self.ctx.display_width / self.char_width()).max(1) as usize;
+        let mut start = 0;
+
+        while start < chars.len() {
+            // Skip whitespace at the start of a page
+            while start < chars.len() && chars[start].is_whitespace() {
+                start += 1;
+            }
+            if start >= chars.len() {
+                break;
+            }
+
+            let limit = (start + per_page).min(chars.len());
+            let mut end = limit;
+            if limit < chars.len() && !chars[limit].is_whitespace() {
+                // Break after the last whitespace; words longer than a page are split
+                if let Some(space) = (start..limit).rev().find(|&i| chars[i].is_whitespace()) {
+                    end = space;
+                }
+            }
+
+            // Trim trailing whitespace from the page
+            let mut trimmed = end;
+            while trimmed > start && chars[trimmed - 1].is_whitespace() {
+                trimmed -= 1;
+            }
+            self.pages.push(start..trimmed);
+            start = end;
+        }
+    }
+
+    // Render the current page, sliding to the next one once its dwell time is over
+    fn render_pages(
+        &self,
+        canvas: &mut EmbeddedGraphicsCanvas,
+        paging: &TextPaging,
+        y_pos: i32,
+        style: &MonoTextStyle<Rgb888>,
+    ) {
+        let dwell = paging.dwell_ms as f32 / 1000.0;
+        let transition = self.page_transition_ms(paging) as f32 / 1000.0;
+        let progress = if transition > 0.0 && self.page_elapsed > dwell {
+            ((self.page_elapsed - dwell) / transition).min(1.0)
+        } else {
+            0.0
+        };
+
+        let offset = (progress * self.ctx.display_width as f32) as i32;
+        self.render_page(canvas, self.page_index, -offset, y_pos, style);
+        if offset > 0 {
+            let next = (self.page_index + 1) % self.pages.len();
+            self.render_page(canvas, next, self.ctx.display_width - offset, y_pos, style);
+        }
+    }
+
+    // Render one page centered on a panel-wide slot starting at `slot_x`
+    fn render_page(
+        &self,
+        canvas: &mut EmbeddedGraphicsCanvas,
+        index: usize,
+        slot_x: i32,
+        y_pos: i32,
+        style: &MonoTextStyle<Rgb888>,
+    ) {
+        let range = self.pages[index].clone();
+        let x = slot_x + (self.ctx.display_width - range.len() as i32 * self.char_width()) / 2;
+
+        match &self.content.text_segments {
+            Some(segments) if !segments.is_empty() => {
+                self.render_segmented_text(canvas, segments, y_pos, x, range);
+            }
+            _ => {
+                let page_text: String = self
+                    .content
+                    .text
+                    .chars()
+                    .skip(range.start)
+                    .take(range.len())
+                    .collect();
+                Text::new(&page_text, Point::new(x, y_pos), *style)
+                    .draw(canvas)
+                    .unwrap();
+            }
+        }
     }
 
     // Render simple (unsegmented) text
@@ -210,42 +669,63 @@ impl TextRenderer {
         y_pos: i32,
         style: &MonoTextStyle<Rgb888>,
     ) {
-        if self.content.scroll {
+        if self.scrolls() {
             Text::new(
                 &self.content.text,
-                Point::new(self.scroll_position, y_pos),
+                Point::new(self.scroll_x(), y_pos),
                 *style,
             )
             .draw(canvas)
             .unwrap();
-        } else {
-            let x = (self.ctx.display_width - self.text_width) / 2;
-            Text::new(&self.content.text, Point::new(x, y_pos), *style)
+        } else if let Some(truncated) = self.truncated_chars {
+            // Cut at a character boundary so no glyph is drawn in part
+            let shown: String = self.content.text.chars().take(truncated).collect();
+            Text::new(&shown, Point::new(self.static_x(), y_pos), *style)
                 .draw(canvas)
                 .unwrap();
+        } else {
+            Text::new(
+                &self.content.text,
+                Point::new(self.static_x(), y_pos),
+                *style,
+            )
+            .draw(canvas)
+            .unwrap();
         }
     }
 
-    // Render segmented text with formatting
+    // Render the characters in `range` with segment formatting, starting at `x_start`
     fn render_segmented_text(
         &self,
         canvas: &mut EmbeddedGraphicsCanvas,
         segments: &[TextSegment],
         y_pos: i32,
+        x_start: i32,
+        range: Range<usize>,
     ) {
-        // Starting X position depends on scroll mode
-        let x_start = if self.content.scroll {
-            self.scroll_position
-        } else {
-            (self.ctx.display_width - self.text_width) / 2
-        };
-
         // Collect formatting data to apply after text rendering
         let mut formatting_effects = Vec::new();
 
         // Convert the full text to a vector of characters for safe indexing
         let chars: Vec<char> = self.content.text.chars().collect();
 
+        // Fill segment backgrounds before any text so bold offsets are not painted over
+        for segment in segments {
+            if let Some(background) = segment.background {
+                let start = segment.start.clamp(range.start, range.end);
+                let end = segment.end.clamp(range.start, range.end);
+                if start < end {
+                    self.fill_segment_background(
+                        canvas,
+                        x_start + (start - range.start) as i32 * self.char_width(),
+                        (end - start) as i32 * self.char_width(),
+                        y_pos,
+                        background,
+                    );
+                }
+            }
+        }
+
         // First pass: render all text segments
         for segment in segments {
             // Apply brightness scaling to segment color
@@ -254,20 +734,19 @@ impl TextRenderer {
             let [sr, sg, sb] = self.ctx.apply_brightness(segment_color);
 
             // Create text style for this segment
-            let font = &FONT_10X20_LATIN1;
-            let segment_style = MonoTextStyle::new(font, Rgb888::new(sr, sg, sb));
+            let segment_style = MonoTextStyle::new(self.font, Rgb888::new(sr, sg, sb));
 
             // Make sure indices are within bounds
-            let start = segment.start.min(chars.len());
-            let end = segment.end.min(chars.len());
+            let start = segment.start.clamp(range.start, range.end);
+            let end = segment.end.clamp(range.start, range.end);
 
             if start < end {
                 // Get the text for this segment
                 let segment_text: String = chars[start..end].iter().collect();
 
                 // Calculate segment width and position
-                let segment_width = (end - start) as i32 * 10;
-                let x_pos = x_start + (start as i32 * 10);
+                let segment_width = (end - start) as i32 * self.char_width();
+                let x_pos = x_start + ((start - range.start) as i32 * self.char_width());
 
                 // Check for bold formatting
                 let has_bold = segment.formatting.as_ref().map_or(false, |fmt| fmt.bold);
@@ -320,6 +799,28 @@ impl TextRenderer {
         }
     }
 
+    // Fill the full character cells of a segment, like a highlighter
+    fn fill_segment_background(
+        &self,
+        eg_canvas: &mut EmbeddedGraphicsCanvas,
+        x_pos: i32,
+        width: i32,
+        y_pos: i32,
+        color: [u8; 3],
+    ) {
+        let [r, g, b] = self.ctx.apply_brightness(color);
+        let font = self.font;
+        let top = y_pos - font.baseline as i32;
+        let canvas = eg_canvas.inner_mut();
+
+        for y in top.max(0)..(top + font.character_size.height as i32).min(self.ctx.display_height)
+        {
+            for x in x_pos.max(0)..(x_pos + width).min(self.ctx.display_width) {
+                canvas.set_pixel(x as usize, y as usize, r, g, b);
+            }
+        }
+    }
+
     // Apply underline and strikethrough effects
     fn apply_text_effects(
         &self,
@@ -332,8 +833,8 @@ impl TextRenderer {
         is_strikethrough: bool,
     ) {
         if is_underline {
-            // Draw line 3px below text baseline using embedded graphics primitives
-            let underline_y = y_pos + 3;
+            // Draw line below the text baseline, 3px for the regular font
+            let underline_y = y_pos + (3 * self.font.character_size.height as i32 / 20).max(1);
 
             // Get the underlying canvas from EmbeddedGraphicsCanvas
             let canvas = eg_canvas.inner_mut();
@@ -347,8 +848,8 @@ impl TextRenderer {
             // Get contrasting color for strikethrough
             let [strike_r, strike_g, strike_b] = self.get_strikethrough_color(r, g, b);
 
-            // Draw line through text center (font_height = 20)
-            let strike_y1 = y_pos - 5;
+            // Draw line through text center, 5px above the baseline for the regular font
+            let strike_y1 = y_pos - self.font.character_size.height as i32 / 4;
             let strike_y2 = strike_y1 - 1; // Second line one pixel above
 
             // Get the underlying canvas
@@ -403,3 +904,93 @@ impl TextRenderer {
         self.ctx.apply_brightness([255, 255, 255])
     }
 }
+
+// Width of `chars` characters drawn in a single line with `font`
+fn glyphs_width(font: &MonoFont, chars: usize) -> i32 {
+    let chars = chars as i32;
+    chars * font.character_size.width as i32 + (chars - 1).max(0) * font.character_spacing as i32
+}
+
+// Minute since the epoch, timed placeholders are expanded again when it changes
+fn current_minute() -> i64 {
+    crate::utils::timezone::utc_now().timestamp() / 60
+}
+
+// Text content with {{name}} placeholders replaced by variable values and {until ...} and
+// {open_state} ones by their timed values. Segments are moved along so they keep covering
+// the same parts of the text.
+fn expand_variables(content: &TextContent) -> TextContent {
+    let mut expanded = content.clone();
+    if !variables::has_placeholders(&content.text) {
+        return expanded;
+    }
+    let (text, positions) = variables::expand(&content.text);
+    let position = |index: usize| positions.get(index).or(positions.last()).copied();
+    if let Some(segments) = &mut expanded.text_segments {
+        for segment in segments.iter_mut() {
+            segment.start = position(segment.start).unwrap_or(0);
+            segment.end = position(segment.end).unwrap_or(0);
+        }
+    }
+    expanded.text = text;
+    expanded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::driver::capture_frame;
+
+    fn text_item(scroll: bool, color: [u8; 3]) -> PlayListItem {
+        let timing = if scroll {
+            serde_json::json!({ "repeat_count": 1 })
+        } else {
+            serde_json::json!({ "duration": 10 })
+        };
+        let mut item = serde_json::json!({
+            "content": {
+                "type": "Text",
+                "data": { "type": "Text", "text": "HELLO", "scroll": scroll, "color": color, "speed": 50.0 }
+            }
+        });
+        item.as_object_mut()
+            .expect("item is an object")
+            .extend(timing.as_object().expect("timing is an object").clone());
+        serde_json::from_value(item).expect("valid item")
+    }
+
+    #[test]
+    fn scrolling_text_moves_left() {
+        let item = text_item(true, [255, 255, 255]);
+        let mut renderer = TextRenderer::new(&item, RenderContext::new(64, 32, 100));
+        renderer.update(0.5);
+        let (before, ..) = capture_frame(&renderer, 64, 32)
+            .lit_bounds()
+            .expect("text is on screen");
+        renderer.update(0.2);
+        let (after, ..) = capture_frame(&renderer, 64, 32)
+            .lit_bounds()
+            .expect("text is on screen");
+
+        // 50 pixels per second for 0.2 seconds
+        assert!(
+            (before - after - 10).abs() <= 1,
+            "moved from {} to {}",
+            before,
+            after
+        );
+    }
+
+    #[test]
+    fn brightness_scales_text_color() {
+        let item = text_item(false, [200, 100, 0]);
+        let full = TextRenderer::new(&item, RenderContext::new(64, 32, 100));
+        let half = TextRenderer::new(&item, RenderContext::new(64, 32, 50));
+
+        let full = capture_frame(&full, 64, 32);
+        let half = capture_frame(&half, 64, 32);
+        assert_eq!(full.max_channel(), 200);
+        assert_eq!(half.max_channel(), 100);
+        assert_eq!(full.to_ascii(), half.to_ascii());
+    }
+}
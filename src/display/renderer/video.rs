@@ -0,0 +1,203 @@
+use log::{debug, error, warn};
+use std::path::{Path, PathBuf};
+
+use crate::display::driver::LedCanvas;
+use crate::display::renderer::{RenderContext, Renderer};
+use crate::models::content::ContentDetails;
+use crate::models::playlist::PlayListItem;
+use crate::models::video::{VideoContent, VideoInfo};
+use crate::storage::manager::{paths, DEFAULT_DIR};
+
+// Frames of an uploaded video, decoded to RGB when it was uploaded
+struct DecodedVideo {
+    info: VideoInfo,
+    frames: Vec<u8>,
+}
+
+pub struct VideoRenderer {
+    ctx: RenderContext,
+    content: VideoContent,
+    video: Option<DecodedVideo>,
+    elapsed_seconds: f32,
+    completed_plays: u32,
+    max_plays: Option<u32>,
+    is_complete: bool,
+}
+
+impl Renderer for VideoRenderer {
+    fn new(content: &PlayListItem, ctx: RenderContext) -> Self
+    where
+        Self: Sized,
+    {
+        let video_content = match &content.content.data {
+            ContentDetails::Video(video_content) => video_content.clone(),
+            _ => unreachable!("VideoRenderer can only be created with video content"),
+        };
+
+        let video = load_video(&video_content.video_id);
+        if video.is_none() {
+            warn!(
+                "Failed to load video {} for playlist item {}",
+                video_content.video_id, content.id
+            );
+        }
+
+        Self {
+            ctx,
+            content: video_content,
+            video,
+            elapsed_seconds: 0.0,
+            completed_plays: 0,
+            max_plays: repeat_count_to_plays(content.repeat_count),
+            is_complete: false,
+        }
+    }
+
+    fn error(&self) -> Option<String> {
+        self.video
+            .is_none()
+            .then(|| format!("Video {} could not be loaded", self.content.video_id))
+    }
+
+    fn update(&mut self, dt: f32) {
+        let Some(video) = &self.video else {
+            self.is_complete = true;
+            return;
+        };
+        if self.is_complete {
+            return;
+        }
+
+        self.elapsed_seconds += dt;
+        let length = video.info.frame_count as f32 / video.info.fps.max(1) as f32;
+        while self.elapsed_seconds >= length {
+            self.completed_plays = self.completed_plays.saturating_add(1);
+            // The last frame stays on screen once all plays are done
+            if self
+                .max_plays
+                .is_some_and(|max_plays| self.completed_plays >= max_plays)
+            {
+                self.elapsed_seconds = length;
+                self.is_complete = true;
+                break;
+            }
+            self.elapsed_seconds -= length;
+        }
+    }
+
+    fn render(&self, canvas: &mut Box<dyn LedCanvas>) {
+        let Some(video) = &self.video else {
+            return;
+        };
+        let info = &video.info;
+
+        // Frame index from the play time, so playback keeps its speed at any frame rate
+        let frame = ((self.elapsed_seconds * info.fps as f32) as usize)
+            .min(info.frame_count.saturating_sub(1) as usize);
+        let Some(pixels) = video
+            .frames
+            .get(frame * info.frame_bytes()..(frame + 1) * info.frame_bytes())
+        else {
+            return;
+        };
+
+        // Videos decoded for another panel size are centered
+        let offset_x = (self.ctx.display_width - info.width as i32) / 2;
+        let offset_y = (self.ctx.display_height - info.height as i32) / 2;
+        for y in 0..info.height as i32 {
+            let panel_y = y + offset_y;
+            if panel_y < 0 || panel_y >= self.ctx.display_height {
+                continue;
+            }
+            for x in 0..info.width as i32 {
+                let panel_x = x + offset_x;
+                if panel_x < 0 || panel_x >= self.ctx.display_width {
+                    continue;
+                }
+                let index = ((y * info.width as i32 + x) * 3) as usize;
+                let [r, g, b] = self.ctx.apply_brightness([
+                    pixels[index],
+                    pixels[index + 1],
+                    pixels[index + 2],
+                ]);
+                canvas.set_pixel(panel_x as usize, panel_y as usize, r, g, b);
+            }
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.is_complete
+    }
+
+    fn reset(&mut self) {
+        self.elapsed_seconds = 0.0;
+        self.completed_plays = 0;
+        self.is_complete = false;
+    }
+
+    fn update_context(&mut self, ctx: RenderContext) {
+        self.ctx = ctx;
+    }
+
+    fn update_content(&mut self, content: &PlayListItem) {
+        if let ContentDetails::Video(video_content) = &content.content.data {
+            if self.content.video_id != video_content.video_id {
+                self.video = load_video(&video_content.video_id);
+            }
+            self.content = video_content.clone();
+            self.max_plays = repeat_count_to_plays(content.repeat_count);
+            self.reset();
+        }
+    }
+
+    fn completed_cycles(&self) -> Option<u32> {
+        Some(self.completed_plays)
+    }
+}
+
+fn repeat_count_to_plays(repeat_count: Option<u32>) -> Option<u32> {
+    match repeat_count {
+        Some(0) | None => None,
+        Some(value) => Some(value),
+    }
+}
+
+fn video_path(video_id: &str, extension: &str) -> PathBuf {
+    let base_dir = std::env::var("LED_STORAGE_DIR").unwrap_or_else(|_| DEFAULT_DIR.to_string());
+    Path::new(&base_dir)
+        .join(paths::VIDEOS_DIR)
+        .join(format!("{}.{}", video_id, extension))
+}
+
+fn load_video(video_id: &str) -> Option<DecodedVideo> {
+    let info_path = video_path(video_id, "json");
+    let info: VideoInfo = match std::fs::read_to_string(&info_path)
+        .map_err(|e| e.to_string())
+        .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+    {
+        Ok(info) => info,
+        Err(err) => {
+            error!("Failed to open video {}: {}", info_path.display(), err);
+            return None;
+        }
+    };
+
+    let frames_path = video_path(video_id, "rgb");
+    let frames = match std::fs::read(&frames_path) {
+        Ok(frames) => frames,
+        Err(err) => {
+            error!("Failed to open video {}: {}", frames_path.display(), err);
+            return None;
+        }
+    };
+    if info.frame_count == 0 || frames.len() < info.frame_bytes() * info.frame_count as usize {
+        error!("Video {} has fewer frames than expected", video_id);
+        return None;
+    }
+
+    debug!(
+        "Loaded video {} ({} frames at {}x{})",
+        video_id, info.frame_count, info.width, info.height
+    );
+    Some(DecodedVideo { info, frames })
+}
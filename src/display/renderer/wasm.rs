@@ -0,0 +1,356 @@
+//! Sandboxed WASM renderer plugins (enabled with the `wasm-plugins` feature).
+//!
+//! A plugin is a WASM module without imports that exports:
+//!
+//! ```text
+//! memory                          linear memory holding the frame buffer
+//! init(width: i32, height: i32) -> i32   called once per instance, non-zero means failure
+//! update(dt: f32)                 advance the animation by `dt` seconds
+//! render() -> i32                 offset of a `width * height * 3` RGB buffer in `memory`
+//! is_complete() -> i32            optional, non-zero ends the item early
+//! ```
+//!
+//! Every call runs with a fuel budget so a misbehaving module cannot stall the display
+//! loop, and linear memory is capped at `MAX_MEMORY_BYTES`. A module that traps or runs
+//! out of fuel is stopped and draws nothing until the item is reset.
+
+use crate::display::driver::LedCanvas;
+use crate::display::renderer::registry::{register_renderer_type, unregister_renderer};
+use crate::display::renderer::{RenderContext, Renderer};
+use crate::models::content::{ContentDetails, ContentType};
+use crate::models::playlist::PlayListItem;
+use log::{error, info};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+use std::time::Instant;
+use wasmtime::{
+    Config, Engine, Instance, Memory, Module, Store, StoreLimits, StoreLimitsBuilder, TypedFunc,
+};
+
+/// Upper bound for a plugin's linear memory
+pub const MAX_MEMORY_BYTES: usize = 16 * 1024 * 1024; // 16 MB
+/// Fuel available to `init`
+const INIT_FUEL: u64 = 100_000_000;
+/// Fuel available to each `update` and `render` call
+const FRAME_FUEL: u64 = 10_000_000;
+
+static ENGINE: Lazy<Engine> = Lazy::new(|| {
+    let mut config = Config::new();
+    config.consume_fuel(true);
+    Engine::new(&config).expect("Failed to create WASM engine")
+});
+
+/// Compiled modules of all loaded plugins, keyed by plugin name
+static MODULES: Lazy<RwLock<HashMap<String, Module>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Typed `init(width, height) -> status` export
+type InitFn = TypedFunc<(i32, i32), i32>;
+
+/// A running plugin instance with its resolved exports
+struct WasmInstance {
+    store: Store<StoreLimits>,
+    memory: Memory,
+    update: TypedFunc<f32, ()>,
+    render: TypedFunc<(), i32>,
+    is_complete: Option<TypedFunc<(), i32>>,
+}
+
+impl WasmInstance {
+    /// Instantiate a module and resolve its exports without calling `init`
+    fn instantiate(module: &Module) -> Result<(Self, InitFn), String> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(MAX_MEMORY_BYTES)
+            .instances(1)
+            .build();
+        let mut store = Store::new(&ENGINE, limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(INIT_FUEL).map_err(|e| e.to_string())?;
+
+        let instance = Instance::new(&mut store, module, &[]).map_err(|e| e.to_string())?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| "module does not export 'memory'".to_string())?;
+        let init = instance
+            .get_typed_func::<(i32, i32), i32>(&mut store, "init")
+            .map_err(|e| format!("invalid 'init' export: {}", e))?;
+        let update = instance
+            .get_typed_func::<f32, ()>(&mut store, "update")
+            .map_err(|e| format!("invalid 'update' export: {}", e))?;
+        let render = instance
+            .get_typed_func::<(), i32>(&mut store, "render")
+            .map_err(|e| format!("invalid 'render' export: {}", e))?;
+        let is_complete = match instance.get_func(&mut store, "is_complete") {
+            Some(func) => Some(
+                func.typed::<(), i32>(&store)
+                    .map_err(|e| format!("invalid 'is_complete' export: {}", e))?,
+            ),
+            None => None,
+        };
+
+        Ok((
+            Self {
+                store,
+                memory,
+                update,
+                render,
+                is_complete,
+            },
+            init,
+        ))
+    }
+
+    /// Instantiate a module and run its `init` export for the given display size
+    fn start(module: &Module, width: i32, height: i32) -> Result<Self, String> {
+        let (mut instance, init) = Self::instantiate(module)?;
+        let status = init
+            .call(&mut instance.store, (width, height))
+            .map_err(|e| format!("init failed: {}", e))?;
+        if status != 0 {
+            return Err(format!("init returned {}", status));
+        }
+        Ok(instance)
+    }
+
+    fn update(&mut self, dt: f32) -> Result<(), String> {
+        self.store.set_fuel(FRAME_FUEL).map_err(|e| e.to_string())?;
+        self.update
+            .call(&mut self.store, dt)
+            .map_err(|e| format!("update failed: {}", e))
+    }
+
+    /// Run `render` and copy the frame buffer out of linear memory
+    fn render(&mut self, len: usize) -> Result<Vec<u8>, String> {
+        self.store.set_fuel(FRAME_FUEL).map_err(|e| e.to_string())?;
+        let offset = self
+            .render
+            .call(&mut self.store, ())
+            .map_err(|e| format!("render failed: {}", e))? as u32 as usize;
+
+        self.memory
+            .data(&self.store)
+            .get(offset..offset.saturating_add(len))
+            .map(|frame| frame.to_vec())
+            .ok_or_else(|| format!("frame buffer at {} is out of bounds", offset))
+    }
+
+    fn is_complete(&mut self) -> Result<bool, String> {
+        let Some(is_complete) = self.is_complete.clone() else {
+            return Ok(false);
+        };
+        self.store.set_fuel(FRAME_FUEL).map_err(|e| e.to_string())?;
+        is_complete
+            .call(&mut self.store, ())
+            .map(|done| done != 0)
+            .map_err(|e| format!("is_complete failed: {}", e))
+    }
+}
+
+/// Renderer backed by an instance of a WASM plugin module
+pub struct WasmPluginRenderer {
+    name: String,
+    module: Option<Module>,
+    // `render` only gets `&self` but calling into the module needs the store mutably
+    instance: Mutex<Option<WasmInstance>>,
+    ctx: RenderContext,
+    duration: Option<u64>,
+    start_time: Instant,
+}
+
+impl WasmPluginRenderer {
+    fn start_instance(&self) -> Option<WasmInstance> {
+        let module = self.module.as_ref()?;
+        match WasmInstance::start(module, self.ctx.display_width, self.ctx.display_height) {
+            Ok(instance) => Some(instance),
+            Err(err) => {
+                error!("WASM plugin '{}' failed to start: {}", self.name, err);
+                None
+            }
+        }
+    }
+
+    fn restart_instance(&mut self) {
+        let instance = self.start_instance();
+        *self.instance.get_mut().unwrap() = instance;
+    }
+
+    /// Stop the instance after a trap so the faulty module is not called every frame
+    fn stop_instance(&self, slot: &mut Option<WasmInstance>, err: String) {
+        error!("WASM plugin '{}' stopped: {}", self.name, err);
+        *slot = None;
+    }
+}
+
+impl Renderer for WasmPluginRenderer {
+    fn new(content: &PlayListItem, ctx: RenderContext) -> Self {
+        let name = match &content.content.data {
+            ContentDetails::Plugin(plugin_content) => plugin_content.plugin.clone(),
+            #[allow(unreachable_patterns)]
+            _ => panic!("Expected plugin content"),
+        };
+        let module = MODULES
+            .read()
+            .ok()
+            .and_then(|modules| modules.get(&name).cloned());
+        if module.is_none() {
+            error!("WASM plugin '{}' is registered but not loaded", name);
+        }
+
+        let mut renderer = Self {
+            name,
+            module,
+            instance: Mutex::new(None),
+            ctx,
+            duration: content.duration,
+            start_time: Instant::now(),
+        };
+        renderer.restart_instance();
+        renderer
+    }
+
+    fn update(&mut self, dt: f32) {
+        let slot = self.instance.get_mut().unwrap();
+        if let Some(instance) = slot {
+            if let Err(err) = instance.update(dt) {
+                error!("WASM plugin '{}' stopped: {}", self.name, err);
+                *slot = None;
+            }
+        }
+    }
+
+    fn render(&self, canvas: &mut Box<dyn LedCanvas>) {
+        let width = self.ctx.display_width.max(0) as usize;
+        let height = self.ctx.display_height.max(0) as usize;
+
+        let mut slot = self.instance.lock().unwrap();
+        let Some(instance) = slot.as_mut() else {
+            return;
+        };
+        let frame = match instance.render(width * height * 3) {
+            Ok(frame) => frame,
+            Err(err) => {
+                self.stop_instance(&mut slot, err);
+                return;
+            }
+        };
+
+        for y in 0..height {
+            for x in 0..width {
+                let idx = (y * width + x) * 3;
+                let [r, g, b] =
+                    self.ctx
+                        .apply_brightness([frame[idx], frame[idx + 1], frame[idx + 2]]);
+                canvas.set_pixel(x, y, r, g, b);
+            }
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        if let Some(duration) = self.duration {
+            if self.start_time.elapsed().as_secs() >= duration {
+                return true;
+            }
+        }
+
+        let mut slot = self.instance.lock().unwrap();
+        let Some(instance) = slot.as_mut() else {
+            return false;
+        };
+        match instance.is_complete() {
+            Ok(done) => done,
+            Err(err) => {
+                self.stop_instance(&mut slot, err);
+                false
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.start_time = Instant::now();
+        self.restart_instance();
+    }
+
+    fn update_context(&mut self, ctx: RenderContext) {
+        let size_changed = ctx.display_width != self.ctx.display_width
+            || ctx.display_height != self.ctx.display_height;
+        self.ctx = ctx;
+        if size_changed {
+            self.restart_instance();
+        }
+    }
+
+    fn update_content(&mut self, content: &PlayListItem) {
+        self.duration = content.duration;
+    }
+
+    fn error(&self) -> Option<String> {
+        self.instance
+            .lock()
+            .unwrap()
+            .is_none()
+            .then(|| format!("WASM plugin '{}' is not running", self.name))
+    }
+}
+
+/// Whether `name` refers to a loaded WASM plugin
+pub fn is_wasm_plugin(name: &str) -> bool {
+    MODULES
+        .read()
+        .map(|modules| modules.contains_key(name))
+        .unwrap_or(false)
+}
+
+/// Compile a module for the plugin `name` and check its exports, without registering it.
+/// Compiling a large module takes a while, so the API runs this on a blocking thread.
+pub fn compile_wasm_plugin(name: &str, bytes: &[u8]) -> Result<Module, String> {
+    if ContentType::is_builtin(name) {
+        return Err(format!(
+            "Plugin name '{}' is reserved for a built-in content type",
+            name
+        ));
+    }
+    let module = Module::new(&ENGINE, bytes).map_err(|e| e.to_string())?;
+    WasmInstance::instantiate(&module)?;
+    Ok(module)
+}
+
+/// Register a compiled module as a renderer under `name`. Replaces a previously loaded plugin
+/// with the same name.
+pub fn register_wasm_plugin(name: &str, module: Module) -> Result<(), String> {
+    MODULES
+        .write()
+        .map_err(|e| e.to_string())?
+        .insert(name.to_string(), module);
+    register_renderer_type::<WasmPluginRenderer>(name);
+    Ok(())
+}
+
+/// Compile a module, check its exports and register it as a renderer under `name`.
+/// Replaces a previously loaded plugin with the same name.
+pub fn load_wasm_plugin(name: &str, bytes: &[u8]) -> Result<(), String> {
+    let module = compile_wasm_plugin(name, bytes)?;
+    register_wasm_plugin(name, module)
+}
+
+/// Unregister a WASM plugin. Running renderers keep their instance until they are dropped.
+pub fn unload_wasm_plugin(name: &str) {
+    if let Ok(mut modules) = MODULES.write() {
+        modules.remove(name);
+    }
+    unregister_renderer(name);
+}
+
+/// Load every stored plugin. Returns the number loaded.
+pub fn load_wasm_plugins(plugins: Vec<(String, Vec<u8>)>) -> usize {
+    let mut loaded = 0;
+    for (name, bytes) in plugins {
+        match load_wasm_plugin(&name, &bytes) {
+            Ok(()) => {
+                info!("Loaded WASM plugin '{}'", name);
+                loaded += 1;
+            }
+            Err(err) => error!("Failed to load WASM plugin '{}': {}", name, err),
+        }
+    }
+    loaded
+}
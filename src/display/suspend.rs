@@ -0,0 +1,122 @@
+//! Low-power state while the display is switched off: the panel is left on a black frame, the
+//! driver is no longer fed frames and, while the process is allowed to raise it again, the
+//! real-time scheduling the controller runs with is handed back until the display is switched
+//! on again
+
+use log::{debug, warn};
+use std::time::Duration;
+
+// How often the display loop still runs while suspended, to follow API changes and timeouts
+pub const SUSPENDED_FRAME_INTERVAL: Duration = Duration::from_millis(100);
+
+// Scheduling of one thread before it was released
+#[cfg(target_os = "linux")]
+struct ThreadScheduling {
+    tid: libc::pid_t,
+    policy: libc::c_int,
+    priority: libc::c_int,
+    nice: libc::c_int,
+}
+
+/// Scheduling the controller's threads had before suspending, restored when dropped
+#[derive(Default)]
+pub struct Suspension {
+    #[cfg(target_os = "linux")]
+    released: Vec<ThreadScheduling>,
+}
+
+impl Suspension {
+    /// Move every real-time or raised thread of the process, including the driver's refresh
+    /// thread, to normal scheduling. Without root, e.g. after privileges were dropped, the
+    /// priority could not be restored on power-on, so scheduling is left as it is.
+    pub fn release_priority() -> Self {
+        let mut suspension = Self::default();
+        #[cfg(target_os = "linux")]
+        {
+            if unsafe { libc::geteuid() } != 0 {
+                debug!("Keeping thread scheduling, it could not be raised again without root");
+                return suspension;
+            }
+            let Ok(tasks) = std::fs::read_dir("/proc/self/task") else {
+                warn!("Could not list threads to release their scheduling priority");
+                return suspension;
+            };
+            for tid in tasks.flatten().filter_map(|task| {
+                task.file_name()
+                    .to_str()
+                    .and_then(|name| name.parse::<libc::pid_t>().ok())
+            }) {
+                if let Some(scheduling) = release_thread(tid) {
+                    suspension.released.push(scheduling);
+                }
+            }
+            debug!(
+                "Released the scheduling priority of {} thread(s)",
+                suspension.released.len()
+            );
+        }
+        suspension
+    }
+}
+
+impl Drop for Suspension {
+    fn drop(&mut self) {
+        #[cfg(target_os = "linux")]
+        for thread in self.released.drain(..) {
+            let param = libc::sched_param {
+                sched_priority: thread.priority,
+            };
+            let restored = unsafe {
+                libc::sched_setscheduler(thread.tid, thread.policy, &param) == 0
+                    && libc::setpriority(libc::PRIO_PROCESS, thread.tid as libc::id_t, thread.nice)
+                        == 0
+            };
+            // Threads that ended in the meantime cannot be found anymore and need no restore
+            let error = std::io::Error::last_os_error();
+            if !restored && error.raw_os_error() != Some(libc::ESRCH) {
+                warn!(
+                    "Failed to restore the scheduling priority of thread {}: {}",
+                    thread.tid, error
+                );
+            }
+        }
+    }
+}
+
+// Switch one thread to SCHED_OTHER at nice 0, returning its previous scheduling if it changed
+#[cfg(target_os = "linux")]
+fn release_thread(tid: libc::pid_t) -> Option<ThreadScheduling> {
+    unsafe {
+        let policy = libc::sched_getscheduler(tid);
+        let mut param = libc::sched_param { sched_priority: 0 };
+        if policy < 0 || libc::sched_getparam(tid, &mut param) != 0 {
+            return None;
+        }
+        // getpriority returns -1 for errors as well as for nice -1, errno tells them apart
+        *libc::__errno_location() = 0;
+        let nice = libc::getpriority(libc::PRIO_PROCESS, tid as libc::id_t);
+        if nice == -1 && *libc::__errno_location() != 0 {
+            return None;
+        }
+        if policy == libc::SCHED_OTHER && nice >= 0 {
+            return None;
+        }
+
+        let normal = libc::sched_param { sched_priority: 0 };
+        if libc::sched_setscheduler(tid, libc::SCHED_OTHER, &normal) != 0 {
+            warn!(
+                "Failed to release real-time scheduling of thread {}: {}",
+                tid,
+                std::io::Error::last_os_error()
+            );
+        }
+        libc::setpriority(libc::PRIO_PROCESS, tid as libc::id_t, 0);
+
+        Some(ThreadScheduling {
+            tid,
+            policy,
+            priority: param.sched_priority,
+            nice,
+        })
+    }
+}
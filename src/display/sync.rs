@@ -0,0 +1,220 @@
+//! Synchronized playback: a master instance broadcasts its playlist position over UDP
+//! (multicast by default) and followers restart and fast-forward their active item to match,
+//! so a row of identical signs transitions and scrolls in lockstep
+
+use crate::display::manager::DisplayManager;
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::net::UdpSocket;
+
+// Multicast group and port used when no --sync-group is configured
+pub const DEFAULT_SYNC_GROUP: &str = "239.255.76.83:5683";
+
+// Followers play on their own when the master has not been heard from for this long
+pub const SYNC_TIMEOUT: Duration = Duration::from_secs(2);
+
+// Followers restart their active item when it is further than this from the master's position
+pub const SYNC_TOLERANCE: Duration = Duration::from_millis(40);
+
+// How often the master looks for transitions, and sends its position without one
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+const BROADCAST_INTERVAL: Duration = Duration::from_millis(250);
+
+// Followers ignore positions further into an item than this, no item plays for a year
+const MAX_ITEM_ELAPSED: Duration = Duration::from_secs(365 * 24 * 60 * 60);
+
+// Largest accepted datagram, item IDs are short
+const MAX_PACKET_BYTES: usize = 1024;
+
+/// Role of this instance in synchronized playback
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SyncMode {
+    Master,
+    Follower,
+}
+
+impl SyncMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "master" => Some(Self::Master),
+            "follower" => Some(Self::Follower),
+            _ => None,
+        }
+    }
+}
+
+/// Playlist position broadcast by the master
+#[derive(Serialize, Deserialize, Debug)]
+struct SyncMessage {
+    // Random per master process, so followers notice a restarted master
+    session: u64,
+    // Master clock: milliseconds since the master started broadcasting
+    clock_ms: u64,
+    item_id: String,
+    index: usize,
+    // Master clock time at which the active item started
+    item_started_ms: u64,
+}
+
+/// Broadcast the playlist position to `group` whenever the item changes and a few
+/// times per second in between
+pub async fn sync_master(display: Arc<tokio::sync::Mutex<DisplayManager>>, group: SocketAddr) {
+    let bind_addr: IpAddr = if group.is_ipv4() {
+        Ipv4Addr::UNSPECIFIED.into()
+    } else {
+        Ipv6Addr::UNSPECIFIED.into()
+    };
+    let socket = match UdpSocket::bind((bind_addr, 0)).await {
+        Ok(socket) => socket,
+        Err(e) => {
+            error!("Failed to create sync socket: {}", e);
+            return;
+        }
+    };
+    if let Err(e) = socket.set_broadcast(true) {
+        warn!("Failed to allow broadcast sync messages: {}", e);
+    }
+    info!(
+        "Broadcasting playback position to followers on udp://{}",
+        group
+    );
+
+    let session = rand::random();
+    let clock = Instant::now();
+    let mut last_sent: Option<(String, u64, Instant)> = None;
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+
+        let message = {
+            let display = display.lock().await;
+            let Some(item) = display.active_item() else {
+                continue;
+            };
+            let clock_ms = clock.elapsed().as_millis() as u64;
+            SyncMessage {
+                session,
+                clock_ms,
+                item_id: item.id.clone(),
+                index: display.playlist.active_index,
+                item_started_ms: clock_ms
+                    .saturating_sub(display.active_item_elapsed().as_millis() as u64),
+            }
+        };
+
+        // Send right away when the item changed or restarted, otherwise only periodically
+        let changed = last_sent.as_ref().is_none_or(|(item_id, started_ms, _)| {
+            *item_id != message.item_id || message.item_started_ms.abs_diff(*started_ms) > 5
+        });
+        let due = last_sent
+            .as_ref()
+            .is_none_or(|(_, _, sent)| sent.elapsed() >= BROADCAST_INTERVAL);
+        if !changed && !due {
+            continue;
+        }
+
+        let payload = serde_json::to_vec(&message).expect("sync message serializes");
+        if let Err(e) = socket.send_to(&payload, group).await {
+            debug!("Failed to send sync message: {}", e);
+        }
+        last_sent = Some((message.item_id, message.item_started_ms, Instant::now()));
+    }
+}
+
+/// Receive the master's playlist position on `group` and keep the display aligned with it
+pub async fn sync_follower(display: Arc<tokio::sync::Mutex<DisplayManager>>, group: SocketAddr) {
+    let bind_addr: IpAddr = if group.is_ipv4() {
+        Ipv4Addr::UNSPECIFIED.into()
+    } else {
+        Ipv6Addr::UNSPECIFIED.into()
+    };
+    let socket = match UdpSocket::bind((bind_addr, group.port())).await {
+        Ok(socket) => socket,
+        Err(e) => {
+            error!(
+                "Failed to bind sync listener to port {}: {}",
+                group.port(),
+                e
+            );
+            return;
+        }
+    };
+    let joined = match group.ip() {
+        IpAddr::V4(ip) if ip.is_multicast() => socket.join_multicast_v4(ip, Ipv4Addr::UNSPECIFIED),
+        IpAddr::V6(ip) if ip.is_multicast() => socket.join_multicast_v6(&ip, 0),
+        _ => Ok(()),
+    };
+    if let Err(e) = joined {
+        error!("Failed to join sync group {}: {}", group, e);
+        return;
+    }
+    info!("Following playback position from udp://{}", group);
+
+    let clock = Instant::now();
+    let mut master: Option<u64> = None;
+    // Master clock minus local clock. The smallest sample has the least network delay,
+    // larger ones pull it up slowly so it follows clock drift between the two devices.
+    let mut offset_ms = 0i64;
+    let mut buffer = vec![0u8; MAX_PACKET_BYTES];
+    loop {
+        let len = match socket.recv(&mut buffer).await {
+            Ok(len) => len,
+            Err(e) => {
+                warn!("Failed to receive sync message: {}", e);
+                continue;
+            }
+        };
+        let local_ms = clock.elapsed().as_millis() as i64;
+        let message: SyncMessage = match serde_json::from_slice(&buffer[..len]) {
+            Ok(message) => message,
+            Err(e) => {
+                debug!("Ignoring invalid sync message: {}", e);
+                continue;
+            }
+        };
+
+        // An item cannot start after the message was sent or have run for longer than any
+        // master has, such messages are corrupt or spoofed
+        if message.item_started_ms > message.clock_ms
+            || message.clock_ms - message.item_started_ms > MAX_ITEM_ELAPSED.as_millis() as u64
+            || message.clock_ms > i64::MAX as u64
+        {
+            debug!(
+                "Ignoring sync message with implausible item start {} ms at {} ms",
+                message.item_started_ms, message.clock_ms
+            );
+            continue;
+        }
+
+        let sample = message.clock_ms as i64 - local_ms;
+        if master != Some(message.session) {
+            info!("Following sync master session {:x}", message.session);
+            master = Some(message.session);
+            offset_ms = sample;
+        } else if sample < offset_ms {
+            offset_ms = sample;
+        } else {
+            offset_ms += (sample - offset_ms).min(1);
+        }
+
+        // Where the master is in its item right now, on the shared clock
+        let master_now_ms = local_ms.saturating_add(offset_ms);
+        let elapsed_ms = master_now_ms
+            .saturating_sub(message.item_started_ms as i64)
+            .clamp(0, MAX_ITEM_ELAPSED.as_millis() as i64) as u64;
+
+        let mut display = display.lock().await;
+        if display.follow_sync(
+            &message.item_id,
+            message.index,
+            Duration::from_millis(elapsed_ms),
+        ) {
+            debug!(
+                "Aligned with sync master on item {} at {} ms",
+                message.item_id, elapsed_ms
+            );
+        }
+    }
+}
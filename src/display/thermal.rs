@@ -0,0 +1,136 @@
+//! Thermal protection: watches the SoC temperature and dims the panel and lowers the frame
+//! rate while the Pi runs hot, restoring both gradually once it has cooled down
+
+use crate::display::manager::DisplayManager;
+use chrono::Utc;
+use log::{info, warn};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+// Temperature of the SoC in millidegrees Celsius
+const THERMAL_ZONE: &str = "/sys/class/thermal/thermal_zone0/temp";
+
+// How often the temperature is read
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+// Brightness is lowered by STEP_DOWN percent per check while hot, down to MIN_PERCENT,
+// and raised by STEP_UP percent per check once cool again
+const STEP_DOWN: u8 = 10;
+const STEP_UP: u8 = 5;
+const MIN_PERCENT: u8 = 30;
+
+// Frame rate the display loop is held to while throttling
+pub const THROTTLED_FPS: u32 = 30;
+
+static STATUS: Mutex<Option<ThermalStatus>> = Mutex::new(None);
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ThermalStatus {
+    // Whether thermal throttling is configured (--thermal-throttle-c above 0)
+    pub enabled: bool,
+    // Whether the temperature sensor could be read
+    pub available: bool,
+    pub temperature_c: Option<f32>,
+    pub throttle_c: f32,
+    pub resume_c: f32,
+    pub throttling: bool,
+    // Share of the configured brightness currently shown
+    pub brightness_percent: u8,
+    pub max_fps: Option<u32>,
+    pub checked_at: Option<String>,
+}
+
+/// Last thermal state, or a disabled status if the monitor is not running
+pub fn status() -> ThermalStatus {
+    STATUS.lock().unwrap().clone().unwrap_or(ThermalStatus {
+        enabled: false,
+        available: false,
+        temperature_c: None,
+        throttle_c: 0.0,
+        resume_c: 0.0,
+        throttling: false,
+        brightness_percent: 100,
+        max_fps: None,
+        checked_at: None,
+    })
+}
+
+fn read_temperature() -> Option<f32> {
+    let millidegrees: i64 = std::fs::read_to_string(THERMAL_ZONE)
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(millidegrees as f32 / 1000.0)
+}
+
+/// Throttle the display while the SoC is at or above `throttle_c` and restore it once it has
+/// cooled down to `resume_c`
+pub async fn monitor(
+    display: Arc<tokio::sync::Mutex<DisplayManager>>,
+    throttle_c: f32,
+    resume_c: f32,
+) {
+    let mut status = ThermalStatus {
+        enabled: true,
+        available: false,
+        temperature_c: None,
+        throttle_c,
+        resume_c,
+        throttling: false,
+        brightness_percent: 100,
+        max_fps: None,
+        checked_at: None,
+    };
+
+    if read_temperature().is_none() {
+        info!(
+            "No temperature sensor at {}, thermal throttling is inactive",
+            THERMAL_ZONE
+        );
+        *STATUS.lock().unwrap() = Some(status);
+        return;
+    }
+    status.available = true;
+    info!(
+        "Thermal throttling above {:.1} °C, resuming below {:.1} °C",
+        throttle_c, resume_c
+    );
+
+    loop {
+        if let Some(temperature) = read_temperature() {
+            let was_throttling = status.throttling;
+            if temperature >= throttle_c {
+                status.throttling = true;
+                status.brightness_percent = status
+                    .brightness_percent
+                    .saturating_sub(STEP_DOWN)
+                    .max(MIN_PERCENT);
+            } else if temperature <= resume_c && status.throttling {
+                status.brightness_percent = (status.brightness_percent + STEP_UP).min(100);
+                status.throttling = status.brightness_percent < 100;
+            }
+            status.max_fps = status.throttling.then_some(THROTTLED_FPS);
+
+            if status.throttling && !was_throttling {
+                warn!(
+                    "SoC at {:.1} °C, reducing brightness and frame rate",
+                    temperature
+                );
+            } else if !status.throttling && was_throttling {
+                info!("SoC cooled down to {:.1} °C, throttling ended", temperature);
+            }
+
+            display
+                .lock()
+                .await
+                .set_thermal_limit(status.brightness_percent, status.max_fps);
+            status.temperature_c = Some(temperature);
+        }
+        status.checked_at = Some(Utc::now().to_rfc3339());
+        *STATUS.lock().unwrap() = Some(status.clone());
+
+        tokio::time::sleep(CHECK_INTERVAL).await;
+    }
+}
@@ -2,24 +2,34 @@ use crate::display::manager::DisplayManager;
 use crate::models::animation::AnimationContent;
 use crate::models::clock::ClockFormat;
 use crate::models::content::ContentDetails;
-use crate::web::api::events::EventState;
-use log::info;
-use std::sync::{Arc, Mutex};
+use crate::models::settings::ClosedBehavior;
+use crate::storage::app_storage::SharedStorage;
+use crate::utils::{operating_hours, timezone};
+use crate::web::api::events::{NowPlayingEvent, PlaylistAction, SharedEventState, WarningEvent};
+use log::{error, info};
+use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
 
 // Display loop function that manages the update cycle
 pub async fn display_loop(
     display: Arc<tokio::sync::Mutex<DisplayManager>>,
-    event_state: Arc<Mutex<EventState>>,
+    storage: SharedStorage,
+    event_state: SharedEventState,
 ) {
     info!("Starting display update loop");
     let mut last_time = Instant::now();
     let mut frame_count = 0;
     let mut last_stats_time = Instant::now();
+    let mut last_progress_time = Instant::now();
+    let mut last_expiry_check = Instant::now();
 
     // Preview timeout in seconds
     const PREVIEW_TIMEOUT: u64 = 5;
+    // Interval between now-playing progress events in seconds
+    const PROGRESS_INTERVAL: u64 = 1;
+    // Interval between checks for expired playlist items
+    const EXPIRY_INTERVAL: Duration = Duration::from_secs(1);
 
     loop {
         let now = Instant::now();
@@ -31,17 +41,53 @@ pub async fn display_loop(
         // Check for preview mode timeout
         if let Some(_session_id) = display_guard.check_preview_timeout(PREVIEW_TIMEOUT) {
             // If preview timed out, broadcast the editor unlock event
-            if let Ok(event_state_guard) = event_state.lock() {
-                event_state_guard.broadcast_editor_lock(false, None);
+            event_state.lock().broadcast_editor_lock(false, None);
+        }
+
+        // Switch between playlist content, the welcome message and the idle screen
+        let idle_changed = display_guard.update_idle_state();
+
+        // Skip or delete playlist items past their expiry time
+        let mut expiry_transition = false;
+        if now.duration_since(last_expiry_check) >= EXPIRY_INTERVAL {
+            last_expiry_check = now;
+            if let Some(expired) = display_guard.check_expired_items() {
+                expiry_transition = expired.skipped_active;
+                if !expired.deleted.is_empty() {
+                    {
+                        let mut storage_guard = storage.lock();
+                        if !storage_guard.save_playlist(&display_guard.playlist) {
+                            error!("Failed to save playlist after deleting expired items");
+                        }
+                    }
+                    event_state.lock().broadcast_playlist_update(
+                        display_guard.playlist.items.clone(),
+                        PlaylistAction::Delete,
+                    );
+                }
             }
         }
 
+        // Skip items whose renderer stopped making progress
+        let watchdog_trip = display_guard.check_watchdog();
+        if let Some(trip) = &watchdog_trip {
+            event_state.lock().broadcast_warning(WarningEvent {
+                code: "watchdog".to_string(),
+                message: format!(
+                    "Item did not finish after {}s and was skipped",
+                    trip.elapsed.as_secs()
+                ),
+                item_id: Some(trip.item_id.clone()),
+            });
+        }
+
         // Check if transition to next item is needed
-        let transition_occurred = display_guard.check_transition();
+        let transition_occurred =
+            expiry_transition || watchdog_trip.is_some() || display_guard.check_transition();
         if transition_occurred {
             let current = display_guard.get_current_content();
             let index = display_guard.playlist.active_index;
-            let total = display_guard.playlist.items.len();
+            let total = display_guard.rotation_len();
 
             // Get content description
             let content_desc = match &current.content.data {
@@ -81,9 +127,43 @@ pub async fn display_loop(
                         AnimationContent::Sparkle { .. } => "Sparkle",
                         AnimationContent::MosaicTwinkle { .. } => "Mosaic Twinkle",
                         AnimationContent::Plasma { .. } => "Plasma Flow",
+                        AnimationContent::Meteor { .. } => "Meteor",
+                        AnimationContent::Fireworks { .. } => "Fireworks",
+                        AnimationContent::NoiseField { .. } => "Noise Field",
+                        AnimationContent::PixelWeather { .. } => "Pixel Weather",
                     };
                     format!("Animation: {}", preset)
                 }
+                ContentDetails::Plugin(plugin_content) => {
+                    format!("Plugin: {}", plugin_content.plugin)
+                }
+                ContentDetails::QrCode(qr_content) => {
+                    format!("QR code: {}", qr_content.data)
+                }
+                ContentDetails::Barcode(barcode_content) => {
+                    format!(
+                        "Barcode: {} ({:?})",
+                        barcode_content.data, barcode_content.format
+                    )
+                }
+                ContentDetails::BigNumber(number_content) => {
+                    format!("Big number: {}", number_content.value)
+                }
+                ContentDetails::Countdown(countdown_content) => {
+                    format!("Countdown to {}", countdown_content.target)
+                }
+                ContentDetails::Command(command_content) => {
+                    format!("Command: {}", command_content.command)
+                }
+                ContentDetails::Slideshow(slideshow_content) => {
+                    format!("Slideshow: {} images", slideshow_content.images.len())
+                }
+                ContentDetails::Video(video_content) => {
+                    format!("Video: {}", video_content.video_id)
+                }
+                ContentDetails::Stream(stream_content) => {
+                    format!("Stream: {}", stream_content.url)
+                }
             };
 
             info!(
@@ -94,11 +174,48 @@ pub async fn display_loop(
             );
         }
 
+        // Publish playback progress on every transition and periodically in between
+        if transition_occurred
+            || idle_changed
+            || now.duration_since(last_progress_time).as_secs() >= PROGRESS_INTERVAL
+        {
+            let progress =
+                NowPlayingEvent::from_display(&display_guard, transition_occurred || idle_changed);
+            event_state.lock().broadcast_now_playing(progress);
+            last_progress_time = now;
+        }
+
+        // Step any running brightness fade before rendering
+        display_guard.update_brightness_fade();
+        display_guard.update_outro();
+
         // Update the renderers with the elapsed time
         display_guard.update_renderer(dt);
 
+        // Show the error placeholder if the active item failed to render
+        if let Some(failure) = display_guard.check_renderer_health() {
+            event_state.lock().broadcast_warning(WarningEvent {
+                code: "render_error".to_string(),
+                message: failure.error,
+                item_id: Some(failure.item_id),
+            });
+        }
+
         // Update the display
         display_guard.update_display();
+        let min_frame_interval = display_guard.min_frame_interval();
+
+        // Tell clients when frames start being dimmed to protect the power supply
+        if let Some(throttle) = display_guard.check_power_guard() {
+            event_state.lock().broadcast_warning(WarningEvent {
+                code: "power_limit".to_string(),
+                message: format!(
+                    "Estimated current of {:.2} A exceeds the {:.2} A limit, brightness reduced to {}%",
+                    throttle.estimated_amps, throttle.limit_amps, throttle.percent
+                ),
+                item_id: display_guard.active_item().map(|item| item.id.clone()),
+            });
+        }
 
         drop(display_guard);
 
@@ -112,6 +229,103 @@ pub async fn display_loop(
             last_stats_time = now;
         }
 
-        tokio::time::sleep(Duration::from_millis(2)).await;
+        // Frames are capped while thermal throttling, otherwise the loop only yields briefly
+        let sleep = min_frame_interval
+            .saturating_sub(now.elapsed())
+            .max(Duration::from_millis(2));
+        tokio::time::sleep(sleep).await;
+    }
+}
+
+// Purge trashed items past their retention time and the images only they used,
+// at startup and then periodically
+pub async fn trash_purge_loop(
+    display: Arc<tokio::sync::Mutex<DisplayManager>>,
+    storage: SharedStorage,
+) {
+    // Interval between purges of the trash
+    const PURGE_INTERVAL: Duration = Duration::from_secs(3600);
+
+    loop {
+        {
+            let display_guard = display.lock().await;
+            let trash_days = display_guard.config().trash_days;
+            let storage_guard = storage.lock();
+            let mut trash = storage_guard.load_trash();
+            let purged = trash.purge_expired(trash_days, chrono::Utc::now());
+            if purged > 0 {
+                if storage_guard.save_trash(&trash) {
+                    info!("Purged {} item(s) from the trash", purged);
+                } else {
+                    error!("Failed to save trash after purging expired items");
+                }
+            }
+        }
+
+        tokio::time::sleep(PURGE_INTERVAL).await;
+    }
+}
+
+// Apply the closed behavior of the operating hours whenever the sign's place opens or closes
+pub async fn operating_hours_loop(
+    display: Arc<tokio::sync::Mutex<DisplayManager>>,
+    event_state: SharedEventState,
+) {
+    // Interval between checks of the operating hours
+    const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+    // Behavior applied for the current closing, None while open. The display is only switched
+    // when this changes, so switching it on by hand while closed lasts until the next opening.
+    let mut applied: Option<ClosedBehavior> = None;
+    loop {
+        let hours = operating_hours::get();
+        let closed =
+            (!hours.is_open_at(timezone::now().naive_local())).then_some(hours.when_closed);
+        if closed != applied {
+            let mut display_guard = display.lock().await;
+            display_guard.set_closed(closed == Some(ClosedBehavior::Idle));
+            if closed == Some(ClosedBehavior::Off) {
+                info!("Operating hours: closed, switching the display off");
+                display_guard.set_powered_on(false);
+                event_state.lock().broadcast_power(false);
+            } else if applied == Some(ClosedBehavior::Off) {
+                info!("Operating hours: open, switching the display on");
+                display_guard.set_powered_on(true);
+                event_state.lock().broadcast_power(true);
+            }
+            applied = closed;
+        }
+
+        tokio::time::sleep(CHECK_INTERVAL).await;
+    }
+}
+
+// Write queued playlist changes once they have waited --save-interval-seconds
+pub async fn pending_save_loop(storage: SharedStorage) {
+    // Interval between checks for a queued playlist
+    const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+    loop {
+        tokio::time::sleep(CHECK_INTERVAL).await;
+        storage.lock().flush_pending(false);
+    }
+}
+
+// Remove uploaded images no item uses every --cleanup-interval-minutes
+pub async fn image_cleanup_loop(
+    display: Arc<tokio::sync::Mutex<DisplayManager>>,
+    storage: SharedStorage,
+    interval: Duration,
+) {
+    loop {
+        tokio::time::sleep(interval).await;
+        let (playlist, grace) = {
+            let display_guard = display.lock().await;
+            (
+                display_guard.playlist.clone(),
+                display_guard.config().cleanup_grace(),
+            )
+        };
+        storage.lock().cleanup_unused_images(&playlist, grace);
     }
 }
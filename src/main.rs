@@ -5,31 +5,76 @@ mod storage;
 mod utils;
 mod web;
 
+use crate::display::brightness_mask::BrightnessMask;
 use crate::display::driver::create_driver;
-use crate::display::update_loop::display_loop;
+use crate::display::sync::SyncMode;
+use crate::display::update_loop::{
+    display_loop, image_cleanup_loop, operating_hours_loop, pending_save_loop, trash_purge_loop,
+};
+use crate::models::collection::Collection;
+use crate::models::panel::{adapt_item, PanelSize};
 use crate::storage::app_storage::create_storage;
+use crate::storage::recovery::{set_report, MissingMedia, PlaylistRecovery, StartupReport};
 use crate::utils::privilege::{check_root_privileges, drop_privileges};
-use crate::web::api::display::get_display_info;
-use crate::web::api::events::{brightness_events, editor_lock_events, playlist_events, EventState};
-use crate::web::api::images::{fetch_image, fetch_image_thumbnail, upload_image, MAX_IMAGE_BYTES};
+use crate::web::api::actions::run_action;
+use crate::web::api::collections::{
+    create_collection, delete_collection, get_collection, get_collections, update_collection,
+};
+use crate::web::api::display::{
+    cancel_color_order_detection, delete_brightness_mask, get_brightness_mask, get_dead_pixels,
+    get_display_info, record_display, report_color_order, start_color_order_detection,
+    update_dead_pixels, upload_brightness_mask,
+};
+use crate::web::api::events::{
+    all_events, brightness_events, editor_lock_events, event_clients, palette_events,
+    playlist_events, preview_events, variable_events, EventState,
+};
+use crate::web::api::images::{
+    edit_image, fetch_image, fetch_image_thumbnail, upload_image, upload_image_from_url,
+};
+use crate::web::api::maintenance::run_cleanup;
+use crate::web::api::palettes::{delete_palette, get_palette, get_palettes, put_palette};
 use crate::web::api::playlist::{
-    create_playlist_item, delete_playlist_item, get_playlist_item, get_playlist_items,
-    reorder_playlist_items, update_playlist_item,
+    create_playlist_item, delete_playlist_item, get_now_playing, get_playlist_item,
+    get_playlist_item_health, get_playlist_items, get_trash, patch_playlist_item,
+    reorder_playlist_items, restore_trash_item, set_tag_enabled, update_big_number_value,
+    update_playlist_item, validate_playlist_item,
 };
+use crate::web::api::plugins::{delete_plugin, list_plugins, upload_plugin, MAX_PLUGIN_BYTES};
 use crate::web::api::preview::{
-    check_session_owner, exit_preview_mode, get_preview_mode_status, ping_preview_mode,
-    start_preview_mode, update_preview,
+    check_session_owner, commit_preview, estimate_text, exit_preview_mode, get_preview_mode_status,
+    ping_preview_mode, start_preview_mode, update_preview,
+};
+use crate::web::api::profiles::{delete_profile, get_profile, list_profiles, put_profile};
+use crate::web::api::settings::{
+    get_brightness, get_content_defaults, get_effective_settings, get_idle_settings,
+    get_operating_hours, get_welcome_settings, update_brightness, update_content_defaults,
+    update_idle_settings, update_operating_hours, update_welcome_settings,
+};
+use crate::web::api::setup::{complete_setup, get_setup};
+use crate::web::api::simple::{get_simple_state, update_simple_state};
+use crate::web::api::system::{
+    get_commands, get_metrics, get_startup_report, get_storage_usage, get_thermal_status,
+    get_time_status, ping,
+};
+use crate::web::api::text::measure_text;
+use crate::web::api::variables::{delete_variable, get_variable, get_variables, put_variable};
+use crate::web::api::videos::{delete_video, list_videos, upload_video, MAX_VIDEO_BYTES};
+use crate::web::etag::with_etag;
+use crate::web::request_log::log_request;
+#[cfg(feature = "embedded-ui")]
+use crate::web::static_assets::{
+    index_handler, manifest_handler, next_assets_handler, service_worker_handler, set_base_path,
+    static_assets_handler,
 };
-use crate::web::api::settings::{get_brightness, update_brightness};
-use crate::web::static_assets::{index_handler, next_assets_handler, static_assets_handler};
 use axum::{
     extract::DefaultBodyLimit,
-    routing::{delete, get, post, put},
+    middleware,
+    routing::{delete, get, patch, post, put},
     Router,
 };
-use chrono::Local;
 use colored::*;
-use config::init_config;
+use config::{init_config, CliArgs};
 use display::manager::DisplayManager;
 use env_logger::Builder;
 use log::{debug, error, info, warn, LevelFilter};
@@ -67,7 +112,9 @@ async fn main() {
             writeln!(
                 buf,
                 "{} [{}] - {}",
-                Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                utils::timezone::to_local(chrono::Utc::now())
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string(),
                 level,
                 message
             )
@@ -78,10 +125,16 @@ async fn main() {
 
     info!("Starting LED Sign Controller");
 
-    // Check for root privileges before doing anything else
-    if let Err(e) = check_root_privileges() {
-        error!("{}", e);
-        std::process::exit(1);
+    // Check for root privileges before doing anything else.
+    // The benchmark and golden frame check never touch the hardware, so they can run as a
+    // regular user.
+    let provided = CliArgs::provided_options();
+    let bench_mode = provided.contains("bench") || provided.contains("golden_frames");
+    if !bench_mode {
+        if let Err(e) = check_root_privileges() {
+            error!("{}", e);
+            std::process::exit(1);
+        }
     }
 
     // Set higher priority for the process if possible
@@ -102,8 +155,18 @@ async fn main() {
         }
     }
 
+    // Storage comes first so settings saved from the web interface can be applied
+    let storage = create_storage(None);
+
     // Initialize configuration
-    let display_config = init_config();
+    let (stored_config, profiles) = {
+        let storage_guard = storage.lock();
+        (
+            storage_guard.load_config().unwrap_or_default(),
+            storage_guard.load_profiles(),
+        )
+    };
+    let display_config = init_config(stored_config, &profiles);
 
     // Validate configuration
     if let Err(errors) = display_config.validate() {
@@ -113,8 +176,135 @@ async fn main() {
         std::process::exit(1);
     }
 
-    // After configuration validation, but before driver initialization
-    let storage = create_storage(None);
+    if let Some(dir) = &display_config.cache_dir {
+        info!("Keeping thumbnails in {}", dir);
+        storage.lock().set_cache_dir(dir);
+    }
+
+    // Compare rendered frames with the golden files instead of running the controller.
+    // This comes before the time zone and locale are applied, the check pins its own.
+    if let Some(dir) = &display_config.golden_frames {
+        #[cfg(feature = "test-util")]
+        {
+            if let Err(e) = display::golden::run_golden_frames(
+                &display_config,
+                std::path::Path::new(dir),
+                display_config.update_golden,
+            ) {
+                error!("Golden frame check failed: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        #[cfg(not(feature = "test-util"))]
+        {
+            error!(
+                "Cannot check golden frames in {}: built without the test-util feature",
+                dir
+            );
+            std::process::exit(1);
+        }
+    }
+
+    // Clock content and log timestamps use the configured time zone and locale from here on
+    if let Some(timezone) = &display_config.timezone {
+        if let Ok(timezone) = timezone.parse() {
+            utils::timezone::set(timezone);
+            info!("Using time zone {}", utils::timezone::name());
+        }
+    }
+    if let Some(strings) = display_config
+        .locale
+        .as_deref()
+        .and_then(utils::locale::lookup)
+    {
+        utils::locale::set(strings);
+        info!("Using locale {}", strings.code);
+    }
+
+    // Command content may only run what the admin allowed
+    let allowlist = display_config.command_allowlist();
+    if !allowlist.is_empty() {
+        info!(
+            "Allowed commands: {}",
+            allowlist.keys().cloned().collect::<Vec<_>>().join(", ")
+        );
+    }
+    utils::commands::set(allowlist);
+
+    // URLs from API clients may only reach public addresses unless the admin allows more
+    if display_config.allow_local_urls {
+        warn!("Image links, HTTP checks and camera streams may reach local addresses");
+    }
+    utils::remote_url::set_allow_local(display_config.allow_local_urls);
+
+    // Uploads cut off by the last shutdown are never finished, nothing else writes there yet
+    storage.lock().clear_uploads();
+
+    // Logged so a run with random effects can be reproduced with --render-seed
+    info!("Random effects use seed {}", display_config.render_seed);
+
+    // Load renderer plugins before any playlist content is turned into renderers
+    if let Some(plugin_dir) = &display_config.plugin_dir {
+        #[cfg(feature = "dynamic-plugins")]
+        {
+            let loaded =
+                display::renderer::plugin::load_plugins_from_dir(std::path::Path::new(plugin_dir));
+            info!("Loaded {} renderer plugin(s) from {}", loaded, plugin_dir);
+        }
+        #[cfg(not(feature = "dynamic-plugins"))]
+        warn!(
+            "Ignoring plugin directory {}: built without the dynamic-plugins feature",
+            plugin_dir
+        );
+    }
+
+    // Load uploaded WASM plugins so playlist items using them can be rendered
+    {
+        let storage_guard = storage.lock();
+        let stored_plugins = storage_guard.list_plugins();
+        #[cfg(feature = "wasm-plugins")]
+        {
+            let plugins = stored_plugins
+                .into_iter()
+                .filter_map(|(name, _)| {
+                    let bytes = storage_guard.load_plugin(&name)?;
+                    Some((name, bytes))
+                })
+                .collect();
+            let loaded = display::renderer::wasm::load_wasm_plugins(plugins);
+            info!("Loaded {} WASM plugin(s)", loaded);
+        }
+        #[cfg(not(feature = "wasm-plugins"))]
+        if !stored_plugins.is_empty() {
+            warn!(
+                "Ignoring {} stored WASM plugin(s): built without the wasm-plugins feature",
+                stored_plugins.len()
+            );
+        }
+    }
+
+    // Run the renderer benchmark instead of the controller
+    if let Some(seconds) = display_config.bench_seconds {
+        if let Err(e) = display::bench::run_bench(&display_config, seconds) {
+            error!("Benchmark failed: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Claim the button pins while we still have the privileges to open the GPIO chip
+    let buttons = if display_config.buttons.is_empty() {
+        None
+    } else {
+        match display::buttons::open(&display_config.gpio_chip, &display_config.buttons) {
+            Ok(buttons) => Some(buttons),
+            Err(e) => {
+                error!("Buttons are unavailable: {}", e);
+                None
+            }
+        }
+    };
 
     // Create the driver - this might drop privileges
     info!("Initializing LED matrix driver (requires elevated privileges)");
@@ -129,23 +319,98 @@ async fn main() {
     // Now drop privileges explicitly if the driver didn't do it
     #[cfg(target_os = "linux")]
     {
-        if let Err(e) = drop_privileges() {
+        // Audio capture starts arecord later on, which needs the audio group for /dev/snd
+        let keep_groups: &[&str] = if display_config.audio_device.is_some() {
+            &["audio"]
+        } else {
+            &[]
+        };
+        if let Err(e) = drop_privileges(keep_groups) {
             error!("Failed to drop privileges: {}", e);
         }
     }
 
     // Initialize display manager with the pre-created driver
     let display = {
-        let storage_guard = storage.lock().unwrap();
-        let persisted_playlist = storage_guard.load_playlist();
+        let mut storage_guard = storage.lock();
+        let mut startup_report = StartupReport::new();
+        let persisted_playlist = storage_guard.recover_playlist(&mut startup_report);
         let persisted_brightness = storage_guard.load_brightness();
+        let persisted_idle_settings = storage_guard.load_idle_settings();
+        let persisted_welcome_settings = storage_guard.load_welcome_settings();
+        let persisted_brightness_mask = storage_guard.load_brightness_mask();
+        let persisted_dead_pixels = storage_guard.load_dead_pixels();
 
-        let mut display_manager = if let Some(playlist) = persisted_playlist {
+        // Variables and palettes are needed before the first item renders
+        utils::variables::load(storage_guard.load_variables());
+        utils::palettes::load(storage_guard.load_palettes());
+        utils::content_defaults::load(storage_guard.load_content_defaults());
+        utils::operating_hours::load(storage_guard.load_operating_hours());
+
+        let mut display_manager = if let Some(mut playlist) = persisted_playlist
+            .filter(|playlist| !(display_config.demo && playlist.items.is_empty()))
+        {
             info!(
                 "Loaded playlist from filesystem with {} items",
                 playlist.items.len()
             );
+
+            // Items designed for another panel size, e.g. copied from another sign, are adapted
+            let panel = PanelSize::new(
+                display_config.display_width(),
+                display_config.display_height(),
+            );
+            let mut changed = false;
+            for item in &mut playlist.items {
+                if let Some(size) = item.created_for.filter(|size| *size != panel) {
+                    info!(
+                        "Adapting playlist item {} designed for a {}x{} panel to {}x{}",
+                        item.id, size.width, size.height, panel.width, panel.height
+                    );
+                    startup_report.adapted_items.push(item.id.clone());
+                }
+                changed |= adapt_item(item, panel);
+            }
+            // A recovered playlist replaces the damaged file, which was kept as a copy
+            let recovered = matches!(
+                startup_report.playlist,
+                PlaylistRecovery::Salvaged | PlaylistRecovery::RestoredFromBackup
+            );
+            if (changed || recovered) && !storage_guard.save_playlist(&playlist) {
+                error!("Failed to save playlist after adapting or recovering it");
+            }
+
+            // Items stay in the playlist without their media, but are reported
+            for item in &playlist.items {
+                for image_id in item.image_ids() {
+                    if !storage_guard.image_path(image_id).exists() {
+                        warn!("Playlist item {} shows missing image {}", item.id, image_id);
+                        startup_report.missing_images.push(MissingMedia {
+                            item_id: item.id.clone(),
+                            media_id: image_id.to_string(),
+                        });
+                    }
+                }
+                if let Some(video_id) = item.video_id() {
+                    if storage_guard.load_video_info(video_id).is_none() {
+                        warn!("Playlist item {} shows missing video {}", item.id, video_id);
+                        startup_report.missing_videos.push(MissingMedia {
+                            item_id: item.id.clone(),
+                            media_id: video_id.to_string(),
+                        });
+                    }
+                }
+            }
+
             DisplayManager::with_playlist_config_and_driver(playlist, &display_config, driver)
+        } else if display_config.demo {
+            // Not persisted, the demo only becomes part of the playlist once it is edited
+            info!("Demo mode: showing the bundled sample playlist");
+            DisplayManager::with_playlist_config_and_driver(
+                display::demo::demo_playlist(),
+                &display_config,
+                driver,
+            )
         } else {
             info!("No saved playlist found, using default");
             DisplayManager::with_config_and_driver(&display_config, driver)
@@ -157,15 +422,72 @@ async fn main() {
             display_manager.set_brightness(brightness);
         }
 
+        // Apply the saved idle screen settings if available
+        if let Some(settings) = persisted_idle_settings {
+            display_manager.set_idle_settings(settings);
+        }
+
+        // Apply the saved welcome message look if available
+        if let Some(settings) = persisted_welcome_settings {
+            display_manager.set_welcome_settings(settings);
+        }
+
+        // Apply the saved brightness mask, scaled to the current panel size
+        if let Some(data) = persisted_brightness_mask {
+            match BrightnessMask::from_image(
+                &data,
+                display_manager.display_width,
+                display_manager.display_height,
+            ) {
+                Ok(mask) => {
+                    info!("Applying saved brightness mask");
+                    display_manager.set_brightness_mask(Some(mask));
+                }
+                Err(e) => error!("Ignoring saved brightness mask: {}", e),
+            }
+        }
+        if !persisted_dead_pixels.is_empty() {
+            info!("Correcting {} dead pixels", persisted_dead_pixels.len());
+            display_manager.set_dead_pixels(persisted_dead_pixels);
+        }
+
+        // Collections whose window is open join the rotation right away
+        let collections: Vec<Collection> = storage_guard
+            .load_collections()
+            .into_iter()
+            .filter(|collection| match collection.validate() {
+                Ok(()) => true,
+                Err(e) => {
+                    error!("Ignoring saved collection '{}': {}", collection.name, e);
+                    false
+                }
+            })
+            .collect();
+        if !collections.is_empty() {
+            info!("Loaded {} collection(s)", collections.len());
+            display_manager.set_collections(collections);
+        }
+
+        set_report(startup_report);
+
         Arc::new(Mutex::new(display_manager))
     };
 
     // Set up signal handlers for clean shutdown
     let display_for_shutdown = display.clone();
+    let storage_for_shutdown = storage.clone();
     if let Err(e) = ctrlc::set_handler(move || {
         info!("Received termination signal, shutting down...");
         SHUTDOWN_FLAG.store(true, Ordering::SeqCst);
 
+        // Write playlist changes still waiting for the save interval
+        match storage_for_shutdown.try_lock_for(std::time::Duration::from_secs(2)) {
+            Some(mut storage_guard) => {
+                storage_guard.flush_pending(true);
+            }
+            None => error!("Could not acquire storage lock, unsaved playlist changes are lost"),
+        }
+
         // Try to get a lock on the display and shut it down
         // Using try_lock to avoid deadlocks since we're in a signal handler
         if let Ok(mut display_guard) = display_for_shutdown.try_lock() {
@@ -180,59 +502,284 @@ async fn main() {
         error!("Error setting Ctrl-C handler: {}", e);
     }
 
+    // Dim the panel and lower the frame rate while the SoC runs hot
+    if display_config.thermal_throttle_c > 0.0 {
+        tokio::spawn(display::thermal::monitor(
+            display.clone(),
+            display_config.thermal_throttle_c,
+            display_config.thermal_resume_c,
+        ));
+    }
+
+    // Warn when the system clock has not been set yet and keep watching for NTP to sync
+    tokio::spawn(utils::time_sync::monitor());
+
     // Create SSE state manager
     let sse_state = EventState::new();
 
     tokio::spawn({
         let display_clone = display.clone();
+        let storage_clone = storage.clone();
         let sse_state_clone = sse_state.clone();
         async move {
             debug!("Display update task started");
-            display_loop(display_clone, sse_state_clone).await;
+            display_loop(display_clone, storage_clone, sse_state_clone).await;
         }
     });
 
+    // Follow the operating hours with the idle screen or by switching the display off
+    tokio::spawn(operating_hours_loop(display.clone(), sse_state.clone()));
+
+    // Remove deleted items from the trash once they are past --trash-days
+    tokio::spawn(trash_purge_loop(display.clone(), storage.clone()));
+
+    // Remove uploaded images no item uses anymore
+    if display_config.cleanup_interval_minutes > 0 {
+        tokio::spawn(image_cleanup_loop(
+            display.clone(),
+            storage.clone(),
+            std::time::Duration::from_secs(display_config.cleanup_interval_minutes as u64 * 60),
+        ));
+    }
+
+    // Coalesce playlist and brightness writes to spare the SD card
+    let save_interval_seconds = display_config.save_interval_seconds();
+    if save_interval_seconds > 0 {
+        if display_config.low_wear {
+            info!(
+                "Low-wear mode: writing state changes every {} seconds and on shutdown",
+                save_interval_seconds
+            );
+        }
+        storage
+            .lock()
+            .set_save_interval(std::time::Duration::from_secs(save_interval_seconds));
+        tokio::spawn(pending_save_loop(storage.clone()));
+    }
+
+    // Receive ambient color frames if enabled
+    if display_config.ambient_udp_port > 0 {
+        let ip_addr = display_config
+            .interface
+            .parse::<std::net::IpAddr>()
+            .expect("Invalid network interface address");
+        let addr = SocketAddr::from((ip_addr, display_config.ambient_udp_port));
+        tokio::spawn(display::ambient::ambient_listener(display.clone(), addr));
+    }
+
+    // Follow the sound level for reactive animations if a capture device is set
+    if let Some(device) = display_config.audio_device.clone() {
+        tokio::spawn(display::audio::audio_capture(device));
+    }
+
+    // Share or follow the playlist position for synchronized playback across signs
+    if let Some(mode) = display_config
+        .sync_mode
+        .as_deref()
+        .and_then(SyncMode::parse)
+    {
+        let group = display_config
+            .sync_group
+            .parse::<SocketAddr>()
+            .expect("Invalid sync group address");
+        match mode {
+            SyncMode::Master => tokio::spawn(display::sync::sync_master(display.clone(), group)),
+            SyncMode::Follower => {
+                tokio::spawn(display::sync::sync_follower(display.clone(), group))
+            }
+        };
+    }
+
     // Create the combined state
     let combined_state = ((display.clone(), storage.clone()), sse_state.clone());
 
+    // Run the actions of physical buttons
+    if let Some(buttons) = buttons {
+        tokio::spawn(display::buttons::monitor(
+            combined_state.clone(),
+            buttons,
+            std::time::Duration::from_millis(display_config.button_debounce_ms),
+        ));
+    }
+
     // API routes with shared storage
     let api_routes = Router::new()
         // New RESTful playlist endpoints
-        .route("/api/playlist/items", get(get_playlist_items))
+        .route(
+            "/api/playlist/items",
+            get(get_playlist_items).layer(middleware::from_fn(with_etag)),
+        )
         .route("/api/playlist/items", post(create_playlist_item))
-        .route("/api/playlist/items/:id", get(get_playlist_item))
+        .route(
+            "/api/playlist/items/:id",
+            get(get_playlist_item).layer(middleware::from_fn(with_etag)),
+        )
         .route("/api/playlist/items/:id", put(update_playlist_item))
+        .route("/api/playlist/items/:id", patch(patch_playlist_item))
         .route("/api/playlist/items/:id", delete(delete_playlist_item))
+        .route(
+            "/api/playlist/items/:id/health",
+            get(get_playlist_item_health),
+        )
+        .route(
+            "/api/playlist/items/:id/value",
+            put(update_big_number_value),
+        )
+        .route("/api/playlist/tags/:tag", put(set_tag_enabled))
+        .route("/api/playlist/trash", get(get_trash))
+        .route("/api/playlist/trash/:id/restore", post(restore_trash_item))
         .route("/api/playlist/reorder", put(reorder_playlist_items))
+        .route("/api/playlist/validate", post(validate_playlist_item))
+        .route("/api/playlist/now", get(get_now_playing))
         // Image upload endpoints
         .route("/api/images", post(upload_image))
+        .route("/api/images/from-url", post(upload_image_from_url))
         .route("/api/images/:id", get(fetch_image))
-        .route("/api/images/:id/thumbnail", get(fetch_image_thumbnail))
+        .route("/api/images/:id/edit", post(edit_image))
+        .route(
+            "/api/images/:id/thumbnail",
+            get(fetch_image_thumbnail).layer(middleware::from_fn(with_etag)),
+        )
+        // WASM plugin endpoints
+        .route("/api/plugins", get(list_plugins))
+        .route("/api/plugins", post(upload_plugin))
+        .route("/api/plugins/:name", delete(delete_plugin))
+        // Video endpoints
+        .route("/api/videos", get(list_videos))
+        .route("/api/videos", post(upload_video))
+        .route("/api/videos/:id", delete(delete_video))
         // Display info endpoint
         .route("/api/display/info", get(get_display_info))
+        .route(
+            "/api/display/detect-color-order",
+            post(start_color_order_detection),
+        )
+        .route(
+            "/api/display/detect-color-order",
+            delete(cancel_color_order_detection),
+        )
+        .route(
+            "/api/display/detect-color-order/report",
+            post(report_color_order),
+        )
+        .route("/api/display/brightness-mask", get(get_brightness_mask))
+        .route("/api/display/brightness-mask", put(upload_brightness_mask))
+        .route(
+            "/api/display/brightness-mask",
+            delete(delete_brightness_mask),
+        )
+        .route("/api/display/dead-pixels", get(get_dead_pixels))
+        .route("/api/display/dead-pixels", put(update_dead_pixels))
+        .route("/api/display/record", post(record_display))
+        // Hardware profile endpoints
+        .route("/api/profiles", get(list_profiles))
+        .route("/api/profiles/:name", get(get_profile))
+        .route("/api/profiles/:name", put(put_profile))
+        .route("/api/profiles/:name", delete(delete_profile))
+        // Reachability check
+        .route("/api/ping", get(ping))
+        .route("/api/system/time", get(get_time_status))
+        .route("/api/system/thermal", get(get_thermal_status))
+        .route("/api/system/commands", get(get_commands))
+        .route("/api/system/metrics", get(get_metrics))
+        .route("/api/system/startup", get(get_startup_report))
+        .route("/api/system/storage", get(get_storage_usage))
+        .route("/api/maintenance/cleanup", post(run_cleanup))
+        .route("/api/collections", get(get_collections))
+        .route("/api/collections", post(create_collection))
+        .route("/api/collections/:name", get(get_collection))
+        .route("/api/collections/:name", put(update_collection))
+        .route("/api/collections/:name", delete(delete_collection))
+        .route("/api/vars", get(get_variables))
+        .route("/api/vars/:name", get(get_variable))
+        .route("/api/vars/:name", put(put_variable))
+        .route("/api/vars/:name", delete(delete_variable))
+        .route("/api/palettes", get(get_palettes))
+        .route("/api/palettes/:name", get(get_palette))
+        .route("/api/palettes/:name", put(put_palette))
+        .route("/api/palettes/:name", delete(delete_palette))
+        // Text measurement for the editor
+        .route("/api/text/measure", get(measure_text))
+        // First-boot setup wizard
+        .route("/api/setup", get(get_setup))
+        .route("/api/setup", post(complete_setup))
         // Settings endpoints
         .route("/api/settings/brightness", get(get_brightness))
         .route("/api/settings/brightness", put(update_brightness))
+        .route("/api/settings/idle", get(get_idle_settings))
+        .route("/api/settings/idle", put(update_idle_settings))
+        .route("/api/settings/welcome", get(get_welcome_settings))
+        .route("/api/settings/welcome", put(update_welcome_settings))
+        .route("/api/settings/defaults", get(get_content_defaults))
+        .route("/api/settings/defaults", put(update_content_defaults))
+        .route("/api/settings/effective", get(get_effective_settings))
+        .route("/api/settings/hours", get(get_operating_hours))
+        .route("/api/settings/hours", put(update_operating_hours))
+        .route("/api/simple/state", get(get_simple_state))
+        .route("/api/simple/state", put(update_simple_state))
+        .route("/api/actions", post(run_action))
         // New SSE endpoint with changed path
+        .route("/api/events", get(all_events))
         .route("/api/events/brightness", get(brightness_events))
         .route("/api/events/editor", get(editor_lock_events))
         .route("/api/events/playlist", get(playlist_events))
+        .route("/api/events/vars", get(variable_events))
+        .route("/api/events/palettes", get(palette_events))
+        .route("/api/events/clients", get(event_clients))
         // New preview mode endpoints
         .route("/api/preview", post(start_preview_mode))
         .route("/api/preview", put(update_preview))
         .route("/api/preview", delete(exit_preview_mode))
         .route("/api/preview/status", get(get_preview_mode_status))
         .route("/api/preview/ping", post(ping_preview_mode))
+        .route("/api/preview/events", get(preview_events))
+        .route("/api/preview/commit", post(commit_preview))
         .route("/api/preview/session", post(check_session_owner))
-        .layer(DefaultBodyLimit::max(MAX_IMAGE_BYTES))
-        .with_state(combined_state);
+        .route("/api/preview/estimate", post(estimate_text))
+        // Image uploads are limited by the configuration, plugin and video uploads by their own
+        // constants
+        .layer(DefaultBodyLimit::max(
+            display_config
+                .max_image_bytes()
+                .max(MAX_PLUGIN_BYTES)
+                .max(MAX_VIDEO_BYTES),
+        ))
+        .with_state(combined_state.clone());
+
+    #[cfg(feature = "graphql")]
+    let api_routes = api_routes.merge(web::api::graphql::router(combined_state));
+
+    // Serve the embedded web interface alongside the API unless running headless
+    #[cfg(feature = "embedded-ui")]
+    let app = if display_config.headless {
+        info!("Headless mode: serving the API only");
+        api_routes
+    } else {
+        set_base_path(&display_config.base_path);
+        Router::new()
+            .route("/", get(index_handler))
+            .route("/_next/*path", get(next_assets_handler))
+            .route("/static/*path", get(static_assets_handler))
+            .route("/manifest.webmanifest", get(manifest_handler))
+            .route("/sw.js", get(service_worker_handler))
+            .nest("", api_routes)
+    };
+    #[cfg(not(feature = "embedded-ui"))]
+    let app = {
+        info!("Built without the embedded-ui feature: serving the API only");
+        api_routes
+    };
 
-    // Simplified static assets setup
-    let app = Router::new()
-        .route("/", get(index_handler))
-        .route("/_next/*path", get(next_assets_handler))
-        .route("/static/*path", get(static_assets_handler))
-        .nest("", api_routes);
+    // Log every request and record its latency per route
+    let app = app.layer(middleware::from_fn(log_request));
+
+    // Mount everything under the base path when running behind a reverse proxy sub-path
+    let app = if display_config.base_path.is_empty() {
+        app
+    } else {
+        info!("Serving under base path {}", display_config.base_path);
+        Router::new().nest(&display_config.base_path, app)
+    };
 
     let ip_addr = display_config
         .interface
@@ -241,6 +788,21 @@ async fn main() {
 
     let addr = SocketAddr::from((ip_addr, display_config.port));
 
+    // Serve the same routes on a Unix domain socket if one is configured
+    #[cfg(unix)]
+    if let Some(path) = &display_config.unix_socket {
+        match web::unix_socket::bind(std::path::Path::new(path)) {
+            Ok(listener) => {
+                info!("Also serving the API on Unix socket {}", path);
+                tokio::spawn(web::unix_socket::serve(listener, app.clone()));
+            }
+            Err(e) => {
+                error!("Failed to bind Unix socket {}: {}", path, e);
+                std::process::exit(1);
+            }
+        }
+    }
+
     info!("Server running on http://{}", addr);
 
     if let Err(e) = axum::serve(
@@ -258,6 +820,7 @@ async fn main() {
     }
 
     info!("Application exiting, cleaning up display...");
+    storage.lock().flush_pending(true);
     let mut display_guard = display.lock().await;
     display_guard.shutdown();
 }
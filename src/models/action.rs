@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+// Something an input source (API, buttons, ...) asks the sign to do, run by
+// web::api::actions::dispatch. In JSON the action name is in "action" and its argument,
+// if any, in "value", e.g. {"action": "show_item", "value": "ITEM_ID"}.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", content = "value", rename_all = "snake_case")]
+pub enum Action {
+    // Skip to the next playlist item
+    NextItem,
+    // Brightness in percent, saved and broadcast like a change through the brightness API
+    SetBrightness(u8),
+    // Show the playlist item with this ID, the rotation continues from there
+    ShowItem(String),
+    // Switch the panel on or off
+    Power(bool),
+    // Blink the whole panel in this color
+    Flash([u8; 3]),
+    // Scroll this text across the panel once, then return to the playlist
+    Notify(String),
+}
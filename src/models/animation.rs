@@ -3,35 +3,63 @@ use serde::{Deserialize, Serialize};
 /// Animation presets supported by the controller.
 /// This enum is represented as an internally tagged union so JSON payloads look like:
 /// {"preset":"Pulse","colors":[...],"cycle_ms":2000}
+/// Instead of `colors`, presets can name a saved palette: {"preset":"Pulse","palette":"sunset"}
+/// With `reactive` set, the sound level captured with --audio-device modulates brightness and
+/// speed.
 #[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(tag = "preset")]
 pub enum AnimationContent {
     Pulse {
+        #[serde(default)]
         colors: Vec<[u8; 3]>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        palette: Option<String>,
+        #[serde(default)]
+        reactive: bool,
         #[serde(default = "default_cycle_ms")]
         cycle_ms: u32,
     },
     PaletteWave {
+        #[serde(default)]
         colors: Vec<[u8; 3]>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        palette: Option<String>,
+        #[serde(default)]
+        reactive: bool,
         #[serde(default = "default_cycle_ms")]
         cycle_ms: u32,
         #[serde(default = "default_wave_count")]
         wave_count: u8,
     },
     DualPulse {
+        #[serde(default)]
         colors: Vec<[u8; 3]>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        palette: Option<String>,
+        #[serde(default)]
+        reactive: bool,
         #[serde(default = "default_cycle_ms")]
         cycle_ms: u32,
         #[serde(default = "default_phase_offset")]
         phase_offset: f32,
     },
     ColorFade {
+        #[serde(default)]
         colors: Vec<[u8; 3]>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        palette: Option<String>,
+        #[serde(default)]
+        reactive: bool,
         #[serde(default = "default_wash_speed")]
         drift_speed: f32,
     },
     Strobe {
+        #[serde(default)]
         colors: Vec<[u8; 3]>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        palette: Option<String>,
+        #[serde(default)]
+        reactive: bool,
         #[serde(default = "default_flash_ms")]
         flash_ms: u32,
         #[serde(default = "default_fade_ms")]
@@ -42,14 +70,24 @@ pub enum AnimationContent {
         randomization_factor: f32,
     },
     Sparkle {
+        #[serde(default)]
         colors: Vec<[u8; 3]>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        palette: Option<String>,
+        #[serde(default)]
+        reactive: bool,
         #[serde(default = "default_sparkle_density")]
         density: f32,
         #[serde(default = "default_sparkle_cycle_ms")]
         twinkle_ms: u32,
     },
     MosaicTwinkle {
+        #[serde(default)]
         colors: Vec<[u8; 3]>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        palette: Option<String>,
+        #[serde(default)]
+        reactive: bool,
         #[serde(default = "default_mosaic_twinkle_tile_size")]
         tile_size: u8,
         #[serde(default = "default_mosaic_twinkle_speed")]
@@ -60,12 +98,138 @@ pub enum AnimationContent {
         border_color: [u8; 3],
     },
     Plasma {
+        #[serde(default)]
         colors: Vec<[u8; 3]>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        palette: Option<String>,
+        #[serde(default)]
+        reactive: bool,
         #[serde(default = "default_plasma_flow_speed")]
         flow_speed: f32,
         #[serde(default = "default_plasma_noise_scale")]
         noise_scale: f32,
     },
+    Meteor {
+        #[serde(default)]
+        colors: Vec<[u8; 3]>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        palette: Option<String>,
+        #[serde(default)]
+        reactive: bool,
+        #[serde(default)]
+        direction: MeteorDirection,
+        #[serde(default = "default_meteor_density")]
+        density: f32,
+        #[serde(default = "default_meteor_speed")]
+        speed: f32,
+        #[serde(default = "default_meteor_trail_ms")]
+        trail_ms: u32,
+    },
+    Fireworks {
+        #[serde(default)]
+        colors: Vec<[u8; 3]>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        palette: Option<String>,
+        #[serde(default)]
+        reactive: bool,
+        #[serde(default = "default_fireworks_burst_rate")]
+        burst_rate: f32,
+        #[serde(default = "default_fireworks_spark_count")]
+        spark_count: u8,
+        #[serde(default = "default_fireworks_gravity")]
+        gravity: f32,
+    },
+    NoiseField {
+        #[serde(default)]
+        colors: Vec<[u8; 3]>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        palette: Option<String>,
+        #[serde(default)]
+        reactive: bool,
+        #[serde(default = "default_noise_field_octaves")]
+        octaves: u8,
+        #[serde(default = "default_noise_field_speed")]
+        speed: f32,
+        #[serde(default = "default_noise_field_cycle_ms")]
+        cycle_ms: u32,
+    },
+    PixelWeather {
+        #[serde(default)]
+        colors: Vec<[u8; 3]>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        palette: Option<String>,
+        #[serde(default)]
+        reactive: bool,
+        #[serde(default)]
+        weather: WeatherKind,
+        #[serde(default = "default_weather_intensity")]
+        intensity: f32,
+    },
+}
+
+/// Weather drawn by the PixelWeather preset
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum WeatherKind {
+    #[default]
+    Rain,
+    Snow,
+    Sun,
+}
+
+/// Direction meteors streak across the panel in
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MeteorDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+    #[default]
+    DownLeft,
+    DownRight,
+    UpLeft,
+    UpRight,
+    // Every meteor picks one of the other directions
+    Random,
+}
+
+impl MeteorDirection {
+    const STREAKS: [MeteorDirection; 8] = [
+        MeteorDirection::Left,
+        MeteorDirection::Right,
+        MeteorDirection::Up,
+        MeteorDirection::Down,
+        MeteorDirection::DownLeft,
+        MeteorDirection::DownRight,
+        MeteorDirection::UpLeft,
+        MeteorDirection::UpRight,
+    ];
+
+    /// Concrete direction of one meteor, `pick` in 0..1 chooses one for `Random`
+    pub fn resolve(self, pick: f32) -> MeteorDirection {
+        match self {
+            MeteorDirection::Random => {
+                let index = (pick * Self::STREAKS.len() as f32) as usize;
+                Self::STREAKS[index.min(Self::STREAKS.len() - 1)]
+            }
+            direction => direction,
+        }
+    }
+
+    /// Unit step in pixels along x and y, diagonals move one pixel on each axis
+    pub fn step(self) -> (f32, f32) {
+        match self {
+            MeteorDirection::Left => (-1.0, 0.0),
+            MeteorDirection::Right => (1.0, 0.0),
+            MeteorDirection::Up => (0.0, -1.0),
+            MeteorDirection::Down | MeteorDirection::Random => (0.0, 1.0),
+            MeteorDirection::DownLeft => (-1.0, 1.0),
+            MeteorDirection::DownRight => (1.0, 1.0),
+            MeteorDirection::UpLeft => (-1.0, -1.0),
+            MeteorDirection::UpRight => (1.0, -1.0),
+        }
+    }
 }
 
 fn default_cycle_ms() -> u32 {
@@ -128,10 +292,52 @@ fn default_plasma_noise_scale() -> f32 {
     1.75
 }
 
+fn default_meteor_density() -> f32 {
+    0.15
+}
+
+fn default_meteor_speed() -> f32 {
+    40.0
+}
+
+fn default_meteor_trail_ms() -> u32 {
+    700
+}
+
+fn default_fireworks_burst_rate() -> f32 {
+    0.8
+}
+
+fn default_fireworks_spark_count() -> u8 {
+    28
+}
+
+fn default_fireworks_gravity() -> f32 {
+    40.0
+}
+
+fn default_noise_field_octaves() -> u8 {
+    3
+}
+
+fn default_noise_field_speed() -> f32 {
+    0.15
+}
+
+fn default_noise_field_cycle_ms() -> u32 {
+    30_000
+}
+
+fn default_weather_intensity() -> f32 {
+    0.5
+}
+
 impl AnimationContent {
     /// Returns true if this animation requires at least one color in the palette.
     fn requires_palette(&self) -> bool {
         match self {
+            // Weather has natural colors, given colors replace them
+            AnimationContent::PixelWeather { .. } => false,
             AnimationContent::Sparkle { .. } => true,
             AnimationContent::Pulse { .. }
             | AnimationContent::PaletteWave { .. }
@@ -139,15 +345,18 @@ impl AnimationContent {
             | AnimationContent::ColorFade { .. }
             | AnimationContent::Strobe { .. }
             | AnimationContent::MosaicTwinkle { .. }
-            | AnimationContent::Plasma { .. } => true,
+            | AnimationContent::Plasma { .. }
+            | AnimationContent::Meteor { .. }
+            | AnimationContent::Fireworks { .. }
+            | AnimationContent::NoiseField { .. } => true,
         }
     }
 
     /// Validate configuration values. Returns an error string on invalid inputs.
     pub fn validate(&self) -> Result<(), String> {
         let palette_len = self.palette().len();
-        if self.requires_palette() && palette_len == 0 {
-            return Err("Animation presets require at least one color".to_string());
+        if self.requires_palette() && palette_len == 0 && self.palette_name().is_none() {
+            return Err("Animation presets require at least one color or a palette".to_string());
         }
 
         match self {
@@ -222,6 +431,51 @@ impl AnimationContent {
                     return Err("noise_scale must be a positive finite value".to_string());
                 }
             }
+            AnimationContent::Meteor {
+                density,
+                speed,
+                trail_ms,
+                ..
+            } => {
+                if !density.is_finite() || *density <= 0.0 || *density > 1.0 {
+                    return Err("density must be in the range (0, 1]".to_string());
+                }
+                if !speed.is_finite() || *speed <= 0.0 {
+                    return Err("speed must be a positive finite value".to_string());
+                }
+                if *trail_ms == 0 {
+                    return Err("trail_ms must be greater than zero".to_string());
+                }
+            }
+            AnimationContent::Fireworks {
+                burst_rate,
+                spark_count,
+                gravity,
+                ..
+            } => {
+                if !burst_rate.is_finite() || *burst_rate <= 0.0 || *burst_rate > 10.0 {
+                    return Err("burst_rate must be in the range (0, 10]".to_string());
+                }
+                if *spark_count == 0 {
+                    return Err("spark_count must be at least 1".to_string());
+                }
+                if !gravity.is_finite() || *gravity <= 0.0 {
+                    return Err("gravity must be a positive finite value".to_string());
+                }
+            }
+            AnimationContent::NoiseField { octaves, speed, .. } => {
+                if *octaves == 0 || *octaves > 6 {
+                    return Err("octaves must be between 1 and 6".to_string());
+                }
+                if !speed.is_finite() || *speed <= 0.0 {
+                    return Err("speed must be a positive finite value".to_string());
+                }
+            }
+            AnimationContent::PixelWeather { intensity, .. } => {
+                if !intensity.is_finite() || *intensity <= 0.0 || *intensity > 1.0 {
+                    return Err("intensity must be in the range (0, 1]".to_string());
+                }
+            }
         }
 
         match self {
@@ -251,7 +505,65 @@ impl AnimationContent {
             | AnimationContent::Strobe { colors, .. }
             | AnimationContent::Sparkle { colors, .. }
             | AnimationContent::MosaicTwinkle { colors, .. }
-            | AnimationContent::Plasma { colors, .. } => colors,
+            | AnimationContent::Plasma { colors, .. }
+            | AnimationContent::Meteor { colors, .. }
+            | AnimationContent::Fireworks { colors, .. }
+            | AnimationContent::NoiseField { colors, .. }
+            | AnimationContent::PixelWeather { colors, .. } => colors,
+        }
+    }
+
+    /// Name of the saved palette used instead of `colors`, if any
+    pub fn palette_name(&self) -> Option<&str> {
+        match self {
+            AnimationContent::Pulse { palette, .. }
+            | AnimationContent::PaletteWave { palette, .. }
+            | AnimationContent::DualPulse { palette, .. }
+            | AnimationContent::ColorFade { palette, .. }
+            | AnimationContent::Strobe { palette, .. }
+            | AnimationContent::Sparkle { palette, .. }
+            | AnimationContent::MosaicTwinkle { palette, .. }
+            | AnimationContent::Plasma { palette, .. }
+            | AnimationContent::Meteor { palette, .. }
+            | AnimationContent::Fireworks { palette, .. }
+            | AnimationContent::NoiseField { palette, .. }
+            | AnimationContent::PixelWeather { palette, .. } => palette.as_deref(),
+        }
+    }
+
+    /// Whether the captured sound level modulates brightness and speed
+    pub fn reactive(&self) -> bool {
+        match self {
+            AnimationContent::Pulse { reactive, .. }
+            | AnimationContent::PaletteWave { reactive, .. }
+            | AnimationContent::DualPulse { reactive, .. }
+            | AnimationContent::ColorFade { reactive, .. }
+            | AnimationContent::Strobe { reactive, .. }
+            | AnimationContent::Sparkle { reactive, .. }
+            | AnimationContent::MosaicTwinkle { reactive, .. }
+            | AnimationContent::Plasma { reactive, .. }
+            | AnimationContent::Meteor { reactive, .. }
+            | AnimationContent::Fireworks { reactive, .. }
+            | AnimationContent::NoiseField { reactive, .. }
+            | AnimationContent::PixelWeather { reactive, .. } => *reactive,
+        }
+    }
+
+    /// Mutable access to the colors, used to fill in a named palette
+    pub fn colors_mut(&mut self) -> &mut Vec<[u8; 3]> {
+        match self {
+            AnimationContent::Pulse { colors, .. }
+            | AnimationContent::PaletteWave { colors, .. }
+            | AnimationContent::DualPulse { colors, .. }
+            | AnimationContent::ColorFade { colors, .. }
+            | AnimationContent::Strobe { colors, .. }
+            | AnimationContent::Sparkle { colors, .. }
+            | AnimationContent::MosaicTwinkle { colors, .. }
+            | AnimationContent::Plasma { colors, .. }
+            | AnimationContent::Meteor { colors, .. }
+            | AnimationContent::Fireworks { colors, .. }
+            | AnimationContent::NoiseField { colors, .. }
+            | AnimationContent::PixelWeather { colors, .. } => colors,
         }
     }
 }
@@ -0,0 +1,246 @@
+use serde::{Deserialize, Serialize};
+
+/// Supported linear barcode symbologies
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq)]
+pub enum BarcodeFormat {
+    /// Printable ASCII; all-digit data is packed two digits per symbol
+    #[default]
+    Code128,
+    /// 12 digits, or 13 with the check digit
+    #[serde(rename = "EAN13")]
+    Ean13,
+    /// 7 digits, or 8 with the check digit
+    #[serde(rename = "EAN8")]
+    Ean8,
+}
+
+fn default_foreground() -> [u8; 3] {
+    [0, 0, 0]
+}
+
+fn default_background() -> [u8; 3] {
+    [255, 255, 255]
+}
+
+fn default_quiet_zone() -> u8 {
+    10
+}
+
+fn default_show_text() -> bool {
+    true
+}
+
+fn default_text_color() -> [u8; 3] {
+    [255, 255, 255]
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct BarcodeContent {
+    #[serde(default)]
+    pub format: BarcodeFormat,
+    /// Value to encode
+    pub data: String,
+    /// Color of the bars. Scanners expect them darker than the background
+    #[serde(default = "default_foreground")]
+    pub foreground: [u8; 3],
+    /// Color of the spaces and the quiet zone
+    #[serde(default = "default_background")]
+    pub background: [u8; 3],
+    /// Preferred quiet zone on each side in modules; shrunk down to 1 when the code would not fit
+    #[serde(default = "default_quiet_zone")]
+    pub quiet_zone: u8,
+    /// Print the encoded value below the bars when the panel is tall enough
+    #[serde(default = "default_show_text")]
+    pub show_text: bool,
+    #[serde(default = "default_text_color")]
+    pub text_color: [u8; 3],
+}
+
+impl BarcodeContent {
+    /// Encode the data into modules from left to right (true = bar), without quiet zones
+    pub fn encode(&self) -> Result<Vec<bool>, String> {
+        match self.format {
+            BarcodeFormat::Code128 => encode_code128(&self.data),
+            BarcodeFormat::Ean13 => encode_ean(&self.data, 13),
+            BarcodeFormat::Ean8 => encode_ean(&self.data, 8),
+        }
+    }
+
+    /// Human-readable text printed under the bars (EAN codes include the check digit)
+    pub fn display_text(&self) -> String {
+        match self.format {
+            BarcodeFormat::Code128 => self.data.clone(),
+            BarcodeFormat::Ean13 | BarcodeFormat::Ean8 => {
+                let length = if self.format == BarcodeFormat::Ean13 {
+                    13
+                } else {
+                    8
+                };
+                if self.data.len() == length - 1 {
+                    let digits: Vec<u8> = self.data.bytes().map(|b| b - b'0').collect();
+                    format!("{}{}", self.data, ean_check_digit(&digits))
+                } else {
+                    self.data.clone()
+                }
+            }
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.data.is_empty() {
+            return Err("Barcode content requires non-empty 'data'".to_string());
+        }
+        self.encode().map(|_| ())
+    }
+}
+
+/// Bar/space widths of the Code 128 symbols 0-105, starting with a bar
+const CODE128_PATTERNS: [&str; 106] = [
+    "212222", "222122", "222221", "121223", "121322", "131222", "122213", "122312", "132212",
+    "221213", "221312", "231212", "112232", "122132", "122231", "113222", "123122", "123221",
+    "223211", "221132", "221231", "213212", "223112", "312131", "311222", "321122", "321221",
+    "312212", "322112", "322211", "212123", "212321", "232121", "111323", "131123", "131321",
+    "112313", "132113", "132311", "211313", "231113", "231311", "112133", "112331", "132131",
+    "113123", "113321", "133121", "313121", "211331", "231131", "213113", "213311", "213131",
+    "311123", "311321", "331121", "312113", "312311", "332111", "314111", "221411", "431111",
+    "111224", "111422", "121124", "121421", "141122", "141221", "112214", "112412", "122114",
+    "122411", "142112", "142211", "241211", "221114", "413111", "241112", "134111", "111242",
+    "121142", "121241", "114212", "124112", "124211", "411212", "421112", "421211", "212141",
+    "214121", "412121", "111143", "111341", "131141", "114113", "114311", "411113", "411311",
+    "113141", "114131", "311141", "411131", "211412", "211214", "211232",
+];
+const CODE128_STOP: &str = "2331112";
+const CODE128_START_B: usize = 104;
+const CODE128_START_C: usize = 105;
+
+fn push_widths(modules: &mut Vec<bool>, widths: &str) {
+    for (index, width) in widths.bytes().enumerate() {
+        let bar = index % 2 == 0;
+        modules.extend(std::iter::repeat_n(bar, (width - b'0') as usize));
+    }
+}
+
+fn encode_code128(data: &str) -> Result<Vec<bool>, String> {
+    let all_digits = data.bytes().all(|b| b.is_ascii_digit());
+
+    // Code set C packs digit pairs; everything else uses code set B
+    let symbols: Vec<usize> = if all_digits && data.len().is_multiple_of(2) {
+        let bytes = data.as_bytes();
+        std::iter::once(CODE128_START_C)
+            .chain(
+                bytes
+                    .chunks(2)
+                    .map(|pair| ((pair[0] - b'0') * 10 + (pair[1] - b'0')) as usize),
+            )
+            .collect()
+    } else {
+        if let Some(invalid) = data.chars().find(|c| !(' '..='~').contains(c)) {
+            return Err(format!(
+                "Code 128 only supports printable ASCII characters, found {:?}",
+                invalid
+            ));
+        }
+        std::iter::once(CODE128_START_B)
+            .chain(data.bytes().map(|b| (b - b' ') as usize))
+            .collect()
+    };
+
+    let checksum = symbols
+        .iter()
+        .enumerate()
+        .map(|(position, value)| position.max(1) * value)
+        .sum::<usize>()
+        % 103;
+
+    let mut modules = Vec::new();
+    for symbol in symbols.iter().chain(std::iter::once(&checksum)) {
+        push_widths(&mut modules, CODE128_PATTERNS[*symbol]);
+    }
+    push_widths(&mut modules, CODE128_STOP);
+    Ok(modules)
+}
+
+/// EAN left-hand odd parity ("L") digit patterns
+const EAN_L_PATTERNS: [&str; 10] = [
+    "0001101", "0011001", "0010011", "0111101", "0100011", "0110001", "0101111", "0111011",
+    "0110111", "0001011",
+];
+/// Parity of the six left-hand EAN-13 digits for each leading digit (false = L, true = G)
+const EAN13_PARITY: [&str; 10] = [
+    "LLLLLL", "LLGLGG", "LLGGLG", "LLGGGL", "LGLLGG", "LGGLLG", "LGGGLL", "LGLGLG", "LGLGGL",
+    "LGGLGL",
+];
+
+fn ean_check_digit(digits: &[u8]) -> u8 {
+    // Weights alternate 3, 1 starting from the rightmost data digit
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(index, digit)| *digit as u32 * if index % 2 == 0 { 3 } else { 1 })
+        .sum();
+    ((10 - sum % 10) % 10) as u8
+}
+
+fn ean_digit(modules: &mut Vec<bool>, digit: u8, encoding: char) {
+    let pattern = EAN_L_PATTERNS[digit as usize].bytes().map(|b| b == b'1');
+    match encoding {
+        // R patterns are L patterns inverted
+        'R' => modules.extend(pattern.map(|bar| !bar)),
+        // G patterns are R patterns reversed
+        'G' => modules.extend(pattern.rev().map(|bar| !bar)),
+        _ => modules.extend(pattern),
+    }
+}
+
+fn encode_ean(data: &str, length: usize) -> Result<Vec<bool>, String> {
+    let name = format!("EAN-{}", length);
+    if !data.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("{} only supports digits", name));
+    }
+    if data.len() != length && data.len() != length - 1 {
+        return Err(format!(
+            "{} requires {} digits, or {} including the check digit",
+            name,
+            length - 1,
+            length
+        ));
+    }
+
+    let mut digits: Vec<u8> = data.bytes().map(|b| b - b'0').collect();
+    let check = ean_check_digit(&digits[..length - 1]);
+    if digits.len() == length {
+        if digits[length - 1] != check {
+            return Err(format!("Invalid {} check digit: expected {}", name, check));
+        }
+    } else {
+        digits.push(check);
+    }
+
+    let mut modules = Vec::new();
+    push_guard(&mut modules, "101");
+    if length == 13 {
+        let parity = EAN13_PARITY[digits[0] as usize];
+        for (digit, encoding) in digits[1..7].iter().zip(parity.chars()) {
+            ean_digit(&mut modules, *digit, encoding);
+        }
+        push_guard(&mut modules, "01010");
+        for digit in &digits[7..] {
+            ean_digit(&mut modules, *digit, 'R');
+        }
+    } else {
+        for digit in &digits[..4] {
+            ean_digit(&mut modules, *digit, 'L');
+        }
+        push_guard(&mut modules, "01010");
+        for digit in &digits[4..] {
+            ean_digit(&mut modules, *digit, 'R');
+        }
+    }
+    push_guard(&mut modules, "101");
+    Ok(modules)
+}
+
+fn push_guard(modules: &mut Vec<bool>, pattern: &str) {
+    modules.extend(pattern.bytes().map(|b| b == b'1'));
+}
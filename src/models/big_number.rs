@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+/// Longest value accepted, in characters
+const MAX_VALUE_LENGTH: usize = 16;
+
+fn default_color() -> [u8; 3] {
+    [255, 255, 255]
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct BigNumberContent {
+    /// Value drawn in seven-segment digits: 0-9, '-', '.', ':' and spaces
+    pub value: String,
+    /// Optional suffix drawn in a regular font after the digits, e.g. "°C" or "kWh". The font
+    /// only covers ISO-8859-1, so e.g. "€" cannot be drawn.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    /// Round numeric values to this many decimal places before drawing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimals: Option<u8>,
+    #[serde(default = "default_color")]
+    pub color: [u8; 3],
+    /// Color of the unit suffix, defaults to `color`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit_color: Option<[u8; 3]>,
+}
+
+impl BigNumberContent {
+    /// Value as drawn, rounded to `decimals` when it parses as a number
+    pub fn display_value(&self) -> String {
+        let value = self.value.trim();
+        match (self.decimals, value.parse::<f64>()) {
+            (Some(decimals), Ok(number)) => format!("{:.*}", decimals as usize, number),
+            _ => value.to_string(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        validate_value(&self.value)
+    }
+}
+
+/// Check that a value only uses characters the seven-segment renderer can draw
+pub fn validate_value(value: &str) -> Result<(), String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err("BigNumber content requires a non-empty 'value'".to_string());
+    }
+    if value.chars().count() > MAX_VALUE_LENGTH {
+        return Err(format!(
+            "BigNumber values are limited to {} characters",
+            MAX_VALUE_LENGTH
+        ));
+    }
+    if let Some(invalid) = value
+        .chars()
+        .find(|c| !c.is_ascii_digit() && !matches!(c, '-' | '.' | ':' | ' '))
+    {
+        return Err(format!(
+            "BigNumber values may only contain digits, '-', '.', ':' and spaces, found {:?}",
+            invalid
+        ));
+    }
+    Ok(())
+}
+
+/// New value for a BigNumber item, sent as a JSON number or string
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum BigNumberValue {
+    Number(serde_json::Number),
+    Text(String),
+}
+
+impl BigNumberValue {
+    pub fn into_string(self) -> String {
+        match self {
+            BigNumberValue::Number(number) => number.to_string(),
+            BigNumberValue::Text(text) => text,
+        }
+    }
+}
+
+/// Request body for updating the value of a BigNumber item in place
+#[derive(Deserialize)]
+pub struct BigNumberValueUpdate {
+    pub value: BigNumberValue,
+    /// Replaces the unit suffix when present; an empty string removes it
+    #[serde(default)]
+    pub unit: Option<String>,
+}
@@ -3,14 +3,69 @@ use serde::{
     Deserialize, Serialize,
 };
 
-// Border effects enum
+// Border effects enum. Effects with colors can name a saved palette instead of listing them.
 #[derive(Clone, Deserialize, Debug, PartialEq)]
 pub enum BorderEffect {
     None,
     Rainbow,
-    Pulse { colors: Vec<[u8; 3]> },
-    Sparkle { colors: Vec<[u8; 3]> },
-    Gradient { colors: Vec<[u8; 3]> },
+    Pulse {
+        #[serde(default)]
+        colors: Vec<[u8; 3]>,
+        #[serde(default)]
+        palette: Option<String>,
+    },
+    Sparkle {
+        #[serde(default)]
+        colors: Vec<[u8; 3]>,
+        #[serde(default)]
+        palette: Option<String>,
+    },
+    Gradient {
+        #[serde(default)]
+        colors: Vec<[u8; 3]>,
+        #[serde(default)]
+        palette: Option<String>,
+    },
+    // Fills clockwise with the first color as the content progresses, e.g. the current minute
+    // of a clock; a second color draws the part still to fill
+    Progress {
+        #[serde(default)]
+        colors: Vec<[u8; 3]>,
+        #[serde(default)]
+        palette: Option<String>,
+    },
+}
+
+impl BorderEffect {
+    /// Name of the saved palette used instead of `colors`, if any
+    pub fn palette_name(&self) -> Option<&str> {
+        match self {
+            BorderEffect::Pulse { palette, .. }
+            | BorderEffect::Sparkle { palette, .. }
+            | BorderEffect::Gradient { palette, .. }
+            | BorderEffect::Progress { palette, .. } => palette.as_deref(),
+            BorderEffect::None | BorderEffect::Rainbow => None,
+        }
+    }
+
+    /// Mutable access to the colors, used to fill in a named palette
+    pub fn colors_mut(&mut self) -> Option<&mut Vec<[u8; 3]>> {
+        match self {
+            BorderEffect::Pulse { colors, .. }
+            | BorderEffect::Sparkle { colors, .. }
+            | BorderEffect::Gradient { colors, .. }
+            | BorderEffect::Progress { colors, .. } => Some(colors),
+            BorderEffect::None | BorderEffect::Rainbow => None,
+        }
+    }
+}
+
+// Payload of effects with colors, the palette name is only written when set
+fn colors_payload(colors: &[[u8; 3]], palette: &Option<String>) -> serde_json::Value {
+    match palette {
+        Some(palette) => serde_json::json!({"colors": colors, "palette": palette}),
+        None => serde_json::json!({"colors": colors}),
+    }
 }
 
 // Provide defaults
@@ -38,19 +93,24 @@ impl Serialize for BorderEffect {
                 map.end()
             }
             // Complex variants continue using the default serialization
-            BorderEffect::Pulse { colors } => {
+            BorderEffect::Pulse { colors, palette } => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("Pulse", &colors_payload(colors, palette))?;
+                map.end()
+            }
+            BorderEffect::Sparkle { colors, palette } => {
                 let mut map = serializer.serialize_map(Some(1))?;
-                map.serialize_entry("Pulse", &serde_json::json!({"colors": colors}))?;
+                map.serialize_entry("Sparkle", &colors_payload(colors, palette))?;
                 map.end()
             }
-            BorderEffect::Sparkle { colors } => {
+            BorderEffect::Gradient { colors, palette } => {
                 let mut map = serializer.serialize_map(Some(1))?;
-                map.serialize_entry("Sparkle", &serde_json::json!({"colors": colors}))?;
+                map.serialize_entry("Gradient", &colors_payload(colors, palette))?;
                 map.end()
             }
-            BorderEffect::Gradient { colors } => {
+            BorderEffect::Progress { colors, palette } => {
                 let mut map = serializer.serialize_map(Some(1))?;
-                map.serialize_entry("Gradient", &serde_json::json!({"colors": colors}))?;
+                map.serialize_entry("Progress", &colors_payload(colors, palette))?;
                 map.end()
             }
         }
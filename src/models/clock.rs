@@ -14,6 +14,19 @@ impl Default for ClockFormat {
     }
 }
 
+/// How digits change when the time ticks over
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ClockTransition {
+    /// Digits switch instantly
+    #[default]
+    None,
+    /// The old digit rolls up out of view while the new one rolls in from below
+    Roll,
+    /// Split-flap style: the top half of the old digit folds down to reveal the new one
+    Flip,
+}
+
 fn default_show_seconds() -> bool {
     false
 }
@@ -22,6 +35,10 @@ fn default_clock_color() -> [u8; 3] {
     [255, 255, 255]
 }
 
+fn default_transition_ms() -> u64 {
+    400
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct ClockContent {
     #[serde(default)]
@@ -30,4 +47,28 @@ pub struct ClockContent {
     pub show_seconds: bool,
     #[serde(default = "default_clock_color")]
     pub color: [u8; 3],
+    #[serde(default)]
+    pub transition: ClockTransition,
+    /// Length of the digit transition in milliseconds
+    #[serde(default = "default_transition_ms")]
+    pub transition_ms: u64,
+    /// Show the weekday and date below the time, in the configured locale
+    #[serde(default)]
+    pub show_date: bool,
+}
+
+impl ClockContent {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.transition != ClockTransition::None {
+            // Transitions have to finish before the next tick starts another one
+            let limit = if self.show_seconds { 900 } else { 5_000 };
+            if !(50..=limit).contains(&self.transition_ms) {
+                return Err(format!(
+                    "Clock 'transition_ms' must be between 50 and {}",
+                    limit
+                ));
+            }
+        }
+        Ok(())
+    }
 }
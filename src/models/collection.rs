@@ -0,0 +1,76 @@
+use crate::models::playlist::PlayListItem;
+use crate::utils::timezone;
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+// Longest collection name in characters
+const MAX_NAME_CHARS: usize = 64;
+
+// Named set of items that joins the rotation every year from `start` to `end`, e.g. a
+// holiday set in December. Its items play after the playlist's own items while the window
+// is open, the playlist itself is left unchanged.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Collection {
+    pub name: String,
+    // First and last day of the window ("MM-DD"), past new year when end is before start
+    pub start: String,
+    pub end: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub items: Vec<PlayListItem>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+// Month and day of a "MM-DD" date, checked against a leap year so 02-29 is allowed
+fn parse_day(day: &str) -> Result<(u32, u32), String> {
+    NaiveDate::parse_from_str(&format!("2000-{}", day), "%Y-%m-%d")
+        .map(|date| (date.month(), date.day()))
+        .map_err(|_| format!("Invalid day '{}': expected MM-DD", day))
+}
+
+/// Names may use letters, digits, '_', '-' and '.'
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().count() <= MAX_NAME_CHARS
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+impl Collection {
+    pub fn validate(&self) -> Result<(), String> {
+        if !is_valid_name(&self.name) {
+            return Err(format!("Invalid collection name '{}'", self.name));
+        }
+        parse_day(&self.start)?;
+        parse_day(&self.end)?;
+        for (index, item) in self.items.iter().enumerate() {
+            if self.items[..index].iter().any(|other| other.id == item.id) {
+                return Err(format!("Duplicate item ID '{}'", item.id));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the collection is enabled and today is inside its window
+    pub fn is_active(&self) -> bool {
+        let (Ok(start), Ok(end)) = (parse_day(&self.start), parse_day(&self.end)) else {
+            return false;
+        };
+        let now = timezone::now();
+        let today = (now.month(), now.day());
+        let in_window = if start <= end {
+            start <= today && today <= end
+        } else {
+            today >= start || today <= end
+        };
+        self.enabled && in_window
+    }
+
+    pub fn has_item(&self, id: &str) -> bool {
+        self.items.iter().any(|item| item.id == id)
+    }
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+fn default_interval_seconds() -> u64 {
+    10
+}
+
+fn default_color() -> [u8; 3] {
+    [255, 255, 255]
+}
+
+fn default_speed() -> f32 {
+    50.0
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CommandContent {
+    /// Name of a command the admin allowed with --allow-command
+    pub command: String,
+    /// Seconds between runs while the item is showing
+    #[serde(default = "default_interval_seconds")]
+    pub interval_seconds: u64,
+    #[serde(default = "default_color")]
+    pub color: [u8; 3],
+    /// Scroll speed in pixels per second, used when the output does not fit the panel
+    #[serde(default = "default_speed")]
+    pub speed: f32,
+}
+
+impl CommandContent {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.command.trim().is_empty() {
+            return Err("Command content requires a 'command' name".to_string());
+        }
+        if !(1..=86_400).contains(&self.interval_seconds) {
+            return Err("'interval_seconds' must be between 1 and 86400".to_string());
+        }
+        if !(self.speed > 0.0 && self.speed <= 1000.0) {
+            return Err("'speed' must be above 0 and at most 1000".to_string());
+        }
+        Ok(())
+    }
+}
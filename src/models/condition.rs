@@ -0,0 +1,183 @@
+use crate::utils::{http_checks, operating_hours, timezone, variables};
+use chrono::{Datelike, NaiveTime, Timelike};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+pub const WEEKDAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+// Shortest interval between two requests of an HTTP check
+const MIN_HTTP_INTERVAL_SECONDS: u64 = 5;
+
+fn default_http_interval() -> u64 {
+    60
+}
+
+// Condition for showing a playlist item, checked whenever the playlist moves on to the next
+// item. An item with several conditions is only shown when all of them hold.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Condition {
+    // Local time from `start` up to `end` ("HH:MM"), past midnight when end is before start
+    TimeRange {
+        start: String,
+        end: String,
+    },
+    // Local day of the week is one of these ("mon" to "sun")
+    Weekday {
+        days: Vec<String>,
+    },
+    // A GET request to `url` succeeds, and its body contains `contains` if given. The request
+    // runs in the background every `interval_seconds`, the condition fails until it answered.
+    Http {
+        url: String,
+        #[serde(default = "default_http_interval")]
+        interval_seconds: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        contains: Option<String>,
+    },
+    // The variable `name` (see /api/vars) compares to `value` with `op`. A variable that is
+    // not set counts as empty.
+    Variable {
+        name: String,
+        #[serde(default)]
+        op: Comparison,
+        value: String,
+    },
+    // At least one of the conditions holds
+    Any {
+        conditions: Vec<Condition>,
+    },
+    // The condition does not hold
+    Not {
+        condition: Box<Condition>,
+    },
+    // The operating hours (see /api/settings/hours) say open, always true while they are
+    // disabled
+    Open,
+}
+
+// How a variable condition compares, lt and gt compare numbers and fail for other values
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Comparison {
+    #[default]
+    Eq,
+    Ne,
+    Lt,
+    Gt,
+    Contains,
+}
+
+impl Comparison {
+    fn matches(self, actual: &str, expected: &str) -> bool {
+        let numbers = || {
+            Some((
+                actual.trim().parse::<f64>().ok()?,
+                expected.trim().parse::<f64>().ok()?,
+            ))
+        };
+        match self {
+            Comparison::Eq => actual == expected,
+            Comparison::Ne => actual != expected,
+            Comparison::Lt => numbers().is_some_and(|(actual, expected)| actual < expected),
+            Comparison::Gt => numbers().is_some_and(|(actual, expected)| actual > expected),
+            Comparison::Contains => actual.contains(expected),
+        }
+    }
+}
+
+pub fn parse_time(time: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(time, "%H:%M")
+        .map_err(|_| format!("Invalid time '{}': expected HH:MM", time))
+}
+
+impl Condition {
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Condition::TimeRange { start, end } => {
+                if parse_time(start)? == parse_time(end)? {
+                    return Err("Time range start and end cannot be the same".to_string());
+                }
+            }
+            Condition::Weekday { days } => {
+                if days.is_empty() {
+                    return Err("Weekday condition requires at least one day".to_string());
+                }
+                if let Some(day) = days.iter().find(|day| !WEEKDAYS.contains(&day.as_str())) {
+                    return Err(format!(
+                        "Invalid weekday '{}': use mon, tue, wed, thu, fri, sat or sun",
+                        day
+                    ));
+                }
+            }
+            Condition::Http {
+                url,
+                interval_seconds,
+                ..
+            } => {
+                if !url.starts_with("http://") && !url.starts_with("https://") {
+                    return Err(format!("Invalid check URL '{}': must be http(s)", url));
+                }
+                if *interval_seconds < MIN_HTTP_INTERVAL_SECONDS {
+                    return Err(format!(
+                        "HTTP check 'interval_seconds' must be at least {}",
+                        MIN_HTTP_INTERVAL_SECONDS
+                    ));
+                }
+            }
+            Condition::Variable { name, .. } => {
+                if !variables::is_valid_name(name) {
+                    return Err(format!("Invalid variable name '{}'", name));
+                }
+            }
+            Condition::Any { conditions } => {
+                if conditions.is_empty() {
+                    return Err("'any' condition requires at least one condition".to_string());
+                }
+                for condition in conditions {
+                    condition.validate()?;
+                }
+            }
+            Condition::Not { condition } => condition.validate()?,
+            Condition::Open => {}
+        }
+        Ok(())
+    }
+
+    /// Whether the condition holds right now
+    pub fn is_met(&self) -> bool {
+        match self {
+            Condition::TimeRange { start, end } => {
+                let (Ok(start), Ok(end)) = (parse_time(start), parse_time(end)) else {
+                    return false;
+                };
+                let now = timezone::now();
+                let now = NaiveTime::from_hms_opt(now.hour(), now.minute(), 0).unwrap_or(start);
+                if start < end {
+                    start <= now && now < end
+                } else {
+                    now >= start || now < end
+                }
+            }
+            Condition::Weekday { days } => {
+                let today = WEEKDAYS[timezone::now().weekday().num_days_from_monday() as usize];
+                days.iter().any(|day| day == today)
+            }
+            Condition::Http {
+                url,
+                interval_seconds,
+                contains,
+            } => http_checks::latest(
+                url,
+                contains.as_deref(),
+                Duration::from_secs(*interval_seconds),
+            ),
+            Condition::Variable { name, op, value } => {
+                op.matches(&variables::get(name).unwrap_or_default(), value)
+            }
+            Condition::Any { conditions } => conditions.iter().any(Condition::is_met),
+            Condition::Not { condition } => !condition.is_met(),
+            Condition::Open => operating_hours::is_open(),
+        }
+    }
+}
@@ -1,7 +1,16 @@
 use crate::models::animation::AnimationContent;
+use crate::models::barcode::BarcodeContent;
+use crate::models::big_number::BigNumberContent;
 use crate::models::clock::ClockContent;
+use crate::models::command::CommandContent;
+use crate::models::countdown::CountdownContent;
 use crate::models::image::ImageContent;
+use crate::models::plugin::PluginContent;
+use crate::models::qrcode::QrCodeContent;
+use crate::models::slideshow::SlideshowContent;
+use crate::models::stream::StreamContent;
 use crate::models::text::TextContent;
+use crate::models::video::VideoContent;
 use serde::{Deserialize, Serialize};
 
 // Add a ContentType enum to models.rs
@@ -11,6 +20,60 @@ pub enum ContentType {
     Image,
     Animation,
     Clock,
+    Plugin,
+    QrCode,
+    Barcode,
+    BigNumber,
+    Countdown,
+    Command,
+    Slideshow,
+    Video,
+    Stream,
+}
+
+impl ContentType {
+    pub const ALL: [ContentType; 13] = [
+        ContentType::Text,
+        ContentType::Image,
+        ContentType::Animation,
+        ContentType::Clock,
+        ContentType::Plugin,
+        ContentType::QrCode,
+        ContentType::Barcode,
+        ContentType::BigNumber,
+        ContentType::Countdown,
+        ContentType::Command,
+        ContentType::Slideshow,
+        ContentType::Video,
+        ContentType::Stream,
+    ];
+
+    /// Whether `name` is the renderer name of a built-in content type, which plugins cannot
+    /// take over
+    pub fn is_builtin(name: &str) -> bool {
+        Self::ALL
+            .iter()
+            .any(|content_type| content_type.as_str() == name)
+    }
+
+    /// Name used when looking up the renderer for this content type
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContentType::Text => "Text",
+            ContentType::Image => "Image",
+            ContentType::Animation => "Animation",
+            ContentType::Clock => "Clock",
+            ContentType::Plugin => "Plugin",
+            ContentType::QrCode => "QrCode",
+            ContentType::Barcode => "Barcode",
+            ContentType::BigNumber => "BigNumber",
+            ContentType::Countdown => "Countdown",
+            ContentType::Command => "Command",
+            ContentType::Slideshow => "Slideshow",
+            ContentType::Video => "Video",
+            ContentType::Stream => "Stream",
+        }
+    }
 }
 
 // Provide default implementation
@@ -36,4 +99,13 @@ pub enum ContentDetails {
     Image(ImageContent),
     Animation(AnimationContent),
     Clock(ClockContent),
+    Plugin(PluginContent),
+    QrCode(QrCodeContent),
+    Barcode(BarcodeContent),
+    BigNumber(BigNumberContent),
+    Countdown(CountdownContent),
+    Command(CommandContent),
+    Slideshow(SlideshowContent),
+    Video(VideoContent),
+    Stream(StreamContent),
 }
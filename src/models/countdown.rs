@@ -0,0 +1,79 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+fn default_color() -> [u8; 3] {
+    [255, 255, 255]
+}
+
+fn default_finished_text() -> String {
+    "00:00".to_string()
+}
+
+/// Extra effect applied while a threshold is active
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ThresholdEffect {
+    #[default]
+    None,
+    /// Switch the digits on and off twice per second
+    Blink,
+    /// Fade the digits in and out once per second
+    Pulse,
+}
+
+/// Style change that applies once the remaining time drops to `remaining_seconds` or below
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CountdownThreshold {
+    pub remaining_seconds: u64,
+    pub color: [u8; 3],
+    #[serde(default)]
+    pub effect: ThresholdEffect,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CountdownContent {
+    /// Moment the countdown reaches zero, as an RFC 3339 timestamp
+    pub target: String,
+    #[serde(default = "default_color")]
+    pub color: [u8; 3],
+    /// Text shown once the target has passed
+    #[serde(default = "default_finished_text")]
+    pub finished_text: String,
+    /// Milestones, the one with the lowest matching `remaining_seconds` wins
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub thresholds: Vec<CountdownThreshold>,
+}
+
+impl CountdownContent {
+    pub fn target_time(&self) -> Result<DateTime<FixedOffset>, String> {
+        DateTime::parse_from_rfc3339(&self.target)
+            .map_err(|e| format!("Invalid countdown 'target' '{}': {}", self.target, e))
+    }
+
+    /// Threshold in effect with the given number of seconds left
+    pub fn active_threshold(&self, remaining_seconds: u64) -> Option<&CountdownThreshold> {
+        self.thresholds
+            .iter()
+            .filter(|threshold| remaining_seconds <= threshold.remaining_seconds)
+            .min_by_key(|threshold| threshold.remaining_seconds)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        self.target_time()?;
+
+        let mut seen = Vec::with_capacity(self.thresholds.len());
+        for threshold in &self.thresholds {
+            if threshold.remaining_seconds == 0 {
+                return Err("Countdown thresholds need a 'remaining_seconds' above 0".to_string());
+            }
+            if seen.contains(&threshold.remaining_seconds) {
+                return Err(format!(
+                    "Duplicate countdown threshold at {} seconds",
+                    threshold.remaining_seconds
+                ));
+            }
+            seen.push(threshold.remaining_seconds);
+        }
+        Ok(())
+    }
+}
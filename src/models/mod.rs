@@ -1,9 +1,23 @@
+pub mod action;
 pub mod animation;
+pub mod barcode;
+pub mod big_number;
 pub mod border_effects;
 pub mod clock;
+pub mod collection;
+pub mod command;
+pub mod condition;
 pub mod content;
+pub mod countdown;
 pub mod image;
+pub mod panel;
 pub mod playlist;
+pub mod plugin;
 pub mod preview;
+pub mod qrcode;
 pub mod settings;
+pub mod slideshow;
+pub mod stream;
 pub mod text;
+pub mod trash;
+pub mod video;
@@ -0,0 +1,63 @@
+use crate::models::content::ContentDetails;
+use crate::models::playlist::PlayListItem;
+use crate::models::text::TextOverflow;
+use serde::{Deserialize, Serialize};
+
+// Panel resolution a playlist item was designed for
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+pub struct PanelSize {
+    pub width: i32,
+    pub height: i32,
+}
+
+impl PanelSize {
+    pub fn new(width: i32, height: i32) -> Self {
+        Self { width, height }
+    }
+}
+
+// Adapt an item designed for another panel size to `panel`, e.g. one exported from a 64x32
+// sign and imported on a 128x64 one. Image positions follow the panel, image zoom follows the
+// smaller of the two axes and static text that may no longer fit shrinks its font. Items
+// without 'created_for' are taken to be designed for `panel`. Returns whether the item
+// changed.
+pub fn adapt_item(item: &mut PlayListItem, panel: PanelSize) -> bool {
+    let Some(designed) = item.created_for.filter(|size| *size != panel) else {
+        let stamped = item.created_for.is_none();
+        item.created_for = Some(panel);
+        return stamped;
+    };
+    if designed.width <= 0 || designed.height <= 0 {
+        item.created_for = Some(panel);
+        return true;
+    }
+
+    let scale_x = panel.width as f32 / designed.width as f32;
+    let scale_y = panel.height as f32 / designed.height as f32;
+    let zoom = scale_x.min(scale_y);
+
+    match &mut item.content.data {
+        ContentDetails::Image(image) => {
+            image.transform.x = (image.transform.x as f32 * scale_x).round() as i32;
+            image.transform.y = (image.transform.y as f32 * scale_y).round() as i32;
+            image.transform.scale *= zoom;
+            if let Some(animation) = &mut image.animation {
+                for keyframe in &mut animation.keyframes {
+                    keyframe.x = (keyframe.x as f32 * scale_x).round() as i32;
+                    keyframe.y = (keyframe.y as f32 * scale_y).round() as i32;
+                    keyframe.scale *= zoom;
+                }
+            }
+        }
+        ContentDetails::Text(text)
+            if !text.scroll && zoom < 1.0 && text.overflow == TextOverflow::Clip =>
+        {
+            text.overflow = TextOverflow::ShrinkFont;
+        }
+        // Everything else lays itself out from the panel size when it renders
+        _ => {}
+    }
+
+    item.created_for = Some(panel);
+    true
+}
@@ -1,7 +1,10 @@
 use crate::models::border_effects::BorderEffect;
+use crate::models::condition::Condition;
 use crate::models::content::{ContentData, ContentDetails};
-use crate::models::text::TextContent;
+use crate::models::panel::PanelSize;
+use crate::models::text::{TextContent, TextOverflow};
 use crate::utils::uuid::generate_uuid_string;
+use chrono::{DateTime, FixedOffset, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -29,7 +32,138 @@ pub struct PlayListItem {
     pub duration: Option<u64>, // Display duration in seconds (None = use repeat_count instead)
     pub repeat_count: Option<u32>, // Number of times to repeat (None = use duration instead)
     pub border_effect: Option<BorderEffect>, // Optional border effect
+    // Flash the border when the content signals an event, e.g. a countdown threshold
+    pub border_reactive: bool,
+    // Milliseconds to fade to black once finished, overrides --outro-ms (0 = cut right away)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outro_ms: Option<u32>,
     pub content: ContentData,
+    // RFC 3339 time after which the item is skipped (or deleted, see --delete-expired-items)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+    // Show the item on a schedule instead of at its playlist position
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interstitial: Option<Interstitial>,
+    // Labels for managing items in groups, e.g. "holiday" or "ads"
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    // Disabled items stay in the playlist but are not shown
+    pub enabled: bool,
+    // Only show the item while all of these hold, checked when the playlist moves on
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub conditions: Vec<Condition>,
+    // Panel size the item was designed for, stamped when it is saved, see models::panel
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_for: Option<PanelSize>,
+    // RFC 3339 times the item was created and last changed, maintained by the server
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    // Who created or last edited the item, as given by the client
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+}
+
+// Recurring item injected between regular items, e.g. a safety notice every 10 minutes.
+// Exactly one of the two intervals is set.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct Interstitial {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub every_minutes: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub every_items: Option<u32>,
+}
+
+impl Interstitial {
+    pub fn validate(&self) -> Result<(), String> {
+        match (self.every_minutes, self.every_items) {
+            (Some(_), Some(_)) | (None, None) => Err(
+                "Interstitial requires exactly one of 'every_minutes' or 'every_items'".to_string(),
+            ),
+            (Some(minutes), None) if !(1..=1440).contains(&minutes) => {
+                Err("Interstitial 'every_minutes' must be between 1 and 1440".to_string())
+            }
+            (None, Some(0)) => Err("Interstitial 'every_items' must be at least 1".to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn parse_expires_at(expires_at: &str) -> Result<DateTime<FixedOffset>, String> {
+    DateTime::parse_from_rfc3339(expires_at)
+        .map_err(|e| format!("Invalid 'expires_at' '{}': {}", expires_at, e))
+}
+
+impl PlayListItem {
+    // Whether the item's expiry time has passed
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .as_deref()
+            .and_then(|expires_at| parse_expires_at(expires_at).ok())
+            .is_some_and(|time| time <= Utc::now())
+    }
+
+    // Whether the item takes part in playback: enabled and not expired
+    pub fn is_playable(&self) -> bool {
+        self.enabled && !self.is_expired()
+    }
+
+    // Whether all of the item's display conditions hold right now
+    pub fn conditions_met(&self) -> bool {
+        self.conditions.iter().all(Condition::is_met)
+    }
+
+    // Stamp a new item, whatever times the client sent
+    pub fn mark_created(&mut self) {
+        let now = Utc::now().to_rfc3339();
+        self.created_at = Some(now.clone());
+        self.updated_at = Some(now);
+    }
+
+    // Stamp an edit that replaces `previous`, keeping its creation time and, unless the client
+    // names a new one, its author
+    pub fn mark_replaced(&mut self, previous: &PlayListItem) {
+        self.created_at = previous.created_at.clone();
+        self.updated_at = Some(Utc::now().to_rfc3339());
+        if self.author.is_none() {
+            self.author = previous.author.clone();
+        }
+    }
+
+    // Stamp an edit made in place, e.g. a new BigNumber value
+    pub fn mark_updated(&mut self) {
+        self.updated_at = Some(Utc::now().to_rfc3339());
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    // Uploaded images the item shows, they must exist and are kept by the image cleanup
+    pub fn image_ids(&self) -> Vec<&str> {
+        match &self.content.data {
+            ContentDetails::Image(image_content) => vec![image_content.image_id.as_str()],
+            ContentDetails::Slideshow(slideshow_content) => slideshow_content
+                .images
+                .iter()
+                .map(|image| image.image_id.as_str())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    // Uploaded video the item shows, it must exist
+    pub fn video_id(&self) -> Option<&str> {
+        match &self.content.data {
+            ContentDetails::Video(video_content) => Some(video_content.video_id.as_str()),
+            _ => None,
+        }
+    }
 }
 
 // Custom deserialization to enforce mutual exclusivity and scroll validation
@@ -45,11 +179,68 @@ impl<'de> Deserialize<'de> for PlayListItem {
             duration: Option<u64>,
             repeat_count: Option<u32>,
             border_effect: Option<BorderEffect>,
+            #[serde(default)]
+            border_reactive: bool,
+            #[serde(default)]
+            outro_ms: Option<u32>,
             content: ContentData,
+            #[serde(default)]
+            expires_at: Option<String>,
+            #[serde(default)]
+            interstitial: Option<Interstitial>,
+            #[serde(default)]
+            tags: Vec<String>,
+            #[serde(default = "default_enabled")]
+            enabled: bool,
+            #[serde(default)]
+            conditions: Vec<Condition>,
+            #[serde(default)]
+            created_for: Option<PanelSize>,
+            #[serde(default)]
+            created_at: Option<String>,
+            #[serde(default)]
+            updated_at: Option<String>,
+            #[serde(default)]
+            author: Option<String>,
         }
 
         let helper = Helper::deserialize(deserializer)?;
 
+        if let Some(expires_at) = &helper.expires_at {
+            parse_expires_at(expires_at).map_err(serde::de::Error::custom)?;
+        }
+
+        if helper.tags.iter().any(|tag| tag.trim().is_empty()) {
+            return Err(serde::de::Error::custom("Tags cannot be empty"));
+        }
+
+        for condition in &helper.conditions {
+            condition.validate().map_err(serde::de::Error::custom)?;
+        }
+
+        if let Some(interstitial) = &helper.interstitial {
+            interstitial.validate().map_err(serde::de::Error::custom)?;
+            if helper.repeat_count == Some(0) {
+                return Err(serde::de::Error::custom(
+                    "Interstitial items cannot repeat forever ('repeat_count' 0)",
+                ));
+            }
+        }
+
+        if let Some(author) = &helper.author {
+            if author.trim().is_empty() || author.chars().count() > 100 {
+                return Err(serde::de::Error::custom(
+                    "'author' must be between 1 and 100 characters",
+                ));
+            }
+        }
+
+        if helper.outro_ms.is_some_and(|ms| ms > 10_000) {
+            return Err(serde::de::Error::custom(
+                "'outro_ms' must be at most 10000 milliseconds",
+            ));
+        }
+
         // Check that exactly one of duration or repeat_count is provided
         match (helper.duration, helper.repeat_count) {
             (Some(_), Some(_)) => {
@@ -78,6 +269,32 @@ impl<'de> Deserialize<'de> for PlayListItem {
                         "When 'scroll' is true, 'repeat_count' must be used instead of 'duration'",
                     ));
                 }
+                if text_content
+                    .pixels_per_second
+                    .is_some_and(|speed| !(speed > 0.0 && speed <= 1000.0))
+                {
+                    return Err(serde::de::Error::custom(
+                        "'pixels_per_second' must be above 0 and at most 1000",
+                    ));
+                }
+                if text_content
+                    .read_time_ms
+                    .is_some_and(|ms| !(500..=600_000).contains(&ms))
+                {
+                    return Err(serde::de::Error::custom(
+                        "'read_time_ms' must be between 500 and 600000",
+                    ));
+                }
+                if let Some(paging) = &text_content.paging {
+                    if !text_content.scroll {
+                        return Err(serde::de::Error::custom(
+                            "'paging' replaces scrolling and requires 'scroll' to be true",
+                        ));
+                    }
+                    if let Err(err) = paging.validate() {
+                        return Err(serde::de::Error::custom(err));
+                    }
+                }
             }
             ContentDetails::Image(image_content) => {
                 if image_content.image_id.trim().is_empty() {
@@ -108,7 +325,7 @@ impl<'de> Deserialize<'de> for PlayListItem {
                     ));
                 }
             }
-            ContentDetails::Clock(_) => {
+            ContentDetails::Clock(clock_content) => {
                 if helper.duration.is_none() {
                     return Err(serde::de::Error::custom(
                         "Clock content requires 'duration' instead of 'repeat_count'",
@@ -119,6 +336,9 @@ impl<'de> Deserialize<'de> for PlayListItem {
                         "Clock content uses 'duration' instead of 'repeat_count'",
                     ));
                 }
+                if let Err(err) = clock_content.validate() {
+                    return Err(serde::de::Error::custom(err));
+                }
             }
             ContentDetails::Animation(animation_content) => {
                 if helper.duration.is_none() {
@@ -135,6 +355,100 @@ impl<'de> Deserialize<'de> for PlayListItem {
                     return Err(serde::de::Error::custom(err));
                 }
             }
+            ContentDetails::QrCode(qr_content) => {
+                if helper.duration.is_none() {
+                    return Err(serde::de::Error::custom(
+                        "QR code content requires 'duration' instead of 'repeat_count'",
+                    ));
+                }
+                if let Err(err) = qr_content.validate() {
+                    return Err(serde::de::Error::custom(err));
+                }
+            }
+            ContentDetails::Barcode(barcode_content) => {
+                if helper.duration.is_none() {
+                    return Err(serde::de::Error::custom(
+                        "Barcode content requires 'duration' instead of 'repeat_count'",
+                    ));
+                }
+                if let Err(err) = barcode_content.validate() {
+                    return Err(serde::de::Error::custom(err));
+                }
+            }
+            ContentDetails::BigNumber(number_content) => {
+                if helper.duration.is_none() {
+                    return Err(serde::de::Error::custom(
+                        "BigNumber content requires 'duration' instead of 'repeat_count'",
+                    ));
+                }
+                if let Err(err) = number_content.validate() {
+                    return Err(serde::de::Error::custom(err));
+                }
+            }
+            ContentDetails::Countdown(countdown_content) => {
+                if helper.duration.is_none() {
+                    return Err(serde::de::Error::custom(
+                        "Countdown content requires 'duration' instead of 'repeat_count'",
+                    ));
+                }
+                if let Err(err) = countdown_content.validate() {
+                    return Err(serde::de::Error::custom(err));
+                }
+            }
+            ContentDetails::Command(command_content) => {
+                if helper.duration.is_none() {
+                    return Err(serde::de::Error::custom(
+                        "Command content requires 'duration' instead of 'repeat_count'",
+                    ));
+                }
+                if let Err(err) = command_content.validate() {
+                    return Err(serde::de::Error::custom(err));
+                }
+            }
+            ContentDetails::Slideshow(slideshow_content) => {
+                if helper.duration.is_some() {
+                    return Err(serde::de::Error::custom(
+                        "Slideshow content must use 'repeat_count' instead of 'duration'",
+                    ));
+                }
+                if let Err(err) = slideshow_content.validate() {
+                    return Err(serde::de::Error::custom(err));
+                }
+            }
+            ContentDetails::Video(video_content) => {
+                if video_content.video_id.trim().is_empty() {
+                    return Err(serde::de::Error::custom(
+                        "Video content requires a valid 'video_id'",
+                    ));
+                }
+                if helper.duration.is_some() {
+                    return Err(serde::de::Error::custom(
+                        "Video content must use 'repeat_count' instead of 'duration'",
+                    ));
+                }
+            }
+            ContentDetails::Stream(stream_content) => {
+                if helper.duration.is_none() {
+                    return Err(serde::de::Error::custom(
+                        "Stream content requires 'duration' instead of 'repeat_count'",
+                    ));
+                }
+                if let Err(err) = stream_content.validate() {
+                    return Err(serde::de::Error::custom(err));
+                }
+            }
+            ContentDetails::Plugin(plugin_content) => {
+                if plugin_content.plugin.trim().is_empty() {
+                    return Err(serde::de::Error::custom(
+                        "Plugin content requires a valid 'plugin' name",
+                    ));
+                }
+                if helper.duration.is_none() {
+                    return Err(serde::de::Error::custom(
+                        "Plugin content requires 'duration' instead of 'repeat_count'",
+                    ));
+                }
+            }
         }
 
         // Determine whether repeat_count is required based on content
@@ -143,6 +457,15 @@ impl<'de> Deserialize<'de> for PlayListItem {
             ContentDetails::Image(image_content) => image_content.animation.is_some(),
             ContentDetails::Clock(_) => false,
             ContentDetails::Animation(_) => false,
+            ContentDetails::Plugin(_) => false,
+            ContentDetails::QrCode(_) => false,
+            ContentDetails::Barcode(_) => false,
+            ContentDetails::BigNumber(_) => false,
+            ContentDetails::Countdown(_) => false,
+            ContentDetails::Command(_) => false,
+            ContentDetails::Slideshow(_) => true,
+            ContentDetails::Video(_) => true,
+            ContentDetails::Stream(_) => false,
         };
 
         // Check if repeat_count is required but missing
@@ -154,7 +477,20 @@ impl<'de> Deserialize<'de> for PlayListItem {
                 ContentDetails::Image(_) => {
                     "Animated images require 'repeat_count' instead of 'duration'"
                 }
-                ContentDetails::Clock(_) => unreachable!(),
+                ContentDetails::Slideshow(_) => {
+                    "Slideshow content requires 'repeat_count' instead of 'duration'"
+                }
+                ContentDetails::Video(_) => {
+                    "Video content requires 'repeat_count' instead of 'duration'"
+                }
+                ContentDetails::Clock(_)
+                | ContentDetails::Plugin(_)
+                | ContentDetails::QrCode(_)
+                | ContentDetails::Barcode(_)
+                | ContentDetails::BigNumber(_)
+                | ContentDetails::Countdown(_)
+                | ContentDetails::Command(_)
+                | ContentDetails::Stream(_) => unreachable!(),
                 ContentDetails::Animation(_) => {
                     "Animation content requires 'duration' instead of 'repeat_count'"
                 }
@@ -165,7 +501,7 @@ impl<'de> Deserialize<'de> for PlayListItem {
         // Additional check: static content that shouldn't repeat_count
         if !requires_repeat_count && helper.repeat_count.is_some() {
             return Err(serde::de::Error::custom(
-                "Repeat count can only be used with scrolling text or animated images",
+                "Repeat count can only be used with scrolling text, animated images, slideshows or videos",
             ));
         }
 
@@ -174,7 +510,18 @@ impl<'de> Deserialize<'de> for PlayListItem {
             duration: helper.duration,
             repeat_count: helper.repeat_count,
             border_effect: helper.border_effect,
+            border_reactive: helper.border_reactive,
+            outro_ms: helper.outro_ms,
             content: helper.content,
+            expires_at: helper.expires_at,
+            interstitial: helper.interstitial,
+            tags: helper.tags,
+            enabled: helper.enabled,
+            conditions: helper.conditions,
+            created_for: helper.created_for,
+            created_at: helper.created_at,
+            updated_at: helper.updated_at,
+            author: helper.author,
         })
     }
 }
@@ -187,6 +534,8 @@ impl Default for PlayListItem {
             duration: Some(10), // Default to 10 seconds duration
             repeat_count: None, // No repeat count by default (exclusive with duration)
             border_effect: None,
+            border_reactive: false,
+            outro_ms: None,
             content: ContentData {
                 content_type: crate::models::content::ContentType::Text,
                 data: ContentDetails::Text(TextContent {
@@ -195,8 +544,47 @@ impl Default for PlayListItem {
                     color: [255, 255, 255],
                     speed: 50.0,
                     text_segments: None,
+                    pixels_per_second: None,
+                    read_time_ms: None,
+                    scroll_ease_ms: None,
+                    antialias: false,
+                    paging: None,
+                    overflow: TextOverflow::default(),
                 }),
             },
+            expires_at: None,
+            interstitial: None,
+            tags: Vec::new(),
+            enabled: true,
+            conditions: Vec::new(),
+            created_for: None,
+            created_at: None,
+            updated_at: None,
+            author: None,
         }
     }
 }
+
+// Whether a playlist item rendered successfully the last time it was shown
+#[derive(Clone, Copy, Serialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    // Not shown since the controller started
+    #[default]
+    Unknown,
+    Ok,
+    Error,
+}
+
+// Runtime health of a playlist item, served by /api/playlist/items/:id/health
+#[derive(Clone, Serialize, Debug, Default)]
+pub struct ItemHealth {
+    pub status: HealthStatus,
+    // Most recent failure, kept after the item recovers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    // RFC 3339 time of the most recent failure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error_at: Option<String>,
+    pub error_count: u32,
+}
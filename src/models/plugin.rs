@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// Content rendered by a renderer that was registered at runtime (e.g. a dynamic library plugin).
+/// `plugin` is the content type string the renderer registered itself under, and `config` is
+/// passed through to the plugin untouched.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct PluginContent {
+    pub plugin: String,
+    #[serde(default)]
+    pub config: serde_json::Value,
+}
@@ -0,0 +1,92 @@
+use qrcode::{EcLevel, QrCode};
+use serde::{Deserialize, Serialize};
+
+/// Share of damaged modules a QR code can recover from
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq)]
+pub enum QrErrorCorrection {
+    /// ~7%, smallest code
+    L,
+    /// ~15%
+    #[default]
+    M,
+    /// ~25%
+    Q,
+    /// ~30%, largest code
+    H,
+}
+
+impl QrErrorCorrection {
+    pub fn ec_level(self) -> EcLevel {
+        match self {
+            QrErrorCorrection::L => EcLevel::L,
+            QrErrorCorrection::M => EcLevel::M,
+            QrErrorCorrection::Q => EcLevel::Q,
+            QrErrorCorrection::H => EcLevel::H,
+        }
+    }
+}
+
+fn default_foreground() -> [u8; 3] {
+    [0, 0, 0]
+}
+
+fn default_background() -> [u8; 3] {
+    [255, 255, 255]
+}
+
+fn default_quiet_zone() -> u8 {
+    4
+}
+
+fn default_caption_color() -> [u8; 3] {
+    [255, 255, 255]
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct QrCodeContent {
+    /// URL or text to encode
+    pub data: String,
+    #[serde(default)]
+    pub error_correction: QrErrorCorrection,
+    /// Color of the dark modules. Scanners expect them darker than the background
+    #[serde(default = "default_foreground")]
+    pub foreground: [u8; 3],
+    /// Color of the light modules and the quiet zone
+    #[serde(default = "default_background")]
+    pub background: [u8; 3],
+    /// Preferred quiet zone in modules; shrunk down to 1 when the code would not fit
+    #[serde(default = "default_quiet_zone")]
+    pub quiet_zone: u8,
+    /// Optional text shown next to the code
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub caption: Option<String>,
+    #[serde(default = "default_caption_color")]
+    pub caption_color: [u8; 3],
+}
+
+impl QrCodeContent {
+    /// Encode the data, returning the modules row by row (true = dark) and the code width
+    pub fn encode(&self) -> Result<(Vec<bool>, usize), String> {
+        let code = QrCode::with_error_correction_level(
+            self.data.as_bytes(),
+            self.error_correction.ec_level(),
+        )
+        .map_err(|e| format!("Failed to encode QR code: {}", e))?;
+
+        let width = code.width();
+        let modules = code
+            .to_colors()
+            .into_iter()
+            .map(|color| color == qrcode::Color::Dark)
+            .collect();
+
+        Ok((modules, width))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.data.is_empty() {
+            return Err("QR code content requires non-empty 'data'".to_string());
+        }
+        self.encode().map(|_| ())
+    }
+}
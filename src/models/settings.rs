@@ -1,8 +1,350 @@
+use crate::models::animation::AnimationContent;
+use crate::models::border_effects::BorderEffect;
+use crate::models::condition::{parse_time, WEEKDAYS};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+// Highest user brightness. Brightness in the API is a percentage of the panel brightness set
+// with --limit-max-brightness, so this shows the panel at exactly that limit.
+pub const MAX_BRIGHTNESS: u8 = 100;
+
 // New structure for brightness settings
 #[derive(Serialize, Deserialize, Clone)]
 pub struct BrightnessSettings {
     pub brightness: u8,
+    // Optional fade duration in milliseconds, 0 or missing changes brightness instantly
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fade_ms: Option<u64>,
+}
+
+// Current brightness and the highest one accepted, both in user percent, together with the
+// panel brightness in percent of full brightness that max_allowed is shown at
+#[derive(Serialize, Clone)]
+pub struct BrightnessStatus {
+    pub current: u8,
+    // Same as current, for clients written before current was added
+    pub brightness: u8,
+    pub max_allowed: u8,
+    pub panel_limit: u8,
+}
+
+// What the display shows while the playlist is empty
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum IdleMode {
+    // Keep showing the welcome message
+    #[default]
+    Welcome,
+    // Turn the panel off
+    Blank,
+    // Show a dimmed clock
+    Clock,
+    // Play an animation preset
+    Animation,
+}
+
+fn default_idle_clock_brightness() -> u8 {
+    20
+}
+
+// Longest accepted wait before the idle behavior starts (1 day)
+const MAX_IDLE_AFTER_MINUTES: u32 = 24 * 60;
+
+// Idle screen settings, managed via /api/settings/idle
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IdleSettings {
+    #[serde(default)]
+    pub mode: IdleMode,
+    // Minutes the welcome message is shown before switching to the idle mode, 0 switches right away
+    #[serde(default)]
+    pub after_minutes: u32,
+    // Brightness of the idle clock in percent, never brighter than the display brightness
+    #[serde(default = "default_idle_clock_brightness")]
+    pub clock_brightness: u8,
+    // Preset played in animation mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub animation: Option<AnimationContent>,
+}
+
+impl Default for IdleSettings {
+    fn default() -> Self {
+        Self {
+            mode: IdleMode::default(),
+            after_minutes: 0,
+            clock_brightness: default_idle_clock_brightness(),
+            animation: None,
+        }
+    }
+}
+
+impl IdleSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.after_minutes > MAX_IDLE_AFTER_MINUTES {
+            return Err(format!(
+                "Idle 'after_minutes' must be at most {}",
+                MAX_IDLE_AFTER_MINUTES
+            ));
+        }
+        if self.clock_brightness > 100 {
+            return Err("Idle 'clock_brightness' must be between 0 and 100".to_string());
+        }
+        match (&self.mode, &self.animation) {
+            (IdleMode::Animation, None) => {
+                Err("Idle mode 'animation' requires an 'animation' preset".to_string())
+            }
+            (_, Some(animation)) => animation.validate(),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn default_welcome_color() -> [u8; 3] {
+    [0, 255, 0]
+}
+
+fn default_welcome_speed() -> f32 {
+    30.0
+}
+
+fn default_welcome_border_effect() -> Option<BorderEffect> {
+    Some(BorderEffect::Pulse {
+        colors: vec![[0, 255, 0], [0, 200, 0]],
+        palette: None,
+    })
+}
+
+// Longest accepted welcome message, in characters
+const MAX_WELCOME_TEXT_CHARS: usize = 1000;
+
+// Look of the welcome message, managed via /api/settings/welcome
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct WelcomeSettings {
+    // Replaces the built-in message, "{url}" is replaced with the web interface address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default = "default_welcome_color")]
+    pub color: [u8; 3],
+    // Scroll speed in pixels per second
+    #[serde(default = "default_welcome_speed")]
+    pub speed: f32,
+    // null shows no border
+    #[serde(default = "default_welcome_border_effect")]
+    pub border_effect: Option<BorderEffect>,
+}
+
+impl Default for WelcomeSettings {
+    fn default() -> Self {
+        Self {
+            text: None,
+            color: default_welcome_color(),
+            speed: default_welcome_speed(),
+            border_effect: default_welcome_border_effect(),
+        }
+    }
+}
+
+impl WelcomeSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(text) = &self.text {
+            if text.trim().is_empty() {
+                return Err("Welcome 'text' must not be empty, omit it for the default".to_string());
+            }
+            if text.chars().count() > MAX_WELCOME_TEXT_CHARS {
+                return Err(format!(
+                    "Welcome 'text' must be at most {} characters",
+                    MAX_WELCOME_TEXT_CHARS
+                ));
+            }
+        }
+        if !(self.speed > 0.0 && self.speed <= 1000.0) {
+            return Err("Welcome 'speed' must be above 0 and at most 1000".to_string());
+        }
+        Ok(())
+    }
+}
+
+fn default_text_color() -> [u8; 3] {
+    [255, 255, 255]
+}
+
+fn default_text_speed() -> f32 {
+    50.0
+}
+
+fn default_image_duration() -> u64 {
+    10
+}
+
+// Values filled in for fields API clients leave out of new items, managed via
+// /api/settings/defaults
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ContentDefaults {
+    #[serde(default = "default_text_color")]
+    pub text_color: [u8; 3],
+    // Scroll speed in pixels per second
+    #[serde(default = "default_text_speed")]
+    pub text_speed: f32,
+    // Seconds a static image is shown when neither 'duration' nor 'repeat_count' is given
+    #[serde(default = "default_image_duration")]
+    pub image_duration: u64,
+    // Border of items that leave out 'border_effect', None adds no border
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub border_effect: Option<BorderEffect>,
+}
+
+impl Default for ContentDefaults {
+    fn default() -> Self {
+        Self {
+            text_color: default_text_color(),
+            text_speed: default_text_speed(),
+            image_duration: default_image_duration(),
+            border_effect: None,
+        }
+    }
+}
+
+impl ContentDefaults {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.text_speed > 0.0 && self.text_speed <= 1000.0) {
+            return Err("Default 'text_speed' must be above 0 and at most 1000".to_string());
+        }
+        if self.image_duration == 0 {
+            return Err("Default 'image_duration' must be at least 1 second".to_string());
+        }
+        Ok(())
+    }
+}
+
+// What the display does while the operating hours say closed
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ClosedBehavior {
+    // Keep playing, only 'open' conditions and {open_state} follow the hours
+    #[default]
+    Play,
+    // Show the idle screen instead of the playlist
+    Idle,
+    // Switch the display off, and on again when opening
+    Off,
+}
+
+// Opening hours of one weekday as "HH:MM". A close before open runs past midnight, equal
+// times are open all day.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DayHours {
+    pub open: String,
+    pub close: String,
+}
+
+// Longest accepted list of holidays
+const MAX_HOLIDAYS: usize = 366;
+
+// Weekly operating hours with holidays, managed via /api/settings/hours
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct OperatingHours {
+    #[serde(default)]
+    pub enabled: bool,
+    // Hours per weekday, keyed "mon" to "sun". Days left out are closed.
+    #[serde(default)]
+    pub week: BTreeMap<String, DayHours>,
+    // Days closed all day, "YYYY-MM-DD" for one date or "MM-DD" for every year
+    #[serde(default)]
+    pub holidays: Vec<String>,
+    #[serde(default)]
+    pub when_closed: ClosedBehavior,
+}
+
+impl OperatingHours {
+    pub fn validate(&self) -> Result<(), String> {
+        for (day, hours) in &self.week {
+            if !WEEKDAYS.contains(&day.as_str()) {
+                return Err(format!(
+                    "Invalid weekday '{}': use mon, tue, wed, thu, fri, sat or sun",
+                    day
+                ));
+            }
+            parse_time(&hours.open)?;
+            parse_time(&hours.close)?;
+        }
+        if self.holidays.len() > MAX_HOLIDAYS {
+            return Err(format!("At most {} holidays are allowed", MAX_HOLIDAYS));
+        }
+        for holiday in &self.holidays {
+            let valid = NaiveDate::parse_from_str(holiday, "%Y-%m-%d").is_ok()
+                || NaiveDate::parse_from_str(&format!("2000-{}", holiday), "%Y-%m-%d").is_ok();
+            if !valid {
+                return Err(format!(
+                    "Invalid holiday '{}': expected YYYY-MM-DD or MM-DD",
+                    holiday
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `now`, a local time, is within the operating hours. Always true while they
+    /// are disabled.
+    pub fn is_open_at(&self, now: NaiveDateTime) -> bool {
+        if !self.enabled {
+            return true;
+        }
+        let time = now.time();
+        if let Some((open, close)) = self.hours_on(now.date()) {
+            let open_now = if open < close {
+                open <= time && time < close
+            } else {
+                open == close || time >= open
+            };
+            if open_now {
+                return true;
+            }
+        }
+        // Hours of the day before that run past midnight
+        now.date()
+            .pred_opt()
+            .and_then(|yesterday| self.hours_on(yesterday))
+            .is_some_and(|(open, close)| close < open && time < close)
+    }
+
+    // Opening and closing time of a date, None on closed days and holidays
+    fn hours_on(&self, date: NaiveDate) -> Option<(NaiveTime, NaiveTime)> {
+        let full_date = date.format("%Y-%m-%d").to_string();
+        let every_year = date.format("%m-%d").to_string();
+        if self
+            .holidays
+            .iter()
+            .any(|holiday| *holiday == full_date || *holiday == every_year)
+        {
+            return None;
+        }
+        let hours = self
+            .week
+            .get(WEEKDAYS[date.weekday().num_days_from_monday() as usize])?;
+        Some((
+            parse_time(&hours.open).ok()?,
+            parse_time(&hours.close).ok()?,
+        ))
+    }
+}
+
+// How a dead or stuck pixel is corrected
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DeadPixelFix {
+    // Always send black, for pixels stuck bright
+    #[default]
+    Off,
+    // Show the average of the working neighbors, for pixels stuck on a wrong color
+    Blend,
+}
+
+// A dead or stuck pixel registered via /api/display/dead-pixels
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct DeadPixel {
+    pub x: i32,
+    pub y: i32,
+    #[serde(default)]
+    pub fix: DeadPixelFix,
 }
 
 // New structure for reordering request
@@ -10,3 +352,9 @@ pub struct BrightnessSettings {
 pub struct ReorderRequest {
     pub item_ids: Vec<String>,
 }
+
+// Enable or disable all playlist items with a tag
+#[derive(Deserialize)]
+pub struct TagEnabledRequest {
+    pub enabled: bool,
+}
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+// Most images in one slideshow
+const MAX_IMAGES: usize = 100;
+
+// Allowed time an image shows for, in milliseconds
+const MIN_DWELL_MS: u32 = 100;
+const MAX_DWELL_MS: u32 = 3_600_000;
+
+fn default_dwell_ms() -> u32 {
+    5000
+}
+
+fn default_transition_ms() -> u32 {
+    500
+}
+
+/// How one image replaces the previous one
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SlideTransition {
+    /// Switch right away
+    #[default]
+    None,
+    /// Cross-fade from the previous image
+    Fade,
+    /// Push the previous image out to the left
+    Slide,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SlideshowImage {
+    pub image_id: String,
+    /// Overrides the slideshow's `dwell_ms` for this image
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dwell_ms: Option<u32>,
+}
+
+/// Uploaded images shown one after the other, each scaled to fit the panel
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SlideshowContent {
+    pub images: Vec<SlideshowImage>,
+    /// How long each image shows, including its transition
+    #[serde(default = "default_dwell_ms")]
+    pub dwell_ms: u32,
+    #[serde(default)]
+    pub transition: SlideTransition,
+    #[serde(default = "default_transition_ms")]
+    pub transition_ms: u32,
+}
+
+impl SlideshowContent {
+    /// How long the image at `index` shows
+    pub fn dwell_ms(&self, index: usize) -> u32 {
+        self.images
+            .get(index)
+            .and_then(|image| image.dwell_ms)
+            .unwrap_or(self.dwell_ms)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.images.is_empty() {
+            return Err("Slideshow content requires at least one image".to_string());
+        }
+        if self.images.len() > MAX_IMAGES {
+            return Err(format!(
+                "Slideshow content allows at most {} images",
+                MAX_IMAGES
+            ));
+        }
+        if self
+            .images
+            .iter()
+            .any(|image| image.image_id.trim().is_empty())
+        {
+            return Err("Slideshow images require a valid 'image_id'".to_string());
+        }
+        for index in 0..self.images.len() {
+            let dwell_ms = self.dwell_ms(index);
+            if !(MIN_DWELL_MS..=MAX_DWELL_MS).contains(&dwell_ms) {
+                return Err(format!(
+                    "Slideshow 'dwell_ms' must be between {} and {}",
+                    MIN_DWELL_MS, MAX_DWELL_MS
+                ));
+            }
+            if self.transition != SlideTransition::None && self.transition_ms >= dwell_ms {
+                return Err("Slideshow 'transition_ms' must be shorter than 'dwell_ms'".to_string());
+            }
+        }
+        Ok(())
+    }
+}
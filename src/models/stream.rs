@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+// Allowed frame rates, in frames per second
+const MIN_FPS: f32 = 0.1;
+const MAX_FPS: f32 = 10.0;
+
+fn default_max_fps() -> f32 {
+    1.0
+}
+
+/// Frames pulled from a camera, e.g. a doorbell, and scaled to fit the panel
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct StreamContent {
+    /// MJPEG stream or JPEG/PNG snapshot over HTTP(S), or an RTSP stream (needs ffmpeg)
+    pub url: String,
+    /// Most frames fetched per second, a new one is only requested once the last has arrived
+    #[serde(default = "default_max_fps")]
+    pub max_fps: f32,
+}
+
+impl StreamContent {
+    pub fn validate(&self) -> Result<(), String> {
+        let url = self.url.trim();
+        if !["http://", "https://", "rtsp://"]
+            .iter()
+            .any(|scheme| url.starts_with(scheme))
+        {
+            return Err("Stream content requires an http, https or rtsp 'url'".to_string());
+        }
+        if !(MIN_FPS..=MAX_FPS).contains(&self.max_fps) {
+            return Err(format!(
+                "'max_fps' must be between {} and {}",
+                MIN_FPS, MAX_FPS
+            ));
+        }
+        Ok(())
+    }
+}
@@ -29,6 +29,65 @@ pub struct TextSegment {
     pub end: usize,             // End index in the text (exclusive, character position)
     pub color: Option<[u8; 3]>, // Changed from tuple to array
     pub formatting: Option<TextFormatting>, // Optional formatting
+    // Optional highlight color filled behind the segment's characters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<[u8; 3]>,
+}
+
+// How paged text moves from one page to the next
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PageTransition {
+    None,
+    #[default]
+    Slide,
+}
+
+fn default_dwell_ms() -> u64 {
+    2500
+}
+
+fn default_page_transition_ms() -> u64 {
+    300
+}
+
+// What static text does when it is wider than the panel
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TextOverflow {
+    // Draw it centered and cut off at both edges
+    #[default]
+    Clip,
+    // Show as many whole characters as fit, followed by an ellipsis
+    TruncateEllipsis,
+    // Use the largest font it fits in, truncating with the smallest if none does
+    ShrinkFont,
+    // Scroll it like scrolling text, static text that fits stays still
+    ScrollIfTooLong,
+}
+
+// Shows scrolling text one panel-width page at a time instead of as a continuous marquee
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct TextPaging {
+    // How long each page stays still
+    #[serde(default = "default_dwell_ms")]
+    pub dwell_ms: u64,
+    #[serde(default)]
+    pub transition: PageTransition,
+    #[serde(default = "default_page_transition_ms")]
+    pub transition_ms: u64,
+}
+
+impl TextPaging {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.dwell_ms < 250 {
+            return Err("Text paging 'dwell_ms' must be at least 250".to_string());
+        }
+        if self.transition == PageTransition::Slide && !(50..=5_000).contains(&self.transition_ms) {
+            return Err("Text paging 'transition_ms' must be between 50 and 5000".to_string());
+        }
+        Ok(())
+    }
 }
 
 // Text-specific content structure
@@ -39,4 +98,22 @@ pub struct TextContent {
     pub color: [u8; 3], // Changed from tuple to array
     pub speed: f32,
     pub text_segments: Option<Vec<TextSegment>>,
+    // Scroll speed as a float, takes precedence over 'speed'
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pixels_per_second: Option<f32>,
+    // Scroll so one full pass of the text takes this long, takes precedence over both speeds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_time_ms: Option<u64>,
+    // Ramp the scroll speed up at the start and down at the end of the last pass
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scroll_ease_ms: Option<u64>,
+    // Soften glyph edges and blend fractional scroll positions, for larger panels
+    #[serde(default)]
+    pub antialias: bool,
+    // Page through the text instead of scrolling it; only used when 'scroll' is true
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paging: Option<TextPaging>,
+    // What static text wider than the panel does; only used when 'scroll' is false
+    #[serde(default)]
+    pub overflow: TextOverflow,
 }
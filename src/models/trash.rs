@@ -0,0 +1,105 @@
+use crate::models::playlist::PlayListItem;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+// Deleted playlist item, kept so it can be restored until the trash is purged
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TrashedItem {
+    pub item: PlayListItem,
+    // RFC 3339 time of deletion
+    pub deleted_at: String,
+}
+
+impl TrashedItem {
+    pub fn new(item: PlayListItem, now: DateTime<Utc>) -> Self {
+        Self {
+            item,
+            deleted_at: now.to_rfc3339(),
+        }
+    }
+
+    /// Time after which the item is purged, None if the deletion time is unreadable
+    pub fn purge_at(&self, days: u32) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.deleted_at)
+            .ok()
+            .map(|deleted_at| deleted_at.with_timezone(&Utc) + Duration::days(days as i64))
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Trash {
+    pub items: Vec<TrashedItem>,
+}
+
+impl Trash {
+    /// Take the item with the given ID out of the trash
+    pub fn take(&mut self, id: &str) -> Option<TrashedItem> {
+        let index = self
+            .items
+            .iter()
+            .position(|trashed| trashed.item.id == id)?;
+        Some(self.items.remove(index))
+    }
+
+    /// Drop items deleted more than `days` days ago, returns how many were dropped
+    pub fn purge_expired(&mut self, days: u32, now: DateTime<Utc>) -> usize {
+        let before = self.items.len();
+        self.items.retain(|trashed| {
+            trashed
+                .purge_at(days)
+                .is_some_and(|purge_at| purge_at > now)
+        });
+        before - self.items.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trashed(id: &str, deleted_at: DateTime<Utc>) -> TrashedItem {
+        let item = PlayListItem {
+            id: id.to_string(),
+            ..PlayListItem::default()
+        };
+        TrashedItem::new(item, deleted_at)
+    }
+
+    #[test]
+    fn purge_drops_only_expired_items() {
+        let now = Utc::now();
+        let mut trash = Trash {
+            items: vec![
+                trashed("old", now - Duration::days(31)),
+                trashed("recent", now - Duration::days(29)),
+            ],
+        };
+        assert_eq!(trash.purge_expired(30, now), 1);
+        assert_eq!(trash.items.len(), 1);
+        assert_eq!(trash.items[0].item.id, "recent");
+    }
+
+    #[test]
+    fn purge_drops_items_with_unreadable_times() {
+        let mut trash = Trash {
+            items: vec![trashed("broken", Utc::now())],
+        };
+        trash.items[0].deleted_at = "yesterday".to_string();
+        assert_eq!(trash.purge_expired(30, Utc::now()), 1);
+        assert!(trash.items.is_empty());
+    }
+
+    #[test]
+    fn take_removes_the_item() {
+        let now = Utc::now();
+        let mut trash = Trash {
+            items: vec![trashed("a", now), trashed("b", now)],
+        };
+        assert_eq!(
+            trash.take("b").map(|trashed| trashed.item.id),
+            Some("b".into())
+        );
+        assert!(trash.take("b").is_none());
+        assert_eq!(trash.items.len(), 1);
+    }
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct VideoContent {
+    /// ID returned by the video upload endpoint
+    pub video_id: String,
+}
+
+/// Decoded video stored next to its frames, see the video upload endpoint
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct VideoInfo {
+    pub video_id: String,
+    /// Size of the frames, the panel size at upload time
+    pub width: u32,
+    pub height: u32,
+    pub fps: u32,
+    pub frame_count: u32,
+}
+
+impl VideoInfo {
+    /// Bytes of one RGB frame
+    pub fn frame_bytes(&self) -> usize {
+        self.width as usize * self.height as usize * 3
+    }
+}
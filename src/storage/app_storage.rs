@@ -1,61 +1,208 @@
-use crate::models::content::ContentDetails;
-use crate::models::playlist::Playlist;
+use crate::config::{HardwareProfile, StoredConfig};
+use crate::models::collection::Collection;
+use crate::models::playlist::{PlayListItem, Playlist};
+use crate::models::settings::{
+    ContentDefaults, DeadPixel, IdleSettings, OperatingHours, WelcomeSettings,
+};
+use crate::models::trash::Trash;
+use crate::models::video::VideoInfo;
 use crate::storage::manager::{paths, StorageManager};
-use log::{debug, error, info};
-use std::collections::HashSet;
+use crate::storage::recovery::{DroppedItem, PlaylistRecovery, StartupReport};
+use log::{debug, error, info, warn};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
 use std::io::ErrorKind;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// Playlist and brightness changes not written yet, with the time of the first change since
+// the last write
+#[derive(Default)]
+struct PendingWrites {
+    playlist: Option<Playlist>,
+    brightness: Option<u8>,
+    since: Option<Instant>,
+}
 
 // Unified storage for all application settings
 pub struct AppStorage {
     storage_manager: StorageManager,
+    // Longest time a change waits before it is written, zero writes right away
+    save_interval: Duration,
+    pending: PendingWrites,
 }
 
 impl AppStorage {
     pub fn new(storage_manager: StorageManager) -> Self {
-        Self { storage_manager }
+        Self {
+            storage_manager,
+            save_interval: Duration::ZERO,
+            pending: PendingWrites::default(),
+        }
+    }
+
+    // Coalesce playlist and brightness writes: a change is written at most `interval` after
+    // the first unsaved one, so a burst of edits costs one write of the file instead of one
+    // per edit
+    pub fn set_save_interval(&mut self, interval: Duration) {
+        self.save_interval = interval;
+    }
+
+    // Keep regenerable files such as thumbnails in `dir`, e.g. a tmpfs, instead of the data
+    // directory
+    pub fn set_cache_dir(&mut self, dir: &str) {
+        self.storage_manager
+            .set_cache_dir(std::path::PathBuf::from(dir));
+    }
+
+    // Queue a change for flush_pending
+    fn queue(&mut self, update: impl FnOnce(&mut PendingWrites)) {
+        update(&mut self.pending);
+        self.pending.since.get_or_insert_with(Instant::now);
+    }
+
+    // Write the queued changes once they have waited the save interval, or right away if
+    // `force` is set, e.g. on shutdown. Returns false if writing any of them failed, those
+    // stay queued to be retried after another interval.
+    pub fn flush_pending(&mut self, force: bool) -> bool {
+        let due = self
+            .pending
+            .since
+            .is_some_and(|since| force || since.elapsed() >= self.save_interval);
+        if !due {
+            return true;
+        }
+        let PendingWrites {
+            playlist,
+            brightness,
+            ..
+        } = std::mem::take(&mut self.pending);
+
+        let mut ok = true;
+        if let Some(playlist) = playlist {
+            if !self.write_playlist(&playlist) {
+                self.queue(|pending| pending.playlist = Some(playlist));
+                ok = false;
+            }
+        }
+        if let Some(brightness) = brightness {
+            if !self.write_brightness(brightness) {
+                self.queue(|pending| pending.brightness = Some(brightness));
+                ok = false;
+            }
+        }
+        ok
     }
 
     // Playlist-related methods
-    pub fn load_playlist(&self) -> Option<Playlist> {
-        // Check if the file exists first
+
+    // Load the playlist at startup, recovering what can be read when the file is damaged.
+    // Items that cannot be read are dropped, and if the file cannot be read at all the backup
+    // taken at the previous startup is used. What happened is recorded in `report`.
+    pub fn recover_playlist(&self, report: &mut StartupReport) -> Option<Playlist> {
         if !self.storage_manager.file_exists(paths::PLAYLIST_FILE) {
             debug!("No playlist file found");
+            report.playlist = PlaylistRecovery::Missing;
             return None;
         }
 
-        // Try to read and parse the file
-        match self.storage_manager.read_file(paths::PLAYLIST_FILE) {
-            Ok(contents) => {
-                debug!("Loaded playlist file, attempting to parse");
-                match serde_json::from_str::<Playlist>(&contents) {
-                    Ok(playlist) => {
-                        info!(
-                            "Successfully loaded playlist with {} items",
-                            playlist.items.len()
-                        );
-                        if let Some(mut playlist) = Some(playlist) {
-                            playlist.active_index = 0;
-                            Some(playlist)
-                        } else {
-                            None
-                        }
-                    }
-                    Err(e) => {
-                        error!("Error parsing playlist file: {}", e);
-                        None
-                    }
-                }
-            }
+        let contents = match self.storage_manager.read_file(paths::PLAYLIST_FILE) {
+            Ok(contents) => contents,
             Err(e) => {
                 error!("Error reading playlist file: {}", e);
+                String::new()
+            }
+        };
+
+        if let Ok(mut playlist) = serde_json::from_str::<Playlist>(&contents) {
+            info!(
+                "Successfully loaded playlist with {} items",
+                playlist.items.len()
+            );
+            if let Err(e) = self
+                .storage_manager
+                .write_file(paths::PLAYLIST_BACKUP_FILE, &contents)
+            {
+                warn!("Failed to back up the playlist: {}", e);
+            }
+            playlist.active_index = 0;
+            report.playlist = PlaylistRecovery::Loaded;
+            report.items_loaded = playlist.items.len();
+            return Some(playlist);
+        }
+
+        // Keep the damaged file, the next save overwrites the playlist file
+        if self
+            .storage_manager
+            .write_file(paths::PLAYLIST_DAMAGED_FILE, &contents)
+            .is_ok()
+        {
+            warn!(
+                "Playlist file could not be read completely, kept a copy as {}",
+                paths::PLAYLIST_DAMAGED_FILE
+            );
+            report.damaged_copy = Some(paths::PLAYLIST_DAMAGED_FILE.to_string());
+        }
+
+        if let Some((playlist, dropped)) = salvage_playlist(&contents) {
+            for item in &dropped {
+                warn!(
+                    "Dropped playlist item {} ({}): {}",
+                    item.index,
+                    item.id.as_deref().unwrap_or("no ID"),
+                    item.error
+                );
+            }
+            report.playlist = PlaylistRecovery::Salvaged;
+            report.items_loaded = playlist.items.len();
+            report.dropped_items = dropped;
+            return Some(playlist);
+        }
+
+        let backup = self
+            .storage_manager
+            .read_file(paths::PLAYLIST_BACKUP_FILE)
+            .ok()
+            .and_then(|backup| salvage_playlist(&backup));
+        match backup {
+            Some((playlist, dropped)) => {
+                warn!(
+                    "Playlist file is unreadable, restored {} items from the backup",
+                    playlist.items.len()
+                );
+                report.playlist = PlaylistRecovery::RestoredFromBackup;
+                report.items_loaded = playlist.items.len();
+                report.dropped_items = dropped;
+                Some(playlist)
+            }
+            None => {
+                error!(
+                    "Playlist file and its backup are unreadable, starting with an empty playlist"
+                );
+                report.playlist = PlaylistRecovery::Unreadable;
                 None
             }
         }
     }
 
-    pub fn save_playlist(&self, playlist: &Playlist) -> bool {
+    // Save the playlist, or with a save interval queue it for flush_pending. Queued changes
+    // always report success, a failed write is logged when it happens.
+    pub fn save_playlist(&mut self, playlist: &Playlist) -> bool {
+        if self.save_interval.is_zero() {
+            return self.write_playlist(playlist);
+        }
+
+        debug!(
+            "Queued playlist with {} items for saving",
+            playlist.items.len()
+        );
+        self.queue(|pending| pending.playlist = Some(playlist.clone()));
+        true
+    }
+
+    fn write_playlist(&self, playlist: &Playlist) -> bool {
         debug!("Saving playlist with {} items", playlist.items.len());
 
         // Serialize the playlist to JSON
@@ -81,9 +228,253 @@ impl AppStorage {
         }
     }
 
+    // Trash of deleted playlist items
+    pub fn load_trash(&self) -> Trash {
+        if !self.storage_manager.file_exists(paths::TRASH_FILE) {
+            return Trash::default();
+        }
+
+        match self.storage_manager.read_file(paths::TRASH_FILE) {
+            Ok(contents) => match serde_json::from_str::<Trash>(&contents) {
+                Ok(trash) => trash,
+                Err(e) => {
+                    error!("Error parsing trash file: {}", e);
+                    Trash::default()
+                }
+            },
+            Err(e) => {
+                error!("Error reading trash file: {}", e);
+                Trash::default()
+            }
+        }
+    }
+
+    pub fn save_trash(&self, trash: &Trash) -> bool {
+        debug!("Saving trash with {} items", trash.items.len());
+
+        match serde_json::to_string_pretty(trash) {
+            Ok(json) => match self.storage_manager.write_file(paths::TRASH_FILE, &json) {
+                Ok(_) => true,
+                Err(e) => {
+                    error!("Error writing trash file: {}", e);
+                    false
+                }
+            },
+            Err(e) => {
+                error!("Error serializing trash: {}", e);
+                false
+            }
+        }
+    }
+
+    // Variables set through /api/vars
+    pub fn load_variables(&self) -> BTreeMap<String, String> {
+        if !self.storage_manager.file_exists(paths::VARIABLES_FILE) {
+            return BTreeMap::new();
+        }
+
+        match self.storage_manager.read_file(paths::VARIABLES_FILE) {
+            Ok(contents) => match serde_json::from_str(&contents) {
+                Ok(variables) => variables,
+                Err(e) => {
+                    error!("Error parsing variables file: {}", e);
+                    BTreeMap::new()
+                }
+            },
+            Err(e) => {
+                error!("Error reading variables file: {}", e);
+                BTreeMap::new()
+            }
+        }
+    }
+
+    pub fn save_variables(&self, variables: &BTreeMap<String, String>) -> bool {
+        debug!("Saving {} variables", variables.len());
+
+        match serde_json::to_string_pretty(variables) {
+            Ok(json) => match self
+                .storage_manager
+                .write_file(paths::VARIABLES_FILE, &json)
+            {
+                Ok(_) => true,
+                Err(e) => {
+                    error!("Error writing variables file: {}", e);
+                    false
+                }
+            },
+            Err(e) => {
+                error!("Error serializing variables: {}", e);
+                false
+            }
+        }
+    }
+
+    // Seasonal collections managed through /api/collections
+    pub fn load_collections(&self) -> Vec<Collection> {
+        if !self.storage_manager.file_exists(paths::COLLECTIONS_FILE) {
+            return Vec::new();
+        }
+
+        match self.storage_manager.read_file(paths::COLLECTIONS_FILE) {
+            Ok(contents) => match serde_json::from_str(&contents) {
+                Ok(collections) => collections,
+                Err(e) => {
+                    error!("Error parsing collections file: {}", e);
+                    Vec::new()
+                }
+            },
+            Err(e) => {
+                error!("Error reading collections file: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    pub fn save_collections(&self, collections: &[Collection]) -> bool {
+        debug!("Saving {} collections", collections.len());
+
+        match serde_json::to_string_pretty(collections) {
+            Ok(json) => match self
+                .storage_manager
+                .write_file(paths::COLLECTIONS_FILE, &json)
+            {
+                Ok(_) => true,
+                Err(e) => {
+                    error!("Error writing collections file: {}", e);
+                    false
+                }
+            },
+            Err(e) => {
+                error!("Error serializing collections: {}", e);
+                false
+            }
+        }
+    }
+
+    // Color palettes saved through /api/palettes
+    pub fn load_palettes(&self) -> BTreeMap<String, Vec<[u8; 3]>> {
+        if !self.storage_manager.file_exists(paths::PALETTES_FILE) {
+            return BTreeMap::new();
+        }
+
+        match self.storage_manager.read_file(paths::PALETTES_FILE) {
+            Ok(contents) => match serde_json::from_str(&contents) {
+                Ok(palettes) => palettes,
+                Err(e) => {
+                    error!("Error parsing palettes file: {}", e);
+                    BTreeMap::new()
+                }
+            },
+            Err(e) => {
+                error!("Error reading palettes file: {}", e);
+                BTreeMap::new()
+            }
+        }
+    }
+
+    pub fn save_palettes(&self, palettes: &BTreeMap<String, Vec<[u8; 3]>>) -> bool {
+        debug!("Saving {} palettes", palettes.len());
+
+        match serde_json::to_string_pretty(palettes) {
+            Ok(json) => match self.storage_manager.write_file(paths::PALETTES_FILE, &json) {
+                Ok(_) => true,
+                Err(e) => {
+                    error!("Error writing palettes file: {}", e);
+                    false
+                }
+            },
+            Err(e) => {
+                error!("Error serializing palettes: {}", e);
+                false
+            }
+        }
+    }
+
+    // Defaults for fields left out of new items, see utils::content_defaults
+    pub fn load_content_defaults(&self) -> ContentDefaults {
+        if !self.storage_manager.file_exists(paths::DEFAULTS_FILE) {
+            return ContentDefaults::default();
+        }
+
+        match self.storage_manager.read_file(paths::DEFAULTS_FILE) {
+            Ok(contents) => match serde_json::from_str(&contents) {
+                Ok(defaults) => defaults,
+                Err(e) => {
+                    error!("Error parsing defaults file: {}", e);
+                    ContentDefaults::default()
+                }
+            },
+            Err(e) => {
+                error!("Error reading defaults file: {}", e);
+                ContentDefaults::default()
+            }
+        }
+    }
+
+    pub fn save_content_defaults(&self, defaults: &ContentDefaults) -> bool {
+        match serde_json::to_string_pretty(defaults) {
+            Ok(json) => match self.storage_manager.write_file(paths::DEFAULTS_FILE, &json) {
+                Ok(_) => {
+                    info!("Content defaults saved");
+                    true
+                }
+                Err(e) => {
+                    error!("Error writing defaults file: {}", e);
+                    false
+                }
+            },
+            Err(e) => {
+                error!("Error serializing content defaults: {}", e);
+                false
+            }
+        }
+    }
+
+    pub fn load_operating_hours(&self) -> OperatingHours {
+        if !self.storage_manager.file_exists(paths::HOURS_FILE) {
+            return OperatingHours::default();
+        }
+
+        match self.storage_manager.read_file(paths::HOURS_FILE) {
+            Ok(contents) => match serde_json::from_str(&contents) {
+                Ok(hours) => hours,
+                Err(e) => {
+                    error!("Error parsing operating hours file: {}", e);
+                    OperatingHours::default()
+                }
+            },
+            Err(e) => {
+                error!("Error reading operating hours file: {}", e);
+                OperatingHours::default()
+            }
+        }
+    }
+
+    pub fn save_operating_hours(&self, hours: &OperatingHours) -> bool {
+        match serde_json::to_string_pretty(hours) {
+            Ok(json) => match self.storage_manager.write_file(paths::HOURS_FILE, &json) {
+                Ok(_) => {
+                    info!("Operating hours saved");
+                    true
+                }
+                Err(e) => {
+                    error!("Error writing operating hours file: {}", e);
+                    false
+                }
+            },
+            Err(e) => {
+                error!("Error serializing operating hours: {}", e);
+                false
+            }
+        }
+    }
+
     // Display settings methods
     pub fn load_brightness(&self) -> Option<u8> {
         debug!("Loading brightness setting");
+        if let Some(brightness) = self.pending.brightness {
+            return Some(brightness);
+        }
 
         if !self.storage_manager.file_exists(paths::BRIGHTNESS_FILE) {
             debug!("No brightness file found");
@@ -115,7 +506,16 @@ impl AppStorage {
         }
     }
 
-    pub fn save_brightness(&self, brightness: u8) {
+    pub fn save_brightness(&mut self, brightness: u8) {
+        if self.save_interval.is_zero() {
+            self.write_brightness(brightness);
+        } else {
+            debug!("Queued brightness {}% for saving", brightness);
+            self.queue(|pending| pending.brightness = Some(brightness));
+        }
+    }
+
+    fn write_brightness(&self, brightness: u8) -> bool {
         debug!("Saving brightness setting: {}%", brightness);
 
         #[derive(serde::Serialize)]
@@ -133,14 +533,295 @@ impl AppStorage {
                 {
                     Ok(_) => {
                         info!("Brightness saved: {}%", brightness);
+                        true
                     }
                     Err(e) => {
                         error!("Error writing brightness file: {}", e);
+                        false
                     }
                 }
             }
             Err(e) => {
                 error!("Error serializing brightness: {}", e);
+                false
+            }
+        }
+    }
+
+    // Idle screen settings methods
+    pub fn load_idle_settings(&self) -> Option<IdleSettings> {
+        debug!("Loading idle settings");
+
+        if !self.storage_manager.file_exists(paths::IDLE_FILE) {
+            debug!("No idle settings file found");
+            return None;
+        }
+
+        match self.storage_manager.read_file(paths::IDLE_FILE) {
+            Ok(contents) => match serde_json::from_str::<IdleSettings>(&contents) {
+                Ok(settings) => {
+                    info!("Loaded idle settings: {:?}", settings.mode);
+                    Some(settings)
+                }
+                Err(e) => {
+                    error!("Error parsing idle settings file: {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Error reading idle settings file: {}", e);
+                None
+            }
+        }
+    }
+
+    pub fn save_idle_settings(&self, settings: &IdleSettings) -> bool {
+        debug!("Saving idle settings");
+
+        match serde_json::to_string_pretty(settings) {
+            Ok(json) => match self.storage_manager.write_file(paths::IDLE_FILE, &json) {
+                Ok(_) => {
+                    info!("Idle settings saved: {:?}", settings.mode);
+                    true
+                }
+                Err(e) => {
+                    error!("Error writing idle settings file: {}", e);
+                    false
+                }
+            },
+            Err(e) => {
+                error!("Error serializing idle settings: {}", e);
+                false
+            }
+        }
+    }
+
+    // Welcome message settings methods
+    pub fn load_welcome_settings(&self) -> Option<WelcomeSettings> {
+        debug!("Loading welcome settings");
+
+        if !self.storage_manager.file_exists(paths::WELCOME_FILE) {
+            debug!("No welcome settings file found");
+            return None;
+        }
+
+        match self.storage_manager.read_file(paths::WELCOME_FILE) {
+            Ok(contents) => match serde_json::from_str::<WelcomeSettings>(&contents) {
+                Ok(settings) => {
+                    info!("Loaded welcome settings");
+                    Some(settings)
+                }
+                Err(e) => {
+                    error!("Error parsing welcome settings file: {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Error reading welcome settings file: {}", e);
+                None
+            }
+        }
+    }
+
+    pub fn save_welcome_settings(&self, settings: &WelcomeSettings) -> bool {
+        debug!("Saving welcome settings");
+
+        match serde_json::to_string_pretty(settings) {
+            Ok(json) => match self.storage_manager.write_file(paths::WELCOME_FILE, &json) {
+                Ok(_) => {
+                    info!("Welcome settings saved");
+                    true
+                }
+                Err(e) => {
+                    error!("Error writing welcome settings file: {}", e);
+                    false
+                }
+            },
+            Err(e) => {
+                error!("Error serializing welcome settings: {}", e);
+                false
+            }
+        }
+    }
+
+    // Brightness mask methods, the mask is kept as the uploaded image
+    pub fn load_brightness_mask(&self) -> Option<Vec<u8>> {
+        if !self
+            .storage_manager
+            .file_exists(paths::BRIGHTNESS_MASK_FILE)
+        {
+            return None;
+        }
+
+        match self.storage_manager.read_bytes(paths::BRIGHTNESS_MASK_FILE) {
+            Ok(bytes) => Some(bytes),
+            Err(e) => {
+                error!("Error reading brightness mask file: {}", e);
+                None
+            }
+        }
+    }
+
+    pub fn save_brightness_mask(&self, data: &[u8]) -> bool {
+        match self
+            .storage_manager
+            .write_bytes(paths::BRIGHTNESS_MASK_FILE, data)
+        {
+            Ok(_) => {
+                info!("Brightness mask saved");
+                true
+            }
+            Err(e) => {
+                error!("Error writing brightness mask file: {}", e);
+                false
+            }
+        }
+    }
+
+    pub fn delete_brightness_mask(&self) -> bool {
+        match self
+            .storage_manager
+            .delete_file(paths::BRIGHTNESS_MASK_FILE)
+        {
+            Ok(_) => true,
+            Err(e) => {
+                error!("Error deleting brightness mask file: {}", e);
+                false
+            }
+        }
+    }
+
+    // Dead or stuck pixels registered through /api/display/dead-pixels
+    pub fn load_dead_pixels(&self) -> Vec<DeadPixel> {
+        if !self.storage_manager.file_exists(paths::DEAD_PIXELS_FILE) {
+            return Vec::new();
+        }
+
+        match self.storage_manager.read_file(paths::DEAD_PIXELS_FILE) {
+            Ok(contents) => match serde_json::from_str(&contents) {
+                Ok(pixels) => pixels,
+                Err(e) => {
+                    error!("Error parsing dead pixels file: {}", e);
+                    Vec::new()
+                }
+            },
+            Err(e) => {
+                error!("Error reading dead pixels file: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    pub fn save_dead_pixels(&self, pixels: &[DeadPixel]) -> bool {
+        debug!("Saving {} dead pixels", pixels.len());
+
+        match serde_json::to_string_pretty(pixels) {
+            Ok(json) => match self
+                .storage_manager
+                .write_file(paths::DEAD_PIXELS_FILE, &json)
+            {
+                Ok(_) => true,
+                Err(e) => {
+                    error!("Error writing dead pixels file: {}", e);
+                    false
+                }
+            },
+            Err(e) => {
+                error!("Error serializing dead pixels: {}", e);
+                false
+            }
+        }
+    }
+
+    // Stored display configuration methods
+    pub fn load_config(&self) -> Option<StoredConfig> {
+        debug!("Loading stored display configuration");
+
+        if !self.storage_manager.file_exists(paths::CONFIG_FILE) {
+            debug!("No stored display configuration found");
+            return None;
+        }
+
+        match self.storage_manager.read_file(paths::CONFIG_FILE) {
+            Ok(contents) => match serde_json::from_str::<StoredConfig>(&contents) {
+                Ok(config) => {
+                    info!("Loaded stored display configuration: {:?}", config);
+                    Some(config)
+                }
+                Err(e) => {
+                    error!("Error parsing display configuration file: {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                error!("Error reading display configuration file: {}", e);
+                None
+            }
+        }
+    }
+
+    pub fn save_config(&self, config: &StoredConfig) -> bool {
+        debug!("Saving stored display configuration");
+
+        match serde_json::to_string_pretty(config) {
+            Ok(json) => match self.storage_manager.write_file(paths::CONFIG_FILE, &json) {
+                Ok(_) => {
+                    info!("Display configuration saved: {:?}", config);
+                    true
+                }
+                Err(e) => {
+                    error!("Error saving display configuration: {}", e);
+                    false
+                }
+            },
+            Err(e) => {
+                error!("Error serializing display configuration: {}", e);
+                false
+            }
+        }
+    }
+
+    // Hardware profile methods
+    pub fn load_profiles(&self) -> BTreeMap<String, HardwareProfile> {
+        debug!("Loading hardware profiles");
+
+        if !self.storage_manager.file_exists(paths::PROFILES_FILE) {
+            debug!("No hardware profiles file found");
+            return BTreeMap::new();
+        }
+
+        match self.storage_manager.read_file(paths::PROFILES_FILE) {
+            Ok(contents) => match serde_json::from_str(&contents) {
+                Ok(profiles) => profiles,
+                Err(e) => {
+                    error!("Error parsing hardware profiles file: {}", e);
+                    BTreeMap::new()
+                }
+            },
+            Err(e) => {
+                error!("Error reading hardware profiles file: {}", e);
+                BTreeMap::new()
+            }
+        }
+    }
+
+    pub fn save_profiles(&self, profiles: &BTreeMap<String, HardwareProfile>) -> bool {
+        debug!("Saving {} hardware profiles", profiles.len());
+
+        match serde_json::to_string_pretty(profiles) {
+            Ok(json) => match self.storage_manager.write_file(paths::PROFILES_FILE, &json) {
+                Ok(_) => {
+                    info!("Hardware profiles saved");
+                    true
+                }
+                Err(e) => {
+                    error!("Error saving hardware profiles: {}", e);
+                    false
+                }
+            },
+            Err(e) => {
+                error!("Error serializing hardware profiles: {}", e);
+                false
             }
         }
     }
@@ -199,19 +880,215 @@ impl AppStorage {
         self.storage_manager.image_file_path(image_id)
     }
 
-    pub fn cleanup_unused_images(&self, playlist: &Playlist) -> usize {
+    // Delete uploads a previous run left unfinished, called at startup before any upload starts
+    pub fn clear_uploads(&self) {
+        match self.storage_manager.clear_uploads_dir() {
+            Ok(0) => {}
+            Ok(removed) => info!("Removed {} unfinished upload(s)", removed),
+            Err(err) => warn!("Failed to remove unfinished uploads: {}", err),
+        }
+    }
+
+    // Temporary file that an upload is streamed into before it is converted
+    pub fn upload_path(&self, name: &str) -> Option<std::path::PathBuf> {
+        match self.storage_manager.upload_file_path(name) {
+            Ok(path) => Some(path),
+            Err(err) => {
+                error!("Failed to prepare upload file {}: {}", name, err);
+                None
+            }
+        }
+    }
+
+    // Space of the data directory's file system and what the stored media take of it, None
+    // if the file system cannot be queried
+    pub fn disk_usage(&self, min_free_bytes: u64) -> Option<DiskUsage> {
+        let (total_bytes, available_bytes) = match self.storage_manager.file_system_space() {
+            Ok(space) => space,
+            Err(err) => {
+                error!("Failed to query free space of the data directory: {}", err);
+                return None;
+            }
+        };
+        Some(DiskUsage {
+            path: self
+                .storage_manager
+                .base_dir()
+                .to_string_lossy()
+                .into_owned(),
+            total_bytes,
+            available_bytes,
+            min_free_bytes,
+            uploads_allowed: available_bytes >= min_free_bytes,
+            images_bytes: self.storage_manager.dir_size(paths::IMAGES_DIR),
+            thumbnails_bytes: self.storage_manager.dir_size(paths::THUMBNAILS_DIR),
+            videos_bytes: self.storage_manager.dir_size(paths::VIDEOS_DIR),
+        })
+    }
+
+    // Whether at least `min_free_bytes` are free for uploads. A file system that cannot be
+    // queried does not block them, the write itself will fail if it is really full.
+    pub fn has_free_space(&self, min_free_bytes: u64) -> bool {
+        if min_free_bytes == 0 {
+            return true;
+        }
+        match self.storage_manager.file_system_space() {
+            Ok((_, available_bytes)) => available_bytes >= min_free_bytes,
+            Err(err) => {
+                warn!("Failed to query free space of the data directory: {}", err);
+                true
+            }
+        }
+    }
+
+    // WASM plugin helpers
+    #[cfg_attr(not(feature = "wasm-plugins"), allow(dead_code))]
+    pub fn save_plugin(&self, name: &str, data: &[u8]) -> bool {
+        match self.storage_manager.save_plugin_file(name, data) {
+            Ok(path) => {
+                info!("Saved plugin {} to {:?}", name, path);
+                true
+            }
+            Err(err) => {
+                error!("Failed to save plugin {}: {}", name, err);
+                false
+            }
+        }
+    }
+
+    #[cfg_attr(not(feature = "wasm-plugins"), allow(dead_code))]
+    pub fn load_plugin(&self, name: &str) -> Option<Vec<u8>> {
+        match self.storage_manager.read_plugin_file(name) {
+            Ok(bytes) => Some(bytes),
+            Err(err) => {
+                error!("Failed to read plugin {}: {}", name, err);
+                None
+            }
+        }
+    }
+
+    pub fn delete_plugin(&self, name: &str) -> bool {
+        match self.storage_manager.delete_plugin_file(name) {
+            Ok(()) => {
+                info!("Deleted plugin {}", name);
+                true
+            }
+            Err(err) => {
+                error!("Failed to delete plugin {}: {}", name, err);
+                false
+            }
+        }
+    }
+
+    pub fn list_plugins(&self) -> Vec<(String, u64)> {
+        match self.storage_manager.list_plugin_files() {
+            Ok(plugins) => plugins,
+            Err(err) => {
+                error!("Failed to list plugins: {}", err);
+                Vec::new()
+            }
+        }
+    }
+
+    pub fn plugin_path(&self, name: &str) -> std::path::PathBuf {
+        self.storage_manager.plugin_file_path(name)
+    }
+
+    // Video helpers
+    // File the decoded frames of a video are written to, creating the videos directory
+    #[cfg_attr(not(feature = "video"), allow(dead_code))]
+    pub fn video_frames_path(&self, video_id: &str) -> Option<std::path::PathBuf> {
+        match self.storage_manager.ensure_videos_dir() {
+            Ok(()) => Some(self.storage_manager.video_file_path(video_id, "rgb")),
+            Err(err) => {
+                error!("Failed to prepare videos directory: {}", err);
+                None
+            }
+        }
+    }
+
+    #[cfg_attr(not(feature = "video"), allow(dead_code))]
+    pub fn save_video_info(&self, info: &VideoInfo) -> bool {
+        let path = self.storage_manager.video_file_path(&info.video_id, "json");
+        let result = serde_json::to_string_pretty(info)
+            .map_err(|e| e.to_string())
+            .and_then(|json| fs::write(&path, json).map_err(|e| e.to_string()));
+        match result {
+            Ok(()) => {
+                info!("Saved video {} to {:?}", info.video_id, path);
+                true
+            }
+            Err(err) => {
+                error!("Failed to save video {}: {}", info.video_id, err);
+                false
+            }
+        }
+    }
+
+    pub fn load_video_info(&self, video_id: &str) -> Option<VideoInfo> {
+        let path = self.storage_manager.video_file_path(video_id, "json");
+        let contents = fs::read_to_string(&path).ok()?;
+        match serde_json::from_str(&contents) {
+            Ok(info) => Some(info),
+            Err(e) => {
+                error!("Error parsing video file {:?}: {}", path, e);
+                None
+            }
+        }
+    }
+
+    pub fn list_videos(&self) -> Vec<VideoInfo> {
+        match self.storage_manager.list_video_ids() {
+            Ok(ids) => ids
+                .iter()
+                .filter_map(|id| self.load_video_info(id))
+                .collect(),
+            Err(err) => {
+                error!("Failed to list videos: {}", err);
+                Vec::new()
+            }
+        }
+    }
+
+    pub fn delete_video(&self, video_id: &str) -> bool {
+        let mut deleted = true;
+        for extension in ["json", "rgb"] {
+            let path = self.storage_manager.video_file_path(video_id, extension);
+            if let Err(err) = fs::remove_file(&path) {
+                if err.kind() != ErrorKind::NotFound {
+                    error!("Failed to delete {:?}: {}", path, err);
+                    deleted = false;
+                }
+            }
+        }
+        if deleted {
+            info!("Deleted video {}", video_id);
+        }
+        deleted
+    }
+
+    // Remove uploaded images no playlist item, trashed item or collection refers to, with
+    // their thumbnails. Images modified within `grace` are kept, they may have been uploaded
+    // for an item that is still being edited.
+    pub fn cleanup_unused_images(&self, playlist: &Playlist, grace: Duration) -> CleanupReport {
+        let mut report = CleanupReport::default();
+
+        // Images of trashed items are kept so the items can be restored, and those of
+        // collections for when their window opens
+        let trash = self.load_trash();
+        let collections = self.load_collections();
         let referenced_ids: HashSet<String> = playlist
             .items
             .iter()
-            .filter_map(|item| match &item.content.data {
-                ContentDetails::Image(image_content) => Some(image_content.image_id.clone()),
-                _ => None,
-            })
+            .chain(trash.items.iter().map(|trashed| &trashed.item))
+            .chain(collections.iter().flat_map(|collection| &collection.items))
+            .flat_map(PlayListItem::image_ids)
+            .map(String::from)
             .collect();
 
         if let Err(err) = self.storage_manager.ensure_images_dir() {
             error!("Unable to ensure images directory before cleanup: {}", err);
-            return 0;
+            return report;
         }
 
         let images_dir = self.storage_manager.get_file_path(paths::IMAGES_DIR);
@@ -223,13 +1100,10 @@ impl AppStorage {
                     "Skipping image cleanup; could not read {:?}: {}",
                     images_dir, err
                 );
-                return 0;
+                return report;
             }
         };
 
-        let mut removed_images = 0usize;
-        let mut removed_thumbnails = 0usize;
-
         for entry in dir_entries {
             let entry = match entry {
                 Ok(entry) => entry,
@@ -264,11 +1138,24 @@ impl AppStorage {
                 continue;
             }
 
+            let metadata = entry.metadata().ok();
+            let age = metadata
+                .as_ref()
+                .and_then(|metadata| metadata.modified().ok())
+                .and_then(|modified| modified.elapsed().ok())
+                .unwrap_or_default();
+            if age < grace {
+                debug!("Keeping recently uploaded unused image {}", image_id);
+                report.kept_recent += 1;
+                continue;
+            }
+
             let mut image_deleted = false;
             match fs::remove_file(&path) {
                 Ok(_) => {
                     debug!("Removed unused image {}", image_id);
-                    removed_images += 1;
+                    report.removed_images += 1;
+                    report.freed_bytes += metadata.map_or(0, |metadata| metadata.len());
                     image_deleted = true;
                 }
                 Err(err) => {
@@ -286,11 +1173,14 @@ impl AppStorage {
 
             if image_deleted {
                 let thumbnail_path = self.storage_manager.thumbnail_file_path(image_id);
+                let thumbnail_bytes =
+                    fs::metadata(&thumbnail_path).map_or(0, |metadata| metadata.len());
                 if thumbnail_path.exists() {
                     match fs::remove_file(&thumbnail_path) {
                         Ok(_) => {
                             debug!("Removed thumbnail for image {}", image_id);
-                            removed_thumbnails += 1;
+                            report.removed_thumbnails += 1;
+                            report.freed_bytes += thumbnail_bytes;
                         }
                         Err(err) => {
                             error!("Failed to remove thumbnail for image {}: {}", image_id, err);
@@ -300,18 +1190,72 @@ impl AppStorage {
             }
         }
 
-        let total_removed = removed_images + removed_thumbnails;
+        let total_removed = report.removed_images + report.removed_thumbnails;
         if total_removed > 0 {
             info!(
                 "Image cleanup removed {} file(s) ({} images, {} thumbnails)",
-                total_removed, removed_images, removed_thumbnails
+                total_removed, report.removed_images, report.removed_thumbnails
             );
         } else {
             debug!("Image cleanup found no unused images to remove");
         }
 
-        total_removed
+        report
+    }
+}
+
+// Disk usage of the data directory, served by GET /api/system/storage
+#[derive(Serialize, Debug)]
+pub struct DiskUsage {
+    pub path: String,
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+    // Free space uploads leave, they are rejected while less is available
+    pub min_free_bytes: u64,
+    pub uploads_allowed: bool,
+    pub images_bytes: u64,
+    pub thumbnails_bytes: u64,
+    pub videos_bytes: u64,
+}
+
+// Outcome of an image cleanup
+#[derive(Serialize, Default, Debug)]
+pub struct CleanupReport {
+    pub removed_images: usize,
+    pub removed_thumbnails: usize,
+    // Unused images kept because they were uploaded within the grace period
+    pub kept_recent: usize,
+    pub freed_bytes: u64,
+}
+
+// Read the items of a playlist file one by one, dropping those that cannot be read, e.g. after
+// validation became stricter. None if the file is not a playlist at all.
+fn salvage_playlist(contents: &str) -> Option<(Playlist, Vec<DroppedItem>)> {
+    let value: serde_json::Value = serde_json::from_str(contents).ok()?;
+    let items = value.get("items")?.as_array()?;
+
+    let mut playlist = Playlist {
+        repeat: value
+            .get("repeat")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(true),
+        ..Playlist::default()
+    };
+    let mut dropped = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        match serde_json::from_value::<PlayListItem>(item.clone()) {
+            Ok(item) => playlist.items.push(item),
+            Err(e) => dropped.push(DroppedItem {
+                index,
+                id: item
+                    .get("id")
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::to_string),
+                error: e.to_string(),
+            }),
+        }
     }
+    Some((playlist, dropped))
 }
 
 // Create a global storage instance that can be shared across threads
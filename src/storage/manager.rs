@@ -13,13 +13,34 @@ pub const DEFAULT_DIR: &str = "/var/lib/led-matrix-controller";
 pub mod paths {
     // Main data files
     pub const PLAYLIST_FILE: &str = "playlist.json";
+    // Playlist as read at the last startup it could be read completely
+    pub const PLAYLIST_BACKUP_FILE: &str = "playlist.backup.json";
+    // Playlist file that could not be read completely, kept for manual recovery
+    pub const PLAYLIST_DAMAGED_FILE: &str = "playlist.damaged.json";
+    pub const TRASH_FILE: &str = "trash.json";
+    pub const VARIABLES_FILE: &str = "variables.json";
+    pub const PALETTES_FILE: &str = "palettes.json";
+    pub const COLLECTIONS_FILE: &str = "collections.json";
     pub const BRIGHTNESS_FILE: &str = "brightness.json";
+    pub const IDLE_FILE: &str = "idle.json";
+    pub const HOURS_FILE: &str = "operating_hours.json";
+    pub const WELCOME_FILE: &str = "welcome.json";
+    pub const BRIGHTNESS_MASK_FILE: &str = "brightness_mask.png";
+    pub const DEAD_PIXELS_FILE: &str = "dead_pixels.json";
+    pub const DEFAULTS_FILE: &str = "defaults.json";
+    pub const CONFIG_FILE: &str = "config.json";
+    pub const PROFILES_FILE: &str = "profiles.json";
     pub const IMAGES_DIR: &str = "images";
     pub const THUMBNAILS_DIR: &str = "thumbnails";
+    pub const PLUGINS_DIR: &str = "plugins";
+    pub const VIDEOS_DIR: &str = "videos";
+    pub const UPLOADS_DIR: &str = "uploads";
 }
 
 pub struct StorageManager {
     base_dir: PathBuf,
+    // Directory for regenerable files like thumbnails, None keeps them in base_dir
+    cache_dir: Option<PathBuf>,
 }
 
 impl StorageManager {
@@ -79,7 +100,10 @@ impl StorageManager {
         };
 
         // Create an instance
-        let manager = Self { base_dir };
+        let manager = Self {
+            base_dir,
+            cache_dir: None,
+        };
 
         // If we have root privileges, properly set up the directory with correct ownership
         if get_current_uid() == 0 {
@@ -100,6 +124,10 @@ impl StorageManager {
         manager
     }
 
+    pub fn base_dir(&self) -> &Path {
+        &self.base_dir
+    }
+
     // Get the full path for a specific file
     pub fn get_file_path(&self, filename: &str) -> PathBuf {
         self.base_dir.join(filename)
@@ -133,8 +161,68 @@ impl StorageManager {
         self.base_dir.join(paths::IMAGES_DIR)
     }
 
+    pub fn set_cache_dir(&mut self, dir: PathBuf) {
+        self.cache_dir = Some(dir);
+    }
+
     fn thumbnails_dir(&self) -> PathBuf {
-        self.base_dir.join(paths::THUMBNAILS_DIR)
+        self.cache_dir
+            .as_ref()
+            .unwrap_or(&self.base_dir)
+            .join(paths::THUMBNAILS_DIR)
+    }
+
+    fn plugins_dir(&self) -> PathBuf {
+        self.base_dir.join(paths::PLUGINS_DIR)
+    }
+
+    fn videos_dir(&self) -> PathBuf {
+        self.base_dir.join(paths::VIDEOS_DIR)
+    }
+
+    fn uploads_dir(&self) -> PathBuf {
+        self.base_dir.join(paths::UPLOADS_DIR)
+    }
+
+    pub fn ensure_uploads_dir(&self) -> IoResult<()> {
+        let uploads_dir = self.uploads_dir();
+        if !uploads_dir.exists() {
+            debug!(
+                "Uploads directory doesn't exist, creating: {:?}",
+                uploads_dir
+            );
+            fs::create_dir_all(&uploads_dir)?;
+            #[cfg(unix)]
+            {
+                let permissions = Permissions::from_mode(0o700);
+                fs::set_permissions(&uploads_dir, permissions)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Remove files of uploads that never finished, e.g. because the controller stopped in the
+    // middle of one. Returns how many were removed.
+    pub fn clear_uploads_dir(&self) -> IoResult<usize> {
+        let uploads_dir = self.uploads_dir();
+        if !uploads_dir.exists() {
+            return Ok(0);
+        }
+        let mut removed = 0;
+        for entry in fs::read_dir(&uploads_dir)? {
+            let path = entry?.path();
+            if path.is_file() {
+                fs::remove_file(&path)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    // Path for an upload in progress, on the SD card rather than a RAM backed /tmp
+    pub fn upload_file_path(&self, name: &str) -> IoResult<PathBuf> {
+        self.ensure_uploads_dir()?;
+        Ok(self.uploads_dir().join(name))
     }
 
     pub fn ensure_images_dir(&self) -> IoResult<()> {
@@ -168,6 +256,37 @@ impl StorageManager {
         Ok(())
     }
 
+    pub fn ensure_plugins_dir(&self) -> IoResult<()> {
+        let plugins_dir = self.plugins_dir();
+        if !plugins_dir.exists() {
+            debug!(
+                "Plugins directory doesn't exist, creating: {:?}",
+                plugins_dir
+            );
+            fs::create_dir_all(&plugins_dir)?;
+            #[cfg(unix)]
+            {
+                let permissions = Permissions::from_mode(0o755);
+                fs::set_permissions(&plugins_dir, permissions)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn ensure_videos_dir(&self) -> IoResult<()> {
+        let videos_dir = self.videos_dir();
+        if !videos_dir.exists() {
+            debug!("Videos directory doesn't exist, creating: {:?}", videos_dir);
+            fs::create_dir_all(&videos_dir)?;
+            #[cfg(unix)]
+            {
+                let permissions = Permissions::from_mode(0o755);
+                fs::set_permissions(&videos_dir, permissions)?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn save_image_file(&self, image_id: &str, data: &[u8]) -> IoResult<PathBuf> {
         self.ensure_images_dir()?;
         let path = self.images_dir().join(format!("{}.png", image_id));
@@ -206,6 +325,86 @@ impl StorageManager {
         fs::read(path)
     }
 
+    #[cfg_attr(not(feature = "wasm-plugins"), allow(dead_code))]
+    pub fn save_plugin_file(&self, name: &str, data: &[u8]) -> IoResult<PathBuf> {
+        self.ensure_plugins_dir()?;
+        let path = self.plugin_file_path(name);
+        debug!("Writing plugin file: {:?}", path);
+        fs::write(&path, data)?;
+        #[cfg(unix)]
+        {
+            let permissions = Permissions::from_mode(0o644);
+            fs::set_permissions(&path, permissions)?;
+        }
+        Ok(path)
+    }
+
+    #[cfg_attr(not(feature = "wasm-plugins"), allow(dead_code))]
+    pub fn read_plugin_file(&self, name: &str) -> IoResult<Vec<u8>> {
+        let path = self.plugin_file_path(name);
+        debug!("Reading plugin file: {:?}", path);
+        fs::read(path)
+    }
+
+    pub fn delete_plugin_file(&self, name: &str) -> IoResult<()> {
+        let path = self.plugin_file_path(name);
+        debug!("Deleting plugin file: {:?}", path);
+        fs::remove_file(path)
+    }
+
+    // List stored WASM plugins as (name, size in bytes), sorted by name
+    pub fn list_plugin_files(&self) -> IoResult<Vec<(String, u64)>> {
+        let plugins_dir = self.plugins_dir();
+        if !plugins_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut plugins = Vec::new();
+        for entry in fs::read_dir(plugins_dir)?.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("wasm") {
+                continue;
+            }
+            if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                let size = entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+                plugins.push((name.to_string(), size));
+            }
+        }
+        plugins.sort();
+        Ok(plugins)
+    }
+
+    pub fn plugin_file_path(&self, name: &str) -> PathBuf {
+        self.plugins_dir().join(format!("{}.wasm", name))
+    }
+
+    // Decoded frames (".rgb") or description (".json") of an uploaded video
+    pub fn video_file_path(&self, video_id: &str, extension: &str) -> PathBuf {
+        self.videos_dir()
+            .join(format!("{}.{}", video_id, extension))
+    }
+
+    // IDs of the stored videos, sorted
+    pub fn list_video_ids(&self) -> IoResult<Vec<String>> {
+        let videos_dir = self.videos_dir();
+        if !videos_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut ids = Vec::new();
+        for entry in fs::read_dir(videos_dir)?.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            if let Some(id) = path.file_stem().and_then(|stem| stem.to_str()) {
+                ids.push(id.to_string());
+            }
+        }
+        ids.sort();
+        Ok(ids)
+    }
+
     pub fn image_file_path(&self, image_id: &str) -> PathBuf {
         self.images_dir().join(format!("{}.png", image_id))
     }
@@ -249,6 +448,63 @@ impl StorageManager {
         Ok(())
     }
 
+    // Read a binary file from storage
+    pub fn read_bytes(&self, filename: &str) -> IoResult<Vec<u8>> {
+        let file_path = self.get_file_path(filename);
+        debug!("Reading file: {:?}", file_path);
+        fs::read(file_path)
+    }
+
+    // Write a binary file to storage with appropriate permissions
+    pub fn write_bytes(&self, filename: &str, data: &[u8]) -> IoResult<()> {
+        self.ensure_directory_exists()?;
+
+        let file_path = self.get_file_path(filename);
+        debug!("Writing to file: {:?}", file_path);
+        fs::write(&file_path, data)?;
+        #[cfg(unix)]
+        {
+            let permissions = Permissions::from_mode(0o644);
+            fs::set_permissions(&file_path, permissions)?;
+        }
+        Ok(())
+    }
+
+    // Total and available bytes of the file system holding the data directory. Available
+    // counts only what an unprivileged user may write, the service runs as one.
+    pub fn file_system_space(&self) -> IoResult<(u64, u64)> {
+        use std::os::unix::ffi::OsStrExt;
+        let path = std::ffi::CString::new(self.base_dir.as_os_str().as_bytes())?;
+        let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+        if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        let block_size = stat.f_frsize as u64;
+        Ok((
+            stat.f_blocks as u64 * block_size,
+            stat.f_bavail as u64 * block_size,
+        ))
+    }
+
+    // Bytes of the files directly in a directory of the data directory, 0 if it does not exist
+    pub fn dir_size(&self, dirname: &str) -> u64 {
+        let dir = if dirname == paths::THUMBNAILS_DIR {
+            self.thumbnails_dir()
+        } else {
+            self.base_dir.join(dirname)
+        };
+        fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|entry| entry.metadata().ok())
+                    .filter(|metadata| metadata.is_file())
+                    .map(|metadata| metadata.len())
+                    .sum()
+            })
+            .unwrap_or(0)
+    }
+
     // Check if a file exists
     pub fn file_exists(&self, filename: &str) -> bool {
         let exists = self.get_file_path(filename).exists();
@@ -256,9 +512,6 @@ impl StorageManager {
         exists
     }
 
-    // Note: kept delete_file since it could be useful later,
-    // but marked with #[allow(dead_code)] to suppress warnings
-    #[allow(dead_code)]
     pub fn delete_file(&self, filename: &str) -> IoResult<()> {
         let file_path = self.get_file_path(filename);
         if file_path.exists() {
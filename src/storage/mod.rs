@@ -1,2 +1,3 @@
 pub mod app_storage;
 pub mod manager;
+pub mod recovery;
@@ -0,0 +1,76 @@
+// What startup recovered from storage, served by GET /api/system/startup so users can see why
+// an item disappeared or shows nothing instead of discovering it days later
+
+use once_cell::sync::OnceCell;
+use serde::Serialize;
+
+static REPORT: OnceCell<StartupReport> = OnceCell::new();
+
+// How the playlist was obtained at startup
+#[derive(Clone, Copy, Serialize, Debug, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaylistRecovery {
+    // Read completely from the playlist file
+    Loaded,
+    // Read from the playlist file with the items that could not be read dropped
+    Salvaged,
+    // The playlist file could not be read, the backup from the previous startup was used
+    RestoredFromBackup,
+    // Neither the playlist file nor its backup could be read, the playlist starts empty
+    Unreadable,
+    // No playlist was saved yet
+    #[default]
+    Missing,
+}
+
+// Playlist item that was dropped because it could not be read
+#[derive(Clone, Serialize, Debug)]
+pub struct DroppedItem {
+    // Position in the saved playlist
+    pub index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub error: String,
+}
+
+// Uploaded image or video a playlist item refers to that is not in storage
+#[derive(Clone, Serialize, Debug)]
+pub struct MissingMedia {
+    pub item_id: String,
+    pub media_id: String,
+}
+
+#[derive(Clone, Serialize, Debug, Default)]
+pub struct StartupReport {
+    pub started_at: String,
+    pub playlist: PlaylistRecovery,
+    pub items_loaded: usize,
+    pub dropped_items: Vec<DroppedItem>,
+    // Copy of a damaged playlist file kept in the data directory, so dropped items can be
+    // recovered by hand
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub damaged_copy: Option<String>,
+    pub missing_images: Vec<MissingMedia>,
+    pub missing_videos: Vec<MissingMedia>,
+    // IDs of items adapted from another panel size
+    pub adapted_items: Vec<String>,
+}
+
+impl StartupReport {
+    pub fn new() -> Self {
+        Self {
+            started_at: chrono::Utc::now().to_rfc3339(),
+            ..Default::default()
+        }
+    }
+}
+
+// Publish the report once startup is done
+pub fn set_report(report: StartupReport) {
+    let _ = REPORT.set(report);
+}
+
+// Report of this run's startup, empty until startup is done
+pub fn report() -> StartupReport {
+    REPORT.get().cloned().unwrap_or_default()
+}
@@ -0,0 +1,181 @@
+// Module for the local commands an admin allows Command content to run (--allow-command).
+// Playlist items only refer to a command by name, so the API can never run anything that is
+// not on this list. Commands run on a background thread and their output is cached per name.
+
+use crate::utils::refresh_cache::RefreshCache;
+use once_cell::sync::Lazy;
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+static ALLOWED: OnceLock<BTreeMap<String, Vec<String>>> = OnceLock::new();
+
+static RESULTS: Lazy<RefreshCache<String, CommandOutput>> = Lazy::new(RefreshCache::new);
+
+// A command still running after this long is killed
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Longest output kept, in characters
+const MAX_OUTPUT_CHARS: usize = 256;
+
+// Most bytes of stdout read, the rest is discarded. Whitespace is collapsed afterwards, so
+// this leaves room beyond MAX_OUTPUT_CHARS.
+const MAX_OUTPUT_BYTES: u64 = 64 * 1024;
+
+/// Latest result of an allowed command
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CommandOutput {
+    // Output of the last successful run, kept when a later run fails
+    pub text: Option<String>,
+    // Why the last run failed, None after a successful one
+    pub error: Option<String>,
+}
+
+/// Parse an allowlist entry "NAME=COMMAND ARGS...". The command is split on whitespace and
+/// run without a shell, so pipes and quoting need a script.
+pub fn parse_entry(spec: &str) -> Result<(String, Vec<String>), String> {
+    let (name, command) = spec
+        .split_once('=')
+        .ok_or_else(|| format!("Invalid allowed command '{}': expected NAME=COMMAND", spec))?;
+    let name = name.trim();
+    let argv: Vec<String> = command.split_whitespace().map(String::from).collect();
+    if name.is_empty() || argv.is_empty() {
+        return Err(format!(
+            "Invalid allowed command '{}': expected NAME=COMMAND",
+            spec
+        ));
+    }
+    Ok((name.to_string(), argv))
+}
+
+/// Use `commands` as the allowlist from now on. Only the first call has an effect.
+pub fn set(commands: BTreeMap<String, Vec<String>>) {
+    let _ = ALLOWED.set(commands);
+}
+
+/// Names of the allowed commands
+pub fn names() -> Vec<String> {
+    ALLOWED
+        .get()
+        .map(|allowed| allowed.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+pub fn is_allowed(name: &str) -> bool {
+    ALLOWED
+        .get()
+        .is_some_and(|allowed| allowed.contains_key(name))
+}
+
+/// Cached output of the command `name`. Starts a new run in the background when the last one
+/// started more than `interval` ago and none is in progress.
+pub fn latest(name: &str, interval: Duration) -> CommandOutput {
+    let Some(argv) = ALLOWED.get().and_then(|allowed| allowed.get(name)) else {
+        return CommandOutput::default();
+    };
+
+    let name = name.to_string();
+    RESULTS.get(&name, interval, || {
+        let (name, argv) = (name.clone(), argv.clone());
+        std::thread::spawn(move || {
+            let result = run(&argv);
+            if let Err(e) = &result {
+                log::warn!("Command '{}' failed: {}", name, e);
+            }
+            RESULTS.finish(&name, |output| match result {
+                Ok(text) => {
+                    *output = CommandOutput {
+                        text: Some(text),
+                        error: None,
+                    }
+                }
+                Err(e) => output.error = Some(e),
+            });
+        });
+        true
+    })
+}
+
+// Run a command and return its stdout with lines joined by spaces
+fn run(argv: &[String]) -> Result<String, String> {
+    let mut child = Command::new(&argv[0])
+        .args(&argv[1..])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("failed to start {}: {}", argv[0], e))?;
+
+    // Read stdout on its own thread so a chatty command cannot fill the pipe and stall. Only
+    // the start is kept, the rest is drained so the command can still finish.
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let reader = std::thread::spawn(move || {
+        let mut output = Vec::new();
+        let _ = (&mut stdout)
+            .take(MAX_OUTPUT_BYTES)
+            .read_to_end(&mut output);
+        let _ = std::io::copy(&mut stdout, &mut std::io::sink());
+        output
+    });
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if started.elapsed() >= COMMAND_TIMEOUT => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!(
+                    "timed out after {} seconds",
+                    COMMAND_TIMEOUT.as_secs()
+                ));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(e) => return Err(format!("failed to wait for {}: {}", argv[0], e)),
+        }
+    };
+    let output = reader.join().unwrap_or_default();
+    if !status.success() {
+        return Err(format!("exited with {}", status));
+    }
+
+    Ok(String::from_utf8_lossy(&output)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(MAX_OUTPUT_CHARS)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_name_and_arguments() {
+        assert_eq!(
+            parse_entry(" temp = /usr/bin/vcgencmd  measure_temp "),
+            Ok((
+                "temp".to_string(),
+                vec!["/usr/bin/vcgencmd".to_string(), "measure_temp".to_string()]
+            ))
+        );
+    }
+
+    #[test]
+    fn keeps_equals_signs_in_the_command() {
+        let (name, argv) = parse_entry("load=cut -d= -f1 /proc/loadavg").unwrap();
+        assert_eq!(name, "load");
+        assert_eq!(argv, ["cut", "-d=", "-f1", "/proc/loadavg"]);
+    }
+
+    #[test]
+    fn rejects_entries_without_name_or_command() {
+        for spec in ["uptime", "=uptime", "uptime=", " = "] {
+            assert!(parse_entry(spec).is_err(), "{} was accepted", spec);
+        }
+    }
+}
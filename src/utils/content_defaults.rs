@@ -0,0 +1,57 @@
+// Module for the defaults saved through /api/settings/defaults. They are filled into the JSON
+// of new items before it is parsed, so fields a client leaves out take the configured values
+// instead of failing validation or falling back to built-in ones.
+
+use crate::models::settings::ContentDefaults;
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+use std::sync::RwLock;
+
+static DEFAULTS: Lazy<RwLock<ContentDefaults>> = Lazy::new(Default::default);
+
+/// Replace the defaults, used with the saved ones at startup
+pub fn load(defaults: ContentDefaults) {
+    *DEFAULTS.write().unwrap() = defaults;
+}
+
+pub fn get() -> ContentDefaults {
+    DEFAULTS.read().unwrap().clone()
+}
+
+/// Fill the defaults into the fields `item` leaves out. Fields given as null are kept.
+pub fn fill(item: &mut Value) {
+    let defaults = get();
+    let Some(fields) = item.as_object_mut() else {
+        return;
+    };
+
+    if let Some(border) = &defaults.border_effect {
+        if !fields.contains_key("border_effect") {
+            if let Ok(border) = serde_json::to_value(border) {
+                fields.insert("border_effect".to_string(), border);
+            }
+        }
+    }
+
+    let has_timing = fields.contains_key("duration") || fields.contains_key("repeat_count");
+    let Some(data) = fields
+        .get_mut("content")
+        .and_then(|content| content.get_mut("data"))
+        .and_then(Value::as_object_mut)
+    else {
+        return;
+    };
+    let needs_duration = match data.get("type").and_then(Value::as_str) {
+        Some("Text") => {
+            data.entry("color").or_insert(json!(defaults.text_color));
+            data.entry("speed").or_insert(json!(defaults.text_speed));
+            false
+        }
+        // Animated images repeat instead, so only static ones get a duration
+        Some("Image") => !has_timing && data.get("animation").is_none_or(Value::is_null),
+        _ => false,
+    };
+    if needs_duration {
+        fields.insert("duration".to_string(), json!(defaults.image_duration));
+    }
+}
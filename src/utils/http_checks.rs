@@ -0,0 +1,61 @@
+// Module for the HTTP checks of item conditions. Checks run in the background and their
+// latest result is cached per URL and expected text, so evaluating a condition never waits
+// for the network.
+
+use crate::utils::refresh_cache::RefreshCache;
+use crate::utils::remote_url;
+use once_cell::sync::Lazy;
+use std::time::Duration;
+
+// Checks are identified by URL and expected text
+type CheckKey = (String, Option<String>);
+
+static RESULTS: Lazy<RefreshCache<CheckKey, bool>> = Lazy::new(RefreshCache::new);
+
+// A request still running after this long counts as failed
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Whether the last GET request to `url` succeeded and its body contained `contains`.
+/// Starts a new request in the background when the last one started more than `interval` ago
+/// and none is in progress. False until the first request has answered.
+pub fn latest(url: &str, contains: Option<&str>, interval: Duration) -> bool {
+    let key = (url.to_string(), contains.map(String::from));
+    RESULTS.get(&key, interval, || {
+        // Checks only run inside the server's runtime, not for benchmarks or golden frames
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return false;
+        };
+        let key = key.clone();
+        runtime.spawn(async move {
+            let passed = match check(&key.0, key.1.as_deref()).await {
+                Ok(passed) => passed,
+                Err(e) => {
+                    log::warn!("HTTP check of {} failed: {}", key.0, e);
+                    false
+                }
+            };
+            RESULTS.finish(&key, |last| *last = passed);
+        });
+        true
+    })
+}
+
+async fn check(url: &str, contains: Option<&str>) -> Result<bool, String> {
+    let url = reqwest::Url::parse(url).map_err(|e| e.to_string())?;
+    remote_url::check_url(&url)?;
+    let client = remote_url::client_builder(10)
+        .timeout(CHECK_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client.get(url).send().await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Ok(false);
+    }
+    match contains {
+        Some(text) => {
+            let body = response.text().await.map_err(|e| e.to_string())?;
+            Ok(body.contains(text))
+        }
+        None => Ok(true),
+    }
+}
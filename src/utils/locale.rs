@@ -0,0 +1,357 @@
+// Module for the configured locale. Text the server draws itself (the welcome message,
+// clock dates, countdown units) is looked up here instead of being hard-coded in English.
+
+use crate::models::content::ContentType;
+use chrono::{DateTime, Datelike, TimeZone};
+use std::sync::OnceLock;
+
+static LOCALE: OnceLock<&'static Strings> = OnceLock::new();
+
+/// Translation table for one language
+pub struct Strings {
+    pub code: &'static str,
+    // Abbreviated weekday names starting with Sunday
+    pub weekdays: [&'static str; 7],
+    // Abbreviated month names starting with January
+    pub months: [&'static str; 12],
+    pub am: &'static str,
+    pub pm: &'static str,
+    // Suffix for the day count in long countdowns, e.g. "3d 04:00"
+    pub days_suffix: &'static str,
+    // Date line under the clock with {weekday}, {day} and {month} placeholders
+    pub date_format: &'static str,
+    // Welcome message with a {url} placeholder for the web interface address
+    pub welcome: &'static str,
+    // Relative times of {until ...} placeholders with a {time} placeholder for the span
+    pub relative_future: &'static str,
+    pub relative_past: &'static str,
+    pub relative_now: &'static str,
+    // Day, days, hours and minutes units of relative times
+    pub day: &'static str,
+    pub days: &'static str,
+    pub hours_unit: &'static str,
+    pub minutes_unit: &'static str,
+    // {open_state} placeholder while the operating hours say open or closed
+    pub open: &'static str,
+    pub closed: &'static str,
+    // Names of the content types in the order of ContentType::ALL, shown on the placeholder
+    // of an item that failed. At most 12 characters so they fit a 64 pixel wide panel.
+    pub content_types: [&'static str; 13],
+}
+
+const EN: Strings = Strings {
+    code: "en",
+    weekdays: ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"],
+    months: [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ],
+    am: "AM",
+    pm: "PM",
+    days_suffix: "d",
+    date_format: "{weekday} {month} {day}",
+    welcome: "LED Matrix Controller | Web interface: {url} | Use web UI to configure display",
+    relative_future: "in {time}",
+    relative_past: "{time} ago",
+    relative_now: "now",
+    day: "day",
+    days: "days",
+    hours_unit: "h",
+    minutes_unit: "min",
+    open: "Open",
+    closed: "Closed",
+    content_types: [
+        "Text",
+        "Image",
+        "Animation",
+        "Clock",
+        "Plugin",
+        "QR code",
+        "Barcode",
+        "Big number",
+        "Countdown",
+        "Command",
+        "Slideshow",
+        "Video",
+        "Stream",
+    ],
+};
+
+const DE: Strings = Strings {
+    code: "de",
+    weekdays: ["So", "Mo", "Di", "Mi", "Do", "Fr", "Sa"],
+    months: [
+        "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+    ],
+    am: "AM",
+    pm: "PM",
+    days_suffix: "T",
+    date_format: "{weekday} {day}. {month}",
+    welcome:
+        "LED Matrix Controller | Weboberfläche: {url} | Anzeige über die Weboberfläche einrichten",
+    relative_future: "in {time}",
+    relative_past: "vor {time}",
+    relative_now: "jetzt",
+    day: "Tag",
+    days: "Tagen",
+    hours_unit: "Std.",
+    minutes_unit: "Min.",
+    open: "Geöffnet",
+    closed: "Geschlossen",
+    content_types: [
+        "Text",
+        "Bild",
+        "Animation",
+        "Uhr",
+        "Plugin",
+        "QR-Code",
+        "Barcode",
+        "Große Zahl",
+        "Countdown",
+        "Befehl",
+        "Diashow",
+        "Video",
+        "Stream",
+    ],
+};
+
+const ES: Strings = Strings {
+    code: "es",
+    weekdays: ["dom", "lun", "mar", "mié", "jue", "vie", "sáb"],
+    months: [
+        "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sep", "oct", "nov", "dic",
+    ],
+    am: "a.m.",
+    pm: "p.m.",
+    days_suffix: "d",
+    date_format: "{weekday} {day} {month}",
+    welcome:
+        "LED Matrix Controller | Interfaz web: {url} | Configure la pantalla desde la interfaz web",
+    relative_future: "en {time}",
+    relative_past: "hace {time}",
+    relative_now: "ahora",
+    day: "día",
+    days: "días",
+    hours_unit: "h",
+    minutes_unit: "min",
+    open: "Abierto",
+    closed: "Cerrado",
+    content_types: [
+        "Texto",
+        "Imagen",
+        "Animación",
+        "Reloj",
+        "Plugin",
+        "Código QR",
+        "Cód. barras",
+        "Número",
+        "Cuenta atrás",
+        "Comando",
+        "Diapositivas",
+        "Vídeo",
+        "Transmisión",
+    ],
+};
+
+const FR: Strings = Strings {
+    code: "fr",
+    weekdays: ["dim", "lun", "mar", "mer", "jeu", "ven", "sam"],
+    months: [
+        "janv", "févr", "mars", "avr", "mai", "juin", "juil", "août", "sept", "oct", "nov", "déc",
+    ],
+    am: "AM",
+    pm: "PM",
+    days_suffix: "j",
+    date_format: "{weekday} {day} {month}",
+    welcome: "LED Matrix Controller | Interface web : {url} | Configurez l'affichage depuis l'interface web",
+    relative_future: "dans {time}",
+    relative_past: "il y a {time}",
+    relative_now: "maintenant",
+    day: "jour",
+    days: "jours",
+    hours_unit: "h",
+    minutes_unit: "min",
+    open: "Ouvert",
+    closed: "Fermé",
+    content_types: [
+        "Texte", "Image", "Animation", "Horloge", "Plugin", "Code QR", "Code-barres",
+        "Grand nombre", "Décompte", "Commande", "Diaporama", "Vidéo", "Flux",
+    ],
+};
+
+const IT: Strings = Strings {
+    code: "it",
+    weekdays: ["dom", "lun", "mar", "mer", "gio", "ven", "sab"],
+    months: [
+        "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+    ],
+    am: "AM",
+    pm: "PM",
+    days_suffix: "g",
+    date_format: "{weekday} {day} {month}",
+    welcome:
+        "LED Matrix Controller | Interfaccia web: {url} | Configura il display dall'interfaccia web",
+    relative_future: "tra {time}",
+    relative_past: "{time} fa",
+    relative_now: "ora",
+    day: "giorno",
+    days: "giorni",
+    hours_unit: "h",
+    minutes_unit: "min",
+    open: "Aperto",
+    closed: "Chiuso",
+    content_types: [
+        "Testo",
+        "Immagine",
+        "Animazione",
+        "Orologio",
+        "Plugin",
+        "Codice QR",
+        "Cod. a barre",
+        "Numero",
+        "Countdown",
+        "Comando",
+        "Diapositive",
+        "Video",
+        "Stream",
+    ],
+};
+
+const NL: Strings = Strings {
+    code: "nl",
+    weekdays: ["zo", "ma", "di", "wo", "do", "vr", "za"],
+    months: [
+        "jan", "feb", "mrt", "apr", "mei", "jun", "jul", "aug", "sep", "okt", "nov", "dec",
+    ],
+    am: "AM",
+    pm: "PM",
+    days_suffix: "d",
+    date_format: "{weekday} {day} {month}",
+    welcome:
+        "LED Matrix Controller | Webinterface: {url} | Stel het display in via de webinterface",
+    relative_future: "over {time}",
+    relative_past: "{time} geleden",
+    relative_now: "nu",
+    day: "dag",
+    days: "dagen",
+    hours_unit: "u",
+    minutes_unit: "min",
+    open: "Open",
+    closed: "Gesloten",
+    content_types: [
+        "Tekst",
+        "Afbeelding",
+        "Animatie",
+        "Klok",
+        "Plugin",
+        "QR-code",
+        "Barcode",
+        "Groot getal",
+        "Aftellen",
+        "Opdracht",
+        "Diashow",
+        "Video",
+        "Stream",
+    ],
+};
+
+const LOCALES: [&Strings; 6] = [&EN, &DE, &ES, &FR, &IT, &NL];
+
+/// Codes of the supported locales
+pub fn supported() -> impl Iterator<Item = &'static str> {
+    LOCALES.iter().map(|strings| strings.code)
+}
+
+/// Translation table for a locale code such as "de" or "de-AT", None if it is not supported
+pub fn lookup(code: &str) -> Option<&'static Strings> {
+    let language = code.split(['-', '_']).next()?.to_lowercase();
+    LOCALES
+        .iter()
+        .copied()
+        .find(|strings| strings.code == language)
+}
+
+/// Use `strings` from now on. Only the first call has an effect.
+pub fn set(strings: &'static Strings) {
+    let _ = LOCALE.set(strings);
+}
+
+/// Translation table in use, English if no locale is configured
+pub fn strings() -> &'static Strings {
+    LOCALE.get().copied().unwrap_or(&EN)
+}
+
+impl Strings {
+    /// Localized short date such as "Fri Oct 16" or "Fr 16. Okt"
+    pub fn format_date<Tz: TimeZone>(&self, date: &DateTime<Tz>) -> String {
+        self.fill_date(self.date_format, date)
+    }
+
+    /// Localized date without the weekday such as "Oct 16" or "16. Okt", for panels too
+    /// narrow for format_date
+    pub fn format_day_month<Tz: TimeZone>(&self, date: &DateTime<Tz>) -> String {
+        self.fill_date(&self.date_format.replace("{weekday}", ""), date)
+            .trim()
+            .to_string()
+    }
+
+    fn fill_date<Tz: TimeZone>(&self, template: &str, date: &DateTime<Tz>) -> String {
+        template
+            .replace(
+                "{weekday}",
+                self.weekdays[date.weekday().num_days_from_sunday() as usize],
+            )
+            .replace("{day}", &date.day().to_string())
+            .replace("{month}", self.months[date.month0() as usize])
+    }
+
+    /// Localized name of a content type, e.g. "Uhr" for clock content
+    pub fn content_type(&self, content_type: &ContentType) -> &'static str {
+        ContentType::ALL
+            .iter()
+            .position(|candidate| candidate == content_type)
+            .map_or(content_type.as_str(), |index| self.content_types[index])
+    }
+
+    pub fn welcome_message(&self, url: &str) -> String {
+        self.welcome.replace("{url}", url)
+    }
+
+    /// Localized time from now to a moment `seconds` away, e.g. "in 3 days", "in 2 h 10 min"
+    /// or "5 min ago". Shown to the minute, so the future rounds up and "now" only shows once
+    /// the moment has come.
+    pub fn format_relative(&self, seconds: i64) -> String {
+        let minutes = if seconds > 0 {
+            (seconds + 59) / 60
+        } else {
+            -seconds / 60
+        };
+        if minutes == 0 {
+            return self.relative_now.to_string();
+        }
+
+        let days = minutes / (24 * 60);
+        let span = if days > 0 {
+            let unit = if days == 1 { self.day } else { self.days };
+            format!("{} {}", days, unit)
+        } else if minutes >= 60 && minutes % 60 == 0 {
+            format!("{} {}", minutes / 60, self.hours_unit)
+        } else if minutes >= 60 {
+            format!(
+                "{} {} {} {}",
+                minutes / 60,
+                self.hours_unit,
+                minutes % 60,
+                self.minutes_unit
+            )
+        } else {
+            format!("{} {}", minutes, self.minutes_unit)
+        };
+
+        let template = if seconds > 0 {
+            self.relative_future
+        } else {
+            self.relative_past
+        };
+        template.replace("{time}", &span)
+    }
+}
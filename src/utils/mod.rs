@@ -1,3 +1,16 @@
+pub mod commands;
+pub mod content_defaults;
+pub mod http_checks;
+pub mod locale;
+pub mod operating_hours;
+pub mod palettes;
 pub mod privilege;
+pub mod refresh_cache;
+pub mod remote_url;
+#[cfg(feature = "embedded-ui")]
 pub mod static_assets;
+pub mod stream_frames;
+pub mod time_sync;
+pub mod timezone;
 pub mod uuid;
+pub mod variables;
@@ -0,0 +1,23 @@
+// Module for the operating hours saved through /api/settings/hours. Item conditions, the
+// {open_state} text placeholder and the display loop ask it whether the sign's place is open.
+
+use crate::models::settings::OperatingHours;
+use crate::utils::timezone;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+
+static HOURS: Lazy<RwLock<OperatingHours>> = Lazy::new(Default::default);
+
+/// Replace the operating hours, used with the saved ones at startup
+pub fn load(hours: OperatingHours) {
+    *HOURS.write() = hours;
+}
+
+pub fn get() -> OperatingHours {
+    HOURS.read().clone()
+}
+
+/// Whether the operating hours say open right now, in the configured time zone
+pub fn is_open() -> bool {
+    HOURS.read().is_open_at(timezone::now().naive_local())
+}
@@ -0,0 +1,85 @@
+// Module for the named color palettes saved through /api/palettes. Animations and border
+// effects can name a palette instead of listing colors; the colors are filled in when their
+// renderers are set up, so editing a palette restyles every item using it. The API handlers
+// persist and broadcast changes.
+
+use crate::models::content::ContentDetails;
+use crate::models::playlist::PlayListItem;
+use once_cell::sync::Lazy;
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+static PALETTES: Lazy<RwLock<BTreeMap<String, Vec<[u8; 3]>>>> = Lazy::new(Default::default);
+
+// Longest palette name, in characters, and most colors in one palette
+pub const MAX_NAME_CHARS: usize = 64;
+pub const MAX_COLORS: usize = 64;
+
+/// Names may use letters, digits, '_', '-' and '.'
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().count() <= MAX_NAME_CHARS
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// Replace all palettes, used with the saved ones at startup
+pub fn load(palettes: BTreeMap<String, Vec<[u8; 3]>>) {
+    *PALETTES.write().unwrap() = palettes;
+}
+
+pub fn all() -> BTreeMap<String, Vec<[u8; 3]>> {
+    PALETTES.read().unwrap().clone()
+}
+
+pub fn get(name: &str) -> Option<Vec<[u8; 3]>> {
+    PALETTES.read().unwrap().get(name).cloned()
+}
+
+/// Save a palette, returns false if it already had these colors
+pub fn set(name: &str, colors: &[[u8; 3]]) -> bool {
+    let mut palettes = PALETTES.write().unwrap();
+    if palettes.get(name).is_some_and(|current| current == colors) {
+        return false;
+    }
+    palettes.insert(name.to_string(), colors.to_vec());
+    true
+}
+
+/// Remove a palette, returns false if there was none
+pub fn remove(name: &str) -> bool {
+    PALETTES.write().unwrap().remove(name).is_some()
+}
+
+/// Palettes an item refers to by name, from its content and its border effect
+pub fn names_used_by(item: &PlayListItem) -> Vec<&str> {
+    let content = match &item.content.data {
+        ContentDetails::Animation(animation) => animation.palette_name(),
+        _ => None,
+    };
+    let border = item
+        .border_effect
+        .as_ref()
+        .and_then(|effect| effect.palette_name());
+    content.into_iter().chain(border).collect()
+}
+
+/// The item with the colors of the palettes it names filled in. Unknown palettes keep the
+/// colors given with the item.
+pub fn resolve(item: &PlayListItem) -> PlayListItem {
+    let mut resolved = item.clone();
+    if let ContentDetails::Animation(animation) = &mut resolved.content.data {
+        if let Some(colors) = animation.palette_name().and_then(get) {
+            *animation.colors_mut() = colors;
+        }
+    }
+    if let Some(effect) = &mut resolved.border_effect {
+        if let Some(colors) = effect.palette_name().and_then(get) {
+            if let Some(effect_colors) = effect.colors_mut() {
+                *effect_colors = colors;
+            }
+        }
+    }
+    resolved
+}
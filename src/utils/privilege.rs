@@ -1,11 +1,11 @@
 // Module for handling privilege-related functionality
 
-use log::info;
+use log::{info, warn};
 use std::io;
 use std::io::{Error, ErrorKind};
 use std::ptr;
 use uzers::switch::{set_both_gid, set_both_uid};
-use uzers::{get_current_uid, get_user_by_name};
+use uzers::{get_current_uid, get_group_by_name, get_user_by_name};
 
 /// Check if the program has root privileges
 pub fn check_root_privileges() -> Result<(), String> {
@@ -16,10 +16,15 @@ pub fn check_root_privileges() -> Result<(), String> {
     Ok(())
 }
 
-/// Helper function to clear all supplementary groups
+/// Helper function to replace all supplementary groups with `gids`
 /// Returns Result with () for success or io::Error
-fn clear_supplementary_groups() -> io::Result<()> {
-    let result = unsafe { libc::setgroups(0, ptr::null()) };
+fn set_supplementary_groups(gids: &[libc::gid_t]) -> io::Result<()> {
+    let list = if gids.is_empty() {
+        ptr::null()
+    } else {
+        gids.as_ptr()
+    };
+    let result = unsafe { libc::setgroups(gids.len(), list) };
 
     if result != 0 {
         Err(io::Error::last_os_error())
@@ -32,7 +37,9 @@ fn clear_supplementary_groups() -> io::Result<()> {
 ///
 /// This function checks if we're still running as root first.
 /// If privileges have already been dropped, it simply logs and returns success.
-pub fn drop_privileges() -> Result<(), Error> {
+/// The groups named in `keep_groups`, e.g. "audio" for devices opened later on, stay
+/// supplementary groups; all others are cleared.
+pub fn drop_privileges(keep_groups: &[&str]) -> Result<(), Error> {
     // Check if we're still running as root
     let current_uid = get_current_uid();
     if current_uid != 0 {
@@ -63,11 +70,24 @@ pub fn drop_privileges() -> Result<(), Error> {
         username, uid, gid
     );
 
-    // Clear all supplementary groups
-    if let Err(e) = clear_supplementary_groups() {
+    // Clear all supplementary groups except the ones still needed
+    let kept: Vec<libc::gid_t> = keep_groups
+        .iter()
+        .filter_map(|name| {
+            let group = get_group_by_name(name);
+            if group.is_none() {
+                warn!(
+                    "Group {} not found, it is not kept after dropping privileges",
+                    name
+                );
+            }
+            group.map(|group| group.gid())
+        })
+        .collect();
+    if let Err(e) = set_supplementary_groups(&kept) {
         return Err(Error::new(
             ErrorKind::PermissionDenied,
-            format!("Failed to clear supplementary groups: {}", e),
+            format!("Failed to set supplementary groups: {}", e),
         ));
     }
 
@@ -0,0 +1,155 @@
+// Module for values that are refreshed in the background and read from a cache, so rendering
+// never waits for a command or the network. Command content, HTTP checks and Stream content
+// each keep one cache. Entries nobody read for a while are dropped, e.g. after the item that
+// used them was deleted.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// Entries not read for this long are dropped
+const UNUSED_EXPIRY: Duration = Duration::from_secs(3600);
+
+// Shortest time between two looks for unused entries
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+struct Entry<V> {
+    value: V,
+    started: Option<Instant>,
+    last_read: Instant,
+    running: bool,
+}
+
+struct Entries<K, V> {
+    entries: HashMap<K, Entry<V>>,
+    last_sweep: Instant,
+}
+
+impl<K, V> Entries<K, V> {
+    // Drop the entries not read for UNUSED_EXPIRY, at most once per SWEEP_INTERVAL
+    fn sweep(&mut self, now: Instant) {
+        if now.duration_since(self.last_sweep) < SWEEP_INTERVAL {
+            return;
+        }
+        self.last_sweep = now;
+        // Running entries stay so their refresh is not started a second time
+        self.entries.retain(|_, entry| {
+            entry.running || now.duration_since(entry.last_read) < UNUSED_EXPIRY
+        });
+    }
+}
+
+/// Cache of values that are refreshed in the background, one refresh per key at a time
+pub struct RefreshCache<K, V> {
+    inner: Mutex<Entries<K, V>>,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone + Default> RefreshCache<K, V> {
+    pub fn new() -> Self {
+        Self {
+            inner: Mutex::new(Entries {
+                entries: HashMap::new(),
+                last_sweep: Instant::now(),
+            }),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone + Default> Default for RefreshCache<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone + Default> RefreshCache<K, V> {
+    /// Cached value for `key`, the default until the first refresh finished. When the last
+    /// refresh started more than `interval` ago and none is in progress `start` is called to
+    /// begin one, which hands its result to `finish`. `start` returns false if it could not.
+    pub fn get(&self, key: &K, interval: Duration, start: impl FnOnce() -> bool) -> V {
+        let mut inner = self.inner.lock().unwrap();
+        let now = Instant::now();
+        inner.sweep(now);
+
+        let entry = inner.entries.entry(key.clone()).or_insert_with(|| Entry {
+            value: V::default(),
+            started: None,
+            last_read: now,
+            running: false,
+        });
+        entry.last_read = now;
+        let due = entry
+            .started
+            .is_none_or(|started| now.duration_since(started) >= interval);
+        if due && !entry.running && start() {
+            entry.started = Some(now);
+            entry.running = true;
+        }
+        entry.value.clone()
+    }
+
+    /// Store the result of a refresh that `get` started by applying `update` to the value
+    pub fn finish(&self, key: &K, update: impl FnOnce(&mut V)) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(entry) = inner.entries.get_mut(key) {
+            entry.running = false;
+            update(&mut entry.value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_refresh_runs_at_a_time() {
+        let cache: RefreshCache<String, u32> = RefreshCache::new();
+        let key = "key".to_string();
+        let mut started = 0;
+        for _ in 0..3 {
+            cache.get(&key, Duration::ZERO, || {
+                started += 1;
+                true
+            });
+        }
+        assert_eq!(started, 1);
+
+        cache.finish(&key, |value| *value = 7);
+        let value = cache.get(&key, Duration::ZERO, || {
+            started += 1;
+            true
+        });
+        assert_eq!((value, started), (7, 2));
+    }
+
+    #[test]
+    fn refresh_that_could_not_start_is_retried() {
+        let cache: RefreshCache<String, u32> = RefreshCache::new();
+        let key = "key".to_string();
+        cache.get(&key, Duration::from_secs(60), || false);
+        let mut started = false;
+        cache.get(&key, Duration::from_secs(60), || {
+            started = true;
+            true
+        });
+        assert!(started);
+    }
+
+    #[test]
+    fn unused_entries_are_dropped() {
+        let cache: RefreshCache<String, u32> = RefreshCache::new();
+        let key = "old".to_string();
+        cache.get(&key, Duration::ZERO, || true);
+        let mut inner = cache.inner.lock().unwrap();
+        inner.sweep(Instant::now() + UNUSED_EXPIRY);
+        assert!(
+            inner.entries.contains_key(&key),
+            "running entry was dropped"
+        );
+
+        inner.entries.get_mut(&key).unwrap().running = false;
+        inner.sweep(Instant::now() + UNUSED_EXPIRY + SWEEP_INTERVAL * 2);
+        assert!(!inner.entries.contains_key(&key));
+    }
+}
@@ -0,0 +1,210 @@
+// Module for requests to URLs that API clients hand in: images fetched from a link, HTTP
+// checks and camera streams. Unless --allow-local-urls is set they may only reach public
+// addresses, so a client cannot use the sign to look into its local network, the loopback
+// interface or cloud metadata services.
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::redirect::Policy;
+use reqwest::Url;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::{Arc, OnceLock};
+
+static ALLOW_LOCAL: OnceLock<bool> = OnceLock::new();
+
+/// Let requests reach local and private addresses from now on, e.g. a doorbell camera on the
+/// LAN. Only the first call has an effect.
+pub fn set_allow_local(allow: bool) {
+    let _ = ALLOW_LOCAL.set(allow);
+}
+
+fn allow_local() -> bool {
+    ALLOW_LOCAL.get().copied().unwrap_or(false)
+}
+
+/// Whether `ip` may be reached, always true with --allow-local-urls
+pub fn is_allowed(ip: IpAddr) -> bool {
+    allow_local() || is_public(ip)
+}
+
+// Whether `ip` is a unicast address on the public internet
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => {
+            if let Some(mapped) = ip.to_ipv4_mapped() {
+                return is_public_v4(mapped);
+            }
+            let first = ip.segments()[0];
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_multicast()
+                // Unique local fc00::/7, link-local fe80::/10 and site-local fec0::/10
+                || first & 0xfe00 == 0xfc00
+                || first & 0xffc0 == 0xfe80
+                || first & 0xffc0 == 0xfec0
+                // Documentation 2001:db8::/32
+                || (first == 0x2001 && ip.segments()[1] == 0x0db8)
+                // NAT64 64:ff9b::/96 embeds an IPv4 address
+                || (ip.segments()[..6] == [0x64, 0xff9b, 0, 0, 0, 0]
+                    && !is_public_v4(nat64_embedded(ip))))
+        }
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        // "This network" 0.0.0.0/8, shared address space 100.64.0.0/10, benchmarking
+        // 198.18.0.0/15 and reserved 240.0.0.0/4
+        || a == 0
+        || (a == 100 && b & 0xc0 == 64)
+        || (a == 198 && b & 0xfe == 18)
+        || a >= 240)
+}
+
+fn nat64_embedded(ip: Ipv6Addr) -> Ipv4Addr {
+    let [.., a, b, c, d] = ip.octets();
+    Ipv4Addr::new(a, b, c, d)
+}
+
+/// Error if `url` names an address that may not be reached directly. Host names are checked
+/// when they are resolved, see client_builder.
+pub fn check_url(url: &Url) -> Result<(), String> {
+    let host = url.host_str().ok_or("URL has no host")?;
+    let Some(ip) = literal_ip(host) else {
+        return Ok(());
+    };
+    if is_allowed(ip) {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} is not a public address (see --allow-local-urls)",
+            ip
+        ))
+    }
+}
+
+/// Resolve the host of `url` and point the URL at its first address that may be reached, for
+/// programs such as ffmpeg that resolve host names themselves
+pub async fn resolve_url(url: &Url) -> Result<Url, String> {
+    check_url(url)?;
+    let host = url.host_str().unwrap_or_default();
+    if literal_ip(host).is_some() {
+        return Ok(url.clone());
+    }
+    let port = url.port_or_known_default().unwrap_or(0);
+    let ip = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| format!("failed to resolve {}: {}", host, e))?
+        .map(|addr| addr.ip())
+        .find(|ip| is_allowed(*ip))
+        .ok_or_else(|| format!("{} has no public address (see --allow-local-urls)", host))?;
+    let mut resolved = url.clone();
+    resolved
+        .set_ip_host(ip)
+        .map_err(|_| format!("cannot connect to {} by address", host))?;
+    Ok(resolved)
+}
+
+// Address a URL host names directly, IPv6 hosts are given in brackets
+fn literal_ip(host: &str) -> Option<IpAddr> {
+    host.trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse()
+        .ok()
+}
+
+// Resolver that drops the addresses which may not be reached. Connections, including those
+// after redirects, only ever go to the addresses it returns.
+struct AllowedResolver;
+
+impl Resolve for AllowedResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|addr| is_allowed(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!(
+                    "{} has no public address (see --allow-local-urls)",
+                    name.as_str()
+                )
+                .into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// HTTP client builder for URLs from API clients, following at most `max_redirects`
+/// redirects. Requests to addresses that may not be reached fail, also after a redirect.
+pub fn client_builder(max_redirects: usize) -> reqwest::ClientBuilder {
+    let redirects = Policy::custom(move |attempt| {
+        if attempt.previous().len() > max_redirects {
+            attempt.error("too many redirects")
+        } else if let Err(e) = check_url(attempt.url()) {
+            attempt.error(e)
+        } else {
+            attempt.follow()
+        }
+    });
+    reqwest::Client::builder()
+        .dns_resolver(Arc::new(AllowedResolver))
+        .redirect(redirects)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_addresses_are_not_public() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.20",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "fe80::1",
+            "fd00::1",
+            "::ffff:192.168.1.1",
+            "64:ff9b::a00:1",
+        ] {
+            assert!(!is_public(ip.parse().unwrap()), "{} counts as public", ip);
+        }
+    }
+
+    #[test]
+    fn internet_addresses_are_public() {
+        for ip in [
+            "1.1.1.1",
+            "93.184.216.34",
+            "2606:4700::1111",
+            "::ffff:8.8.8.8",
+        ] {
+            assert!(is_public(ip.parse().unwrap()), "{} counts as local", ip);
+        }
+    }
+
+    #[test]
+    fn literal_local_hosts_are_rejected() {
+        let url = Url::parse("http://169.254.169.254/latest/meta-data/").unwrap();
+        assert!(check_url(&url).is_err());
+        let url = Url::parse("http://[::1]:3000/api/playlist").unwrap();
+        assert!(check_url(&url).is_err());
+        let url = Url::parse("http://example.com/image.png").unwrap();
+        assert!(check_url(&url).is_ok());
+    }
+}
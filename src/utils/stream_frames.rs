@@ -0,0 +1,147 @@
+// Module for the camera frames shown by Stream content. Frames are fetched in the background
+// and the latest one is cached per URL, so rendering never waits for the network and items
+// showing the same camera share the requests.
+
+use crate::utils::refresh_cache::RefreshCache;
+use crate::utils::remote_url;
+use image::RgbImage;
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+use std::time::Duration;
+
+static FRAMES: Lazy<RefreshCache<String, StreamFrame>> = Lazy::new(RefreshCache::new);
+
+// A fetch still running after this long counts as failed
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Largest encoded frame accepted, in bytes
+const MAX_FRAME_BYTES: usize = 8 * 1024 * 1024;
+
+// Start and end markers of a JPEG image inside an MJPEG stream
+const JPEG_START: [u8; 2] = [0xFF, 0xD8];
+const JPEG_END: [u8; 2] = [0xFF, 0xD9];
+
+/// Latest frame of a stream
+#[derive(Clone, Debug, Default)]
+pub struct StreamFrame {
+    // Last frame fetched successfully, kept when a later fetch fails
+    pub image: Option<Arc<RgbImage>>,
+    // Increases with every new frame, so callers only rescale when it changes
+    pub sequence: u64,
+    // Why the last fetch failed, None after a successful one
+    pub error: Option<String>,
+}
+
+/// Cached frame of the stream at `url`. Starts a new fetch in the background when the last
+/// one started more than `interval` ago and none is in progress.
+pub fn latest(url: &str, interval: Duration) -> StreamFrame {
+    let url = url.to_string();
+    FRAMES.get(&url, interval, || {
+        // Frames are only fetched inside the server's runtime, not for benchmarks or golden frames
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return false;
+        };
+        let url = url.clone();
+        runtime.spawn(async move {
+            let result = fetch(&url).await;
+            if let Err(e) = &result {
+                log::warn!("Fetching a frame from {} failed: {}", url, e);
+            }
+            FRAMES.finish(&url, |frame| match result {
+                Ok(image) => {
+                    *frame = StreamFrame {
+                        image: Some(Arc::new(image)),
+                        sequence: frame.sequence + 1,
+                        error: None,
+                    }
+                }
+                Err(e) => frame.error = Some(e),
+            });
+        });
+        true
+    })
+}
+
+async fn fetch(url: &str) -> Result<RgbImage, String> {
+    let bytes = if url.starts_with("rtsp://") {
+        fetch_rtsp(url).await?
+    } else {
+        fetch_http(url).await?
+    };
+    tokio::task::spawn_blocking(move || {
+        image::load_from_memory(&bytes)
+            .map(|decoded| decoded.to_rgb8())
+            .map_err(|e| format!("failed to decode frame: {}", e))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Snapshot bodies are returned as they are, MJPEG streams are read up to their first frame
+async fn fetch_http(url: &str) -> Result<Vec<u8>, String> {
+    let url = reqwest::Url::parse(url).map_err(|e| e.to_string())?;
+    remote_url::check_url(&url)?;
+    let client = remote_url::client_builder(10)
+        .timeout(FETCH_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut response = client.get(url).send().await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("server answered {}", response.status()));
+    }
+
+    let is_mjpeg = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("multipart/x-mixed-replace"));
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+        body.extend_from_slice(&chunk);
+        if is_mjpeg {
+            if let Some(frame) = first_jpeg(&body) {
+                return Ok(frame.to_vec());
+            }
+        }
+        if body.len() > MAX_FRAME_BYTES {
+            return Err("frame is too large".to_string());
+        }
+    }
+    if is_mjpeg {
+        return Err("stream ended before a complete frame".to_string());
+    }
+    Ok(body)
+}
+
+// First complete JPEG image in an MJPEG body
+fn first_jpeg(body: &[u8]) -> Option<&[u8]> {
+    let start = body.windows(2).position(|pair| pair == JPEG_START)?;
+    let length = body[start..].windows(2).position(|pair| pair == JPEG_END)?;
+    Some(&body[start..start + length + JPEG_END.len()])
+}
+
+// Grab a single frame with the ffmpeg binary, encoded as PNG
+async fn fetch_rtsp(url: &str) -> Result<Vec<u8>, String> {
+    // ffmpeg resolves host names itself, so it is handed an address that was checked
+    let url = reqwest::Url::parse(url).map_err(|e| e.to_string())?;
+    let url = remote_url::resolve_url(&url).await?;
+    let command = tokio::process::Command::new("ffmpeg")
+        .args(["-v", "error", "-nostdin", "-rtsp_transport", "tcp", "-i"])
+        .arg(url.as_str())
+        .args(["-frames:v", "1", "-an"])
+        .args(["-f", "image2pipe", "-c:v", "png", "-"])
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(FETCH_TIMEOUT, command)
+        .await
+        .map_err(|_| format!("timed out after {} seconds", FETCH_TIMEOUT.as_secs()))?
+        .map_err(|e| format!("failed to run ffmpeg: {}", e))?;
+    if !output.status.success() || output.stdout.is_empty() {
+        return Err(format!(
+            "ffmpeg failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(output.stdout)
+}
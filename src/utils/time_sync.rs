@@ -0,0 +1,124 @@
+// Module for checking that the system clock has been set. A Pi without a real-time clock
+// boots with a stale time until NTP syncs, so clock and countdown content would be wrong.
+
+use crate::utils::timezone;
+use chrono::{Datelike, Utc};
+use log::{info, warn};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+// How often the sync state is checked again after startup
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+// A clock reading earlier than this year has certainly not been set
+const MIN_PLAUSIBLE_YEAR: i32 = 2024;
+
+// Assume the clock is fine until the first check says otherwise
+static SYNCED: AtomicBool = AtomicBool::new(true);
+static LAST_STATUS: Mutex<Option<TimeSyncStatus>> = Mutex::new(None);
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TimeSyncStatus {
+    pub synced: bool,
+    // "kernel" when the kernel's NTP state was read, "plausibility" when only the year was checked
+    pub source: &'static str,
+    // Estimated maximum clock error reported by the kernel
+    pub max_error_ms: Option<u64>,
+    pub utc_time: String,
+    pub local_time: String,
+    // Configured time zone used for local_time, "system" if none is set
+    pub timezone: String,
+    pub checked_at: String,
+}
+
+/// Whether the last check found the system clock synchronized
+pub fn is_synced() -> bool {
+    SYNCED.load(Ordering::Relaxed)
+}
+
+/// Result of the last check, checking now if there was none yet
+pub fn status() -> TimeSyncStatus {
+    if let Some(status) = LAST_STATUS.lock().unwrap().clone() {
+        let now = Utc::now();
+        return TimeSyncStatus {
+            utc_time: now.to_rfc3339(),
+            local_time: timezone::to_local(now).to_rfc3339(),
+            timezone: timezone::name(),
+            ..status
+        };
+    }
+    check()
+}
+
+/// Check the clock now and remember the result
+pub fn check() -> TimeSyncStatus {
+    let now = Utc::now();
+    let plausible = now.year() >= MIN_PLAUSIBLE_YEAR;
+
+    let (synced, source, max_error_ms) = match kernel_sync_state() {
+        Some((kernel_synced, max_error_ms)) => (kernel_synced && plausible, "kernel", max_error_ms),
+        None => (plausible, "plausibility", None),
+    };
+
+    let status = TimeSyncStatus {
+        synced,
+        source,
+        max_error_ms,
+        utc_time: now.to_rfc3339(),
+        local_time: timezone::to_local(now).to_rfc3339(),
+        timezone: timezone::name(),
+        checked_at: now.to_rfc3339(),
+    };
+    SYNCED.store(synced, Ordering::Relaxed);
+    *LAST_STATUS.lock().unwrap() = Some(status.clone());
+    status
+}
+
+/// Read the kernel's NTP state; None if it is not available on this platform
+#[cfg(target_os = "linux")]
+fn kernel_sync_state() -> Option<(bool, Option<u64>)> {
+    // SAFETY: with modes 0 adjtimex only reads the kernel clock state into the struct
+    let mut timex: libc::timex = unsafe { std::mem::zeroed() };
+    let state = unsafe { libc::adjtimex(&mut timex) };
+    if state == -1 {
+        return None;
+    }
+
+    let synced = state != libc::TIME_ERROR && timex.status & libc::STA_UNSYNC == 0;
+    let max_error_ms = u64::try_from(timex.maxerror).ok().map(|us| us / 1000);
+    Some((synced, max_error_ms))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn kernel_sync_state() -> Option<(bool, Option<u64>)> {
+    None
+}
+
+/// Check at startup and keep checking, logging whenever the state changes
+pub async fn monitor() {
+    let status = check();
+    if status.synced {
+        info!("System clock is synchronized ({})", status.utc_time);
+    } else {
+        warn!(
+            "System clock is not synchronized ({}); clock and countdown content may be wrong until NTP syncs",
+            status.utc_time
+        );
+    }
+
+    let mut synced = status.synced;
+    loop {
+        tokio::time::sleep(CHECK_INTERVAL).await;
+        let status = check();
+        if status.synced != synced {
+            synced = status.synced;
+            if synced {
+                info!("System clock is now synchronized ({})", status.utc_time);
+            } else {
+                warn!("System clock lost synchronization ({})", status.utc_time);
+            }
+        }
+    }
+}
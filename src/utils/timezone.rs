@@ -0,0 +1,64 @@
+// Module for the configured time zone. Clock content and log timestamps use it instead of
+// the system time zone, which is often UTC in containers or on a Pi that was never configured.
+
+use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::sync::{Mutex, OnceLock};
+
+static TIMEZONE: OnceLock<Tz> = OnceLock::new();
+
+// Pinned current time, only set while capturing golden frames
+static FROZEN_TIME: Mutex<Option<DateTime<Utc>>> = Mutex::new(None);
+
+/// Use `timezone` from now on. Only the first call has an effect.
+pub fn set(timezone: Tz) {
+    let _ = TIMEZONE.set(timezone);
+}
+
+/// Name of the time zone in use, "system" when none is configured
+pub fn name() -> String {
+    TIMEZONE.get().map_or_else(
+        || "system".to_string(),
+        |timezone| timezone.name().to_string(),
+    )
+}
+
+/// Current time in the configured time zone, or the system time zone if none is set
+pub fn now() -> DateTime<FixedOffset> {
+    to_local(utc_now())
+}
+
+/// Current UTC time as seen by renderers, the pinned time while one is set
+pub fn utc_now() -> DateTime<Utc> {
+    FROZEN_TIME.lock().unwrap().unwrap_or_else(Utc::now)
+}
+
+/// Pin the time renderers see so time-based content draws the same frames on every run,
+/// None to follow the system clock again
+#[cfg_attr(not(feature = "test-util"), allow(dead_code))]
+pub fn freeze(time: Option<DateTime<Utc>>) {
+    *FROZEN_TIME.lock().unwrap() = time;
+}
+
+/// Convert a UTC time to the configured time zone
+pub fn to_local(time: DateTime<Utc>) -> DateTime<FixedOffset> {
+    match TIMEZONE.get() {
+        Some(timezone) => time.with_timezone(timezone).fixed_offset(),
+        None => time.with_timezone(&Local).fixed_offset(),
+    }
+}
+
+/// Interpret a wall-clock time in the configured time zone. Times a daylight saving change
+/// repeats take the earlier one, times it skips give None.
+pub fn from_local(time: NaiveDateTime) -> Option<DateTime<Utc>> {
+    match TIMEZONE.get() {
+        Some(timezone) => timezone
+            .from_local_datetime(&time)
+            .earliest()
+            .map(|time| time.with_timezone(&Utc)),
+        None => Local
+            .from_local_datetime(&time)
+            .earliest()
+            .map(|time| time.with_timezone(&Utc)),
+    }
+}
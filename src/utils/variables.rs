@@ -0,0 +1,223 @@
+// Module for the variables external systems set through /api/vars. Text content shows them
+// with {{name}} placeholders and item conditions can compare them, so the sign reacts to new
+// values without items being edited. The API handlers persist and broadcast changes.
+// Text content can also show the time until a moment with {until 2025-12-24T18:00} and
+// whether the operating hours say open with {open_state}.
+
+use crate::utils::{locale, operating_hours, timezone};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use once_cell::sync::Lazy;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+static VARIABLES: Lazy<RwLock<BTreeMap<String, String>>> = Lazy::new(Default::default);
+
+// Bumped on every change so renderers know when to expand their text again
+static VERSION: AtomicU64 = AtomicU64::new(0);
+
+// Longest variable name and value, in characters
+pub const MAX_NAME_CHARS: usize = 64;
+pub const MAX_VALUE_CHARS: usize = 1000;
+
+/// Names may use letters, digits, '_', '-' and '.'
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().count() <= MAX_NAME_CHARS
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// Replace all variables, used with the saved ones at startup
+pub fn load(variables: BTreeMap<String, String>) {
+    *VARIABLES.write().unwrap() = variables;
+    VERSION.fetch_add(1, Ordering::SeqCst);
+}
+
+pub fn all() -> BTreeMap<String, String> {
+    VARIABLES.read().unwrap().clone()
+}
+
+pub fn get(name: &str) -> Option<String> {
+    VARIABLES.read().unwrap().get(name).cloned()
+}
+
+/// Set a variable, returns false if it already had this value
+pub fn set(name: &str, value: &str) -> bool {
+    let mut variables = VARIABLES.write().unwrap();
+    if variables.get(name).is_some_and(|current| current == value) {
+        return false;
+    }
+    variables.insert(name.to_string(), value.to_string());
+    VERSION.fetch_add(1, Ordering::SeqCst);
+    true
+}
+
+/// Remove a variable, returns false if there was none
+pub fn remove(name: &str) -> bool {
+    let removed = VARIABLES.write().unwrap().remove(name).is_some();
+    if removed {
+        VERSION.fetch_add(1, Ordering::SeqCst);
+    }
+    removed
+}
+
+/// Changes whenever a variable is set or removed
+pub fn version() -> u64 {
+    VERSION.load(Ordering::SeqCst)
+}
+
+/// Whether `text` may contain placeholders expand replaces
+pub fn has_placeholders(text: &str) -> bool {
+    text.contains('{')
+}
+
+/// Whether `text` contains {until ...} or {open_state} placeholders, whose expansion changes
+/// with the time
+pub fn has_timed_placeholders(text: &str) -> bool {
+    text.contains("{until ") || text.contains("{open_state}")
+}
+
+// Moment of an {until ...} placeholder: an RFC 3339 timestamp, or a date and time of day
+// ("2025-12-24T18:00", seconds optional, a space instead of 'T' works too) or a date alone in
+// the configured time zone
+fn parse_moment(moment: &str) -> Option<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(moment) {
+        return Some(time.with_timezone(&Utc));
+    }
+    let local = [
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(moment, format).ok())
+    .or_else(|| {
+        NaiveDate::parse_from_str(moment, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+    })?;
+    timezone::from_local(local)
+}
+
+// Value of a single-brace placeholder: the localized time until the moment of {until ...} or
+// the open state of {open_state}. None if it is neither.
+fn timed_value(placeholder: &str) -> Option<String> {
+    if placeholder == "open_state" {
+        let strings = locale::strings();
+        let state = if operating_hours::is_open() {
+            strings.open
+        } else {
+            strings.closed
+        };
+        return Some(state.to_string());
+    }
+    let moment = parse_moment(placeholder.strip_prefix("until ")?.trim())?;
+    let seconds = (moment - timezone::utc_now()).num_seconds();
+    Some(locale::strings().format_relative(seconds))
+}
+
+/// Replace {{name}} placeholders in `text` with the variables' values, {until ...}
+/// placeholders with the time until their moment and {open_state} with the open state.
+/// Unknown variables are replaced with nothing, text that is not a valid placeholder is kept
+/// as it is. Also returns where each character position of `text` (including the end) ended
+/// up in the expanded text, so character ranges can be moved along.
+pub fn expand(text: &str) -> (String, Vec<usize>) {
+    let chars: Vec<char> = text.chars().collect();
+    if !has_placeholders(text) {
+        return (text.to_string(), (0..=chars.len()).collect());
+    }
+
+    let mut expanded = String::with_capacity(text.len());
+    let mut expanded_len = 0;
+    let mut positions = Vec::with_capacity(chars.len() + 1);
+    let variables = VARIABLES.read().unwrap();
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == '{' && chars.get(i + 1) == Some(&'{') {
+            let close = (i + 2..chars.len().saturating_sub(1))
+                .find(|&j| chars[j] == '}' && chars[j + 1] == '}');
+            if let Some(close) = close {
+                let name: String = chars[i + 2..close].iter().collect();
+                if is_valid_name(name.trim()) {
+                    let value = variables.get(name.trim()).map_or("", String::as_str);
+                    positions.extend(std::iter::repeat_n(expanded_len, close + 2 - i));
+                    expanded.push_str(value);
+                    expanded_len += value.chars().count();
+                    i = close + 2;
+                    continue;
+                }
+            }
+        } else if chars[i] == '{' {
+            let close = (i + 1..chars.len()).find(|&j| chars[j] == '}');
+            let placeholder = close.map(|close| chars[i + 1..close].iter().collect::<String>());
+            if let (Some(close), Some(value)) =
+                (close, placeholder.as_deref().and_then(timed_value))
+            {
+                positions.extend(std::iter::repeat_n(expanded_len, close + 1 - i));
+                expanded.push_str(&value);
+                expanded_len += value.chars().count();
+                i = close + 1;
+                continue;
+            }
+        }
+        positions.push(expanded_len);
+        expanded.push(chars[i]);
+        expanded_len += 1;
+        i += 1;
+    }
+    positions.push(expanded_len);
+    (expanded, positions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Variables are global, so every test uses names of its own
+
+    #[test]
+    fn replaces_variables_and_maps_positions() {
+        set("expand_test.temp", "21.5");
+        let (text, positions) = expand("Out: {{expand_test.temp}} C");
+        assert_eq!(text, "Out: 21.5 C");
+        // "Out: " keeps its positions, the placeholder collapses onto the start of the value
+        assert_eq!(&positions[..6], &[0, 1, 2, 3, 4, 5]);
+        assert_eq!(positions[25], 9);
+        assert_eq!(positions.last(), Some(&11));
+        assert_eq!(
+            positions.len(),
+            "Out: {{expand_test.temp}} C".chars().count() + 1
+        );
+    }
+
+    #[test]
+    fn unknown_variables_expand_to_nothing() {
+        assert_eq!(expand("[{{expand_test.unset}}]").0, "[]");
+    }
+
+    #[test]
+    fn text_that_is_no_placeholder_is_kept() {
+        for text in [
+            "{{not a name}}",
+            "{{open",
+            "{unknown}",
+            "{until someday}",
+            "a { b",
+        ] {
+            assert_eq!(expand(text).0, text);
+        }
+    }
+
+    #[test]
+    fn until_shows_the_time_since_a_past_moment() {
+        let (text, _) = expand("Opened {until 2000-01-01T00:00:00Z}");
+        assert!(
+            text.starts_with("Opened ") && text.ends_with(" days ago"),
+            "{}",
+            text
+        );
+    }
+}
@@ -0,0 +1,77 @@
+// Runs actions for every input source (this API, the simple state endpoint, buttons), so the
+// control logic lives in one place
+
+use crate::models::action::Action;
+use crate::models::settings::BrightnessSettings;
+use crate::web::api::events::NowPlayingEvent;
+use crate::web::api::settings::update_brightness;
+use crate::web::api::CombinedState;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::Json;
+use log::{debug, warn};
+
+// Longest notification text in characters
+const MAX_NOTIFY_CHARS: usize = 500;
+
+/// Run `action`. Fails with the status an API client would get for it.
+pub async fn dispatch(combined_state: &CombinedState, action: Action) -> Result<(), StatusCode> {
+    debug!("Running action {:?}", action);
+    let ((display, _), event_state) = combined_state;
+    match action {
+        Action::NextItem => {
+            let mut display = display.lock().await;
+            display.skip_to_next();
+            // The update loop only reports transitions it made itself
+            event_state
+                .lock()
+                .broadcast_now_playing(NowPlayingEvent::from_display(&display, true));
+        }
+        Action::SetBrightness(brightness) => {
+            // Goes through the brightness handler so the change is saved and broadcast
+            let settings = BrightnessSettings {
+                brightness,
+                fade_ms: None,
+            };
+            let _ = update_brightness(State(combined_state.clone()), Json(settings)).await?;
+        }
+        Action::ShowItem(id) => {
+            let mut display = display.lock().await;
+            if !display.show_item(&id) {
+                warn!("Cannot show item {}: no such playable item", id);
+                return Err(StatusCode::NOT_FOUND);
+            }
+            event_state
+                .lock()
+                .broadcast_now_playing(NowPlayingEvent::from_display(&display, true));
+        }
+        Action::Power(on) => {
+            let mut display = display.lock().await;
+            if display.is_powered_on() != on {
+                display.set_powered_on(on);
+                event_state.lock().broadcast_power(on);
+            }
+        }
+        Action::Flash(color) => display.lock().await.flash(color),
+        Action::Notify(text) => {
+            let text = text.trim();
+            if text.is_empty() || text.chars().count() > MAX_NOTIFY_CHARS {
+                warn!("Rejected notification with empty or overlong text");
+                return Err(StatusCode::BAD_REQUEST);
+            }
+            display.lock().await.notify(text);
+        }
+    }
+    Ok(())
+}
+
+// Handler for running an action through the API
+pub async fn run_action(
+    State(combined_state): State<CombinedState>,
+    Json(action): Json<Action>,
+) -> StatusCode {
+    match dispatch(&combined_state, action).await {
+        Ok(()) => StatusCode::NO_CONTENT,
+        Err(status) => status,
+    }
+}
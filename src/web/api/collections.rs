@@ -0,0 +1,202 @@
+// Seasonal collections whose items join the rotation during their window, see
+// models::collection. They are stored apart from the playlist, which they never change.
+
+use crate::display::manager::DisplayManager;
+use crate::display::renderer::registry::is_plugin_registered;
+use crate::models::collection::Collection;
+use crate::storage::app_storage::AppStorage;
+use crate::web::api::playlist::extract_plugin_name;
+use crate::web::api::CombinedState;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use log::{debug, error, info, warn};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct CollectionStatus {
+    #[serde(flatten)]
+    pub collection: Collection,
+    // Whether its items are part of the rotation today
+    pub active: bool,
+}
+
+impl From<&Collection> for CollectionStatus {
+    fn from(collection: &Collection) -> Self {
+        Self {
+            active: collection.is_active(),
+            collection: collection.clone(),
+        }
+    }
+}
+
+// Check a new or changed collection. Its items need existing images and plugins, and their
+// IDs may not be used by the playlist or another collection.
+fn check_collection(
+    display: &DisplayManager,
+    storage: &AppStorage,
+    collection: &Collection,
+) -> Result<(), StatusCode> {
+    if let Err(e) = collection.validate() {
+        warn!("Rejected collection '{}': {}", collection.name, e);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    for item in &collection.items {
+        for image_id in item.image_ids() {
+            if !storage.image_path(image_id).exists() {
+                warn!(
+                    "Rejected collection item referencing missing image {}",
+                    image_id
+                );
+                return Err(StatusCode::BAD_REQUEST);
+            }
+        }
+        if let Some(video_id) = item.video_id() {
+            if storage.load_video_info(video_id).is_none() {
+                warn!(
+                    "Rejected collection item referencing missing video {}",
+                    video_id
+                );
+                return Err(StatusCode::BAD_REQUEST);
+            }
+        }
+        if let Some(plugin) = extract_plugin_name(item) {
+            if !is_plugin_registered(plugin) {
+                warn!("Rejected collection item using unknown plugin {}", plugin);
+                return Err(StatusCode::BAD_REQUEST);
+            }
+        }
+
+        let taken = display
+            .playlist
+            .items
+            .iter()
+            .any(|other| other.id == item.id)
+            || display
+                .collections()
+                .iter()
+                .any(|other| other.name != collection.name && other.has_item(&item.id));
+        if taken {
+            warn!(
+                "Rejected collection item {}: its ID is already used",
+                item.id
+            );
+            return Err(StatusCode::CONFLICT);
+        }
+    }
+    Ok(())
+}
+
+fn save_collections(display: &DisplayManager, storage: &AppStorage) {
+    if !storage.save_collections(display.collections()) {
+        error!("Failed to save collections");
+    }
+}
+
+// Handler for listing all collections
+pub async fn get_collections(
+    State(combined_state): State<CombinedState>,
+) -> Json<Vec<CollectionStatus>> {
+    debug!("Getting all collections");
+    let ((display, _), _) = combined_state;
+    let display = display.lock().await;
+    Json(display.collections().iter().map(Into::into).collect())
+}
+
+// Handler for a single collection
+pub async fn get_collection(
+    State(combined_state): State<CombinedState>,
+    Path(name): Path<String>,
+) -> Result<Json<CollectionStatus>, StatusCode> {
+    let ((display, _), _) = combined_state;
+    let display = display.lock().await;
+    display
+        .collections()
+        .iter()
+        .find(|collection| collection.name == name)
+        .map(|collection| Json(collection.into()))
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+// Handler for creating a collection
+pub async fn create_collection(
+    State(combined_state): State<CombinedState>,
+    Json(collection): Json<Collection>,
+) -> Result<(StatusCode, Json<CollectionStatus>), StatusCode> {
+    let ((display, storage), _) = combined_state;
+    let mut display_guard = display.lock().await;
+    let storage_guard = storage.lock();
+
+    if display_guard
+        .collections()
+        .iter()
+        .any(|other| other.name == collection.name)
+    {
+        warn!("Collection '{}' already exists", collection.name);
+        return Err(StatusCode::CONFLICT);
+    }
+    check_collection(&display_guard, &storage_guard, &collection)?;
+
+    info!(
+        "Created collection '{}' with {} item(s)",
+        collection.name,
+        collection.items.len()
+    );
+    let status = CollectionStatus::from(&collection);
+    let mut collections = display_guard.collections().to_vec();
+    collections.push(collection);
+    display_guard.set_collections(collections);
+    save_collections(&display_guard, &storage_guard);
+
+    Ok((StatusCode::CREATED, Json(status)))
+}
+
+// Handler for replacing a collection, the name in the path wins over the one in the body
+pub async fn update_collection(
+    State(combined_state): State<CombinedState>,
+    Path(name): Path<String>,
+    Json(mut collection): Json<Collection>,
+) -> Result<Json<CollectionStatus>, StatusCode> {
+    let ((display, storage), _) = combined_state;
+    let mut display_guard = display.lock().await;
+    let storage_guard = storage.lock();
+
+    let index = display_guard
+        .collections()
+        .iter()
+        .position(|other| other.name == name)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    collection.name = name;
+    check_collection(&display_guard, &storage_guard, &collection)?;
+
+    info!("Updated collection '{}'", collection.name);
+    let status = CollectionStatus::from(&collection);
+    let mut collections = display_guard.collections().to_vec();
+    collections[index] = collection;
+    display_guard.set_collections(collections);
+    save_collections(&display_guard, &storage_guard);
+
+    Ok(Json(status))
+}
+
+// Handler for deleting a collection
+pub async fn delete_collection(
+    State(combined_state): State<CombinedState>,
+    Path(name): Path<String>,
+) -> StatusCode {
+    let ((display, storage), _) = combined_state;
+    let mut display_guard = display.lock().await;
+
+    let mut collections = display_guard.collections().to_vec();
+    let before = collections.len();
+    collections.retain(|collection| collection.name != name);
+    if collections.len() == before {
+        return StatusCode::NOT_FOUND;
+    }
+
+    info!("Deleted collection '{}'", name);
+    display_guard.set_collections(collections);
+    save_collections(&display_guard, &storage.lock());
+    StatusCode::NO_CONTENT
+}
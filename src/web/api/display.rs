@@ -1,12 +1,37 @@
-use axum::{extract::State, Json};
-use serde::Serialize;
+use axum::{
+    extract::{Multipart, State},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use bytes::Bytes;
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
+use crate::display::brightness_mask::BrightnessMask;
+use crate::display::color_order::{ColorChannel, ColorOrderDetection, DetectionStep};
+use crate::display::recorder::encode_gif;
+use crate::display::renderer::registry::registered_content_types;
+use crate::models::settings::DeadPixel;
 use crate::web::api::CombinedState;
 
+// Most dead pixels that can be registered
+const MAX_DEAD_PIXELS: usize = 1024;
+
+// Longest recording and largest pixel scale of recorded GIFs
+const MAX_RECORD_SECONDS: u32 = 30;
+const MAX_RECORD_SCALE: u32 = 8;
+
+// Extra time allowed for the display loop to hand over a finished recording
+const RECORD_GRACE: Duration = Duration::from_secs(5);
+
 #[derive(Serialize)]
 pub struct DisplayInfoResponse {
     pub width: i32,
     pub height: i32,
+    /// Content type strings that have a renderer, including loaded plugins
+    pub content_types: Vec<String>,
 }
 
 pub async fn get_display_info(
@@ -17,5 +42,296 @@ pub async fn get_display_info(
     Json(DisplayInfoResponse {
         width: display_guard.display_width,
         height: display_guard.display_height,
+        content_types: registered_content_types(),
     })
 }
+
+#[derive(Serialize)]
+pub struct ColorOrderDetectionResponse {
+    pub complete: bool,
+    /// Test color currently shown while detection is running
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub showing: Option<ColorChannel>,
+    /// Sequences still consistent with the answers so far
+    pub candidates: Vec<String>,
+    /// Detected sequence, once complete
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub led_sequence: Option<String>,
+    /// Whether the detected sequence differs from the one the driver is running with
+    pub restart_required: bool,
+}
+
+impl ColorOrderDetectionResponse {
+    fn in_progress(detection: &ColorOrderDetection) -> Self {
+        Self {
+            complete: false,
+            showing: Some(detection.current_color()),
+            candidates: detection.candidates(),
+            led_sequence: None,
+            restart_required: false,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ColorOrderReport {
+    pub seen: ColorChannel,
+}
+
+// Handler for starting color-order detection
+pub async fn start_color_order_detection(
+    State(combined_state): State<CombinedState>,
+) -> Json<ColorOrderDetectionResponse> {
+    let ((display, _storage), _events) = combined_state;
+    let mut display_guard = display.lock().await;
+    let detection = display_guard.start_color_order_detection();
+    Json(ColorOrderDetectionResponse::in_progress(detection))
+}
+
+// Handler for reporting the color the user sees during detection
+pub async fn report_color_order(
+    State(combined_state): State<CombinedState>,
+    Json(report): Json<ColorOrderReport>,
+) -> Result<Json<ColorOrderDetectionResponse>, StatusCode> {
+    let ((display, storage), _events) = combined_state;
+    let mut display_guard = display.lock().await;
+
+    let step = display_guard
+        .report_color_order(report.seen)
+        .ok_or(StatusCode::CONFLICT)?;
+
+    match step {
+        DetectionStep::Continue => {
+            let detection = display_guard
+                .color_order_detection()
+                .ok_or(StatusCode::CONFLICT)?;
+            Ok(Json(ColorOrderDetectionResponse::in_progress(detection)))
+        }
+        DetectionStep::Inconsistent => {
+            warn!("Color-order detection failed: answers do not match any sequence");
+            Err(StatusCode::UNPROCESSABLE_ENTITY)
+        }
+        DetectionStep::Detected(sequence) => {
+            info!("Detected LED color sequence: {}", sequence);
+
+            let storage_guard = storage.lock();
+            let mut config = storage_guard.load_config().unwrap_or_default();
+            config.led_sequence = Some(sequence.clone());
+            if !storage_guard.save_config(&config) {
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+
+            Ok(Json(ColorOrderDetectionResponse {
+                complete: true,
+                showing: None,
+                candidates: vec![sequence.clone()],
+                restart_required: !sequence.eq_ignore_ascii_case(display_guard.led_sequence()),
+                led_sequence: Some(sequence),
+            }))
+        }
+    }
+}
+
+// Handler for cancelling color-order detection
+pub async fn cancel_color_order_detection(
+    State(combined_state): State<CombinedState>,
+) -> StatusCode {
+    let ((display, _storage), _events) = combined_state;
+    let mut display_guard = display.lock().await;
+    if display_guard.cancel_color_order_detection() {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
+
+// Handler for downloading the brightness mask as uploaded
+pub async fn get_brightness_mask(
+    State(combined_state): State<CombinedState>,
+) -> Result<Response, StatusCode> {
+    let ((_display, storage), _events) = combined_state;
+    let bytes = storage
+        .lock()
+        .load_brightness_mask()
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let headers = [(header::CONTENT_TYPE, HeaderValue::from_static("image/png"))];
+    Ok((headers, Bytes::from(bytes)).into_response())
+}
+
+// Handler for uploading a grayscale brightness mask (multipart `file` field)
+pub async fn upload_brightness_mask(
+    State(combined_state): State<CombinedState>,
+    mut multipart: Multipart,
+) -> StatusCode {
+    let ((display, storage), _events) = combined_state;
+    let (max_bytes, width, height) = {
+        let display_guard = display.lock().await;
+        (
+            display_guard.config().max_image_bytes(),
+            display_guard.display_width,
+            display_guard.display_height,
+        )
+    };
+
+    let mut data = Vec::new();
+    while let Ok(Some(mut field)) = multipart.next_field().await {
+        if field.name() != Some("file") {
+            continue;
+        }
+        while let Ok(Some(chunk)) = field.chunk().await {
+            data.extend_from_slice(&chunk);
+            if data.len() > max_bytes {
+                return StatusCode::PAYLOAD_TOO_LARGE;
+            }
+        }
+        break;
+    }
+    if data.is_empty() {
+        return StatusCode::BAD_REQUEST;
+    }
+
+    let decoded = {
+        let data = data.clone();
+        tokio::task::spawn_blocking(move || BrightnessMask::from_image(&data, width, height)).await
+    };
+    let mask = match decoded {
+        Ok(Ok(mask)) => mask,
+        Ok(Err(e)) => {
+            warn!("Rejected brightness mask: {}", e);
+            return StatusCode::BAD_REQUEST;
+        }
+        Err(e) => {
+            error!("Decoding the brightness mask failed: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR;
+        }
+    };
+
+    if !storage.lock().save_brightness_mask(&data) {
+        return StatusCode::INTERNAL_SERVER_ERROR;
+    }
+    display.lock().await.set_brightness_mask(Some(mask));
+    info!("Brightness mask applied");
+    StatusCode::NO_CONTENT
+}
+
+// Handler for removing the brightness mask
+pub async fn delete_brightness_mask(State(combined_state): State<CombinedState>) -> StatusCode {
+    let ((display, storage), _events) = combined_state;
+    let mut display_guard = display.lock().await;
+    if !display_guard.has_brightness_mask() {
+        return StatusCode::NOT_FOUND;
+    }
+    if !storage.lock().delete_brightness_mask() {
+        return StatusCode::INTERNAL_SERVER_ERROR;
+    }
+    display_guard.set_brightness_mask(None);
+    info!("Brightness mask removed");
+    StatusCode::NO_CONTENT
+}
+
+// Handler for listing the registered dead or stuck pixels
+pub async fn get_dead_pixels(State(combined_state): State<CombinedState>) -> Json<Vec<DeadPixel>> {
+    let ((display, _storage), _events) = combined_state;
+    let display_guard = display.lock().await;
+    Json(display_guard.dead_pixels().to_vec())
+}
+
+// Handler for replacing the registered dead or stuck pixels, an empty list clears them
+pub async fn update_dead_pixels(
+    State(combined_state): State<CombinedState>,
+    Json(pixels): Json<Vec<DeadPixel>>,
+) -> Result<Json<Vec<DeadPixel>>, StatusCode> {
+    let ((display, storage), _events) = combined_state;
+    let mut display_guard = display.lock().await;
+
+    let (width, height) = (display_guard.display_width, display_guard.display_height);
+    if pixels.len() > MAX_DEAD_PIXELS {
+        warn!(
+            "Rejected {} dead pixels, at most {} are allowed",
+            pixels.len(),
+            MAX_DEAD_PIXELS
+        );
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if let Some(pixel) = pixels
+        .iter()
+        .find(|pixel| !(0..width).contains(&pixel.x) || !(0..height).contains(&pixel.y))
+    {
+        warn!(
+            "Rejected dead pixel ({}, {}) outside the panel",
+            pixel.x, pixel.y
+        );
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    // A pixel listed twice keeps its last fix
+    let mut unique: Vec<DeadPixel> = Vec::with_capacity(pixels.len());
+    for pixel in pixels {
+        unique.retain(|known| (known.x, known.y) != (pixel.x, pixel.y));
+        unique.push(pixel);
+    }
+
+    if !storage.lock().save_dead_pixels(&unique) {
+        error!("Failed to save dead pixels");
+    }
+    info!("{} dead pixels registered", unique.len());
+    display_guard.set_dead_pixels(unique.clone());
+    Ok(Json(unique))
+}
+
+fn default_record_scale() -> u32 {
+    1
+}
+
+#[derive(Deserialize)]
+pub struct RecordRequest {
+    pub seconds: u32,
+    /// Size of each panel pixel in the GIF, in GIF pixels
+    #[serde(default = "default_record_scale")]
+    pub scale: u32,
+}
+
+// Handler for recording what the panel shows as an animated GIF, answers once it is done
+pub async fn record_display(
+    State(combined_state): State<CombinedState>,
+    Json(request): Json<RecordRequest>,
+) -> Result<Response, StatusCode> {
+    if !(1..=MAX_RECORD_SECONDS).contains(&request.seconds)
+        || !(1..=MAX_RECORD_SCALE).contains(&request.scale)
+    {
+        warn!(
+            "Rejected recording of {} seconds at scale {}",
+            request.seconds, request.scale
+        );
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let ((display, _storage), _events) = combined_state;
+    let duration = Duration::from_secs(request.seconds as u64);
+    let receiver = display
+        .lock()
+        .await
+        .start_recording(duration)
+        .ok_or(StatusCode::CONFLICT)?;
+    info!("Recording the display for {} seconds", request.seconds);
+
+    let recording = match tokio::time::timeout(duration + RECORD_GRACE, receiver).await {
+        Ok(Ok(recording)) if !recording.frames.is_empty() => recording,
+        _ => {
+            error!("Recording the display failed: no frames were captured");
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    let scale = request.scale;
+    let gif = tokio::task::spawn_blocking(move || encode_gif(&recording, scale))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .map_err(|e| {
+            error!("Encoding the recording failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let headers = [(header::CONTENT_TYPE, HeaderValue::from_static("image/gif"))];
+    Ok((headers, Bytes::from(gif)).into_response())
+}
@@ -1,15 +1,23 @@
+use crate::display::manager::DisplayManager;
 use crate::models::playlist::PlayListItem;
 use crate::models::settings::BrightnessSettings;
 use crate::web::api::CombinedState;
 use axum::{
-    extract::State,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
     response::{sse::Event, Sse},
+    Json,
 };
+use chrono::{DateTime, Utc};
 use futures::stream::{self, Stream};
+use log::debug;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, Mutex};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
 use std::time::Duration;
-use tokio::sync::broadcast::{self, Sender};
+use tokio::sync::broadcast::{self, error::RecvError, Receiver, Sender};
 use tokio_stream::StreamExt as _;
 
 // Define event types for editor lock
@@ -27,6 +35,35 @@ pub struct PlaylistUpdateEvent {
     pub action: PlaylistAction,
 }
 
+// Problems detected while playing the playlist, e.g. an item skipped by the watchdog
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WarningEvent {
+    pub code: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item_id: Option<String>,
+}
+
+// Variable set or removed through /api/vars, `value` is None when it was removed
+#[derive(Clone, Serialize, Deserialize)]
+pub struct VariableEvent {
+    pub name: String,
+    pub value: Option<String>,
+}
+
+// Palette saved or removed through /api/palettes, `colors` is None when it was removed
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PaletteEvent {
+    pub name: String,
+    pub colors: Option<Vec<[u8; 3]>>,
+}
+
+// Panel switched on or off, through the API, the simple state endpoint or a button
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PowerEvent {
+    pub on: bool,
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub enum PlaylistAction {
     Add,
@@ -35,84 +72,412 @@ pub enum PlaylistAction {
     Reorder,
 }
 
+// Progress of the active playlist item, sent on transitions and periodically while playing
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NowPlayingEvent {
+    pub item_id: Option<String>,
+    pub index: Option<usize>,
+    pub elapsed_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining_ms: Option<u64>,
+    // Fraction (0-1) of the item completed, also known for items timed by repeat_count
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<f32>,
+    pub transition: bool,
+}
+
+impl NowPlayingEvent {
+    pub fn from_display(display: &DisplayManager, transition: bool) -> Self {
+        let Some(item) = display.active_item() else {
+            // Empty playlist - the welcome screen is showing
+            return Self {
+                item_id: None,
+                index: None,
+                elapsed_ms: 0,
+                duration_ms: None,
+                remaining_ms: None,
+                progress: None,
+                transition,
+            };
+        };
+
+        let elapsed_ms = display.active_item_elapsed().as_millis() as u64;
+        // Only duration based items have a known end, repeat counts depend on the renderer
+        let duration_ms = item.duration.map(|seconds| seconds * 1000);
+
+        Self {
+            item_id: Some(item.id.clone()),
+            index: Some(display.playlist.active_index),
+            elapsed_ms,
+            duration_ms,
+            remaining_ms: duration_ms.map(|duration| duration.saturating_sub(elapsed_ms)),
+            progress: display.active_progress(),
+            transition,
+        }
+    }
+}
+
+// Event tagged with the sequence number used as the SSE event ID
+#[derive(Clone)]
+pub struct SequencedEvent<T> {
+    pub id: u64,
+    pub data: T,
+}
+
+// Broadcast channel that remembers its most recent events so reconnecting
+// clients can catch up from their Last-Event-ID
+pub struct EventChannel<T: Clone> {
+    tx: Sender<SequencedEvent<T>>,
+    history: VecDeque<SequencedEvent<T>>,
+    history_len: usize,
+}
+
+impl<T: Clone> EventChannel<T> {
+    fn new(history_len: usize, capacity: usize) -> Self {
+        let (tx, _) = broadcast::channel(capacity);
+        Self {
+            tx,
+            history: VecDeque::with_capacity(history_len),
+            history_len,
+        }
+    }
+
+    fn send(&mut self, id: u64, data: T) {
+        let event = SequencedEvent { id, data };
+        if self.history.len() == self.history_len {
+            self.history.pop_front();
+        }
+        self.history.push_back(event.clone());
+        let _ = self.tx.send(event);
+    }
+
+    // Subscribe to live events, returning the buffered events newer than `last_event_id` to replay first
+    pub fn subscribe(
+        &self,
+        last_event_id: Option<u64>,
+    ) -> (Vec<SequencedEvent<T>>, Receiver<SequencedEvent<T>>) {
+        let replay = match last_event_id {
+            Some(last_id) => self
+                .history
+                .iter()
+                .filter(|event| event.id > last_id)
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+        (replay, self.tx.subscribe())
+    }
+}
+
+// Number of events kept per channel for Last-Event-ID replay
+const HISTORY_LEN: usize = 32;
+
+// Events a client may fall behind on a channel before it misses some and is told to resync.
+// The channels are bounded, so a stalled client never makes the controller buffer more.
+const CHANNEL_CAPACITY: usize = 100;
+
+// Progress is sent several times per second, only a few are worth queueing
+const NOW_PLAYING_CAPACITY: usize = 16;
+
+// Counters of one open event stream, shared with the stream and dropped when it closes
+pub struct EventClient {
+    id: u64,
+    endpoint: &'static str,
+    connected_at: DateTime<Utc>,
+    events_sent: AtomicU64,
+    events_dropped: AtomicU64,
+    resyncs: AtomicU64,
+}
+
+impl EventClient {
+    fn sent(&self, count: usize) {
+        self.events_sent.fetch_add(count as u64, Ordering::Relaxed);
+    }
+
+    fn lagged(&self, dropped: u64) {
+        self.events_dropped.fetch_add(dropped, Ordering::Relaxed);
+        self.resyncs.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[derive(Serialize)]
+pub struct EventClientStatus {
+    pub id: u64,
+    pub endpoint: &'static str,
+    pub connected_at: String,
+    pub events_sent: u64,
+    pub events_dropped: u64,
+    pub resyncs: u64,
+}
+
 // Singleton for managing all event types
 pub struct EventState {
-    brightness_tx: Sender<BrightnessSettings>,
-    editor_lock_tx: Sender<EditorLockEvent>,
-    playlist_tx: Sender<PlaylistUpdateEvent>,
+    // Shared across channels so one Last-Event-ID is meaningful for every stream
+    next_event_id: u64,
+    brightness: EventChannel<BrightnessSettings>,
+    editor_lock: EventChannel<EditorLockEvent>,
+    playlist: EventChannel<PlaylistUpdateEvent>,
+    now_playing: EventChannel<NowPlayingEvent>,
+    warning: EventChannel<WarningEvent>,
+    variable: EventChannel<VariableEvent>,
+    palette: EventChannel<PaletteEvent>,
+    power: EventChannel<PowerEvent>,
+    // Open event streams, gone once their stream is dropped
+    clients: Vec<Weak<EventClient>>,
+    next_client_id: u64,
 }
 
 impl EventState {
     pub fn new() -> Arc<Mutex<Self>> {
-        let (brightness_tx, _) = broadcast::channel(100);
-        let (editor_lock_tx, _) = broadcast::channel(100);
-        let (playlist_tx, _) = broadcast::channel(100);
-
         Arc::new(Mutex::new(Self {
-            brightness_tx,
-            editor_lock_tx,
-            playlist_tx,
+            next_event_id: 1,
+            brightness: EventChannel::new(HISTORY_LEN, CHANNEL_CAPACITY),
+            editor_lock: EventChannel::new(HISTORY_LEN, CHANNEL_CAPACITY),
+            playlist: EventChannel::new(HISTORY_LEN, CHANNEL_CAPACITY),
+            // Only the latest progress is worth replaying
+            now_playing: EventChannel::new(1, NOW_PLAYING_CAPACITY),
+            warning: EventChannel::new(HISTORY_LEN, CHANNEL_CAPACITY),
+            variable: EventChannel::new(HISTORY_LEN, CHANNEL_CAPACITY),
+            palette: EventChannel::new(HISTORY_LEN, CHANNEL_CAPACITY),
+            power: EventChannel::new(HISTORY_LEN, CHANNEL_CAPACITY),
+            clients: Vec::new(),
+            next_client_id: 1,
         }))
     }
 
-    pub fn get_brightness_sender(&self) -> Sender<BrightnessSettings> {
-        self.brightness_tx.clone()
+    // Register a new event stream of `endpoint`, counted while the returned handle lives
+    fn connect_client(&mut self, endpoint: &'static str) -> Arc<EventClient> {
+        let client = Arc::new(EventClient {
+            id: self.next_client_id,
+            endpoint,
+            connected_at: Utc::now(),
+            events_sent: AtomicU64::new(0),
+            events_dropped: AtomicU64::new(0),
+            resyncs: AtomicU64::new(0),
+        });
+        self.next_client_id += 1;
+        self.clients.retain(|client| client.strong_count() > 0);
+        self.clients.push(Arc::downgrade(&client));
+        client
+    }
+
+    // Counters of the event streams currently open
+    pub fn client_status(&self) -> Vec<EventClientStatus> {
+        self.clients
+            .iter()
+            .filter_map(Weak::upgrade)
+            .map(|client| EventClientStatus {
+                id: client.id,
+                endpoint: client.endpoint,
+                connected_at: client.connected_at.to_rfc3339(),
+                events_sent: client.events_sent.load(Ordering::Relaxed),
+                events_dropped: client.events_dropped.load(Ordering::Relaxed),
+                resyncs: client.resyncs.load(Ordering::Relaxed),
+            })
+            .collect()
     }
 
-    pub fn broadcast_brightness(&self, brightness: BrightnessSettings) {
-        let _ = self.brightness_tx.send(brightness);
+    fn take_event_id(&mut self) -> u64 {
+        let id = self.next_event_id;
+        self.next_event_id += 1;
+        id
     }
 
-    pub fn get_editor_lock_sender(&self) -> Sender<EditorLockEvent> {
-        self.editor_lock_tx.clone()
+    // Translate a client's Last-Event-ID, ignoring IDs from before a restart
+    fn resume_from(&self, last_event_id: Option<u64>) -> Option<u64> {
+        last_event_id.filter(|id| *id < self.next_event_id)
     }
 
-    pub fn broadcast_editor_lock(&self, is_locked: bool, locked_by: Option<String>) {
+    pub fn brightness_channel(&self) -> &EventChannel<BrightnessSettings> {
+        &self.brightness
+    }
+
+    pub fn broadcast_brightness(&mut self, brightness: BrightnessSettings) {
+        let id = self.take_event_id();
+        self.brightness.send(id, brightness);
+    }
+
+    pub fn editor_lock_channel(&self) -> &EventChannel<EditorLockEvent> {
+        &self.editor_lock
+    }
+
+    pub fn broadcast_editor_lock(&mut self, is_locked: bool, locked_by: Option<String>) {
         let event = EditorLockEvent {
             locked: is_locked,
             locked_by,
         };
-        let _ = self.editor_lock_tx.send(event);
+        let id = self.take_event_id();
+        self.editor_lock.send(id, event);
     }
 
-    pub fn get_playlist_sender(&self) -> Sender<PlaylistUpdateEvent> {
-        self.playlist_tx.clone()
+    pub fn playlist_channel(&self) -> &EventChannel<PlaylistUpdateEvent> {
+        &self.playlist
     }
 
-    pub fn broadcast_playlist_update(&self, items: Vec<PlayListItem>, action: PlaylistAction) {
+    pub fn broadcast_playlist_update(&mut self, items: Vec<PlayListItem>, action: PlaylistAction) {
         let event = PlaylistUpdateEvent { items, action };
-        let _ = self.playlist_tx.send(event);
+        let id = self.take_event_id();
+        self.playlist.send(id, event);
+    }
+
+    pub fn now_playing_channel(&self) -> &EventChannel<NowPlayingEvent> {
+        &self.now_playing
+    }
+
+    pub fn broadcast_now_playing(&mut self, event: NowPlayingEvent) {
+        let id = self.take_event_id();
+        self.now_playing.send(id, event);
+    }
+
+    pub fn warning_channel(&self) -> &EventChannel<WarningEvent> {
+        &self.warning
+    }
+
+    pub fn broadcast_warning(&mut self, event: WarningEvent) {
+        let id = self.take_event_id();
+        self.warning.send(id, event);
+    }
+
+    pub fn variable_channel(&self) -> &EventChannel<VariableEvent> {
+        &self.variable
+    }
+
+    pub fn broadcast_variable(&mut self, name: String, value: Option<String>) {
+        let id = self.take_event_id();
+        self.variable.send(id, VariableEvent { name, value });
+    }
+
+    pub fn palette_channel(&self) -> &EventChannel<PaletteEvent> {
+        &self.palette
+    }
+
+    pub fn broadcast_palette(&mut self, name: String, colors: Option<Vec<[u8; 3]>>) {
+        let id = self.take_event_id();
+        self.palette.send(id, PaletteEvent { name, colors });
+    }
+
+    pub fn power_channel(&self) -> &EventChannel<PowerEvent> {
+        &self.power
+    }
+
+    pub fn broadcast_power(&mut self, on: bool) {
+        let id = self.take_event_id();
+        self.power.send(id, PowerEvent { on });
     }
 }
 
 pub type SharedEventState = Arc<Mutex<EventState>>;
 
-// Handler for brightness SSE events
-pub async fn brightness_events(
-    State(combined_state): State<CombinedState>,
-) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
-    let brightness_rx = {
-        let (_, event_state) = &combined_state;
-        let event_state = event_state.lock().unwrap();
-        event_state.get_brightness_sender().subscribe()
-    };
+// Parse the Last-Event-ID header browsers send when an EventSource reconnects
+fn last_event_id(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+}
 
-    let stream = stream::unfold(brightness_rx, |mut rx| async move {
+// How a channel's events are written to the SSE stream
+#[derive(Clone, Copy)]
+enum EventEncoding {
+    // Unnamed event with the raw JSON payload
+    Plain,
+    // Named SSE event with the raw JSON payload
+    Named(&'static str),
+    // Unnamed event with a `{"type": ..., "data": ...}` envelope, used by `/api/events`
+    Tagged(&'static str),
+}
+
+fn encode_event<T: Serialize>(event: &SequencedEvent<T>, encoding: EventEncoding) -> Event {
+    let sse_event = Event::default().id(event.id.to_string());
+    match encoding {
+        EventEncoding::Plain => sse_event.data(serde_json::to_string(&event.data).unwrap()),
+        EventEncoding::Named(name) => sse_event
+            .event(name)
+            .data(serde_json::to_string(&event.data).unwrap()),
+        EventEncoding::Tagged(tag) => {
+            let envelope = serde_json::json!({ "type": tag, "data": &event.data });
+            sse_event.data(envelope.to_string())
+        }
+    }
+}
+
+// Encode the buffered events of a subscription, keeping their IDs for ordering
+fn replay_events<T: Serialize>(
+    replay: &[SequencedEvent<T>],
+    encoding: EventEncoding,
+) -> Vec<(u64, Event)> {
+    replay
+        .iter()
+        .map(|event| (event.id, encode_event(event, encoding)))
+        .collect()
+}
+
+// Event telling a client it missed `dropped` events and should fetch the current state again
+fn resync_event(dropped: u64, encoding: EventEncoding) -> Event {
+    let data = serde_json::json!({ "dropped": dropped });
+    match encoding {
+        EventEncoding::Plain | EventEncoding::Named(_) => {
+            Event::default().event("resync").data(data.to_string())
+        }
+        EventEncoding::Tagged(_) => {
+            Event::default().data(serde_json::json!({ "type": "resync", "data": data }).to_string())
+        }
+    }
+}
+
+// Stream live events from a channel receiver. A client reading slower than events are sent
+// falls behind until the channel drops its oldest events; it then gets a resync event
+// instead of the missed ones and continues with the newest.
+fn live_events<T>(
+    rx: Receiver<SequencedEvent<T>>,
+    encoding: EventEncoding,
+    client: Arc<EventClient>,
+) -> impl Stream<Item = Result<Event, axum::Error>> + Send + 'static
+where
+    T: Clone + Serialize + Send + 'static,
+{
+    stream::unfold((rx, client), move |(mut rx, client)| async move {
         match rx.recv().await {
-            Ok(brightness) => {
-                let payload = serde_json::to_string(&brightness).unwrap();
-                let event = Event::default().data(payload);
-                Some((Ok(event), rx))
+            Ok(event) => {
+                client.sent(1);
+                Some((Ok(encode_event(&event, encoding)), (rx, client)))
             }
-            Err(_) => {
-                // Keep connection alive with a comment
-                let event = Event::default().event("ping").data("");
-                Some((Ok(event), rx))
+            Err(RecvError::Lagged(dropped)) => {
+                debug!(
+                    "Event client {} ({}) fell behind, dropped {} event(s)",
+                    client.id, client.endpoint, dropped
+                );
+                client.lagged(dropped);
+                Some((Ok(resync_event(dropped, encoding)), (rx, client)))
             }
+            // Only happens on shutdown, when the event state is dropped
+            Err(RecvError::Closed) => None,
         }
-    });
+    })
+}
 
-    // Add keepalive logic
+// Turn a channel subscription into SSE events: buffered events first, then live ones
+fn channel_stream<T>(
+    (replay, rx): (Vec<SequencedEvent<T>>, Receiver<SequencedEvent<T>>),
+    encoding: EventEncoding,
+    client: &Arc<EventClient>,
+) -> impl Stream<Item = Result<Event, axum::Error>> + Send + 'static
+where
+    T: Clone + Serialize + Send + 'static,
+{
+    client.sent(replay.len());
+    let replayed = replay_events(&replay, encoding)
+        .into_iter()
+        .map(|(_, event)| Ok(event));
+    stream::iter(replayed).chain(live_events(rx, encoding, client.clone()))
+}
+
+// Add keepalive logic shared by all SSE endpoints
+fn with_keepalive(
+    stream: impl Stream<Item = Result<Event, axum::Error>> + Send + 'static,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
     let keepalive = stream::repeat_with(|| Event::default().event("ping").data(""))
         .map(Ok)
         .throttle(Duration::from_secs(30));
@@ -124,76 +489,227 @@ pub async fn brightness_events(
     )
 }
 
+// Handler for brightness SSE events. Brightness changes are sent as unnamed events, the
+// panel being switched on or off as `power` events.
+pub async fn brightness_events(
+    State(combined_state): State<CombinedState>,
+    headers: HeaderMap,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let (subscription, power_subscription, client) = {
+        let (_, event_state) = &combined_state;
+        let mut event_state = event_state.lock();
+        let resume_from = event_state.resume_from(last_event_id(&headers));
+        (
+            event_state.brightness_channel().subscribe(resume_from),
+            event_state.power_channel().subscribe(resume_from),
+            event_state.connect_client("brightness"),
+        )
+    };
+
+    with_keepalive(
+        channel_stream(subscription, EventEncoding::Plain, &client).merge(channel_stream(
+            power_subscription,
+            EventEncoding::Named("power"),
+            &client,
+        )),
+    )
+}
+
 // Handler for editor lock SSE events
 pub async fn editor_lock_events(
     State(combined_state): State<CombinedState>,
+    headers: HeaderMap,
 ) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
-    let lock_rx = {
+    let (subscription, client) = {
         let (_, event_state) = &combined_state;
-        let event_state = event_state.lock().unwrap();
-        event_state.get_editor_lock_sender().subscribe()
+        let mut event_state = event_state.lock();
+        let resume_from = event_state.resume_from(last_event_id(&headers));
+        (
+            event_state.editor_lock_channel().subscribe(resume_from),
+            event_state.connect_client("editor"),
+        )
     };
 
-    let stream = stream::unfold(lock_rx, |mut rx| async move {
-        match rx.recv().await {
-            Ok(lock_event) => {
-                let payload = serde_json::to_string(&lock_event).unwrap();
-                let event = Event::default().data(payload);
-                Some((Ok(event), rx))
-            }
-            Err(_) => {
-                // Keep connection alive with a comment
-                let event = Event::default().event("ping").data("");
-                Some((Ok(event), rx))
-            }
+    with_keepalive(channel_stream(subscription, EventEncoding::Plain, &client))
+}
+
+// Handler for playlist update SSE events.
+// Playlist changes are sent as unnamed events, playback progress as `now_playing` events
+// and playback problems as `warning` events.
+pub async fn playlist_events(
+    State(combined_state): State<CombinedState>,
+    headers: HeaderMap,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let (playlist_subscription, now_playing_subscription, warning_subscription, client) = {
+        let (_, event_state) = &combined_state;
+        let mut event_state = event_state.lock();
+        let resume_from = event_state.resume_from(last_event_id(&headers));
+        (
+            event_state.playlist_channel().subscribe(resume_from),
+            event_state.now_playing_channel().subscribe(resume_from),
+            event_state.warning_channel().subscribe(resume_from),
+            event_state.connect_client("playlist"),
+        )
+    };
+
+    with_keepalive(
+        channel_stream(playlist_subscription, EventEncoding::Plain, &client)
+            .merge(channel_stream(
+                now_playing_subscription,
+                EventEncoding::Named("now_playing"),
+                &client,
+            ))
+            .merge(channel_stream(
+                warning_subscription,
+                EventEncoding::Named("warning"),
+                &client,
+            )),
+    )
+}
+
+#[derive(Deserialize)]
+pub struct PreviewEventsQuery {
+    pub session_id: String,
+}
+
+// Handler for the event stream of a preview session, carrying editor lock events. The session
+// stays alive while the stream is open, so editors holding it do not need to ping.
+pub async fn preview_events(
+    State(combined_state): State<CombinedState>,
+    Query(query): Query<PreviewEventsQuery>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, StatusCode> {
+    let ((display, _), event_state) = &combined_state;
+    let connection = {
+        let display = display.lock().await;
+        if !display.is_in_preview_mode() {
+            return Err(StatusCode::NOT_FOUND);
         }
+        display
+            .connect_preview(&query.session_id)
+            .ok_or(StatusCode::FORBIDDEN)?
+    };
+
+    let (subscription, client) = {
+        let mut event_state = event_state.lock();
+        let resume_from = event_state.resume_from(last_event_id(&headers));
+        (
+            event_state.editor_lock_channel().subscribe(resume_from),
+            event_state.connect_client("preview"),
+        )
+    };
+
+    // The connection is dropped together with the stream when the client disconnects
+    let stream = channel_stream(subscription, EventEncoding::Plain, &client).map(move |event| {
+        let _ = &connection;
+        event
     });
+    Ok(with_keepalive(stream))
+}
 
-    // Add keepalive logic
-    let keepalive = stream::repeat_with(|| Event::default().event("ping").data(""))
-        .map(Ok)
-        .throttle(Duration::from_secs(30));
+// Handler for variable change SSE events
+pub async fn variable_events(
+    State(combined_state): State<CombinedState>,
+    headers: HeaderMap,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let (subscription, client) = {
+        let (_, event_state) = &combined_state;
+        let mut event_state = event_state.lock();
+        let resume_from = event_state.resume_from(last_event_id(&headers));
+        (
+            event_state.variable_channel().subscribe(resume_from),
+            event_state.connect_client("vars"),
+        )
+    };
 
-    Sse::new(stream.merge(keepalive)).keep_alive(
-        axum::response::sse::KeepAlive::new()
-            .interval(Duration::from_secs(15))
-            .text("keep-alive-text"),
-    )
+    with_keepalive(channel_stream(subscription, EventEncoding::Plain, &client))
 }
 
-// Handler for playlist update SSE events
-pub async fn playlist_events(
+// Handler for palette change SSE events
+pub async fn palette_events(
     State(combined_state): State<CombinedState>,
+    headers: HeaderMap,
 ) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
-    let playlist_rx = {
+    let (subscription, client) = {
         let (_, event_state) = &combined_state;
-        let event_state = event_state.lock().unwrap();
-        event_state.get_playlist_sender().subscribe()
+        let mut event_state = event_state.lock();
+        let resume_from = event_state.resume_from(last_event_id(&headers));
+        (
+            event_state.palette_channel().subscribe(resume_from),
+            event_state.connect_client("palettes"),
+        )
     };
 
-    let stream = stream::unfold(playlist_rx, |mut rx| async move {
-        match rx.recv().await {
-            Ok(playlist_event) => {
-                let payload = serde_json::to_string(&playlist_event).unwrap();
-                let event = Event::default().data(payload);
-                Some((Ok(event), rx))
-            }
-            Err(_) => {
-                // Keep connection alive with a comment
-                let event = Event::default().event("ping").data("");
-                Some((Ok(event), rx))
-            }
-        }
-    });
+    with_keepalive(channel_stream(subscription, EventEncoding::Plain, &client))
+}
 
-    // Add keepalive logic
-    let keepalive = stream::repeat_with(|| Event::default().event("ping").data(""))
-        .map(Ok)
-        .throttle(Duration::from_secs(30));
+// Handler for the consolidated SSE stream carrying every channel as tagged events
+pub async fn all_events(
+    State(combined_state): State<CombinedState>,
+    headers: HeaderMap,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let (brightness, editor, playlist, now_playing, warning, variable, palette, power, client) = {
+        let (_, event_state) = &combined_state;
+        let mut event_state = event_state.lock();
+        let resume_from = event_state.resume_from(last_event_id(&headers));
+        (
+            event_state.brightness_channel().subscribe(resume_from),
+            event_state.editor_lock_channel().subscribe(resume_from),
+            event_state.playlist_channel().subscribe(resume_from),
+            event_state.now_playing_channel().subscribe(resume_from),
+            event_state.warning_channel().subscribe(resume_from),
+            event_state.variable_channel().subscribe(resume_from),
+            event_state.palette_channel().subscribe(resume_from),
+            event_state.power_channel().subscribe(resume_from),
+            event_state.connect_client("all"),
+        )
+    };
 
-    Sse::new(stream.merge(keepalive)).keep_alive(
-        axum::response::sse::KeepAlive::new()
-            .interval(Duration::from_secs(15))
-            .text("keep-alive-text"),
-    )
+    const BRIGHTNESS: EventEncoding = EventEncoding::Tagged("brightness");
+    const EDITOR: EventEncoding = EventEncoding::Tagged("editor");
+    const PLAYLIST: EventEncoding = EventEncoding::Tagged("playlist");
+    const NOW_PLAYING: EventEncoding = EventEncoding::Tagged("now_playing");
+    const WARNING: EventEncoding = EventEncoding::Tagged("warning");
+    const VARIABLE: EventEncoding = EventEncoding::Tagged("variable");
+    const PALETTE: EventEncoding = EventEncoding::Tagged("palette");
+    const POWER: EventEncoding = EventEncoding::Tagged("power");
+
+    // Replay missed events from all channels in the order they were sent
+    let mut replay = replay_events(&brightness.0, BRIGHTNESS);
+    replay.extend(replay_events(&editor.0, EDITOR));
+    replay.extend(replay_events(&playlist.0, PLAYLIST));
+    replay.extend(replay_events(&now_playing.0, NOW_PLAYING));
+    replay.extend(replay_events(&warning.0, WARNING));
+    replay.extend(replay_events(&variable.0, VARIABLE));
+    replay.extend(replay_events(&palette.0, PALETTE));
+    replay.extend(replay_events(&power.0, POWER));
+    replay.sort_by_key(|(id, _)| *id);
+    client.sent(replay.len());
+    let replayed = stream::iter(replay.into_iter().map(|(_, event)| Ok(event)));
+
+    let live = live_events(brightness.1, BRIGHTNESS, client.clone())
+        .merge(live_events(editor.1, EDITOR, client.clone()))
+        .merge(live_events(playlist.1, PLAYLIST, client.clone()))
+        .merge(live_events(now_playing.1, NOW_PLAYING, client.clone()))
+        .merge(live_events(warning.1, WARNING, client.clone()))
+        .merge(live_events(variable.1, VARIABLE, client.clone()))
+        .merge(live_events(palette.1, PALETTE, client.clone()))
+        .merge(live_events(power.1, POWER, client));
+
+    with_keepalive(replayed.chain(live))
+}
+
+#[derive(Serialize)]
+pub struct EventClientsResponse {
+    pub clients: Vec<EventClientStatus>,
+}
+
+// Handler for the counters of the open event streams, to spot clients that fall behind
+pub async fn event_clients(
+    State(combined_state): State<CombinedState>,
+) -> Json<EventClientsResponse> {
+    let (_, event_state) = &combined_state;
+    Json(EventClientsResponse {
+        clients: event_state.lock().client_status(),
+    })
 }
@@ -0,0 +1,398 @@
+// Optional GraphQL API (built with the "graphql" feature) over the same state as the REST API.
+// Queries and mutations call the REST handlers so both APIs validate, persist and broadcast
+// changes the same way. Subscriptions expose the SSE event channels.
+
+use crate::config::EffectiveSetting;
+use crate::models::action::Action;
+use crate::models::border_effects::BorderEffect;
+use crate::models::content::ContentDetails;
+use crate::models::panel::PanelSize;
+use crate::models::playlist::{Interstitial, ItemHealth, PlayListItem};
+use crate::models::settings::{
+    BrightnessSettings, IdleSettings, ReorderRequest, TagEnabledRequest,
+};
+use crate::web::api::actions::dispatch;
+use crate::web::api::events::{
+    EditorLockEvent, NowPlayingEvent, PlaylistUpdateEvent, SequencedEvent, WarningEvent,
+};
+use crate::web::api::images::{upload_image_from_url, ImageFromUrlRequest, ImageUploadResponse};
+use crate::web::api::playlist::{
+    create_playlist_item, delete_playlist_item, get_now_playing, get_playlist_item_health,
+    get_playlist_items, reorder_playlist_items, set_tag_enabled, update_playlist_item, ItemJson,
+    ItemsQuery, NowPlayingResponse,
+};
+use crate::web::api::settings::{get_effective_settings, update_brightness, update_idle_settings};
+use crate::web::api::CombinedState;
+use async_graphql::{
+    Context, ErrorExtensions, Json, Object, Schema, SimpleObject, Subscription, ID,
+};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{sse::Event, Sse},
+    routing::post,
+    Router,
+};
+use futures::stream::{self, Stream, StreamExt};
+use std::collections::BTreeMap;
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+
+pub type ApiSchema = Schema<QueryRoot, MutationRoot, SubscriptionRoot>;
+
+// Turn a failed REST handler status into a GraphQL error carrying the status code
+fn status_error(status: StatusCode) -> async_graphql::Error {
+    async_graphql::Error::new(status.canonical_reason().unwrap_or("Request failed"))
+        .extend_with(|_, extensions| extensions.set("status", status.as_u16()))
+}
+
+fn state<'a>(ctx: &Context<'a>) -> CombinedState {
+    ctx.data_unchecked::<CombinedState>().clone()
+}
+
+// Playlist item with its common fields typed and its content as JSON
+pub struct PlaylistItem(PlayListItem);
+
+#[Object]
+impl PlaylistItem {
+    async fn id(&self) -> ID {
+        ID(self.0.id.clone())
+    }
+
+    async fn duration(&self) -> Option<u64> {
+        self.0.duration
+    }
+
+    async fn repeat_count(&self) -> Option<u32> {
+        self.0.repeat_count
+    }
+
+    async fn content_type(&self) -> &str {
+        self.0.content.content_type.as_str()
+    }
+
+    // Content payload as documented for the REST API
+    async fn content(&self) -> Json<&ContentDetails> {
+        Json(&self.0.content.data)
+    }
+
+    async fn border_effect(&self) -> Option<Json<&BorderEffect>> {
+        self.0.border_effect.as_ref().map(Json)
+    }
+
+    async fn border_reactive(&self) -> bool {
+        self.0.border_reactive
+    }
+
+    async fn outro_ms(&self) -> Option<u32> {
+        self.0.outro_ms
+    }
+
+    async fn created_for(&self) -> Option<Json<PanelSize>> {
+        self.0.created_for.map(Json)
+    }
+
+    async fn created_at(&self) -> Option<&str> {
+        self.0.created_at.as_deref()
+    }
+
+    async fn updated_at(&self) -> Option<&str> {
+        self.0.updated_at.as_deref()
+    }
+
+    async fn author(&self) -> Option<&str> {
+        self.0.author.as_deref()
+    }
+
+    async fn expires_at(&self) -> Option<&str> {
+        self.0.expires_at.as_deref()
+    }
+
+    async fn interstitial(&self) -> Option<Json<&Interstitial>> {
+        self.0.interstitial.as_ref().map(Json)
+    }
+
+    async fn tags(&self) -> &[String] {
+        &self.0.tags
+    }
+
+    async fn enabled(&self) -> bool {
+        self.0.enabled
+    }
+
+    // The whole item in its REST form, e.g. to edit and pass to updateItem
+    async fn json(&self) -> Json<&PlayListItem> {
+        Json(&self.0)
+    }
+
+    async fn health(&self, ctx: &Context<'_>) -> async_graphql::Result<Json<ItemHealth>> {
+        let health = get_playlist_item_health(State(state(ctx)), Path(self.0.id.clone()))
+            .await
+            .map_err(status_error)?;
+        Ok(Json(health.0))
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ImageInfo {
+    id: ID,
+    url: String,
+    thumbnail_url: String,
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    // Playlist items, only those with `tag` if given
+    async fn playlist(&self, ctx: &Context<'_>, tag: Option<String>) -> Vec<PlaylistItem> {
+        let query = ItemsQuery { tag, sort: None };
+        let items = get_playlist_items(State(state(ctx)), Query(query)).await;
+        items.0.into_iter().map(PlaylistItem).collect()
+    }
+
+    async fn item(&self, ctx: &Context<'_>, id: ID) -> Option<PlaylistItem> {
+        let ((display, _), _) = state(ctx);
+        let display = display.lock().await;
+        display
+            .playlist
+            .items
+            .iter()
+            .find(|item| item.id == *id)
+            .cloned()
+            .map(PlaylistItem)
+    }
+
+    async fn now_playing(&self, ctx: &Context<'_>) -> Json<NowPlayingResponse> {
+        Json(get_now_playing(State(state(ctx))).await.0)
+    }
+
+    async fn brightness(&self, ctx: &Context<'_>) -> u8 {
+        let ((display, _), _) = state(ctx);
+        let display = display.lock().await;
+        display.get_brightness()
+    }
+
+    async fn idle_settings(&self, ctx: &Context<'_>) -> Json<IdleSettings> {
+        let ((display, _), _) = state(ctx);
+        let display = display.lock().await;
+        Json(display.idle_settings().clone())
+    }
+
+    // Resolved startup configuration with the source of each value
+    async fn settings(&self, ctx: &Context<'_>) -> Json<BTreeMap<&'static str, EffectiveSetting>> {
+        Json(get_effective_settings(State(state(ctx))).await.0)
+    }
+
+    // Stored image, None if no image exists for the ID
+    async fn image(&self, ctx: &Context<'_>, id: ID) -> Option<ImageInfo> {
+        let ((_, storage), _) = state(ctx);
+        let exists = storage.lock().image_path(&id).exists();
+        exists.then(|| ImageInfo {
+            url: format!("/api/images/{}", *id),
+            thumbnail_url: format!("/api/images/{}/thumbnail", *id),
+            id,
+        })
+    }
+}
+
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    async fn create_item(
+        &self,
+        ctx: &Context<'_>,
+        item: Json<PlayListItem>,
+    ) -> async_graphql::Result<PlaylistItem> {
+        let (status, item) = create_playlist_item(State(state(ctx)), ItemJson(item.0)).await;
+        if status != StatusCode::CREATED {
+            return Err(status_error(status));
+        }
+        Ok(PlaylistItem(item.0))
+    }
+
+    async fn update_item(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        item: Json<PlayListItem>,
+    ) -> async_graphql::Result<PlaylistItem> {
+        let item = update_playlist_item(State(state(ctx)), Path(id.0), ItemJson(item.0))
+            .await
+            .map_err(status_error)?;
+        Ok(PlaylistItem(item.0))
+    }
+
+    async fn delete_item(&self, ctx: &Context<'_>, id: ID) -> async_graphql::Result<bool> {
+        delete_playlist_item(State(state(ctx)), Path(id.0))
+            .await
+            .map_err(status_error)?;
+        Ok(true)
+    }
+
+    async fn reorder_items(
+        &self,
+        ctx: &Context<'_>,
+        item_ids: Vec<ID>,
+    ) -> async_graphql::Result<Vec<PlaylistItem>> {
+        let request = ReorderRequest {
+            item_ids: item_ids.into_iter().map(|id| id.0).collect(),
+        };
+        let items = reorder_playlist_items(State(state(ctx)), axum::Json(request))
+            .await
+            .map_err(status_error)?;
+        Ok(items.0.into_iter().map(PlaylistItem).collect())
+    }
+
+    // Enable or disable all items with a tag, returns the changed items
+    async fn set_tag_enabled(
+        &self,
+        ctx: &Context<'_>,
+        tag: String,
+        enabled: bool,
+    ) -> async_graphql::Result<Vec<PlaylistItem>> {
+        let request = TagEnabledRequest { enabled };
+        let items = set_tag_enabled(State(state(ctx)), Path(tag), axum::Json(request))
+            .await
+            .map_err(status_error)?;
+        Ok(items.0.into_iter().map(PlaylistItem).collect())
+    }
+
+    async fn set_brightness(
+        &self,
+        ctx: &Context<'_>,
+        brightness: u8,
+        fade_ms: Option<u64>,
+    ) -> async_graphql::Result<u8> {
+        let settings = BrightnessSettings {
+            brightness,
+            fade_ms,
+        };
+        let settings = update_brightness(State(state(ctx)), axum::Json(settings))
+            .await
+            .map_err(status_error)?;
+        Ok(settings.0.brightness)
+    }
+
+    // Run an action given as in POST /api/actions
+    async fn run_action(
+        &self,
+        ctx: &Context<'_>,
+        action: Json<Action>,
+    ) -> async_graphql::Result<bool> {
+        dispatch(&state(ctx), action.0)
+            .await
+            .map_err(status_error)?;
+        Ok(true)
+    }
+
+    async fn update_idle_settings(
+        &self,
+        ctx: &Context<'_>,
+        settings: Json<IdleSettings>,
+    ) -> async_graphql::Result<Json<IdleSettings>> {
+        let settings = update_idle_settings(State(state(ctx)), axum::Json(settings.0))
+            .await
+            .map_err(status_error)?;
+        Ok(Json(settings.0))
+    }
+
+    async fn upload_image_from_url(
+        &self,
+        ctx: &Context<'_>,
+        url: String,
+    ) -> async_graphql::Result<Json<ImageUploadResponse>> {
+        let image =
+            upload_image_from_url(State(state(ctx)), axum::Json(ImageFromUrlRequest { url }))
+                .await
+                .map_err(status_error)?;
+        Ok(Json(image.0))
+    }
+}
+
+// Live events of a channel, skipping events a slow subscriber missed
+fn channel_events<T>(rx: Receiver<SequencedEvent<T>>) -> impl Stream<Item = Json<T>>
+where
+    T: Clone + Send + 'static,
+{
+    stream::unfold(rx, |mut rx| async move {
+        loop {
+            match rx.recv().await {
+                Ok(event) => return Some((Json(event.data), rx)),
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    })
+}
+
+pub struct SubscriptionRoot;
+
+#[Subscription]
+impl SubscriptionRoot {
+    async fn brightness(&self, ctx: &Context<'_>) -> impl Stream<Item = Json<BrightnessSettings>> {
+        let (_, event_state) = state(ctx);
+        let (_, rx) = event_state.lock().brightness_channel().subscribe(None);
+        channel_events(rx)
+    }
+
+    async fn editor_lock(&self, ctx: &Context<'_>) -> impl Stream<Item = Json<EditorLockEvent>> {
+        let (_, event_state) = state(ctx);
+        let (_, rx) = event_state.lock().editor_lock_channel().subscribe(None);
+        channel_events(rx)
+    }
+
+    async fn playlist(&self, ctx: &Context<'_>) -> impl Stream<Item = Json<PlaylistUpdateEvent>> {
+        let (_, event_state) = state(ctx);
+        let (_, rx) = event_state.lock().playlist_channel().subscribe(None);
+        channel_events(rx)
+    }
+
+    async fn now_playing(&self, ctx: &Context<'_>) -> impl Stream<Item = Json<NowPlayingEvent>> {
+        let (_, event_state) = state(ctx);
+        let (_, rx) = event_state.lock().now_playing_channel().subscribe(None);
+        channel_events(rx)
+    }
+
+    async fn warnings(&self, ctx: &Context<'_>) -> impl Stream<Item = Json<WarningEvent>> {
+        let (_, event_state) = state(ctx);
+        let (_, rx) = event_state.lock().warning_channel().subscribe(None);
+        channel_events(rx)
+    }
+}
+
+// Handler for queries and mutations
+async fn graphql_handler(
+    State(schema): State<ApiSchema>,
+    axum::Json(request): axum::Json<async_graphql::Request>,
+) -> axum::Json<async_graphql::Response> {
+    axum::Json(schema.execute(request).await)
+}
+
+// Handler for subscriptions over SSE: every result is sent as a `next` event and a
+// `complete` event ends the stream. Queries and mutations work too and yield one result.
+async fn graphql_stream_handler(
+    State(schema): State<ApiSchema>,
+    axum::Json(request): axum::Json<async_graphql::Request>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let results = schema.execute_stream(request).map(|response| {
+        Ok(Event::default()
+            .event("next")
+            .data(serde_json::to_string(&response).unwrap()))
+    });
+    let complete = stream::once(async { Ok(Event::default().event("complete").data("")) });
+
+    Sse::new(results.chain(complete)).keep_alive(axum::response::sse::KeepAlive::default())
+}
+
+// Routes for /api/graphql, with the schema reading from the shared application state
+pub fn router(state: CombinedState) -> Router {
+    let schema = Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
+        .data(state)
+        .finish();
+
+    Router::new()
+        .route("/api/graphql", post(graphql_handler))
+        .route("/api/graphql/stream", post(graphql_stream_handler))
+        .with_state(schema)
+}
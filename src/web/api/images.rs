@@ -1,21 +1,35 @@
 use std::io::Cursor;
+use std::path::{Path as FsPath, PathBuf};
+use std::time::Duration;
 
 use axum::{
-    extract::{Multipart, Path, State},
+    body::Body,
+    extract::{Multipart, Path, Request, State},
     http::{header, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
 use bytes::Bytes;
-use image::{DynamicImage, ImageFormat, ImageReader};
+use image::{imageops::FilterType, DynamicImage, ImageError, ImageFormat, ImageReader, Limits};
 use log::{error, warn};
+use tokio::io::AsyncWriteExt;
+use tower::ServiceExt;
+use tower_http::services::ServeFile;
 
-use crate::{utils::uuid::generate_uuid_string, web::api::CombinedState};
+use crate::{
+    storage::app_storage::SharedStorage,
+    utils::{remote_url, uuid::generate_uuid_string},
+    web::api::CombinedState,
+    web::etag::{file_entity_tag, matches as etag_matches},
+};
 
-pub const MAX_IMAGE_BYTES: usize = 30 * 1024 * 1024; // 30 MB
 pub const THUMBNAIL_MAX_WIDTH: u32 = 128;
 pub const THUMBNAIL_MAX_HEIGHT: u32 = 96;
 
+// Limits for downloading images from a URL
+const URL_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const URL_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
 #[derive(serde::Serialize)]
 pub struct ImageUploadResponse {
     pub image_id: String,
@@ -58,14 +72,127 @@ fn decode_image_from_bytes(bytes: &[u8]) -> Result<DynamicImage, StatusCode> {
     })
 }
 
+// Temporary upload file, removed again when the upload has been handled
+pub(crate) struct UploadFile(pub(crate) PathBuf);
+
+impl Drop for UploadFile {
+    fn drop(&mut self) {
+        if let Err(err) = std::fs::remove_file(&self.0) {
+            if err.kind() != std::io::ErrorKind::NotFound {
+                warn!("Failed to remove upload file {:?}: {}", self.0, err);
+            }
+        }
+    }
+}
+
+// Reject an upload with 507 Insufficient Storage while the data directory has less than
+// `min_free_bytes` free, before any of it is received
+pub(crate) fn ensure_free_space(
+    storage: &SharedStorage,
+    min_free_bytes: u64,
+) -> Result<(), StatusCode> {
+    if storage.lock().has_free_space(min_free_bytes) {
+        return Ok(());
+    }
+    warn!(
+        "Rejected upload: less than {} MB free in the data directory",
+        min_free_bytes / (1024 * 1024)
+    );
+    Err(StatusCode::INSUFFICIENT_STORAGE)
+}
+
+// Uploaded image converted to the stored PNG and thumbnail
+struct ConvertedImage {
+    png_bytes: Vec<u8>,
+    width: u32,
+    height: u32,
+    thumbnail_bytes: Vec<u8>,
+    thumbnail_width: u32,
+    thumbnail_height: u32,
+}
+
+// Decode an upload and convert it to PNG. Images larger than `max_dimension` on either side
+// are rejected before their pixels are allocated, so a small file cannot decode to gigabytes.
+fn convert_image_file(path: &FsPath, max_dimension: u32) -> Result<ConvertedImage, StatusCode> {
+    let mut reader = ImageReader::open(path)
+        .map_err(|err| {
+            error!("Failed to open uploaded file {:?}: {}", path, err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .with_guessed_format()
+        .map_err(|err| {
+            warn!("Failed to guess image format: {}", err);
+            StatusCode::UNSUPPORTED_MEDIA_TYPE
+        })?;
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(max_dimension);
+    limits.max_image_height = Some(max_dimension);
+    reader.limits(limits);
+
+    let decoded = reader.decode().map_err(|err| match err {
+        ImageError::Limits(err) => {
+            warn!("Rejected image above {}px: {}", max_dimension, err);
+            StatusCode::PAYLOAD_TOO_LARGE
+        }
+        err => {
+            warn!("Failed to decode image: {}", err);
+            StatusCode::UNSUPPORTED_MEDIA_TYPE
+        }
+    })?;
+    encode_image(&decoded)
+}
+
+// Encode an image as the stored PNG and build its thumbnail
+fn encode_image(decoded: &DynamicImage) -> Result<ConvertedImage, StatusCode> {
+    let width = decoded.width();
+    let height = decoded.height();
+
+    let mut cursor = Cursor::new(Vec::new());
+    decoded
+        .write_to(&mut cursor, ImageFormat::Png)
+        .map_err(|err| {
+            error!("Failed to encode PNG: {}", err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let (thumbnail_bytes, thumbnail_width, thumbnail_height) = build_thumbnail(decoded)?;
+
+    Ok(ConvertedImage {
+        png_bytes: cursor.into_inner(),
+        width,
+        height,
+        thumbnail_bytes,
+        thumbnail_width,
+        thumbnail_height,
+    })
+}
+
 pub async fn upload_image(
     State(combined_state): State<CombinedState>,
     mut multipart: Multipart,
 ) -> Result<Json<ImageUploadResponse>, StatusCode> {
-    let ((_display, storage), _events) = combined_state;
-    let mut image_bytes: Option<Vec<u8>> = None;
+    let ((display, storage), _events) = combined_state;
+    let (max_bytes, max_dimension, min_free_bytes) = {
+        let display_guard = display.lock().await;
+        let config = display_guard.config();
+        (
+            config.max_image_bytes(),
+            config.max_image_dimension,
+            config.min_free_space_bytes(),
+        )
+    };
+    ensure_free_space(&storage, min_free_bytes)?;
+
+    let image_id = generate_uuid_string();
+    let upload = storage
+        .lock()
+        .upload_path(&image_id)
+        .map(UploadFile)
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    let mut received = 0;
 
-    while let Some(field) = multipart
+    while let Some(mut field) = multipart
         .next_field()
         .await
         .map_err(|_| StatusCode::BAD_REQUEST)?
@@ -76,85 +203,405 @@ pub async fn upload_image(
             }
         }
 
-        let mut data = Vec::new();
-        let mut field_reader = field;
-        while let Some(chunk) = field_reader
-            .chunk()
-            .await
-            .map_err(|_| StatusCode::BAD_REQUEST)?
-        {
-            if data.len() + chunk.len() > MAX_IMAGE_BYTES {
+        // Stream the file to disk so large uploads never sit in memory as a whole
+        let mut file = tokio::fs::File::create(&upload.0).await.map_err(|err| {
+            error!("Failed to create upload file {:?}: {}", upload.0, err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        while let Some(chunk) = field.chunk().await.map_err(|_| StatusCode::BAD_REQUEST)? {
+            received += chunk.len();
+            if received > max_bytes {
                 return Err(StatusCode::PAYLOAD_TOO_LARGE);
             }
-            data.extend_from_slice(&chunk);
-        }
-
-        if data.is_empty() {
-            return Err(StatusCode::BAD_REQUEST);
+            file.write_all(&chunk).await.map_err(|err| {
+                error!("Failed to write upload file {:?}: {}", upload.0, err);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
         }
-
-        image_bytes = Some(data);
+        file.flush().await.map_err(|err| {
+            error!("Failed to write upload file {:?}: {}", upload.0, err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
         break;
     }
 
-    let uploaded = image_bytes.ok_or(StatusCode::BAD_REQUEST)?;
-
-    let mut reader = ImageReader::new(Cursor::new(&uploaded));
-    reader = reader.with_guessed_format().map_err(|err| {
-        warn!("Failed to guess image format: {}", err);
-        StatusCode::UNSUPPORTED_MEDIA_TYPE
-    })?;
+    if received == 0 {
+        return Err(StatusCode::BAD_REQUEST);
+    }
 
-    let decoded = reader.decode().map_err(|err| {
-        warn!("Failed to decode image: {}", err);
-        StatusCode::UNSUPPORTED_MEDIA_TYPE
-    })?;
-    let width = decoded.width();
-    let height = decoded.height();
+    store_upload(&storage, image_id, &upload, max_dimension)
+        .await
+        .map(Json)
+}
 
-    let mut cursor = Cursor::new(Vec::new());
-    decoded
-        .write_to(&mut cursor, ImageFormat::Png)
+// Convert a finished upload file and store it as image and thumbnail
+async fn store_upload(
+    storage: &SharedStorage,
+    image_id: String,
+    upload: &UploadFile,
+    max_dimension: u32,
+) -> Result<ImageUploadResponse, StatusCode> {
+    // Decoding is CPU bound, keep it off the async workers
+    let path = upload.0.clone();
+    let converted = tokio::task::spawn_blocking(move || convert_image_file(&path, max_dimension))
+        .await
         .map_err(|err| {
-            error!("Failed to encode PNG: {}", err);
+            error!("Image conversion task failed: {}", err);
             StatusCode::INTERNAL_SERVER_ERROR
-        })?;
-    let png_bytes = cursor.into_inner();
+        })??;
 
-    let (thumbnail_bytes, thumbnail_width, thumbnail_height) = build_thumbnail(&decoded)?;
+    save_converted(storage, image_id, converted)
+}
 
-    let image_id = generate_uuid_string();
+// Store a converted image and its thumbnail under `image_id`
+fn save_converted(
+    storage: &SharedStorage,
+    image_id: String,
+    converted: ConvertedImage,
+) -> Result<ImageUploadResponse, StatusCode> {
     {
-        let storage_guard = storage.lock().unwrap();
-        if !storage_guard.save_image(&image_id, &png_bytes) {
+        let storage_guard = storage.lock();
+        if !storage_guard.save_image(&image_id, &converted.png_bytes) {
             return Err(StatusCode::INTERNAL_SERVER_ERROR);
         }
-        if !storage_guard.save_thumbnail(&image_id, &thumbnail_bytes) {
+        if !storage_guard.save_thumbnail(&image_id, &converted.thumbnail_bytes) {
             return Err(StatusCode::INTERNAL_SERVER_ERROR);
         }
     }
 
-    Ok(Json(ImageUploadResponse {
+    Ok(ImageUploadResponse {
         image_id,
-        width,
-        height,
-        thumbnail_width,
-        thumbnail_height,
-    }))
+        width: converted.width,
+        height: converted.height,
+        thumbnail_width: converted.thumbnail_width,
+        thumbnail_height: converted.thumbnail_height,
+    })
+}
+
+// One step of an image edit, applied in the order given
+#[derive(serde::Deserialize, Debug)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ImageEditOperation {
+    // Keep the given rectangle, which must lie within the image
+    Crop {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    },
+    // Rotate clockwise by 90, 180 or 270 degrees
+    Rotate {
+        degrees: u32,
+    },
+    // Scale to the given size, the panel size when omitted
+    Resize {
+        width: Option<u32>,
+        height: Option<u32>,
+        #[serde(default)]
+        fit: ResizeFit,
+    },
+}
+
+// How a resize treats an aspect ratio different from the target size
+#[derive(serde::Deserialize, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ResizeFit {
+    // Scale to fit within the size, the result may be smaller on one side
+    #[default]
+    Contain,
+    // Scale to cover the size and crop the overflow around the center
+    Cover,
+    // Scale to exactly the size, distorting the image
+    Stretch,
 }
 
+#[derive(serde::Deserialize)]
+pub struct ImageEditRequest {
+    pub operations: Vec<ImageEditOperation>,
+}
+
+// Handler for cropping, rotating and resizing a stored image, e.g. to fit a photo taken on a
+// phone to the panel. The result is stored as a new image and the original is kept, so items
+// using it are unaffected.
+pub async fn edit_image(
+    State(combined_state): State<CombinedState>,
+    Path(image_id): Path<String>,
+    Json(request): Json<ImageEditRequest>,
+) -> Result<Json<ImageUploadResponse>, StatusCode> {
+    let ((display, storage), _events) = combined_state;
+    if request.operations.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let (panel_width, panel_height, max_dimension, min_free_bytes) = {
+        let display_guard = display.lock().await;
+        let config = display_guard.config();
+        (
+            config.display_width().max(1) as u32,
+            config.display_height().max(1) as u32,
+            config.max_image_dimension,
+            config.min_free_space_bytes(),
+        )
+    };
+    ensure_free_space(&storage, min_free_bytes)?;
+
+    let image_bytes = storage
+        .lock()
+        .load_image(&image_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let converted = tokio::task::spawn_blocking(move || {
+        let mut image = image::load_from_memory_with_format(&image_bytes, ImageFormat::Png)
+            .map_err(|err| {
+                error!("Failed to decode stored image {}: {}", image_id, err);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        for operation in &request.operations {
+            image = apply_edit(image, operation, (panel_width, panel_height), max_dimension)?;
+        }
+        encode_image(&image)
+    })
+    .await
+    .map_err(|err| {
+        error!("Image edit task failed: {}", err);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })??;
+
+    save_converted(&storage, generate_uuid_string(), converted).map(Json)
+}
+
+// Apply one edit operation. Operations that do not fit the image, or would produce an image
+// that is empty or larger than `max_dimension`, are rejected with 400.
+fn apply_edit(
+    image: DynamicImage,
+    operation: &ImageEditOperation,
+    panel: (u32, u32),
+    max_dimension: u32,
+) -> Result<DynamicImage, StatusCode> {
+    let edited = match *operation {
+        ImageEditOperation::Crop {
+            x,
+            y,
+            width,
+            height,
+        } => {
+            let fits = x
+                .checked_add(width)
+                .is_some_and(|right| right <= image.width())
+                && y.checked_add(height)
+                    .is_some_and(|bottom| bottom <= image.height());
+            if !fits {
+                warn!(
+                    "Rejected crop {}x{}+{}+{} of a {}x{} image",
+                    width,
+                    height,
+                    x,
+                    y,
+                    image.width(),
+                    image.height()
+                );
+                return Err(StatusCode::BAD_REQUEST);
+            }
+            image.crop_imm(x, y, width, height)
+        }
+        ImageEditOperation::Rotate { degrees } => match degrees % 360 {
+            0 => image,
+            90 => image.rotate90(),
+            180 => image.rotate180(),
+            270 => image.rotate270(),
+            _ => {
+                warn!("Rejected rotation by {} degrees", degrees);
+                return Err(StatusCode::BAD_REQUEST);
+            }
+        },
+        ImageEditOperation::Resize { width, height, fit } => {
+            let width = width.unwrap_or(panel.0);
+            let height = height.unwrap_or(panel.1);
+            if width == 0 || height == 0 || width > max_dimension || height > max_dimension {
+                warn!("Rejected resize to {}x{}", width, height);
+                return Err(StatusCode::BAD_REQUEST);
+            }
+            match fit {
+                ResizeFit::Contain => image.resize(width, height, FilterType::Triangle),
+                ResizeFit::Cover => image.resize_to_fill(width, height, FilterType::Triangle),
+                ResizeFit::Stretch => image.resize_exact(width, height, FilterType::Triangle),
+            }
+        }
+    };
+
+    if edited.width() == 0 || edited.height() == 0 {
+        warn!("Rejected image edit {:?} that leaves no pixels", operation);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    Ok(edited)
+}
+
+#[derive(serde::Deserialize)]
+pub struct ImageFromUrlRequest {
+    pub url: String,
+}
+
+// Download an image server-side so it can be added by pasting a link
+pub async fn upload_image_from_url(
+    State(combined_state): State<CombinedState>,
+    Json(request): Json<ImageFromUrlRequest>,
+) -> Result<Json<ImageUploadResponse>, StatusCode> {
+    let ((display, storage), _events) = combined_state;
+    let (max_bytes, max_dimension, min_free_bytes) = {
+        let display_guard = display.lock().await;
+        let config = display_guard.config();
+        (
+            config.max_image_bytes(),
+            config.max_image_dimension,
+            config.min_free_space_bytes(),
+        )
+    };
+    ensure_free_space(&storage, min_free_bytes)?;
+
+    let url = reqwest::Url::parse(request.url.trim()).map_err(|err| {
+        warn!("Rejected image URL {:?}: {}", request.url, err);
+        StatusCode::BAD_REQUEST
+    })?;
+    if !matches!(url.scheme(), "http" | "https") {
+        warn!("Rejected image URL with scheme {:?}", url.scheme());
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if let Err(err) = remote_url::check_url(&url) {
+        warn!("Rejected image URL {}: {}", url, err);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let image_id = generate_uuid_string();
+    let upload = storage
+        .lock()
+        .upload_path(&image_id)
+        .map(UploadFile)
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    match tokio::time::timeout(
+        URL_DOWNLOAD_TIMEOUT,
+        download_to_file(url.clone(), &upload.0, max_bytes),
+    )
+    .await
+    {
+        Ok(result) => result?,
+        Err(_) => {
+            warn!("Timed out downloading image from {}", url);
+            return Err(StatusCode::GATEWAY_TIMEOUT);
+        }
+    }
+
+    store_upload(&storage, image_id, &upload, max_dimension)
+        .await
+        .map(Json)
+}
+
+// Stream a remote file to disk, stopping as soon as it exceeds `max_bytes`
+async fn download_to_file(
+    url: reqwest::Url,
+    path: &FsPath,
+    max_bytes: usize,
+) -> Result<(), StatusCode> {
+    // Hosts resolving to local addresses fail to connect, also after a redirect
+    let client = remote_url::client_builder(5)
+        .connect_timeout(URL_CONNECT_TIMEOUT)
+        .build()
+        .map_err(|err| {
+            error!("Failed to create HTTP client: {}", err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let mut response = client.get(url.clone()).send().await.map_err(|err| {
+        warn!("Failed to download image from {}: {}", url, err);
+        if err.is_timeout() {
+            StatusCode::GATEWAY_TIMEOUT
+        } else {
+            StatusCode::BAD_GATEWAY
+        }
+    })?;
+    if !response.status().is_success() {
+        warn!(
+            "Image download from {} returned status {}",
+            url,
+            response.status()
+        );
+        return Err(StatusCode::BAD_GATEWAY);
+    }
+    // Reject early when the server announces a file that is too large
+    if response
+        .content_length()
+        .is_some_and(|length| length > max_bytes as u64)
+    {
+        return Err(StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    let mut file = tokio::fs::File::create(path).await.map_err(|err| {
+        error!("Failed to create upload file {:?}: {}", path, err);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let mut received = 0;
+    while let Some(chunk) = response.chunk().await.map_err(|err| {
+        warn!("Failed to download image from {}: {}", url, err);
+        StatusCode::BAD_GATEWAY
+    })? {
+        received += chunk.len();
+        if received > max_bytes {
+            return Err(StatusCode::PAYLOAD_TOO_LARGE);
+        }
+        file.write_all(&chunk).await.map_err(|err| {
+            error!("Failed to write upload file {:?}: {}", path, err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    }
+    file.flush().await.map_err(|err| {
+        error!("Failed to write upload file {:?}: {}", path, err);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    if received == 0 {
+        warn!("Image download from {} was empty", url);
+        return Err(StatusCode::BAD_GATEWAY);
+    }
+    Ok(())
+}
+
+// Stream a stored image from disk. Range requests get just the requested bytes, so large
+// assets can be fetched in chunks or resumed, and If-None-Match is answered with 304.
 pub async fn fetch_image(
     State(combined_state): State<CombinedState>,
     Path(image_id): Path<String>,
+    request: Request,
 ) -> Result<Response, StatusCode> {
     let ((_display, storage), _events) = combined_state;
-    let storage_guard = storage.lock().unwrap();
-    if let Some(bytes) = storage_guard.load_image(&image_id) {
-        let headers = [(header::CONTENT_TYPE, HeaderValue::from_static("image/png"))];
-        Ok((headers, Bytes::from(bytes)).into_response())
-    } else {
-        Err(StatusCode::NOT_FOUND)
+    let path = storage.lock().image_path(&image_id);
+    if !path.is_file() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let etag = {
+        let path = path.clone();
+        tokio::task::spawn_blocking(move || file_entity_tag(&path))
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .map_err(|e| {
+                error!("Failed to read image {}: {}", image_id, e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?
+    };
+    let etag = HeaderValue::from_str(&etag).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if request
+        .headers()
+        .get(header::IF_NONE_MATCH)
+        .is_some_and(|if_none_match| etag_matches(if_none_match, etag.to_str().unwrap_or("")))
+    {
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response());
+    }
+
+    let Ok(response) = ServeFile::new(&path).oneshot(request).await;
+    let mut response = response.map(Body::new);
+    if response.status().is_success() {
+        response.headers_mut().insert(header::ETAG, etag);
     }
+    Ok(response)
 }
 
 pub async fn fetch_image_thumbnail(
@@ -163,25 +610,32 @@ pub async fn fetch_image_thumbnail(
 ) -> Result<Response, StatusCode> {
     let ((_display, storage), _events) = combined_state;
 
-    let storage_guard = storage.lock().unwrap();
+    let image_bytes = {
+        let storage_guard = storage.lock();
 
-    if let Some(bytes) = storage_guard.load_thumbnail(&image_id) {
-        let headers = [(header::CONTENT_TYPE, HeaderValue::from_static("image/png"))];
-        return Ok((headers, Bytes::from(bytes)).into_response());
-    }
+        if let Some(bytes) = storage_guard.load_thumbnail(&image_id) {
+            let headers = [(header::CONTENT_TYPE, HeaderValue::from_static("image/png"))];
+            return Ok((headers, Bytes::from(bytes)).into_response());
+        }
 
-    let image_bytes = match storage_guard.load_image(&image_id) {
-        Some(bytes) => bytes,
-        None => return Err(StatusCode::NOT_FOUND),
+        match storage_guard.load_image(&image_id) {
+            Some(bytes) => bytes,
+            None => return Err(StatusCode::NOT_FOUND),
+        }
     };
 
-    drop(storage_guard);
-
-    let decoded = decode_image_from_bytes(&image_bytes)?;
-    let (thumbnail_bytes, _, _) = build_thumbnail(&decoded)?;
+    // Decoding a full-size image takes long enough to stall other requests on this worker
+    let (thumbnail_bytes, _, _) = tokio::task::spawn_blocking(move || {
+        build_thumbnail(&decode_image_from_bytes(&image_bytes)?)
+    })
+    .await
+    .map_err(|err| {
+        error!("Thumbnail task failed: {}", err);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })??;
 
     {
-        let storage_guard = storage.lock().unwrap();
+        let storage_guard = storage.lock();
         if !storage_guard.save_thumbnail(&image_id, &thumbnail_bytes) {
             warn!("Failed to persist regenerated thumbnail for {}", image_id);
         }
@@ -0,0 +1,31 @@
+// Maintenance tasks that otherwise run on a schedule, e.g. removing unused images
+
+use crate::storage::app_storage::CleanupReport;
+use crate::web::api::CombinedState;
+use axum::extract::{Query, State};
+use axum::Json;
+use serde::Deserialize;
+use std::time::Duration;
+
+#[derive(Deserialize)]
+pub struct CleanupQuery {
+    // Overrides --cleanup-grace-minutes, 0 also removes images uploaded just now
+    pub grace_minutes: Option<u32>,
+}
+
+// Handler removing uploaded images no item uses right away
+pub async fn run_cleanup(
+    State(combined_state): State<CombinedState>,
+    Query(query): Query<CleanupQuery>,
+) -> Json<CleanupReport> {
+    let ((display, storage), _) = &combined_state;
+    let (playlist, grace) = {
+        let display_guard = display.lock().await;
+        let grace = match query.grace_minutes {
+            Some(minutes) => Duration::from_secs(minutes as u64 * 60),
+            None => display_guard.config().cleanup_grace(),
+        };
+        (display_guard.playlist.clone(), grace)
+    };
+    Json(storage.lock().cleanup_unused_images(&playlist, grace))
+}
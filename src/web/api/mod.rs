@@ -3,14 +3,32 @@ use crate::storage::app_storage::SharedStorage;
 use crate::web::api::events::SharedEventState;
 use std::sync::Arc;
 
+pub mod actions;
+pub mod collections;
 pub mod display;
 pub mod events;
+#[cfg(feature = "graphql")]
+pub mod graphql;
 pub mod images;
+pub mod maintenance;
+pub mod palettes;
 pub mod playlist;
+pub mod plugins;
 pub mod preview;
+pub mod profiles;
 pub mod settings;
+pub mod setup;
+pub mod simple;
+pub mod system;
+pub mod text;
+pub mod variables;
+pub mod videos;
 
-// Type alias for our application state
+// Type alias for our application state.
+// Storage and event state use parking_lot locks, which are not poisoned when a handler panics
+// while holding one, so one failed request cannot make every later one fail. They are only
+// held for short synchronous sections and never across an await; lock the display first when
+// both are needed. Slow work such as decoding images runs in spawn_blocking without a lock.
 pub type AppState = (Arc<tokio::sync::Mutex<DisplayManager>>, SharedStorage);
 // Combined state type including SSE state
 pub type CombinedState = (AppState, SharedEventState);
@@ -0,0 +1,114 @@
+// Named color palettes animations and border effects can use instead of listing colors, see
+// utils::palettes. Changes are saved right away, restyle the items on screen and are
+// broadcast as palette events.
+
+use crate::utils::palettes;
+use crate::web::api::CombinedState;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+pub struct Palette {
+    pub name: String,
+    pub colors: Vec<[u8; 3]>,
+}
+
+#[derive(Deserialize)]
+pub struct PaletteUpdate {
+    pub colors: Vec<[u8; 3]>,
+}
+
+// Save the palettes, restyle the items on screen and tell clients about the change
+async fn publish(combined_state: &CombinedState, name: &str, colors: Option<Vec<[u8; 3]>>) {
+    let ((display, storage), event_state) = combined_state;
+    if !storage.lock().save_palettes(&palettes::all()) {
+        error!("Failed to save palettes");
+    }
+    display.lock().await.refresh_palette(name);
+    event_state
+        .lock()
+        .broadcast_palette(name.to_string(), colors);
+}
+
+// Handler for listing all palettes
+pub async fn get_palettes() -> Json<BTreeMap<String, Vec<[u8; 3]>>> {
+    Json(palettes::all())
+}
+
+// Handler for a single palette
+pub async fn get_palette(Path(name): Path<String>) -> Result<Json<Palette>, StatusCode> {
+    let colors = palettes::get(&name).ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(Palette { name, colors }))
+}
+
+// Handler for saving a palette, creating it if needed
+pub async fn put_palette(
+    State(combined_state): State<CombinedState>,
+    Path(name): Path<String>,
+    Json(update): Json<PaletteUpdate>,
+) -> Result<Json<Palette>, StatusCode> {
+    if !palettes::is_valid_name(&name) {
+        warn!("Rejected palette with invalid name '{}'", name);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if update.colors.is_empty() || update.colors.len() > palettes::MAX_COLORS {
+        warn!(
+            "Rejected palette '{}' with {} colors",
+            name,
+            update.colors.len()
+        );
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    if palettes::set(&name, &update.colors) {
+        info!(
+            "Palette '{}' saved with {} colors",
+            name,
+            update.colors.len()
+        );
+        publish(&combined_state, &name, Some(update.colors.clone())).await;
+    }
+    Ok(Json(Palette {
+        name,
+        colors: update.colors,
+    }))
+}
+
+// Handler for removing a palette that is not used by the playlist or a collection
+pub async fn delete_palette(
+    State(combined_state): State<CombinedState>,
+    Path(name): Path<String>,
+) -> StatusCode {
+    if palettes::get(&name).is_none() {
+        return StatusCode::NOT_FOUND;
+    }
+
+    {
+        let ((display, _), _) = &combined_state;
+        let display_guard = display.lock().await;
+        let in_use = display_guard
+            .playlist
+            .items
+            .iter()
+            .chain(
+                display_guard
+                    .collections()
+                    .iter()
+                    .flat_map(|collection| &collection.items),
+            )
+            .any(|item| palettes::names_used_by(item).contains(&name.as_str()));
+        if in_use {
+            warn!("Rejected deleting palette '{}' used by the playlist", name);
+            return StatusCode::CONFLICT;
+        }
+    }
+
+    palettes::remove(&name);
+    info!("Palette '{}' removed", name);
+    publish(&combined_state, &name, None).await;
+    StatusCode::NO_CONTENT
+}
@@ -1,36 +1,113 @@
-use crate::models::content::ContentDetails;
-use crate::models::playlist::PlayListItem;
-use crate::models::settings::ReorderRequest;
-use crate::web::api::events::PlaylistAction;
+use crate::display::audio;
+use crate::display::graphics::fonts::{font_by_name, DEFAULT_FONT};
+use crate::display::manager::DisplayManager;
+use crate::display::renderer::registry::is_plugin_registered;
+use crate::display::renderer::{create_renderer, RenderContext};
+use crate::models::big_number::{validate_value, BigNumberValueUpdate};
+use crate::models::content::{ContentDetails, ContentType};
+use crate::models::panel::adapt_item;
+use crate::models::playlist::{ItemHealth, PlayListItem};
+use crate::models::settings::{ReorderRequest, TagEnabledRequest};
+use crate::models::text::TextOverflow;
+use crate::models::trash::TrashedItem;
+use crate::storage::app_storage::AppStorage;
+use crate::utils::{commands, content_defaults, palettes};
+use crate::web::api::events::{PlaylistAction, SharedEventState};
+use crate::web::api::text::{text_size, unsupported_chars};
 use crate::web::api::CombinedState;
-use axum::extract::{Path, State};
+use axum::async_trait;
+use axum::extract::{FromRequest, Path, Query, Request, State};
 use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
 use axum::Json;
-use log::{debug, error, warn};
+use chrono::Utc;
+use log::{debug, error, info, warn};
+use parking_lot::MutexGuard;
+
+#[derive(serde::Deserialize)]
+pub struct ItemsQuery {
+    // Only return items with this tag
+    #[serde(default)]
+    pub tag: Option<String>,
+    // Order the items by this time, newest first, instead of by playlist position
+    #[serde(default)]
+    pub sort: Option<ItemSort>,
+}
+
+#[derive(serde::Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemSort {
+    CreatedAt,
+    UpdatedAt,
+}
+
+/// Playlist item from a JSON body, with the configured defaults filled into the fields the
+/// client left out
+pub struct ItemJson(pub PlayListItem);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequest<S> for ItemJson {
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(mut value) = Json::<serde_json::Value>::from_request(req, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        content_defaults::fill(&mut value);
+        serde_json::from_value(value).map(ItemJson).map_err(|e| {
+            (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                format!(
+                    "Failed to deserialize the JSON body into the target type: {}",
+                    e
+                ),
+            )
+                .into_response()
+        })
+    }
+}
 
 // Handler for getting all playlist items
 pub async fn get_playlist_items(
     State(combined_state): State<CombinedState>,
+    Query(query): Query<ItemsQuery>,
 ) -> Json<Vec<PlayListItem>> {
     debug!("Getting all playlist items");
     let ((display, _), _) = combined_state;
     let display = display.lock().await;
-    Json(display.playlist.items.clone())
+    let mut items: Vec<PlayListItem> = display
+        .playlist
+        .items
+        .iter()
+        .filter(|item| query.tag.as_deref().is_none_or(|tag| item.has_tag(tag)))
+        .cloned()
+        .collect();
+
+    // Times are all written in UTC, so they sort as text. Items saved before the times were
+    // recorded come last.
+    if let Some(sort) = query.sort {
+        let time = |item: &PlayListItem| match sort {
+            ItemSort::CreatedAt => item.created_at.clone(),
+            ItemSort::UpdatedAt => item.updated_at.clone(),
+        };
+        items.sort_by_key(|item| std::cmp::Reverse(time(item)));
+    }
+    Json(items)
 }
 
 // Handler for creating a new playlist item
 pub async fn create_playlist_item(
     State(combined_state): State<CombinedState>,
-    Json(item): Json<PlayListItem>,
+    ItemJson(mut item): ItemJson,
 ) -> (StatusCode, Json<PlayListItem>) {
     debug!("Creating new playlist item");
 
     // No need to check for empty ID - deserialization already handled it
     let ((display, storage), event_state) = combined_state;
     let mut display_guard = display.lock().await;
-    let storage_guard = storage.lock().unwrap();
+    let mut storage_guard = storage.lock();
 
-    if let Some(image_id) = extract_image_id(&item) {
+    for image_id in item.image_ids() {
         if !storage_guard.image_path(image_id).exists() {
             warn!(
                 "Rejected playlist item referencing missing image {}",
@@ -40,24 +117,104 @@ pub async fn create_playlist_item(
         }
     }
 
+    if let Some(video_id) = item.video_id() {
+        if storage_guard.load_video_info(video_id).is_none() {
+            warn!(
+                "Rejected playlist item referencing missing video {}",
+                video_id
+            );
+            return (StatusCode::BAD_REQUEST, Json(item));
+        }
+    }
+
+    if let Some(plugin) = extract_plugin_name(&item) {
+        if !is_plugin_registered(plugin) {
+            warn!("Rejected playlist item using unknown plugin {}", plugin);
+            return (StatusCode::BAD_REQUEST, Json(item));
+        }
+    }
+
+    // Collection items share the rotation, so their IDs are taken as well
+    if display_guard
+        .collections()
+        .iter()
+        .any(|collection| collection.has_item(&item.id))
+    {
+        warn!(
+            "Rejected playlist item {}: a collection uses its ID",
+            item.id
+        );
+        return (StatusCode::CONFLICT, Json(item));
+    }
+
+    // Imported items designed for another panel size are adapted to this one
+    adapt_item(&mut item, display_guard.panel_size());
+    item.mark_created();
     display_guard.playlist.items.push(item.clone());
 
     // Save updated playlist
-    if storage_guard.save_playlist(&display_guard.playlist) {
-        storage_guard.cleanup_unused_images(&display_guard.playlist);
-    } else {
+    if !storage_guard.save_playlist(&display_guard.playlist) {
         error!("Failed to save playlist after adding new item");
     }
     drop(storage_guard);
 
     // Broadcast the playlist update
-    let event_state_guard = event_state.lock().unwrap();
+    let mut event_state_guard = event_state.lock();
     event_state_guard
         .broadcast_playlist_update(display_guard.playlist.items.clone(), PlaylistAction::Add);
 
     (StatusCode::CREATED, Json(item))
 }
 
+#[derive(serde::Serialize)]
+pub struct NowPlayingResponse {
+    // None while the playlist is empty and the welcome screen is shown
+    pub item: Option<PlayListItem>,
+    pub index: Option<usize>,
+    pub elapsed_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_cycles: Option<u32>,
+    // Fraction (0-1) of the item completed, for progress bars
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<f32>,
+    pub preview_active: bool,
+    // True while the configured idle screen replaces the welcome message
+    pub idle: bool,
+}
+
+// Handler for getting the item currently being displayed
+pub async fn get_now_playing(
+    State(combined_state): State<CombinedState>,
+) -> Json<NowPlayingResponse> {
+    debug!("Getting now playing item");
+    let ((display, _), _) = combined_state;
+    let display_guard = display.lock().await;
+
+    let item = display_guard.active_item().cloned();
+    let elapsed_ms = if item.is_some() {
+        display_guard.active_item_elapsed().as_millis() as u64
+    } else {
+        0
+    };
+    let remaining_ms = item
+        .as_ref()
+        .and_then(|item| item.duration)
+        .map(|seconds| (seconds * 1000).saturating_sub(elapsed_ms));
+
+    Json(NowPlayingResponse {
+        index: item.as_ref().map(|_| display_guard.playlist.active_index),
+        item,
+        elapsed_ms,
+        remaining_ms,
+        completed_cycles: display_guard.active_completed_cycles(),
+        progress: display_guard.active_progress(),
+        preview_active: display_guard.is_in_preview_mode(),
+        idle: display_guard.is_idle(),
+    })
+}
+
 // Handler for getting a specific playlist item
 pub async fn get_playlist_item(
     State(combined_state): State<CombinedState>,
@@ -81,63 +238,238 @@ pub async fn get_playlist_item(
     }
 }
 
+// Handler for the runtime health of a playlist item, e.g. an image that failed to load
+pub async fn get_playlist_item_health(
+    State(combined_state): State<CombinedState>,
+    Path(id): Path<String>,
+) -> Result<Json<ItemHealth>, StatusCode> {
+    debug!("Getting health of playlist item with ID: {}", id);
+
+    let ((display, _), _) = combined_state;
+    let display_guard = display.lock().await;
+
+    if display_guard
+        .playlist
+        .items
+        .iter()
+        .any(|item| item.id == id)
+    {
+        Ok(Json(display_guard.item_health(&id)))
+    } else {
+        Err(StatusCode::NOT_FOUND)
+    }
+}
+
 // Handler for updating a specific playlist item
 pub async fn update_playlist_item(
     State(combined_state): State<CombinedState>,
     Path(id): Path<String>,
-    Json(updated_item): Json<PlayListItem>,
+    ItemJson(updated_item): ItemJson,
 ) -> Result<Json<PlayListItem>, StatusCode> {
     debug!("Updating playlist item with ID: {}", id);
 
     let ((display, storage), event_state) = combined_state;
     let mut display_guard = display.lock().await;
-    let storage_guard = storage.lock().unwrap();
+    let storage_guard = storage.lock();
 
-    if let Some(index) = display_guard
+    let index = display_guard
         .playlist
         .items
         .iter()
         .position(|item| item.id == id)
-    {
-        if let Some(new_image_id) = extract_image_id(&updated_item) {
-            if !storage_guard.image_path(new_image_id).exists() {
-                warn!(
-                    "Rejected playlist update referencing missing image {}",
-                    new_image_id
-                );
-                return Err(StatusCode::BAD_REQUEST);
-            }
-        }
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    replace_item(
+        &mut display_guard,
+        storage_guard,
+        &event_state,
+        index,
+        id,
+        updated_item,
+    )
+    .map(Json)
+}
+
+// Handler for partial item updates with JSON merge patch semantics (RFC 7386): fields in
+// the body replace the item's fields, nested objects are merged and `null` removes a field
+pub async fn patch_playlist_item(
+    State(combined_state): State<CombinedState>,
+    Path(id): Path<String>,
+    Json(patch): Json<serde_json::Value>,
+) -> Result<Json<PlayListItem>, StatusCode> {
+    debug!("Patching playlist item with ID: {}", id);
 
-        let mut item_to_update = updated_item;
-        item_to_update.id = id;
+    let ((display, storage), event_state) = combined_state;
+    let mut display_guard = display.lock().await;
+    let storage_guard = storage.lock();
 
-        display_guard.playlist.items[index] = item_to_update.clone();
+    let index = display_guard
+        .playlist
+        .items
+        .iter()
+        .position(|item| item.id == id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let mut merged = serde_json::to_value(&display_guard.playlist.items[index]).map_err(|e| {
+        error!("Failed to serialize playlist item {}: {}", id, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    merge_patch(&mut merged, &patch);
+
+    // Deserializing runs the same checks as a full update, which rejects invalid items
+    // with 422 as well
+    let updated_item: PlayListItem = serde_json::from_value(merged).map_err(|e| {
+        warn!("Rejected patch for item {}: {}", id, e);
+        StatusCode::UNPROCESSABLE_ENTITY
+    })?;
+
+    replace_item(
+        &mut display_guard,
+        storage_guard,
+        &event_state,
+        index,
+        id,
+        updated_item,
+    )
+    .map(Json)
+}
 
-        // Save updated playlist
-        if storage_guard.save_playlist(&display_guard.playlist) {
-            storage_guard.cleanup_unused_images(&display_guard.playlist);
+// Apply a JSON merge patch (RFC 7386) to `target`
+fn merge_patch(target: &mut serde_json::Value, patch: &serde_json::Value) {
+    let serde_json::Value::Object(patch_fields) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = serde_json::Value::Object(serde_json::Map::new());
+    }
+    let target_fields = target.as_object_mut().expect("target is an object");
+    for (key, value) in patch_fields {
+        if value.is_null() {
+            target_fields.remove(key);
         } else {
-            error!("Failed to save playlist after updating item");
+            merge_patch(
+                target_fields
+                    .entry(key.clone())
+                    .or_insert(serde_json::Value::Null),
+                value,
+            );
         }
-        drop(storage_guard);
+    }
+}
 
-        // Broadcast the playlist update
-        let event_state_guard = event_state.lock().unwrap();
-        event_state_guard.broadcast_playlist_update(
-            display_guard.playlist.items.clone(),
-            PlaylistAction::Update,
-        );
+// Replace the item at `index` after checking its references, then save and announce the change
+fn replace_item(
+    display: &mut DisplayManager,
+    mut storage: MutexGuard<'_, AppStorage>,
+    event_state: &SharedEventState,
+    index: usize,
+    id: String,
+    updated_item: PlayListItem,
+) -> Result<PlayListItem, StatusCode> {
+    for new_image_id in updated_item.image_ids() {
+        if !storage.image_path(new_image_id).exists() {
+            warn!(
+                "Rejected playlist update referencing missing image {}",
+                new_image_id
+            );
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
 
-        // Reset display state if currently showing this item
-        if display_guard.playlist.active_index == index {
-            display_guard.reset_display_state();
+    if let Some(video_id) = updated_item.video_id() {
+        if storage.load_video_info(video_id).is_none() {
+            warn!(
+                "Rejected playlist update referencing missing video {}",
+                video_id
+            );
+            return Err(StatusCode::BAD_REQUEST);
         }
+    }
 
-        Ok(Json(item_to_update))
-    } else {
-        Err(StatusCode::NOT_FOUND)
+    if let Some(plugin) = extract_plugin_name(&updated_item) {
+        if !is_plugin_registered(plugin) {
+            warn!("Rejected playlist update using unknown plugin {}", plugin);
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+
+    let mut item_to_update = updated_item;
+    item_to_update.id = id;
+    adapt_item(&mut item_to_update, display.panel_size());
+    item_to_update.mark_replaced(&display.playlist.items[index]);
+
+    display.playlist.items[index] = item_to_update.clone();
+
+    // Save updated playlist
+    if !storage.save_playlist(&display.playlist) {
+        error!("Failed to save playlist after updating item");
     }
+    drop(storage);
+
+    // Broadcast the playlist update
+    let mut event_state_guard = event_state.lock();
+    event_state_guard
+        .broadcast_playlist_update(display.playlist.items.clone(), PlaylistAction::Update);
+
+    // Reset display state if currently showing this item
+    if display.playlist.active_index == index {
+        display.reset_display_state();
+    }
+
+    Ok(item_to_update)
+}
+
+// Handler for updating the value of a BigNumber item without resetting playback
+pub async fn update_big_number_value(
+    State(combined_state): State<CombinedState>,
+    Path(id): Path<String>,
+    Json(update): Json<BigNumberValueUpdate>,
+) -> Result<Json<PlayListItem>, StatusCode> {
+    debug!("Updating value of playlist item with ID: {}", id);
+
+    let ((display, storage), event_state) = combined_state;
+    let mut display_guard = display.lock().await;
+
+    let index = display_guard
+        .playlist
+        .items
+        .iter()
+        .position(|item| item.id == id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let value = update.value.into_string();
+    if let Err(err) = validate_value(&value) {
+        warn!("Rejected value update for item {}: {}", id, err);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let item = &mut display_guard.playlist.items[index];
+    let ContentDetails::BigNumber(number) = &mut item.content.data else {
+        warn!("Rejected value update for non-BigNumber item {}", id);
+        return Err(StatusCode::BAD_REQUEST);
+    };
+    number.value = value;
+    if let Some(unit) = update.unit {
+        number.unit = Some(unit).filter(|unit| !unit.is_empty());
+    }
+    item.mark_updated();
+    let updated_item = item.clone();
+
+    if !storage.lock().save_playlist(&display_guard.playlist) {
+        error!("Failed to save playlist after updating item value");
+    }
+
+    // Broadcast the playlist update
+    let mut event_state_guard = event_state.lock();
+    event_state_guard
+        .broadcast_playlist_update(display_guard.playlist.items.clone(), PlaylistAction::Update);
+
+    if display_guard.playlist.active_index == index {
+        display_guard.refresh_active_content();
+    }
+
+    Ok(Json(updated_item))
 }
 
 // Handler for deleting a specific playlist item
@@ -149,7 +481,7 @@ pub async fn delete_playlist_item(
 
     let ((display, storage), event_state) = combined_state;
     let mut display_guard = display.lock().await;
-    let storage_guard = storage.lock().unwrap();
+    let mut storage_guard = storage.lock();
 
     // Find the index of the item with the given ID
     if let Some(index) = display_guard
@@ -158,8 +490,17 @@ pub async fn delete_playlist_item(
         .iter()
         .position(|item| item.id == id)
     {
-        // Remove the item
-        display_guard.playlist.items.remove(index);
+        // Remove the item, keeping it in the trash unless the trash is disabled
+        let removed = display_guard.playlist.items.remove(index);
+        if display_guard.config().trash_days > 0 {
+            // A restored item deleted again replaces its older copy
+            let mut trash = storage_guard.load_trash();
+            trash.items.retain(|trashed| trashed.item.id != removed.id);
+            trash.items.push(TrashedItem::new(removed, Utc::now()));
+            if !storage_guard.save_trash(&trash) {
+                error!("Failed to save trash after deleting item");
+            }
+        }
 
         // Adjust active_index if necessary
         if !display_guard.playlist.items.is_empty() {
@@ -172,15 +513,13 @@ pub async fn delete_playlist_item(
         }
 
         // Save updated playlist
-        if storage_guard.save_playlist(&display_guard.playlist) {
-            storage_guard.cleanup_unused_images(&display_guard.playlist);
-        } else {
+        if !storage_guard.save_playlist(&display_guard.playlist) {
             error!("Failed to save playlist after deleting item");
         }
         drop(storage_guard);
 
         // Broadcast the playlist update
-        let event_state_guard = event_state.lock().unwrap();
+        let mut event_state_guard = event_state.lock();
         event_state_guard.broadcast_playlist_update(
             display_guard.playlist.items.clone(),
             PlaylistAction::Delete,
@@ -195,6 +534,140 @@ pub async fn delete_playlist_item(
     }
 }
 
+#[derive(serde::Serialize)]
+pub struct TrashEntry {
+    pub item: PlayListItem,
+    pub deleted_at: String,
+    // When the item is purged for good
+    pub purge_at: Option<String>,
+}
+
+// Handler for listing deleted items that can still be restored
+pub async fn get_trash(State(combined_state): State<CombinedState>) -> Json<Vec<TrashEntry>> {
+    debug!("Getting trashed playlist items");
+    let ((display, storage), _) = combined_state;
+    let trash_days = display.lock().await.config().trash_days;
+    let trash = storage.lock().load_trash();
+
+    Json(
+        trash
+            .items
+            .into_iter()
+            .map(|trashed| TrashEntry {
+                purge_at: trashed
+                    .purge_at(trash_days)
+                    .map(|purge_at| purge_at.to_rfc3339()),
+                item: trashed.item,
+                deleted_at: trashed.deleted_at,
+            })
+            .collect(),
+    )
+}
+
+// Handler for moving a deleted item back to the end of the playlist
+pub async fn restore_trash_item(
+    State(combined_state): State<CombinedState>,
+    Path(id): Path<String>,
+) -> Result<Json<PlayListItem>, StatusCode> {
+    debug!("Restoring playlist item with ID: {}", id);
+
+    let ((display, storage), event_state) = combined_state;
+    let mut display_guard = display.lock().await;
+    let mut storage_guard = storage.lock();
+
+    // An item with the same ID may have been created again since
+    if display_guard
+        .playlist
+        .items
+        .iter()
+        .any(|item| item.id == id)
+        || display_guard
+            .collections()
+            .iter()
+            .any(|collection| collection.has_item(&id))
+    {
+        warn!(
+            "Cannot restore item {}: the playlist or a collection already has it",
+            id
+        );
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let mut trash = storage_guard.load_trash();
+    let trashed = trash.take(&id).ok_or(StatusCode::NOT_FOUND)?;
+    display_guard.playlist.items.push(trashed.item.clone());
+
+    // Write the playlist first, right away even with a save interval, so neither a failure nor
+    // a crash before the queued write can lose the item
+    if !storage_guard.save_playlist(&display_guard.playlist) || !storage_guard.flush_pending(true) {
+        error!("Failed to save playlist after restoring item");
+    } else if !storage_guard.save_trash(&trash) {
+        error!("Failed to save trash after restoring item");
+    }
+    drop(storage_guard);
+
+    // Broadcast the playlist update
+    let mut event_state_guard = event_state.lock();
+    event_state_guard
+        .broadcast_playlist_update(display_guard.playlist.items.clone(), PlaylistAction::Add);
+
+    Ok(Json(trashed.item))
+}
+
+// Handler for enabling or disabling all items with a tag at once
+pub async fn set_tag_enabled(
+    State(combined_state): State<CombinedState>,
+    Path(tag): Path<String>,
+    Json(request): Json<TagEnabledRequest>,
+) -> Result<Json<Vec<PlayListItem>>, StatusCode> {
+    debug!(
+        "Setting enabled to {} for items tagged {}",
+        request.enabled, tag
+    );
+
+    let ((display, storage), event_state) = combined_state;
+    let mut display_guard = display.lock().await;
+
+    let mut tagged = Vec::new();
+    for item in display_guard.playlist.items.iter_mut() {
+        if item.has_tag(&tag) {
+            item.enabled = request.enabled;
+            item.mark_updated();
+            tagged.push(item.clone());
+        }
+    }
+    if tagged.is_empty() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    info!(
+        "{} {} item(s) tagged {}",
+        if request.enabled {
+            "Enabled"
+        } else {
+            "Disabled"
+        },
+        tagged.len(),
+        tag
+    );
+
+    // Save updated playlist. A disabled active item is skipped by the update loop.
+    let mut storage_guard = storage.lock();
+    if !storage_guard.save_playlist(&display_guard.playlist) {
+        error!(
+            "Failed to save playlist after changing items tagged {}",
+            tag
+        );
+    }
+    drop(storage_guard);
+
+    // Broadcast the playlist update
+    let mut event_state_guard = event_state.lock();
+    event_state_guard
+        .broadcast_playlist_update(display_guard.playlist.items.clone(), PlaylistAction::Update);
+
+    Ok(Json(tagged))
+}
+
 // Handler for reordering playlist items
 pub async fn reorder_playlist_items(
     State(combined_state): State<CombinedState>,
@@ -250,25 +723,236 @@ pub async fn reorder_playlist_items(
     display_guard.reset_display_state();
 
     // Save updated playlist
-    let storage_guard = storage.lock().unwrap();
-    if storage_guard.save_playlist(&display_guard.playlist) {
-        storage_guard.cleanup_unused_images(&display_guard.playlist);
-    } else {
+    let mut storage_guard = storage.lock();
+    if !storage_guard.save_playlist(&display_guard.playlist) {
         error!("Failed to save playlist after reordering items");
     }
     drop(storage_guard);
 
     // Broadcast the playlist update
-    let event_state_guard = event_state.lock().unwrap();
+    let mut event_state_guard = event_state.lock();
     event_state_guard.broadcast_playlist_update(new_items.clone(), PlaylistAction::Reorder);
 
     // Return the reordered items
     Ok(Json(new_items))
 }
 
-fn extract_image_id(item: &PlayListItem) -> Option<&str> {
+// Problem found while validating an item, `code` is meant for the editor to match on
+#[derive(serde::Serialize)]
+pub struct ValidationIssue {
+    pub code: &'static str,
+    pub message: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct ValidationReport {
+    // False when the item would be rejected or fail to render
+    pub valid: bool,
+    pub errors: Vec<ValidationIssue>,
+    pub warnings: Vec<ValidationIssue>,
+}
+
+// Handler for dry-run validation of a playlist item. Runs the model validation and builds
+// the item's renderer without saving anything, so the editor can show problems up front.
+pub async fn validate_playlist_item(
+    State(combined_state): State<CombinedState>,
+    Json(mut value): Json<serde_json::Value>,
+) -> Json<ValidationReport> {
+    debug!("Validating playlist item");
+    content_defaults::fill(&mut value);
+
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    let item = match serde_json::from_value::<PlayListItem>(value) {
+        Ok(item) => item,
+        Err(e) => {
+            errors.push(ValidationIssue {
+                code: "invalid_item",
+                message: e.to_string(),
+            });
+            return Json(ValidationReport {
+                valid: false,
+                errors,
+                warnings,
+            });
+        }
+    };
+
+    let ((display, storage), _) = combined_state;
+    let (display_width, display_height) = {
+        let display_guard = display.lock().await;
+        (display_guard.display_width, display_guard.display_height)
+    };
+
+    for image_id in item.image_ids() {
+        if !storage.lock().image_path(image_id).exists() {
+            errors.push(ValidationIssue {
+                code: "missing_image",
+                message: format!("Image {} does not exist", image_id),
+            });
+        }
+    }
+
+    if let Some(video_id) = item.video_id() {
+        if storage.lock().load_video_info(video_id).is_none() {
+            errors.push(ValidationIssue {
+                code: "missing_video",
+                message: format!("Video {} does not exist", video_id),
+            });
+        }
+    }
+
+    if let Some(plugin) = extract_plugin_name(&item) {
+        if ContentType::is_builtin(plugin) {
+            errors.push(ValidationIssue {
+                code: "unknown_plugin",
+                message: format!(
+                    "'{}' is a built-in content type, not a plugin. Use it as the item's type",
+                    plugin
+                ),
+            });
+        } else if !is_plugin_registered(plugin) {
+            errors.push(ValidationIssue {
+                code: "unknown_plugin",
+                message: format!("Plugin '{}' is not installed", plugin),
+            });
+        }
+    }
+
+    for palette in palettes::names_used_by(&item) {
+        if palettes::get(palette).is_none() {
+            warnings.push(ValidationIssue {
+                code: "unknown_palette",
+                message: format!(
+                    "Palette '{}' does not exist, the colors given with the item are used",
+                    palette
+                ),
+            });
+        }
+    }
+
+    if let ContentDetails::Command(command_content) = &item.content.data {
+        if !commands::is_allowed(&command_content.command) {
+            errors.push(ValidationIssue {
+                code: "unknown_command",
+                message: format!(
+                    "Command '{}' is not allowed on this controller",
+                    command_content.command
+                ),
+            });
+        }
+    }
+
+    // Build the renderer the display would use, e.g. to check that the image decodes
+    if errors.is_empty() {
+        let ctx = RenderContext::new(display_width, display_height, 100);
+        if let Some(error) = create_renderer(&item, ctx).error() {
+            errors.push(ValidationIssue {
+                code: "render_error",
+                message: error,
+            });
+        }
+    }
+
+    if let ContentDetails::Text(text_content) = &item.content.data {
+        let unsupported = unsupported_chars(&text_content.text);
+        if !unsupported.is_empty() {
+            warnings.push(ValidationIssue {
+                code: "unsupported_characters",
+                message: format!(
+                    "The font cannot draw {}, they are shown as '?'",
+                    unsupported.join(" ")
+                ),
+            });
+        }
+
+        if let Some(font) = font_by_name(DEFAULT_FONT) {
+            let width = text_size(&text_content.text, font).width as i32;
+            if !text_content.scroll
+                && text_content.overflow == TextOverflow::Clip
+                && width > display_width
+            {
+                warnings.push(ValidationIssue {
+                    code: "text_clipped",
+                    message: format!(
+                        "Text is {}px wide but the panel is {}px, enable scrolling or choose an 'overflow' to show all of it",
+                        width, display_width
+                    ),
+                });
+            }
+        }
+    }
+
+    if let ContentDetails::Animation(animation_content) = &item.content.data {
+        if animation_content.reactive() && !audio::is_enabled() {
+            warnings.push(ValidationIssue {
+                code: "audio_disabled",
+                message: "The controller captures no audio, start it with --audio-device for 'reactive' to have an effect".to_string(),
+            });
+        }
+    }
+
+    if item.is_expired() {
+        warnings.push(ValidationIssue {
+            code: "expired",
+            message: "'expires_at' has already passed, the item will never be shown".to_string(),
+        });
+    }
+
+    Json(ValidationReport {
+        valid: errors.is_empty(),
+        errors,
+        warnings,
+    })
+}
+
+pub fn extract_plugin_name(item: &PlayListItem) -> Option<&str> {
     match &item.content.data {
-        ContentDetails::Image(image_content) => Some(image_content.image_id.as_str()),
+        ContentDetails::Plugin(plugin_content) => Some(plugin_content.plugin.as_str()),
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn null_removes_a_field() {
+        let mut item = json!({"duration": 10, "tags": ["lobby"]});
+        merge_patch(&mut item, &json!({"duration": null}));
+        assert_eq!(item, json!({"tags": ["lobby"]}));
+    }
+
+    #[test]
+    fn nested_objects_are_merged_and_arrays_replaced() {
+        let mut item = json!({
+            "content": {"data": {"text": "Hello", "color": [255, 0, 0]}},
+            "tags": ["lobby", "menu"]
+        });
+        merge_patch(
+            &mut item,
+            &json!({"content": {"data": {"color": [0, 255, 0]}}, "tags": ["bar"]}),
+        );
+        assert_eq!(
+            item,
+            json!({
+                "content": {"data": {"text": "Hello", "color": [0, 255, 0]}},
+                "tags": ["bar"]
+            })
+        );
+    }
+
+    #[test]
+    fn non_object_patch_replaces_the_target() {
+        let mut item = json!({"duration": 10});
+        merge_patch(&mut item, &json!(["not", "an", "object"]));
+        assert_eq!(item, json!(["not", "an", "object"]));
+
+        let mut field = json!({"text": "Hello"});
+        merge_patch(&mut field, &json!({"text": {"nested": true}}));
+        assert_eq!(field, json!({"text": {"nested": true}}));
+    }
+}
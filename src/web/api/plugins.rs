@@ -0,0 +1,198 @@
+use axum::{
+    extract::{Multipart, Path, State},
+    http::StatusCode,
+    Json,
+};
+use log::{debug, warn};
+
+use crate::display::renderer::registry::is_renderer_registered;
+use crate::models::content::ContentDetails;
+use crate::models::playlist::PlayListItem;
+use crate::web::api::CombinedState;
+
+#[cfg_attr(not(feature = "wasm-plugins"), allow(dead_code))]
+pub const MAX_PLUGIN_BYTES: usize = 8 * 1024 * 1024; // 8 MB
+
+#[derive(serde::Serialize)]
+pub struct PluginInfo {
+    pub name: String,
+    pub size: u64,
+    pub loaded: bool,
+}
+
+// Plugin names double as file names and content keys
+fn is_valid_plugin_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+// Handler for listing stored WASM plugins
+pub async fn list_plugins(State(combined_state): State<CombinedState>) -> Json<Vec<PluginInfo>> {
+    debug!("Listing WASM plugins");
+    let ((_display, storage), _events) = combined_state;
+    let plugins = storage.lock().list_plugins();
+
+    Json(
+        plugins
+            .into_iter()
+            .map(|(name, size)| PluginInfo {
+                loaded: is_renderer_registered(&name),
+                name,
+                size,
+            })
+            .collect(),
+    )
+}
+
+// Handler for uploading a WASM plugin (multipart `name` + `file` fields)
+#[cfg(feature = "wasm-plugins")]
+pub async fn upload_plugin(
+    State(combined_state): State<CombinedState>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<PluginInfo>), StatusCode> {
+    use crate::display::renderer::wasm::{
+        compile_wasm_plugin, is_wasm_plugin, register_wasm_plugin,
+    };
+    use log::error;
+
+    let ((_display, storage), _events) = combined_state;
+    let mut name: Option<String> = None;
+    let mut module_bytes: Option<Vec<u8>> = None;
+
+    while let Some(mut field) = multipart
+        .next_field()
+        .await
+        .map_err(|_| StatusCode::BAD_REQUEST)?
+    {
+        match field.name() {
+            Some("name") => {
+                name = Some(field.text().await.map_err(|_| StatusCode::BAD_REQUEST)?);
+            }
+            Some("file") => {
+                let mut data = Vec::new();
+                while let Some(chunk) = field.chunk().await.map_err(|_| StatusCode::BAD_REQUEST)? {
+                    if data.len() + chunk.len() > MAX_PLUGIN_BYTES {
+                        return Err(StatusCode::PAYLOAD_TOO_LARGE);
+                    }
+                    data.extend_from_slice(&chunk);
+                }
+                module_bytes = Some(data);
+            }
+            _ => continue,
+        }
+    }
+
+    let name = name.map(|name| name.trim().to_string()).unwrap_or_default();
+    let module_bytes = module_bytes.ok_or(StatusCode::BAD_REQUEST)?;
+    if !is_valid_plugin_name(&name) || module_bytes.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    // Never let a plugin shadow a built-in content type or a native plugin
+    if is_renderer_registered(&name) && !is_wasm_plugin(&name) {
+        warn!("Rejected WASM plugin with reserved name {}", name);
+        return Err(StatusCode::CONFLICT);
+    }
+
+    // Compiling blocks for a while on large modules, so it stays off the runtime's workers
+    let (module_bytes, compiled) = tokio::task::spawn_blocking(move || {
+        let compiled = compile_wasm_plugin(&name, &module_bytes);
+        (module_bytes, compiled.map(|module| (name, module)))
+    })
+    .await
+    .map_err(|err| {
+        error!("WASM plugin compilation panicked: {}", err);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let (name, module) = compiled.map_err(|err| {
+        warn!("Rejected WASM plugin: {}", err);
+        StatusCode::UNPROCESSABLE_ENTITY
+    })?;
+
+    // Only a plugin that was saved goes live, so it is still there after a restart
+    if !storage.lock().save_plugin(&name, &module_bytes) {
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+    register_wasm_plugin(&name, module).map_err(|err| {
+        error!("Failed to register WASM plugin {}: {}", name, err);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(PluginInfo {
+            name,
+            size: module_bytes.len() as u64,
+            loaded: true,
+        }),
+    ))
+}
+
+#[cfg(not(feature = "wasm-plugins"))]
+pub async fn upload_plugin(
+    State(_combined_state): State<CombinedState>,
+    _multipart: Multipart,
+) -> Result<(StatusCode, Json<PluginInfo>), StatusCode> {
+    warn!("Rejected WASM plugin upload: built without the wasm-plugins feature");
+    Err(StatusCode::NOT_IMPLEMENTED)
+}
+
+// Handler for deleting a WASM plugin that is not used by the playlist
+pub async fn delete_plugin(
+    State(combined_state): State<CombinedState>,
+    Path(name): Path<String>,
+) -> StatusCode {
+    debug!("Deleting WASM plugin {}", name);
+
+    if !is_valid_plugin_name(&name) {
+        return StatusCode::BAD_REQUEST;
+    }
+
+    let ((display, storage), _events) = combined_state;
+    let display_guard = display.lock().await;
+    let storage_guard = storage.lock();
+
+    if !storage_guard.plugin_path(&name).exists() {
+        return StatusCode::NOT_FOUND;
+    }
+
+    // Items in collections or the trash can come back into the playlist at any time
+    let uses_plugin = |item: &PlayListItem| match &item.content.data {
+        ContentDetails::Plugin(plugin_content) => plugin_content.plugin == name,
+        _ => false,
+    };
+    let used_by = if display_guard.playlist.items.iter().any(uses_plugin) {
+        Some("the playlist")
+    } else if storage_guard
+        .load_collections()
+        .iter()
+        .any(|collection| collection.items.iter().any(uses_plugin))
+    {
+        Some("a collection")
+    } else if storage_guard
+        .load_trash()
+        .items
+        .iter()
+        .any(|trashed| uses_plugin(&trashed.item))
+    {
+        Some("an item in the trash")
+    } else {
+        None
+    };
+    if let Some(used_by) = used_by {
+        warn!("Rejected deleting WASM plugin {} used by {}", name, used_by);
+        return StatusCode::CONFLICT;
+    }
+
+    #[cfg(feature = "wasm-plugins")]
+    crate::display::renderer::wasm::unload_wasm_plugin(&name);
+
+    if storage_guard.delete_plugin(&name) {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
@@ -1,8 +1,14 @@
+use crate::display::renderer::registry::is_plugin_registered;
+use crate::display::renderer::{RenderContext, Renderer, TextRenderer};
+use crate::models::content::ContentDetails;
 use crate::models::playlist::PlayListItem;
 use crate::models::preview::PreviewModeState;
 use crate::utils::uuid::generate_uuid_string;
+use crate::web::api::events::PlaylistAction;
+use crate::web::api::playlist::extract_plugin_name;
 use crate::web::api::CombinedState;
 use axum::{extract::State, http::StatusCode, response::Json};
+use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
 
 // New response type for preview mode operations
@@ -43,6 +49,53 @@ pub struct PingPreviewRequest {
     pub session_id: String,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct CommitPreviewRequest {
+    pub session_id: String,
+}
+
+#[derive(Serialize)]
+pub struct TextEstimate {
+    /// Width of the text in pixels, as it scrolls
+    pub text_width: i32,
+    pub display_width: i32,
+    /// Seconds one scroll pass (or pass through all pages) takes, null for static text
+    pub pass_seconds: Option<f32>,
+    pub repeat_count: Option<u32>,
+    /// Seconds the item shows for, null when it repeats forever
+    pub total_seconds: Option<f32>,
+}
+
+// Handler for estimating how long a text item shows on the current panel, without needing
+// a preview session
+pub async fn estimate_text(
+    State(combined_state): State<CombinedState>,
+    Json(item): Json<PlayListItem>,
+) -> Result<Json<TextEstimate>, StatusCode> {
+    if !matches!(item.content.data, ContentDetails::Text(_)) {
+        warn!("Rejected estimate for non-text item {}", item.id);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let ((display, _), _) = combined_state;
+    let (display_width, display_height) = {
+        let display_guard = display.lock().await;
+        (display_guard.display_width, display_guard.display_height)
+    };
+
+    let renderer = TextRenderer::new(
+        &item,
+        RenderContext::new(display_width, display_height, 100),
+    );
+    Ok(Json(TextEstimate {
+        text_width: renderer.text_width(),
+        display_width,
+        pass_seconds: renderer.pass_seconds(),
+        repeat_count: item.repeat_count,
+        total_seconds: renderer.total_seconds(),
+    }))
+}
+
 // Handler for exiting preview mode
 pub async fn exit_preview_mode(
     State(combined_state): State<CombinedState>,
@@ -63,7 +116,7 @@ pub async fn exit_preview_mode(
     }
 
     // If we were in preview mode, broadcast an unlock event
-    let event_state_guard = event_state.lock().unwrap();
+    let mut event_state_guard = event_state.lock();
     event_state_guard.broadcast_editor_lock(false, None);
 
     display_guard.exit_preview_mode();
@@ -120,7 +173,7 @@ pub async fn start_preview_mode(
     let session_id = generate_uuid_string();
 
     // Broadcast that the editor is now locked
-    let event_state_guard = event_state.lock().unwrap();
+    let mut event_state_guard = event_state.lock();
     event_state_guard.broadcast_editor_lock(true, Some(session_id.clone()));
 
     // Pass the session ID to the display manager
@@ -173,3 +226,92 @@ pub async fn update_preview(
         session_id: update_req.session_id,
     }))
 }
+
+// Handler for saving the previewed content to the playlist and leaving preview mode in one step.
+// Updates the playlist item with the same ID, or adds the content as a new item.
+pub async fn commit_preview(
+    State(combined_state): State<CombinedState>,
+    Json(commit_req): Json<CommitPreviewRequest>,
+) -> Result<(StatusCode, Json<PlayListItem>), StatusCode> {
+    let ((display, storage), event_state) = combined_state;
+    let mut display_guard = display.lock().await;
+
+    // Check if this session owns the lock
+    if !display_guard.is_in_preview_mode() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    if !display_guard.is_preview_session_owner(&commit_req.session_id) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let item = display_guard
+        .preview_content()
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let mut storage_guard = storage.lock();
+    for image_id in item.image_ids() {
+        if !storage_guard.image_path(image_id).exists() {
+            warn!(
+                "Rejected preview commit referencing missing image {}",
+                image_id
+            );
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+
+    if let Some(video_id) = item.video_id() {
+        if storage_guard.load_video_info(video_id).is_none() {
+            warn!(
+                "Rejected preview commit referencing missing video {}",
+                video_id
+            );
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+
+    if let Some(plugin) = extract_plugin_name(&item) {
+        if !is_plugin_registered(plugin) {
+            warn!("Rejected preview commit using unknown plugin {}", plugin);
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+
+    let existing = display_guard
+        .playlist
+        .items
+        .iter()
+        .position(|existing| existing.id == item.id);
+    let (status, action) = match existing {
+        Some(index) => {
+            display_guard.playlist.items[index] = item.clone();
+            (StatusCode::OK, PlaylistAction::Update)
+        }
+        None => {
+            display_guard.playlist.items.push(item.clone());
+            (StatusCode::CREATED, PlaylistAction::Add)
+        }
+    };
+    info!("Committing preview content as playlist item {}", item.id);
+
+    // Save updated playlist
+    if !storage_guard.save_playlist(&display_guard.playlist) {
+        error!("Failed to save playlist after committing preview");
+    }
+    drop(storage_guard);
+
+    display_guard.exit_preview_mode();
+
+    // Broadcast the playlist update and release the editor lock
+    let mut event_state_guard = event_state.lock();
+    event_state_guard.broadcast_playlist_update(display_guard.playlist.items.clone(), action);
+    event_state_guard.broadcast_editor_lock(false, None);
+
+    // Restart the active item if it was the one just edited
+    if existing == Some(display_guard.playlist.active_index) {
+        display_guard.reset_display_state();
+    }
+
+    Ok((status, Json(item)))
+}
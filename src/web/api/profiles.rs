@@ -0,0 +1,128 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use log::{debug, warn};
+use serde::Serialize;
+
+use crate::config::{is_valid_profile_name, HardwareProfile};
+use crate::web::api::CombinedState;
+
+#[derive(Serialize)]
+pub struct ProfileResponse {
+    pub name: String,
+    /// Whether the controller was started with this profile
+    pub active: bool,
+    #[serde(flatten)]
+    pub profile: HardwareProfile,
+}
+
+// Handler for listing all hardware profiles
+pub async fn list_profiles(
+    State(combined_state): State<CombinedState>,
+) -> Json<Vec<ProfileResponse>> {
+    debug!("Listing hardware profiles");
+    let ((display, storage), _events) = combined_state;
+    let active = display.lock().await.profile_name().map(str::to_string);
+    let profiles = storage.lock().load_profiles();
+
+    Json(
+        profiles
+            .into_iter()
+            .map(|(name, profile)| ProfileResponse {
+                active: active.as_deref() == Some(name.as_str()),
+                name,
+                profile,
+            })
+            .collect(),
+    )
+}
+
+// Handler for getting a single hardware profile
+pub async fn get_profile(
+    State(combined_state): State<CombinedState>,
+    Path(name): Path<String>,
+) -> Result<Json<ProfileResponse>, StatusCode> {
+    debug!("Getting hardware profile {}", name);
+    let ((display, storage), _events) = combined_state;
+    let active = display.lock().await.profile_name() == Some(name.as_str());
+    let profile = storage
+        .lock()
+        .load_profiles()
+        .remove(&name)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(ProfileResponse {
+        name,
+        active,
+        profile,
+    }))
+}
+
+// Handler for creating or replacing a hardware profile
+pub async fn put_profile(
+    State(combined_state): State<CombinedState>,
+    Path(name): Path<String>,
+    Json(profile): Json<HardwareProfile>,
+) -> Result<(StatusCode, Json<ProfileResponse>), StatusCode> {
+    debug!("Saving hardware profile {}", name);
+
+    if !is_valid_profile_name(&name) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if let Err(err) = profile.validate() {
+        warn!("Rejected hardware profile {}: {}", name, err);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let ((display, storage), _events) = combined_state;
+    let active = display.lock().await.profile_name() == Some(name.as_str());
+    let storage_guard = storage.lock();
+
+    let mut profiles = storage_guard.load_profiles();
+    let created = profiles.insert(name.clone(), profile.clone()).is_none();
+    if !storage_guard.save_profiles(&profiles) {
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    let status = if created {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+    Ok((
+        status,
+        Json(ProfileResponse {
+            name,
+            active,
+            profile,
+        }),
+    ))
+}
+
+// Handler for deleting a hardware profile that is not in use
+pub async fn delete_profile(
+    State(combined_state): State<CombinedState>,
+    Path(name): Path<String>,
+) -> StatusCode {
+    debug!("Deleting hardware profile {}", name);
+    let ((display, storage), _events) = combined_state;
+
+    if display.lock().await.profile_name() == Some(name.as_str()) {
+        warn!("Rejected deleting active hardware profile {}", name);
+        return StatusCode::CONFLICT;
+    }
+
+    let storage_guard = storage.lock();
+    let mut profiles = storage_guard.load_profiles();
+    if profiles.remove(&name).is_none() {
+        return StatusCode::NOT_FOUND;
+    }
+
+    if storage_guard.save_profiles(&profiles) {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
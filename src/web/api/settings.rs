@@ -1,29 +1,165 @@
-use crate::models::settings::BrightnessSettings;
+use crate::config::EffectiveSetting;
+use crate::models::settings::{
+    BrightnessSettings, BrightnessStatus, ContentDefaults, IdleSettings, OperatingHours,
+    WelcomeSettings, MAX_BRIGHTNESS,
+};
+use crate::utils::{content_defaults, operating_hours};
 use crate::web::api::CombinedState;
 use axum::extract::State;
+use axum::http::StatusCode;
 use axum::Json;
-use log::info;
+use log::{error, info, warn};
+use std::collections::BTreeMap;
 use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU8, Ordering};
 use std::time::Duration;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-// New handler to get the current brightness
-pub async fn get_brightness(
-    State(combined_state): State<CombinedState>,
-) -> Json<BrightnessSettings> {
+// Longest accepted brightness fade (1 hour)
+const MAX_BRIGHTNESS_FADE_MS: u64 = 60 * 60 * 1000;
+
+// New handler to get the current brightness and the configured limit
+pub async fn get_brightness(State(combined_state): State<CombinedState>) -> Json<BrightnessStatus> {
     let ((display, _), _) = combined_state;
     let display = display.lock().await;
 
     let brightness = display.get_brightness();
+    Json(BrightnessStatus {
+        current: brightness,
+        brightness,
+        max_allowed: MAX_BRIGHTNESS,
+        panel_limit: display.brightness_limit(),
+    })
+}
+
+// Handler for the fully-resolved startup configuration with the source of each value
+pub async fn get_effective_settings(
+    State(combined_state): State<CombinedState>,
+) -> Json<BTreeMap<&'static str, EffectiveSetting>> {
+    let ((display, _), _) = combined_state;
+    let display = display.lock().await;
+
+    Json(display.config().effective_settings())
+}
+
+// Handler for the idle screen settings
+pub async fn get_idle_settings(State(combined_state): State<CombinedState>) -> Json<IdleSettings> {
+    let ((display, _), _) = combined_state;
+    let display = display.lock().await;
+
+    Json(display.idle_settings().clone())
+}
+
+// Handler for updating the idle screen settings, applied right away and persisted
+pub async fn update_idle_settings(
+    State(combined_state): State<CombinedState>,
+    Json(settings): Json<IdleSettings>,
+) -> Result<Json<IdleSettings>, StatusCode> {
+    if let Err(e) = settings.validate() {
+        warn!("Rejected idle settings: {}", e);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let ((display, storage), _) = combined_state;
+    let mut display = display.lock().await;
+    display.set_idle_settings(settings.clone());
+
+    let storage_guard = storage.lock();
+    if !storage_guard.save_idle_settings(&settings) {
+        error!("Failed to save idle settings");
+    }
+
+    Ok(Json(settings))
+}
+
+// Handler for the welcome message settings
+pub async fn get_welcome_settings(
+    State(combined_state): State<CombinedState>,
+) -> Json<WelcomeSettings> {
+    let ((display, _), _) = combined_state;
+    let display = display.lock().await;
+
+    Json(display.welcome_settings().clone())
+}
+
+// Handler for updating the welcome message settings, applied right away and persisted
+pub async fn update_welcome_settings(
+    State(combined_state): State<CombinedState>,
+    Json(settings): Json<WelcomeSettings>,
+) -> Result<Json<WelcomeSettings>, StatusCode> {
+    if let Err(e) = settings.validate() {
+        warn!("Rejected welcome settings: {}", e);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let ((display, storage), _) = combined_state;
+    let mut display = display.lock().await;
+    display.set_welcome_settings(settings.clone());
+
+    let storage_guard = storage.lock();
+    if !storage_guard.save_welcome_settings(&settings) {
+        error!("Failed to save welcome settings");
+    }
+
+    Ok(Json(settings))
+}
+
+// Handler for the defaults filled into fields left out of new items
+pub async fn get_content_defaults() -> Json<ContentDefaults> {
+    Json(content_defaults::get())
+}
+
+// Handler for updating the content defaults, used for items sent from now on and persisted
+pub async fn update_content_defaults(
+    State(combined_state): State<CombinedState>,
+    Json(defaults): Json<ContentDefaults>,
+) -> Result<Json<ContentDefaults>, StatusCode> {
+    if let Err(e) = defaults.validate() {
+        warn!("Rejected content defaults: {}", e);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    content_defaults::load(defaults.clone());
+
+    let ((_, storage), _) = combined_state;
+    let storage_guard = storage.lock();
+    if !storage_guard.save_content_defaults(&defaults) {
+        error!("Failed to save content defaults");
+    }
 
-    Json(BrightnessSettings { brightness })
+    Ok(Json(defaults))
+}
+
+// Handler for the weekly operating hours and holidays
+pub async fn get_operating_hours() -> Json<OperatingHours> {
+    Json(operating_hours::get())
+}
+
+// Handler for updating the operating hours, applied within a second and persisted
+pub async fn update_operating_hours(
+    State(combined_state): State<CombinedState>,
+    Json(hours): Json<OperatingHours>,
+) -> Result<Json<OperatingHours>, StatusCode> {
+    if let Err(e) = hours.validate() {
+        warn!("Rejected operating hours: {}", e);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    operating_hours::load(hours.clone());
+
+    let ((_, storage), _) = combined_state;
+    let storage_guard = storage.lock();
+    if !storage_guard.save_operating_hours(&hours) {
+        error!("Failed to save operating hours");
+    }
+
+    Ok(Json(hours))
 }
 
 // Handler for updating brightness - applies brightness through color scaling
 pub async fn update_brightness(
     State(combined_state): State<CombinedState>,
     Json(settings): Json<BrightnessSettings>,
-) -> Json<BrightnessSettings> {
+) -> Result<Json<BrightnessSettings>, StatusCode> {
     // Initialize static variables on first call
     static INITIALIZED: AtomicBool = AtomicBool::new(false);
     static LAST_BRIGHTNESS: AtomicU8 = AtomicU8::new(0);
@@ -43,13 +179,26 @@ pub async fn update_brightness(
     // Always update the display immediately
     let mut display = display.lock().await;
 
+    if settings.brightness > MAX_BRIGHTNESS {
+        warn!(
+            "Rejected brightness {} above the maximum of {}",
+            settings.brightness, MAX_BRIGHTNESS
+        );
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
     // Initialize the static variable on first call
     if !INITIALIZED.load(Ordering::SeqCst) {
         LAST_BRIGHTNESS.store(display.get_brightness(), Ordering::SeqCst);
         INITIALIZED.store(true, Ordering::SeqCst);
     }
 
-    display.set_brightness(settings.brightness);
+    match settings.fade_ms {
+        Some(fade_ms) if fade_ms > 0 => {
+            display.fade_brightness(settings.brightness, fade_ms.min(MAX_BRIGHTNESS_FADE_MS))
+        }
+        _ => display.set_brightness(settings.brightness),
+    }
 
     // Update tracking for brightness
     let prev_brightness = LAST_BRIGHTNESS.swap(settings.brightness, Ordering::SeqCst);
@@ -72,9 +221,10 @@ pub async fn update_brightness(
         }
 
         // Broadcast the brightness change via SSE
-        let sse_state_guard = sse_state.lock().unwrap();
+        let mut sse_state_guard = sse_state.lock();
         sse_state_guard.broadcast_brightness(BrightnessSettings {
             brightness: settings.brightness,
+            fade_ms: settings.fade_ms,
         });
 
         // Get current brightness for the task
@@ -106,15 +256,14 @@ pub async fn update_brightness(
                 // Reset pending flag
                 SAVE_PENDING.store(false, Ordering::SeqCst);
 
-                if let Ok(storage_guard) = storage_clone.lock() {
-                    storage_guard.save_brightness(brightness);
-                }
+                storage_clone.lock().save_brightness(brightness);
             }
         });
     }
 
     // Return the updated settings
-    Json(BrightnessSettings {
+    Ok(Json(BrightnessSettings {
         brightness: display.get_brightness(),
-    })
+        fade_ms: settings.fade_ms,
+    }))
 }
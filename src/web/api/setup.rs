@@ -0,0 +1,145 @@
+use axum::{extract::State, http::StatusCode, Json};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::models::settings::BrightnessSettings;
+use crate::web::api::CombinedState;
+
+#[derive(Serialize)]
+pub struct SetupStatusResponse {
+    pub setup_complete: bool,
+    /// Driver the controller is running with ("null" until a driver is configured)
+    pub driver: &'static str,
+    pub rows: usize,
+    pub cols: usize,
+    pub chain_length: usize,
+    pub parallel: usize,
+    pub brightness: u8,
+    /// Configured IANA time zone, None when the system time zone is used
+    pub timezone: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SetupRequest {
+    pub driver: String,
+    pub rows: usize,
+    pub cols: usize,
+    pub chain_length: usize,
+    pub parallel: usize,
+    pub brightness: u8,
+    /// IANA time zone such as "Europe/Berlin", omitted to keep the system time zone
+    #[serde(default)]
+    pub timezone: Option<String>,
+}
+
+impl SetupRequest {
+    fn validate(&self) -> Result<(), String> {
+        if self.driver != "native" && self.driver != "binding" {
+            return Err(format!(
+                "Invalid driver type '{}': must be 'native' or 'binding'",
+                self.driver
+            ));
+        }
+        if self.rows == 0 || self.cols == 0 || self.chain_length == 0 {
+            return Err("Rows, columns and chain length must be greater than 0".to_string());
+        }
+        if self.parallel == 0 || self.parallel > 3 {
+            return Err("Parallel chains must be between 1 and 3".to_string());
+        }
+        if self.brightness > 100 {
+            return Err("Brightness must be between 0 and 100".to_string());
+        }
+        if let Some(timezone) = &self.timezone {
+            if timezone.parse::<chrono_tz::Tz>().is_err() {
+                return Err(format!("Invalid time zone '{}'", timezone));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+pub struct SetupResponse {
+    pub setup_complete: bool,
+    /// Whether the controller must be restarted for the driver, geometry or time zone to take effect
+    pub restart_required: bool,
+}
+
+// Handler for the state of the first-boot setup wizard
+pub async fn get_setup(State(combined_state): State<CombinedState>) -> Json<SetupStatusResponse> {
+    let ((display, storage), _events) = combined_state;
+    let display_guard = display.lock().await;
+    let config = display_guard.config();
+    let setup_complete = storage
+        .lock()
+        .load_config()
+        .is_some_and(|stored| stored.setup_complete);
+
+    Json(SetupStatusResponse {
+        setup_complete,
+        driver: config.driver_type.name(),
+        rows: config.rows,
+        cols: config.cols,
+        chain_length: config.chain_length,
+        parallel: config.parallel,
+        brightness: display_guard.get_brightness(),
+        timezone: config.timezone.clone(),
+    })
+}
+
+// Handler for completing the setup wizard: persists the hardware configuration
+// and applies the brightness right away
+pub async fn complete_setup(
+    State(combined_state): State<CombinedState>,
+    Json(request): Json<SetupRequest>,
+) -> Result<Json<SetupResponse>, StatusCode> {
+    if let Err(err) = request.validate() {
+        warn!("Rejected setup: {}", err);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let ((display, storage), events) = combined_state;
+    let mut display_guard = display.lock().await;
+
+    {
+        let mut storage_guard = storage.lock();
+        let mut stored = storage_guard.load_config().unwrap_or_default();
+        stored.setup_complete = true;
+        stored.driver = Some(request.driver.clone());
+        stored.rows = Some(request.rows);
+        stored.cols = Some(request.cols);
+        stored.chain_length = Some(request.chain_length);
+        stored.parallel = Some(request.parallel);
+        stored.timezone = request.timezone.clone();
+        if !storage_guard.save_config(&stored) {
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        storage_guard.save_brightness(request.brightness);
+    }
+
+    display_guard.set_brightness(request.brightness);
+    events.lock().broadcast_brightness(BrightnessSettings {
+        brightness: request.brightness,
+        fade_ms: None,
+    });
+
+    // CLI arguments and environment variables still win over the saved values, so
+    // compare against what is actually running
+    let config = display_guard.config();
+    let restart_required = config.driver_type.name() != request.driver
+        || config.rows != request.rows
+        || config.cols != request.cols
+        || config.chain_length != request.chain_length
+        || config.parallel != request.parallel
+        || config.timezone != request.timezone;
+
+    info!(
+        "Setup completed: {} driver, {}x{} panels, chain {}, parallel {}",
+        request.driver, request.cols, request.rows, request.chain_length, request.parallel
+    );
+
+    Ok(Json(SetupResponse {
+        setup_complete: true,
+        restart_required,
+    }))
+}
@@ -0,0 +1,181 @@
+// Minimal state endpoint with smart plug semantics (on/off, brightness, text) for bridges
+// such as Homebridge or ha-bridge that expose the sign to voice assistants
+
+use crate::models::action::Action;
+use crate::models::content::ContentDetails;
+use crate::models::playlist::PlayListItem;
+use crate::models::settings::MAX_BRIGHTNESS;
+use crate::web::api::actions::dispatch;
+use crate::web::api::playlist::{
+    create_playlist_item, delete_playlist_item, update_playlist_item, ItemJson,
+};
+use crate::web::api::CombinedState;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+// Playlist item holding the text set through this endpoint
+const SIMPLE_TEXT_ITEM_ID: &str = "simple-text";
+
+#[derive(Serialize)]
+pub struct SimpleState {
+    pub on: bool,
+    pub brightness: u8,
+    // Text set through this endpoint, None if there is none
+    pub text: Option<String>,
+}
+
+// Every field is optional, only the given ones are changed
+#[derive(Deserialize)]
+pub struct SimpleStateUpdate {
+    pub on: Option<bool>,
+    pub brightness: Option<u8>,
+    // Shows the text right away, an empty string removes it again
+    pub text: Option<String>,
+}
+
+async fn current_state(combined_state: CombinedState) -> SimpleState {
+    let ((display, _), _) = combined_state;
+    let display = display.lock().await;
+
+    let text = display
+        .playlist
+        .items
+        .iter()
+        .find(|item| item.id == SIMPLE_TEXT_ITEM_ID)
+        .and_then(|item| match &item.content.data {
+            ContentDetails::Text(text_content) => Some(text_content.text.clone()),
+            _ => None,
+        });
+
+    SimpleState {
+        on: display.is_powered_on(),
+        brightness: display.get_brightness(),
+        text,
+    }
+}
+
+// White scrolling text, shown once per playlist cycle
+fn text_item(text: String) -> PlayListItem {
+    let mut item = PlayListItem {
+        id: SIMPLE_TEXT_ITEM_ID.to_string(),
+        duration: None,
+        repeat_count: Some(1),
+        ..PlayListItem::default()
+    };
+    if let ContentDetails::Text(text_content) = &mut item.content.data {
+        text_content.text = text;
+    }
+    item
+}
+
+// Text item as the playlist would store it, deserializing runs the same checks as the
+// playlist endpoints
+fn checked_text_item(text: String) -> Result<PlayListItem, StatusCode> {
+    serde_json::to_value(text_item(text))
+        .and_then(serde_json::from_value)
+        .map_err(|e| {
+            warn!("Rejected simple state text: {}", e);
+            StatusCode::UNPROCESSABLE_ENTITY
+        })
+}
+
+// Handler for the simplified display state
+pub async fn get_simple_state(State(combined_state): State<CombinedState>) -> Json<SimpleState> {
+    Json(current_state(combined_state).await)
+}
+
+// Handler for updating the simplified display state. Text and brightness changes go through
+// the regular playlist and brightness handlers, so they are saved and broadcast as usual.
+pub async fn update_simple_state(
+    State(combined_state): State<CombinedState>,
+    Json(update): Json<SimpleStateUpdate>,
+) -> Result<Json<SimpleState>, StatusCode> {
+    // Everything is checked before anything changes, so a rejected text does not leave the
+    // brightness changed
+    if update
+        .brightness
+        .is_some_and(|brightness| brightness > MAX_BRIGHTNESS)
+    {
+        warn!(
+            "Rejected simple state with brightness above {}",
+            MAX_BRIGHTNESS
+        );
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    // None leaves the text alone, Some(None) removes it
+    let text_item = match update.text.as_deref().map(str::trim) {
+        None => None,
+        Some("") => Some(None),
+        Some(text) => Some(Some(checked_text_item(text.to_string())?)),
+    };
+
+    let exists = {
+        let ((display, _), _) = &combined_state;
+        let display = display.lock().await;
+        if text_item.as_ref().is_some_and(Option::is_some)
+            && display
+                .collections()
+                .iter()
+                .any(|collection| collection.has_item(SIMPLE_TEXT_ITEM_ID))
+        {
+            warn!(
+                "Rejected simple state text: a collection uses the ID {}",
+                SIMPLE_TEXT_ITEM_ID
+            );
+            return Err(StatusCode::CONFLICT);
+        }
+        display
+            .playlist
+            .items
+            .iter()
+            .any(|item| item.id == SIMPLE_TEXT_ITEM_ID)
+    };
+
+    if let Some(brightness) = update.brightness {
+        dispatch(&combined_state, Action::SetBrightness(brightness)).await?;
+    }
+
+    match text_item {
+        Some(None) if exists => {
+            delete_playlist_item(
+                State(combined_state.clone()),
+                Path(SIMPLE_TEXT_ITEM_ID.to_string()),
+            )
+            .await?;
+        }
+        None | Some(None) => {}
+        Some(Some(item)) => {
+            if exists {
+                let _ = update_playlist_item(
+                    State(combined_state.clone()),
+                    Path(SIMPLE_TEXT_ITEM_ID.to_string()),
+                    ItemJson(item),
+                )
+                .await?;
+            } else {
+                let (status, _) =
+                    create_playlist_item(State(combined_state.clone()), ItemJson(item)).await;
+                if status != StatusCode::CREATED {
+                    return Err(status);
+                }
+            }
+
+            // Show the new text right away
+            dispatch(
+                &combined_state,
+                Action::ShowItem(SIMPLE_TEXT_ITEM_ID.to_string()),
+            )
+            .await?;
+        }
+    }
+
+    if let Some(on) = update.on {
+        dispatch(&combined_state, Action::Power(on)).await?;
+    }
+
+    Ok(Json(current_state(combined_state).await))
+}
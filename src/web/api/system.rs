@@ -0,0 +1,75 @@
+use crate::display::thermal::{self, ThermalStatus};
+use crate::storage::app_storage::DiskUsage;
+use crate::storage::recovery::{self, StartupReport};
+use crate::utils::commands;
+use crate::utils::time_sync::{self, TimeSyncStatus};
+use crate::web::api::CombinedState;
+use crate::web::request_log::{self, RouteMetrics};
+use axum::{extract::State, http::StatusCode, Json};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct PingResponse {
+    pub ok: bool,
+    pub version: &'static str,
+}
+
+// Lightweight reachability check; touches no shared state so it answers even while
+// the display is busy
+pub async fn ping() -> Json<PingResponse> {
+    Json(PingResponse {
+        ok: true,
+        version: env!("CARGO_PKG_VERSION"),
+    })
+}
+
+// Handler for the system clock's synchronization state. Clock and countdown content show
+// a blinking marker while this reports `synced: false`.
+pub async fn get_time_status() -> Json<TimeSyncStatus> {
+    Json(time_sync::status())
+}
+
+// Handler for the SoC temperature and whether the display is throttled because of it
+pub async fn get_thermal_status() -> Json<ThermalStatus> {
+    Json(thermal::status())
+}
+
+#[derive(Serialize)]
+pub struct CommandsResponse {
+    pub commands: Vec<String>,
+}
+
+// Handler for the names of the commands Command content may run, as allowed by the admin
+pub async fn get_commands() -> Json<CommandsResponse> {
+    Json(CommandsResponse {
+        commands: commands::names(),
+    })
+}
+
+#[derive(Serialize)]
+pub struct MetricsResponse {
+    pub routes: Vec<RouteMetrics>,
+}
+
+// Handler for the request latency of every route since startup
+pub async fn get_metrics() -> Json<MetricsResponse> {
+    Json(MetricsResponse {
+        routes: request_log::route_metrics(),
+    })
+}
+
+// Handler for what startup recovered from storage, e.g. playlist items that were dropped
+// because they could not be read
+pub async fn get_startup_report() -> Json<StartupReport> {
+    Json(recovery::report())
+}
+
+// Handler for the free space of the data directory and what stored media take of it
+pub async fn get_storage_usage(
+    State(combined_state): State<CombinedState>,
+) -> Result<Json<DiskUsage>, StatusCode> {
+    let ((display, storage), _events) = combined_state;
+    let min_free_bytes = display.lock().await.config().min_free_space_bytes();
+    let usage = storage.lock().disk_usage(min_free_bytes);
+    usage.map(Json).ok_or(StatusCode::INTERNAL_SERVER_ERROR)
+}
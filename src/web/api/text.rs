@@ -0,0 +1,85 @@
+use crate::display::graphics::fonts::{font_by_name, font_names, DEFAULT_FONT};
+use crate::web::api::CombinedState;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use embedded_graphics::geometry::{Dimensions, Point, Size};
+use embedded_graphics::mono_font::{MonoFont, MonoTextStyle};
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::text::{Baseline, Text};
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct MeasureQuery {
+    pub text: String,
+    #[serde(default)]
+    pub font: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct TextMeasurement {
+    pub font: String,
+    pub width: u32,
+    pub height: u32,
+    pub display_width: i32,
+    pub display_height: i32,
+    /// Whether the text fits the panel without scrolling
+    pub fits: bool,
+    /// Characters outside ISO 8859-1, which the fonts draw as '?'
+    pub unsupported: Vec<String>,
+}
+
+// Size of `text` when drawn in a single line with `font`
+pub fn text_size(text: &str, font: &MonoFont) -> Size {
+    let style = MonoTextStyle::new(font, BinaryColor::On);
+    Text::with_baseline(text, Point::zero(), style, Baseline::Top)
+        .bounding_box()
+        .size
+}
+
+// Characters outside ISO 8859-1, each listed once in order of appearance
+pub fn unsupported_chars(text: &str) -> Vec<String> {
+    let mut unsupported: Vec<String> = Vec::new();
+    for c in text.chars().filter(|c| *c as u32 > 0xFF) {
+        let c = c.to_string();
+        if !unsupported.contains(&c) {
+            unsupported.push(c);
+        }
+    }
+    unsupported
+}
+
+// Handler for measuring text with the same fonts the renderers use
+pub async fn measure_text(
+    State(combined_state): State<CombinedState>,
+    Query(query): Query<MeasureQuery>,
+) -> Result<Json<TextMeasurement>, StatusCode> {
+    let font_name = query.font.as_deref().unwrap_or(DEFAULT_FONT);
+    let Some(font) = font_by_name(font_name) else {
+        warn!(
+            "Rejected text measurement with unknown font '{}', expected one of {:?}",
+            font_name,
+            font_names()
+        );
+        return Err(StatusCode::BAD_REQUEST);
+    };
+
+    let size = text_size(&query.text, font);
+    let unsupported = unsupported_chars(&query.text);
+
+    let ((display, _), _) = combined_state;
+    let display_guard = display.lock().await;
+    let (display_width, display_height) =
+        (display_guard.display_width, display_guard.display_height);
+
+    Ok(Json(TextMeasurement {
+        font: font_name.to_lowercase(),
+        width: size.width,
+        height: size.height,
+        display_width,
+        display_height,
+        fits: size.width as i32 <= display_width && size.height as i32 <= display_height,
+        unsupported,
+    }))
+}
@@ -0,0 +1,82 @@
+// Variables external systems set for text placeholders and item conditions, see
+// utils::variables. Changes are saved right away and broadcast as variable events.
+
+use crate::utils::variables;
+use crate::web::api::CombinedState;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+pub struct Variable {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Deserialize)]
+pub struct VariableUpdate {
+    pub value: String,
+}
+
+// Save the variables and tell clients about the change
+fn publish(combined_state: &CombinedState, name: &str, value: Option<String>) {
+    let ((_, storage), event_state) = combined_state;
+    if !storage.lock().save_variables(&variables::all()) {
+        error!("Failed to save variables");
+    }
+    event_state
+        .lock()
+        .broadcast_variable(name.to_string(), value);
+}
+
+// Handler for listing all variables
+pub async fn get_variables() -> Json<BTreeMap<String, String>> {
+    Json(variables::all())
+}
+
+// Handler for a single variable
+pub async fn get_variable(Path(name): Path<String>) -> Result<Json<Variable>, StatusCode> {
+    let value = variables::get(&name).ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(Variable { name, value }))
+}
+
+// Handler for setting a variable, creating it if needed
+pub async fn put_variable(
+    State(combined_state): State<CombinedState>,
+    Path(name): Path<String>,
+    Json(update): Json<VariableUpdate>,
+) -> Result<Json<Variable>, StatusCode> {
+    if !variables::is_valid_name(&name) {
+        warn!("Rejected variable with invalid name '{}'", name);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if update.value.chars().count() > variables::MAX_VALUE_CHARS {
+        warn!("Rejected overlong value for variable '{}'", name);
+        return Err(StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    if variables::set(&name, &update.value) {
+        info!("Variable '{}' set to '{}'", name, update.value);
+        publish(&combined_state, &name, Some(update.value.clone()));
+    }
+    Ok(Json(Variable {
+        name,
+        value: update.value,
+    }))
+}
+
+// Handler for removing a variable
+pub async fn delete_variable(
+    State(combined_state): State<CombinedState>,
+    Path(name): Path<String>,
+) -> StatusCode {
+    if !variables::remove(&name) {
+        return StatusCode::NOT_FOUND;
+    }
+    info!("Variable '{}' removed", name);
+    publish(&combined_state, &name, None);
+    StatusCode::NO_CONTENT
+}
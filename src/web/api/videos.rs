@@ -0,0 +1,243 @@
+use axum::{
+    extract::{Multipart, Path, State},
+    http::StatusCode,
+    Json,
+};
+use log::{debug, warn};
+
+use crate::models::video::VideoInfo;
+use crate::web::api::CombinedState;
+
+#[cfg_attr(not(feature = "video"), allow(dead_code))]
+pub const MAX_VIDEO_BYTES: usize = 50 * 1024 * 1024; // 50 MB
+
+// Longer uploads are cut off, the frames are kept in memory while playing
+#[cfg_attr(not(feature = "video"), allow(dead_code))]
+const MAX_VIDEO_SECONDS: u32 = 60;
+
+// Frame rate videos are decoded at
+#[cfg_attr(not(feature = "video"), allow(dead_code))]
+const VIDEO_FPS: u32 = 25;
+
+// Decoding still running after this long is given up, ffmpeg is killed with it. Leaves
+// slower Pis enough time for a full length clip.
+#[cfg(feature = "video")]
+const DECODE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+
+// Video IDs are generated UUIDs and double as file names
+fn is_valid_video_id(video_id: &str) -> bool {
+    !video_id.is_empty()
+        && video_id.len() <= 64
+        && video_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+// Handler for listing the decoded videos
+pub async fn list_videos(State(combined_state): State<CombinedState>) -> Json<Vec<VideoInfo>> {
+    debug!("Listing videos");
+    let ((_display, storage), _events) = combined_state;
+    let videos = storage.lock().list_videos();
+    Json(videos)
+}
+
+// Handler for uploading an MP4 or WebM video (multipart `file` field). The video is decoded
+// with ffmpeg into RGB frames of the panel size, which is all that is kept.
+#[cfg(feature = "video")]
+pub async fn upload_video(
+    State(combined_state): State<CombinedState>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<VideoInfo>), StatusCode> {
+    use crate::utils::uuid::generate_uuid_string;
+    use crate::web::api::images::{ensure_free_space, UploadFile};
+    use log::error;
+    use tokio::io::AsyncWriteExt;
+
+    let ((display, storage), _events) = combined_state;
+    let (width, height, min_free_bytes) = {
+        let display_guard = display.lock().await;
+        let config = display_guard.config();
+        (
+            config.display_width().max(1) as u32,
+            config.display_height().max(1) as u32,
+            config.min_free_space_bytes(),
+        )
+    };
+    ensure_free_space(&storage, min_free_bytes)?;
+
+    let video_id = generate_uuid_string();
+    let (upload, frames_path) = {
+        let storage_guard = storage.lock();
+        let upload = storage_guard
+            .upload_path(&video_id)
+            .map(UploadFile)
+            .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+        let frames_path = storage_guard
+            .video_frames_path(&video_id)
+            .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+        (upload, frames_path)
+    };
+    // Removed again if decoding fails, kept once the video is saved
+    let frames = UploadFile(frames_path.clone());
+    let mut received = 0;
+
+    while let Some(mut field) = multipart
+        .next_field()
+        .await
+        .map_err(|_| StatusCode::BAD_REQUEST)?
+    {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let is_video = field
+            .content_type()
+            .is_some_and(|content_type| matches!(content_type, "video/mp4" | "video/webm"))
+            || field.file_name().is_some_and(|file_name| {
+                let file_name = file_name.to_ascii_lowercase();
+                file_name.ends_with(".mp4") || file_name.ends_with(".webm")
+            });
+        if !is_video {
+            warn!("Rejected video upload that is neither MP4 nor WebM");
+            return Err(StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        }
+
+        // Stream the file to disk so large uploads never sit in memory as a whole
+        let mut file = tokio::fs::File::create(&upload.0).await.map_err(|err| {
+            error!("Failed to create upload file {:?}: {}", upload.0, err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        while let Some(chunk) = field.chunk().await.map_err(|_| StatusCode::BAD_REQUEST)? {
+            received += chunk.len();
+            if received > MAX_VIDEO_BYTES {
+                return Err(StatusCode::PAYLOAD_TOO_LARGE);
+            }
+            file.write_all(&chunk).await.map_err(|err| {
+                error!("Failed to write upload file {:?}: {}", upload.0, err);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        }
+        file.flush().await.map_err(|err| {
+            error!("Failed to write upload file {:?}: {}", upload.0, err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        break;
+    }
+
+    if received == 0 {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    // Scale to fit the panel keeping the aspect ratio, padded with black
+    let filter = format!(
+        "fps={fps},scale={w}:{h}:force_original_aspect_ratio=decrease:flags=area,\
+         pad={w}:{h}:(ow-iw)/2:(oh-ih)/2",
+        fps = VIDEO_FPS,
+        w = width,
+        h = height
+    );
+    let command = tokio::process::Command::new("ffmpeg")
+        .args(["-v", "error", "-nostdin", "-y", "-i"])
+        .arg(&upload.0)
+        .args(["-t", &MAX_VIDEO_SECONDS.to_string(), "-an", "-vf", &filter])
+        .args(["-f", "rawvideo", "-pix_fmt", "rgb24"])
+        .arg(&frames_path)
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(DECODE_TIMEOUT, command)
+        .await
+        .map_err(|_| {
+            warn!(
+                "Gave up decoding video after {} seconds",
+                DECODE_TIMEOUT.as_secs()
+            );
+            StatusCode::UNPROCESSABLE_ENTITY
+        })?
+        .map_err(|err| {
+            error!("Failed to run ffmpeg: {}", err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    if !output.status.success() {
+        warn!(
+            "Failed to decode video: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    let mut info = VideoInfo {
+        video_id,
+        width,
+        height,
+        fps: VIDEO_FPS,
+        frame_count: 0,
+    };
+    let decoded_bytes = tokio::fs::metadata(&frames_path)
+        .await
+        .map(|metadata| metadata.len())
+        .unwrap_or(0);
+    info.frame_count = (decoded_bytes / info.frame_bytes() as u64) as u32;
+    if info.frame_count == 0 {
+        warn!("Rejected video without any frames");
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    if !storage.lock().save_video_info(&info) {
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+    // Keep the frames now that the video is saved
+    std::mem::forget(frames);
+
+    Ok((StatusCode::CREATED, Json(info)))
+}
+
+#[cfg(not(feature = "video"))]
+pub async fn upload_video(
+    State(_combined_state): State<CombinedState>,
+    _multipart: Multipart,
+) -> Result<(StatusCode, Json<VideoInfo>), StatusCode> {
+    warn!("Rejected video upload: built without the video feature");
+    Err(StatusCode::NOT_IMPLEMENTED)
+}
+
+// Handler for deleting a video that is not used by the playlist or a collection
+pub async fn delete_video(
+    State(combined_state): State<CombinedState>,
+    Path(video_id): Path<String>,
+) -> StatusCode {
+    debug!("Deleting video {}", video_id);
+
+    if !is_valid_video_id(&video_id) {
+        return StatusCode::BAD_REQUEST;
+    }
+
+    let ((display, storage), _events) = combined_state;
+    let display_guard = display.lock().await;
+    let storage_guard = storage.lock();
+
+    if storage_guard.load_video_info(&video_id).is_none() {
+        return StatusCode::NOT_FOUND;
+    }
+
+    let in_use = display_guard
+        .playlist
+        .items
+        .iter()
+        .chain(
+            display_guard
+                .collections()
+                .iter()
+                .flat_map(|collection| &collection.items),
+        )
+        .any(|item| item.video_id() == Some(video_id.as_str()));
+    if in_use {
+        warn!("Rejected deleting video {} used by the playlist", video_id);
+        return StatusCode::CONFLICT;
+    }
+
+    if storage_guard.delete_video(&video_id) {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
@@ -0,0 +1,129 @@
+// Strong ETags for GET routes that clients poll, e.g. the playlist and images. The response
+// body is hashed, so the tag changes exactly when the content does, and a request whose
+// If-None-Match lists the current tag is answered with 304 Not Modified and no body.
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use log::error;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+// Tag of a served file with the size and modification time it was computed for
+struct FileTag {
+    len: u64,
+    modified: SystemTime,
+    tag: String,
+}
+
+static FILE_TAGS: Lazy<Mutex<HashMap<PathBuf, FileTag>>> = Lazy::new(Default::default);
+
+// FNV-1a offset basis, the hash of no bytes
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+
+// Largest body tagged, responses are never bigger than an uploaded image
+const MAX_TAGGED_BODY: usize = 64 * 1024 * 1024;
+
+// Middleware adding an ETag to successful GET responses and answering revalidations
+pub async fn with_etag(request: Request, next: Next) -> Response {
+    if request.method() != Method::GET {
+        return next.run(request).await;
+    }
+    let if_none_match = request.headers().get(header::IF_NONE_MATCH).cloned();
+
+    let response = next.run(request).await;
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, MAX_TAGGED_BODY).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            error!("Failed to read response body for its ETag: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let etag = entity_tag(&bytes);
+    let Ok(value) = HeaderValue::from_str(&etag) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    if if_none_match.is_some_and(|header| matches(&header, &etag)) {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ETAG, value);
+        if let Some(cache_control) = parts.headers.get(header::CACHE_CONTROL) {
+            headers.insert(header::CACHE_CONTROL, cache_control.clone());
+        }
+        return (StatusCode::NOT_MODIFIED, headers).into_response();
+    }
+
+    parts.headers.insert(header::ETAG, value);
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+// Quoted 64-bit FNV-1a hash of the body. Stable across restarts, unlike the std hasher.
+fn entity_tag(bytes: &[u8]) -> String {
+    format!("\"{:016x}\"", fnv1a(FNV_OFFSET, bytes))
+}
+
+// Same tag as entity_tag for the file's contents, read in chunks instead of all at once and
+// cached until the file changes
+pub fn file_entity_tag(path: &Path) -> std::io::Result<String> {
+    let mut file = File::open(path)?;
+    let metadata = file.metadata()?;
+    let modified = metadata.modified()?;
+    if let Some(cached) = FILE_TAGS.lock().unwrap().get(path) {
+        if cached.len == metadata.len() && cached.modified == modified {
+            return Ok(cached.tag.clone());
+        }
+    }
+
+    let mut hash = FNV_OFFSET;
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hash = fnv1a(hash, &buffer[..read]);
+    }
+    let tag = format!("\"{:016x}\"", hash);
+    FILE_TAGS.lock().unwrap().insert(
+        path.to_path_buf(),
+        FileTag {
+            len: metadata.len(),
+            modified,
+            tag: tag.clone(),
+        },
+    );
+    Ok(tag)
+}
+
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+// Whether an If-None-Match header lists `etag` or is "*". If-None-Match compares weakly, so
+// a W/ prefix the client kept is ignored.
+pub fn matches(if_none_match: &HeaderValue, etag: &str) -> bool {
+    let Ok(value) = if_none_match.to_str() else {
+        return false;
+    };
+    value
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
@@ -1,2 +1,7 @@
 pub mod api;
+pub mod etag;
+pub mod request_log;
+#[cfg(feature = "embedded-ui")]
 pub mod static_assets;
+#[cfg(unix)]
+pub mod unix_socket;
@@ -0,0 +1,91 @@
+// Service worker for the LED Sign Controller web interface.
+//
+// Keeps the app shell and the last successful API responses cached so the
+// interface can still be opened, and shows the last known state, while the
+// sign is briefly unreachable.
+
+const CACHE_NAME = "led-sign-v1";
+const APP_SHELL = ["/", "/manifest.webmanifest"];
+
+self.addEventListener("install", (event) => {
+  event.waitUntil(
+    caches
+      .open(CACHE_NAME)
+      .then((cache) => cache.addAll(APP_SHELL))
+      .then(() => self.skipWaiting())
+  );
+});
+
+self.addEventListener("activate", (event) => {
+  event.waitUntil(
+    caches
+      .keys()
+      .then((keys) =>
+        Promise.all(
+          keys.filter((key) => key !== CACHE_NAME).map((key) => caches.delete(key))
+        )
+      )
+      .then(() => self.clients.claim())
+  );
+});
+
+// Build assets have hashed names and never change: serve them from the cache first
+async function cacheFirst(request) {
+  const cached = await caches.match(request);
+  if (cached) {
+    return cached;
+  }
+  const response = await fetch(request);
+  if (response.ok) {
+    const cache = await caches.open(CACHE_NAME);
+    cache.put(request, response.clone());
+  }
+  return response;
+}
+
+// Pages and API state: prefer fresh data, fall back to the last cached copy
+async function networkFirst(request) {
+  try {
+    const response = await fetch(request);
+    if (response.ok) {
+      const cache = await caches.open(CACHE_NAME);
+      cache.put(request, response.clone());
+    }
+    return response;
+  } catch (error) {
+    const cached = await caches.match(request);
+    if (cached) {
+      return cached;
+    }
+    if (request.mode === "navigate") {
+      const shell = await caches.match("/");
+      if (shell) {
+        return shell;
+      }
+    }
+    throw error;
+  }
+}
+
+self.addEventListener("fetch", (event) => {
+  const request = event.request;
+  const url = new URL(request.url);
+
+  // Only cache our own GET requests; event streams and the reachability check stay live.
+  // EventSource asks for text/event-stream, so every SSE route is recognized by that.
+  const accept = request.headers.get("accept") || "";
+  if (
+    request.method !== "GET" ||
+    url.origin !== self.location.origin ||
+    accept.includes("text/event-stream") ||
+    url.pathname === "/api/ping"
+  ) {
+    return;
+  }
+
+  if (url.pathname.startsWith("/_next/static/")) {
+    event.respondWith(cacheFirst(request));
+  } else {
+    event.respondWith(networkFirst(request));
+  }
+});
@@ -0,0 +1,111 @@
+// Request logging and per-route latency. Every request is logged at debug level with its
+// method, path, status and latency, and counted in a latency histogram of its route that
+// GET /api/system/metrics reports, so slow endpoints like image upload can be spotted.
+
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use log::debug;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+// Upper bounds of the histogram buckets in milliseconds, slower requests go in a last bucket
+const BUCKET_BOUNDS_MS: [u64; 10] = [1, 5, 10, 25, 50, 100, 250, 500, 1000, 5000];
+
+// Latency of the requests to one route, keyed by method and route pattern
+#[derive(Default)]
+struct RouteLatency {
+    count: u64,
+    total_ms: f64,
+    max_ms: f64,
+    buckets: [u64; BUCKET_BOUNDS_MS.len() + 1],
+}
+
+static ROUTES: Lazy<Mutex<BTreeMap<(String, String), RouteLatency>>> = Lazy::new(Default::default);
+
+// Middleware logging each request and recording its latency
+pub async fn log_request(request: Request, next: Next) -> Response {
+    let started = Instant::now();
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    // Routes are aggregated by pattern, e.g. /api/playlist/items/:id, not per item
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|matched| matched.as_str().to_string());
+
+    let response = next.run(request).await;
+
+    let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+    debug!(
+        "{} {} {} {:.1}ms",
+        method,
+        path,
+        response.status().as_u16(),
+        elapsed_ms
+    );
+
+    // Unmatched paths would let any client grow the table, they all share one entry
+    let route = route.unwrap_or_else(|| "unmatched".to_string());
+    let mut routes = ROUTES.lock().unwrap();
+    let latency = routes.entry((method.to_string(), route)).or_default();
+    latency.count += 1;
+    latency.total_ms += elapsed_ms;
+    latency.max_ms = latency.max_ms.max(elapsed_ms);
+    let bucket = BUCKET_BOUNDS_MS
+        .iter()
+        .position(|bound| elapsed_ms <= *bound as f64)
+        .unwrap_or(BUCKET_BOUNDS_MS.len());
+    latency.buckets[bucket] += 1;
+
+    response
+}
+
+#[derive(Serialize)]
+pub struct LatencyBucket {
+    // Upper bound in milliseconds, None for the bucket of slower requests
+    pub le_ms: Option<u64>,
+    pub count: u64,
+}
+
+#[derive(Serialize)]
+pub struct RouteMetrics {
+    pub method: String,
+    pub route: String,
+    pub count: u64,
+    pub mean_ms: f64,
+    pub max_ms: f64,
+    pub buckets: Vec<LatencyBucket>,
+}
+
+// Latency of every route requested since startup, sorted by route and method
+pub fn route_metrics() -> Vec<RouteMetrics> {
+    let mut metrics: Vec<RouteMetrics> = ROUTES
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|((method, route), latency)| RouteMetrics {
+            method: method.clone(),
+            route: route.clone(),
+            count: latency.count,
+            mean_ms: latency.total_ms / latency.count.max(1) as f64,
+            max_ms: latency.max_ms,
+            buckets: latency
+                .buckets
+                .iter()
+                .enumerate()
+                .map(|(i, count)| LatencyBucket {
+                    le_ms: BUCKET_BOUNDS_MS.get(i).copied(),
+                    count: *count,
+                })
+                .collect(),
+        })
+        .collect();
+    metrics.sort_by(|a, b| a.route.cmp(&b.route).then(a.method.cmp(&b.method)));
+    metrics
+}
@@ -5,6 +5,75 @@ use axum::{
     response::{Html, IntoResponse},
 };
 use log::{debug, warn};
+use once_cell::sync::OnceCell;
+use std::borrow::Cow;
+
+// URL prefix the server is mounted under, see --base-path
+static BASE_PATH: OnceCell<String> = OnceCell::new();
+
+// Root-relative paths the web interface refers to
+const ROOT_PATHS: [&str; 5] = [
+    "/_next/",
+    "/static/",
+    "/api/",
+    "/manifest.webmanifest",
+    "/sw.js",
+];
+
+// Characters a root-relative reference follows in HTML, JavaScript and CSS
+const REFERENCE_OPENERS: [char; 4] = ['"', '\'', '`', '('];
+
+// Documents whose bare "/" means the interface's start page. Elsewhere, e.g. in minified
+// bundles, a lone "/" is usually a path separator as in split("/") and must stay as it is.
+const ROOT_DOCUMENTS: [&str; 3] = ["index.html", "manifest.webmanifest", "sw.js"];
+
+// Serve the interface under `base_path`, called once at startup
+pub fn set_base_path(base_path: &str) {
+    let _ = BASE_PATH.set(base_path.to_string());
+}
+
+fn base_path() -> &'static str {
+    BASE_PATH.get().map(String::as_str).unwrap_or("")
+}
+
+// Point root-relative references in a text asset at `base`, e.g. "/_next/app.js" becomes
+// "/led/_next/app.js", and with `rewrite_root` "/" becomes "/led/". The frontend is built for
+// the root, so without this it would load its scripts and call the API outside the prefix.
+fn with_base_path(content: &str, base: &str, rewrite_root: bool) -> String {
+    let mut content = content.to_string();
+    for opener in REFERENCE_OPENERS {
+        for path in ROOT_PATHS {
+            content = content.replace(
+                &format!("{}{}", opener, path),
+                &format!("{}{}{}", opener, base, path),
+            );
+        }
+        if rewrite_root && opener != '(' {
+            content = content.replace(
+                &format!("{}/{}", opener, opener),
+                &format!("{}{}/{}", opener, base, opener),
+            );
+        }
+    }
+    content
+}
+
+// Asset bytes with references rewritten for the base path if it is a text asset
+fn asset_body(path: &str, data: Cow<'static, [u8]>) -> Cow<'static, [u8]> {
+    if base_path().is_empty() {
+        return data;
+    }
+    let is_text = matches!(
+        path.rsplit('.').next(),
+        Some("html" | "js" | "mjs" | "css" | "json" | "webmanifest")
+    );
+    match std::str::from_utf8(&data) {
+        Ok(text) if is_text => Cow::Owned(
+            with_base_path(text, base_path(), ROOT_DOCUMENTS.contains(&path)).into_bytes(),
+        ),
+        _ => data,
+    }
+}
 
 // Update index handler to use embedded assets and properly handle ownership
 pub async fn index_handler() -> Html<String> {
@@ -16,9 +85,12 @@ pub async fn index_handler() -> Html<String> {
         .expect("Failed to convert index.html to UTF-8")
         .to_string();
 
-    // Process the content to fix paths if needed
-    // Note: This is a basic approach - you might need more sophisticated HTML parsing
-    let processed_content = content;
+    // Point asset and API references at the base path if the server runs under one
+    let processed_content = if base_path().is_empty() {
+        content
+    } else {
+        with_base_path(&content, base_path(), true)
+    };
 
     Html(processed_content)
 }
@@ -42,7 +114,7 @@ pub async fn next_assets_handler(req: Request) -> impl IntoResponse {
             (
                 StatusCode::OK,
                 [(header::CONTENT_TYPE, content_type)],
-                content.data,
+                asset_body(&full_path, content.data),
             )
                 .into_response()
         }
@@ -69,7 +141,7 @@ pub async fn static_assets_handler(Path(path): Path<String>) -> impl IntoRespons
             (
                 StatusCode::OK,
                 [(header::CONTENT_TYPE, content_type)],
-                content.data,
+                asset_body(&path, content.data),
             )
                 .into_response()
         }
@@ -79,3 +151,80 @@ pub async fn static_assets_handler(Path(path): Path<String>) -> impl IntoRespons
         }
     }
 }
+
+// Built-in PWA files, used unless the frontend build ships its own
+const DEFAULT_MANIFEST: &str = include_str!("pwa/manifest.webmanifest");
+const DEFAULT_SERVICE_WORKER: &str = include_str!("pwa/sw.js");
+
+// Serve the web app manifest so the interface can be installed on phones
+pub async fn manifest_handler() -> impl IntoResponse {
+    let body = match StaticAssets::get("manifest.webmanifest") {
+        Some(content) => content.data,
+        None => Cow::Borrowed(DEFAULT_MANIFEST.as_bytes()),
+    };
+    let body = asset_body("manifest.webmanifest", body).into_owned();
+
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "application/manifest+json")],
+        body,
+    )
+}
+
+// Serve the service worker from the site root so its scope covers the whole interface
+pub async fn service_worker_handler() -> impl IntoResponse {
+    let body = match StaticAssets::get("sw.js") {
+        Some(content) => content.data,
+        None => Cow::Borrowed(DEFAULT_SERVICE_WORKER.as_bytes()),
+    };
+    let body = asset_body("sw.js", body).into_owned();
+    // The worker may control everything below the base path
+    let scope = format!("{}/", base_path());
+
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "application/javascript".to_string()),
+            // Browsers must always check for a new worker instead of using a cached copy
+            (header::CACHE_CONTROL, "no-cache".to_string()),
+            (
+                header::HeaderName::from_static("service-worker-allowed"),
+                scope,
+            ),
+        ],
+        body,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Excerpt in the style of a minified Next.js bundle
+    const BUNDLE: &str = r#"let e="/_next/static/chunks/";fetch("/api/playlist");t.split("/").filter(Boolean).join("/");if(p.startsWith('/'))r=`/`;"#;
+
+    #[test]
+    fn bundles_keep_lone_slashes() {
+        assert_eq!(
+            with_base_path(BUNDLE, "/led", false),
+            r#"let e="/led/_next/static/chunks/";fetch("/led/api/playlist");t.split("/").filter(Boolean).join("/");if(p.startsWith('/'))r=`/`;"#
+        );
+    }
+
+    #[test]
+    fn root_documents_point_the_start_page_at_the_base_path() {
+        let html = r#"<a href="/">Home</a><script src="/_next/app.js"></script>"#;
+        assert_eq!(
+            with_base_path(html, "/led", true),
+            r#"<a href="/led/">Home</a><script src="/led/_next/app.js"></script>"#
+        );
+    }
+
+    #[test]
+    fn css_urls_are_rewritten() {
+        assert_eq!(
+            with_base_path("a{background:url(/static/bg.png)}", "/led", false),
+            "a{background:url(/led/static/bg.png)}"
+        );
+    }
+}